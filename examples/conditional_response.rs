@@ -52,13 +52,14 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .await;
 
     // Time-based responses (different responses based on time of day)
+    // The time comes from the server's clock, so tests can pin it with `with_clock`
     server
         .expect()
         .path("/api/greeting")
         .method("GET")
         .respond()
-        .conditional(|_| {
-            let hour = chrono::Local::now().hour();
+        .respond_with(|request| {
+            let hour = request.received_at.with_timezone(&chrono::Local).hour();
 
             if hour < 12 {
                 MockResponse::new(200)
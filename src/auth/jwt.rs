@@ -0,0 +1,152 @@
+use std::fmt;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use ring::rand::SystemRandom;
+use ring::signature::{self, EcdsaKeyPair, KeyPair};
+use serde_json::{Value, json};
+use thiserror::Error;
+
+/// Errors produced while reading or checking a JWT
+#[derive(Debug, Error)]
+pub enum JwtError {
+    #[error("token is not a well-formed JWT")]
+    Malformed,
+
+    #[error("unsupported algorithm {0}")]
+    UnsupportedAlgorithm(String),
+
+    #[error("signature is invalid")]
+    InvalidSignature,
+
+    #[error("failed to create signing key")]
+    KeyGeneration,
+}
+
+/// ES256 key used to sign test tokens
+pub struct SigningKey {
+    kid: String,
+
+    key_pair: EcdsaKeyPair,
+
+    rng: SystemRandom,
+}
+
+impl fmt::Debug for SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningKey")
+            .field("kid", &self.kid)
+            .finish()
+    }
+}
+
+impl SigningKey {
+    /// Generates a fresh P-256 key pair
+    pub fn generate() -> Result<Self, JwtError> {
+        let rng = SystemRandom::new();
+        let algorithm = &signature::ECDSA_P256_SHA256_FIXED_SIGNING;
+
+        let pkcs8 =
+            EcdsaKeyPair::generate_pkcs8(algorithm, &rng).map_err(|_| JwtError::KeyGeneration)?;
+        let key_pair = EcdsaKeyPair::from_pkcs8(algorithm, pkcs8.as_ref(), &rng)
+            .map_err(|_| JwtError::KeyGeneration)?;
+
+        Ok(Self {
+            kid: uuid::Uuid::new_v4().to_string(),
+            key_pair,
+            rng,
+        })
+    }
+
+    pub fn kid(&self) -> &str {
+        &self.kid
+    }
+
+    /// Uncompressed public key point (`0x04 || x || y`)
+    pub fn public_key(&self) -> &[u8] {
+        self.key_pair.public_key().as_ref()
+    }
+
+    /// Signs `claims` and returns the compact JWT
+    pub fn sign(&self, claims: &Value) -> Result<String, JwtError> {
+        let header = json!({"alg": "ES256", "typ": "JWT", "kid": self.kid});
+        let signing_input = format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(header.to_string()),
+            URL_SAFE_NO_PAD.encode(claims.to_string())
+        );
+
+        let signature = self
+            .key_pair
+            .sign(&self.rng, signing_input.as_bytes())
+            .map_err(|_| JwtError::InvalidSignature)?;
+
+        Ok(format!(
+            "{}.{}",
+            signing_input,
+            URL_SAFE_NO_PAD.encode(signature.as_ref())
+        ))
+    }
+
+    /// Public key in JWK format
+    pub fn jwk(&self) -> Value {
+        let point = self.public_key();
+        json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": self.kid,
+            "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+            "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+        })
+    }
+}
+
+/// A JWT split into its parts without checking the signature
+#[derive(Clone, Debug)]
+pub struct DecodedJwt {
+    pub header: Value,
+
+    pub claims: Value,
+
+    /// `header.payload` as it appeared in the token
+    pub signing_input: String,
+
+    pub signature: Vec<u8>,
+}
+
+impl DecodedJwt {
+    /// Decodes a compact JWT without verifying it
+    pub fn decode(token: &str) -> Result<Self, JwtError> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(JwtError::Malformed);
+        };
+
+        let decode_json = |part: &str| -> Result<Value, JwtError> {
+            let bytes = URL_SAFE_NO_PAD
+                .decode(part)
+                .map_err(|_| JwtError::Malformed)?;
+            serde_json::from_slice(&bytes).map_err(|_| JwtError::Malformed)
+        };
+
+        Ok(Self {
+            header: decode_json(header)?,
+            claims: decode_json(payload)?,
+            signing_input: format!("{}.{}", header, payload),
+            signature: URL_SAFE_NO_PAD
+                .decode(signature)
+                .map_err(|_| JwtError::Malformed)?,
+        })
+    }
+
+    pub fn algorithm(&self) -> &str {
+        self.header
+            .get("alg")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+    }
+}
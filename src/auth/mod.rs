@@ -0,0 +1,7 @@
+mod jwt;
+mod oidc;
+mod validation;
+
+pub use jwt::*;
+pub use oidc::*;
+pub use validation::*;
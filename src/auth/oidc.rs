@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use chrono::Utc;
+use serde_json::{Map, Value, json};
+
+use super::{JwtError, SigningKey};
+
+/// Configuration of the built-in OAuth2 / OpenID Connect provider
+///
+/// # Example
+/// ```
+/// # use mimic_rs::MockServer;
+/// # use mimic_rs::auth::OidcConfig;
+/// # use serde_json::json;
+/// # use std::time::Duration;
+/// let server = MockServer::new("./resources").with_oidc(
+///     OidcConfig::default()
+///         .audience("orders-api")
+///         .expires_in(Duration::from_secs(300))
+///         .claim("roles", json!(["admin"])),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct OidcConfig {
+    /// Issuer URL, derived from the `Host` header when not set
+    pub issuer: Option<String>,
+
+    /// Audience of issued tokens, the client id when not set
+    pub audience: Option<String>,
+
+    /// Subject used when the grant does not name a user
+    pub subject: String,
+
+    pub expires_in: Duration,
+
+    /// Additional claims added to every token
+    pub claims: Map<String, Value>,
+}
+
+impl Default for OidcConfig {
+    fn default() -> Self {
+        Self {
+            issuer: None,
+            audience: None,
+            subject: "test-user".to_string(),
+            expires_in: Duration::from_secs(3600),
+            claims: Map::new(),
+        }
+    }
+}
+
+impl OidcConfig {
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.issuer = Some(issuer.trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn audience(mut self, audience: &str) -> Self {
+        self.audience = Some(audience.to_string());
+        self
+    }
+
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.subject = subject.to_string();
+        self
+    }
+
+    pub fn expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = expires_in;
+        self
+    }
+
+    pub fn claim(mut self, key: &str, value: Value) -> Self {
+        self.claims.insert(key.to_string(), value);
+        self
+    }
+}
+
+/// What an authorization code or refresh token stands for
+#[derive(Clone, Debug)]
+pub(crate) struct Grant {
+    pub client_id: String,
+
+    pub subject: String,
+
+    pub scope: String,
+
+    pub nonce: Option<String>,
+}
+
+/// Running OAuth2 / OIDC provider holding the signing key and issued codes
+#[derive(Debug)]
+pub struct OidcProvider {
+    config: OidcConfig,
+
+    key: SigningKey,
+
+    codes: Mutex<HashMap<String, Grant>>,
+
+    refresh_tokens: Mutex<HashMap<String, Grant>>,
+}
+
+impl OidcProvider {
+    pub fn new(config: OidcConfig) -> Result<Self, JwtError> {
+        Ok(Self {
+            config,
+            key: SigningKey::generate()?,
+            codes: Mutex::new(HashMap::new()),
+            refresh_tokens: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn config(&self) -> &OidcConfig {
+        &self.config
+    }
+
+    pub fn signing_key(&self) -> &SigningKey {
+        &self.key
+    }
+
+    /// Returns the configured issuer or one derived from `host`
+    pub fn issuer(&self, host: Option<&str>) -> String {
+        match (&self.config.issuer, host) {
+            (Some(issuer), _) => issuer.clone(),
+            (None, Some(host)) => format!("http://{}", host),
+            (None, None) => "http://localhost".to_string(),
+        }
+    }
+
+    /// Issues a signed token with the standard claims plus `extra_claims`
+    ///
+    /// Useful for tests that need a valid token without going through `/token`.
+    pub fn issue_token(
+        &self,
+        issuer: &str,
+        subject: &str,
+        audience: &str,
+        extra_claims: &Map<String, Value>,
+    ) -> Result<String, JwtError> {
+        let now = Utc::now().timestamp();
+
+        let mut claims = Map::new();
+        claims.insert("iss".to_string(), json!(issuer));
+        claims.insert("sub".to_string(), json!(subject));
+        claims.insert("aud".to_string(), json!(audience));
+        claims.insert("iat".to_string(), json!(now));
+        claims.insert(
+            "exp".to_string(),
+            json!(now + self.config.expires_in.as_secs() as i64),
+        );
+        claims.insert("jti".to_string(), json!(uuid::Uuid::new_v4().to_string()));
+        claims.extend(self.config.claims.clone());
+        claims.extend(extra_claims.clone());
+
+        self.key.sign(&Value::Object(claims))
+    }
+
+    pub(crate) fn store_code(&self, grant: Grant) -> String {
+        let code = uuid::Uuid::new_v4().simple().to_string();
+        if let Ok(mut codes) = self.codes.lock() {
+            codes.insert(code.clone(), grant);
+        }
+        code
+    }
+
+    /// Codes are single use
+    pub(crate) fn take_code(&self, code: &str) -> Option<Grant> {
+        self.codes.lock().ok()?.remove(code)
+    }
+
+    pub(crate) fn store_refresh_token(&self, grant: Grant) -> String {
+        let token = format!("refresh-{}", uuid::Uuid::new_v4().simple());
+        if let Ok(mut tokens) = self.refresh_tokens.lock() {
+            tokens.insert(token.clone(), grant);
+        }
+        token
+    }
+
+    pub(crate) fn refresh_grant(&self, token: &str) -> Option<Grant> {
+        self.refresh_tokens.lock().ok()?.get(token).cloned()
+    }
+}
@@ -0,0 +1,348 @@
+use std::collections::HashMap;
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Utc};
+use ring::{hmac, signature};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use super::{DecodedJwt, OidcProvider};
+use crate::models::MockResponse;
+
+/// Key used to verify the signature of incoming tokens
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VerificationKey {
+    /// Shared secret for HS256/HS384/HS512
+    Hmac(String),
+
+    /// Public key in JWK format (`EC` P-256 or `RSA`)
+    Jwk(Value),
+
+    /// The signing key of the server's built-in OIDC provider
+    Provider,
+}
+
+/// Requirements an incoming bearer token has to fulfil
+///
+/// A missing, malformed, badly signed or expired token is answered with 401.
+/// A valid token lacking the required issuer, audience, scopes or claims is
+/// answered with 403. If another expectation matches the same request and
+/// accepts the token, that one is used instead.
+///
+/// # Example
+/// ```
+/// # use mimic_rs::auth::JwtRequirement;
+/// # use serde_json::json;
+/// let requirement = JwtRequirement::hmac("secret")
+///     .audience("orders-api")
+///     .scope("orders:write")
+///     .claim("tenant", json!("acme"));
+/// ```
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JwtRequirement {
+    pub key: VerificationKey,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub issuer: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub audience: Option<String>,
+
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub scopes: Vec<String>,
+
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub claims: HashMap<String, Value>,
+
+    /// Accept tokens whose `exp` lies in the past
+    #[serde(default)]
+    pub allow_expired: bool,
+
+    /// Clock skew tolerated for `exp` and `nbf`, in seconds
+    #[serde(default)]
+    pub leeway_secs: i64,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub unauthorized_response: Option<MockResponse>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub forbidden_response: Option<MockResponse>,
+}
+
+impl JwtRequirement {
+    pub fn new(key: VerificationKey) -> Self {
+        Self {
+            key,
+            issuer: None,
+            audience: None,
+            scopes: Vec::new(),
+            claims: HashMap::new(),
+            allow_expired: false,
+            leeway_secs: 0,
+            unauthorized_response: None,
+            forbidden_response: None,
+        }
+    }
+
+    /// Tokens signed with a shared HMAC secret
+    pub fn hmac(secret: &str) -> Self {
+        Self::new(VerificationKey::Hmac(secret.to_string()))
+    }
+
+    /// Tokens signed by the key described by `jwk`
+    pub fn jwk(jwk: Value) -> Self {
+        Self::new(VerificationKey::Jwk(jwk))
+    }
+
+    /// Tokens issued by the server's built-in OIDC provider
+    pub fn provider() -> Self {
+        Self::new(VerificationKey::Provider)
+    }
+
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.issuer = Some(issuer.to_string());
+        self
+    }
+
+    pub fn audience(mut self, audience: &str) -> Self {
+        self.audience = Some(audience.to_string());
+        self
+    }
+
+    /// Requires a scope in the `scope` (space separated) or `scp` claim
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.scopes.push(scope.to_string());
+        self
+    }
+
+    /// Requires a claim to have the given value (or contain it, for arrays)
+    pub fn claim(mut self, key: &str, value: Value) -> Self {
+        self.claims.insert(key.to_string(), value);
+        self
+    }
+
+    pub fn allow_expired(mut self) -> Self {
+        self.allow_expired = true;
+        self
+    }
+
+    pub fn leeway(mut self, seconds: i64) -> Self {
+        self.leeway_secs = seconds;
+        self
+    }
+
+    /// Response for missing or invalid tokens (default: 401)
+    pub fn on_unauthorized(mut self, response: MockResponse) -> Self {
+        self.unauthorized_response = Some(response);
+        self
+    }
+
+    /// Response for valid tokens with insufficient claims (default: 403)
+    pub fn on_forbidden(mut self, response: MockResponse) -> Self {
+        self.forbidden_response = Some(response);
+        self
+    }
+
+    /// Checks the `Authorization` header, returning the rejection response on failure
+    pub fn validate(
+        &self,
+        authorization: Option<&str>,
+        provider: Option<&OidcProvider>,
+    ) -> Result<(), Box<MockResponse>> {
+        self.validate_at(authorization, provider, Utc::now())
+    }
+
+    /// Like [`Self::validate`], checking `exp` and `nbf` against `now`
+    pub fn validate_at(
+        &self,
+        authorization: Option<&str>,
+        provider: Option<&OidcProvider>,
+        now: DateTime<Utc>,
+    ) -> Result<(), Box<MockResponse>> {
+        let token = authorization
+            .and_then(|value| {
+                value
+                    .strip_prefix("Bearer ")
+                    .or_else(|| value.strip_prefix("bearer "))
+            })
+            .ok_or_else(|| self.unauthorized("missing bearer token"))?;
+
+        let decoded = DecodedJwt::decode(token.trim())
+            .map_err(|_| self.unauthorized("token is not a well-formed JWT"))?;
+
+        self.verify_signature(&decoded, provider)
+            .map_err(|reason| self.unauthorized(reason))?;
+
+        let now = now.timestamp();
+        let claims = &decoded.claims;
+
+        if !self.allow_expired
+            && let Some(exp) = claims.get("exp").and_then(Value::as_i64)
+            && exp + self.leeway_secs <= now
+        {
+            return Err(self.unauthorized("token has expired"));
+        }
+        if let Some(nbf) = claims.get("nbf").and_then(Value::as_i64)
+            && nbf - self.leeway_secs > now
+        {
+            return Err(self.unauthorized("token is not valid yet"));
+        }
+
+        if let Some(issuer) = &self.issuer
+            && claims.get("iss").and_then(Value::as_str) != Some(issuer.as_str())
+        {
+            return Err(self.forbidden("insufficient_scope", "issuer is not accepted"));
+        }
+
+        if let Some(audience) = &self.audience
+            && !claim_contains(claims.get("aud"), &Value::String(audience.clone()))
+        {
+            return Err(self.forbidden("insufficient_scope", "audience is not accepted"));
+        }
+
+        let granted = granted_scopes(claims);
+        if let Some(missing) = self.scopes.iter().find(|s| !granted.contains(s)) {
+            return Err(self.forbidden("insufficient_scope", &format!("missing scope {}", missing)));
+        }
+
+        for (key, expected) in &self.claims {
+            if !claim_contains(claims.get(key), expected) {
+                return Err(self.forbidden(
+                    "insufficient_scope",
+                    &format!("claim {} does not match", key),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn verify_signature(
+        &self,
+        decoded: &DecodedJwt,
+        provider: Option<&OidcProvider>,
+    ) -> Result<(), &'static str> {
+        let message = decoded.signing_input.as_bytes();
+        let sig = decoded.signature.as_slice();
+        let algorithm = decoded.algorithm();
+
+        match &self.key {
+            VerificationKey::Hmac(secret) => {
+                let hmac_algorithm = match algorithm {
+                    "HS256" => hmac::HMAC_SHA256,
+                    "HS384" => hmac::HMAC_SHA384,
+                    "HS512" => hmac::HMAC_SHA512,
+                    _ => return Err("unexpected token algorithm"),
+                };
+                hmac::verify(
+                    &hmac::Key::new(hmac_algorithm, secret.as_bytes()),
+                    message,
+                    sig,
+                )
+                .map_err(|_| "signature is invalid")
+            }
+            VerificationKey::Provider => {
+                let provider = provider.ok_or("no OIDC provider is configured")?;
+                if algorithm != "ES256" {
+                    return Err("unexpected token algorithm");
+                }
+                signature::UnparsedPublicKey::new(
+                    &signature::ECDSA_P256_SHA256_FIXED,
+                    provider.signing_key().public_key(),
+                )
+                .verify(message, sig)
+                .map_err(|_| "signature is invalid")
+            }
+            VerificationKey::Jwk(jwk) => verify_with_jwk(jwk, algorithm, message, sig),
+        }
+    }
+
+    fn unauthorized(&self, reason: &str) -> Box<MockResponse> {
+        Box::new(
+            self.unauthorized_response
+                .clone()
+                .unwrap_or_else(|| rejection(401, "invalid_token", reason)),
+        )
+    }
+
+    fn forbidden(&self, error: &str, reason: &str) -> Box<MockResponse> {
+        Box::new(
+            self.forbidden_response
+                .clone()
+                .unwrap_or_else(|| rejection(403, error, reason)),
+        )
+    }
+}
+
+fn verify_with_jwk(
+    jwk: &Value,
+    algorithm: &str,
+    message: &[u8],
+    sig: &[u8],
+) -> Result<(), &'static str> {
+    let field = |name: &str| -> Result<Vec<u8>, &'static str> {
+        jwk.get(name)
+            .and_then(Value::as_str)
+            .and_then(|v| URL_SAFE_NO_PAD.decode(v).ok())
+            .ok_or("verification key is incomplete")
+    };
+
+    match (jwk.get("kty").and_then(Value::as_str), algorithm) {
+        (Some("EC"), "ES256") => {
+            let mut point = vec![0x04];
+            point.extend(field("x")?);
+            point.extend(field("y")?);
+            signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_FIXED, point)
+                .verify(message, sig)
+                .map_err(|_| "signature is invalid")
+        }
+        (Some("RSA"), "RS256") => signature::RsaPublicKeyComponents {
+            n: field("n")?,
+            e: field("e")?,
+        }
+        .verify(&signature::RSA_PKCS1_2048_8192_SHA256, message, sig)
+        .map_err(|_| "signature is invalid"),
+        (Some("oct"), "HS256") => {
+            let key = hmac::Key::new(hmac::HMAC_SHA256, &field("k")?);
+            hmac::verify(&key, message, sig).map_err(|_| "signature is invalid")
+        }
+        _ => Err("unexpected token algorithm"),
+    }
+}
+
+/// Scopes from `scope` (space separated string) or `scp` (string or array)
+fn granted_scopes(claims: &Value) -> Vec<String> {
+    let mut scopes = Vec::new();
+    for key in ["scope", "scp"] {
+        match claims.get(key) {
+            Some(Value::String(s)) => scopes.extend(s.split(' ').map(String::from)),
+            Some(Value::Array(values)) => {
+                scopes.extend(values.iter().filter_map(Value::as_str).map(String::from))
+            }
+            _ => {}
+        }
+    }
+    scopes
+}
+
+fn claim_contains(actual: Option<&Value>, expected: &Value) -> bool {
+    match actual {
+        Some(Value::Array(values)) if !expected.is_array() => values.contains(expected),
+        Some(actual) => actual == expected,
+        None => false,
+    }
+}
+
+fn rejection(status: u16, error: &str, description: &str) -> MockResponse {
+    MockResponse::new(status)
+        .with_header(
+            "WWW-Authenticate",
+            &format!(
+                "Bearer error=\"{}\", error_description=\"{}\"",
+                error, description
+            ),
+        )
+        .with_json_body(json!({"error": error, "error_description": description}))
+}
@@ -0,0 +1,72 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use mimic_rs::import;
+use mimic_rs::mappings::{self, MAPPINGS_DIR};
+use mimic_rs::models::CreateExpectationRequest;
+use tracing::{info, warn};
+
+use super::{ExportArgs, ExportFormat};
+
+/// File the `wiremock` format writes all stubs into, below `<out>/mappings`
+const WIREMOCK_FILE: &str = "mimic-rs.json";
+
+/// Pulls the expectations of a running instance and writes them as mapping files
+pub async fn run(args: ExportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let url = format!("{}/_expectations", args.from.trim_end_matches('/'));
+
+    let expectations: Vec<CreateExpectationRequest> =
+        reqwest::get(&url).await?.error_for_status()?.json().await?;
+
+    // WireMock looks for body files in __files instead of the root
+    let files_dir = match args.format {
+        ExportFormat::Mimic => args.out.clone(),
+        ExportFormat::WireMock => args.out.join("__files"),
+    };
+    for expectation in &expectations {
+        if let Some(file_name) = &expectation.response.body_file
+            && !files_dir.join(file_name).exists()
+        {
+            warn!(
+                "{} {} references body file {} which has to be copied from the server's resources directory into {}",
+                expectation.method,
+                expectation.path,
+                file_name,
+                files_dir.display()
+            );
+        }
+    }
+
+    let count = match args.format {
+        ExportFormat::Mimic => {
+            mappings::write_mappings(&args.out.join(MAPPINGS_DIR), &expectations)?.len()
+        }
+        ExportFormat::WireMock => {
+            let file = write_wiremock(&args.out, &expectations)?;
+            info!("Wrote {}", file.display());
+            expectations.len()
+        }
+    };
+
+    info!(
+        "Exported {} expectations from {} into {}",
+        count,
+        args.from,
+        Path::new(&args.out).display()
+    );
+
+    Ok(())
+}
+
+/// Writes all expectations as one WireMock mapping file
+fn write_wiremock(
+    out: &Path,
+    expectations: &[CreateExpectationRequest],
+) -> std::io::Result<PathBuf> {
+    let dir = out.join(MAPPINGS_DIR);
+    fs::create_dir_all(&dir)?;
+    let file = dir.join(WIREMOCK_FILE);
+    let document = import::to_wiremock(expectations);
+    fs::write(&file, serde_json::to_string_pretty(&document)?)?;
+    Ok(file)
+}
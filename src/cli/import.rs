@@ -0,0 +1,19 @@
+use mimic_rs::import;
+use tracing::info;
+
+use super::ImportArgs;
+
+/// Converts an external definition into mapping files
+pub fn run(args: ImportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let imported = import::import_file(args.format, &args.file)?;
+    let written = imported.write_to(&args.out)?;
+
+    info!(
+        "Imported {} expectations from {} into {}",
+        written.len(),
+        args.file.display(),
+        args.out.display()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,796 @@
+pub mod export;
+pub mod import;
+pub mod replay;
+
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use mimic_rs::import::ImportFormat;
+use mimic_rs::logging::{self, LogConfig};
+use thiserror::Error;
+
+pub const USAGE: &str = "\
+Usage: mimic-rs [OPTIONS] [PORT] [RESOURCES_DIR]
+       mimic-rs import <openapi|har|postman|wiremock> <FILE> [--out DIR]
+       mimic-rs export --from <URL> [--out DIR] [--format mimic|wiremock]
+       mimic-rs replay <RECORDINGS_DIR> --target <URL>
+
+Arguments:
+  [PORT]           Port to listen on (default: 8080)
+  [RESOURCES_DIR]  Directory with response files and mappings (default: ./resources)
+
+Commands:
+  import           Convert external definitions into mapping files
+  export           Write the expectations of a running instance as mapping files
+  replay           Re-send recorded requests to a target and diff the responses
+
+Options:
+  --no-admin             Disable the admin API (/_setup, /_reset, ...)
+  --allow-shutdown       Stop the server on POST /_shutdown
+  --no-405               Answer 404 instead of 405 for unstubbed methods
+  --explain-404          Explain unmatched requests in a JSON 404 body
+  --cors                 Answer CORS preflights and allow any origin
+  --tls-cert <FILE>      Serve HTTPS with the PEM certificate chain in FILE
+  --tls-key <FILE>       PEM private key for --tls-cert
+  --oidc                 Enable the built-in OAuth2/OIDC provider
+  --drain-timeout <SECS> Time in-flight requests get on shutdown (default: 30)
+  --accept-delay <MS>    Hold new connections for MS milliseconds before serving them
+  --tls-handshake-delay <MS>
+                         Hold TLS handshakes for MS milliseconds before answering them
+  --bandwidth <RATE>     Cap egress of all responses, e.g. 256KB (per second)
+  --chaos <FILE>         Enable chaos mode with the JSON configuration in FILE
+  --seed <N>             Make chaos, random delays and generated values reproducible
+  --downtime <FILE>      Schedule the outages in FILE (JSON array of windows)
+  --strip-prefix <PATH>  Remove a gateway path prefix before matching
+  --dump-requests <FILE> Write the request log to FILE on shutdown
+  --journal <FILE>       Persist the request log in the SQLite database FILE
+  --access-log <FILE>    Write one JSON line per request to FILE (- for stdout)
+  --redact-header <NAME> Hide the values of header NAME in logs (repeatable)
+  --capture-dir <DIR>    Save multipart and binary request bodies in DIR
+  --idempotency-key <HEADER>
+                         Replay the stored response for repeated keys in HEADER
+  --persist <DIR>        Save expectations created via /_setup in DIR and restore them
+  --mappings <DIR>       Load mapping files from DIR (default: RESOURCES_DIR/mappings)
+  --watch                Reload mapping and response files when they change
+  --openapi <FILE>       Stub every operation of an OpenAPI 3 document (JSON or YAML)
+  --validate             Answer requests violating the --openapi document with 400
+  --wiremock <DIR>       Serve the stubs of a WireMock root (mappings/ and __files/)
+  --har <FILE>           Replay the responses recorded in a HAR archive
+  --har-latency          Delay --har responses by their recorded server wait time
+  --peer <URL>           Sync expectations with another instance (repeatable)
+  --proxy-to <URL>       Forward unmatched requests to URL and record them
+  --fallback-proxy <URL> Forward unmatched requests to URL without recording
+  --static <PREFIX=DIR>  Serve the files in DIR below PREFIX (repeatable)
+  --log-level <LEVEL>    off, error, warn, info, debug or trace (default: info)
+  --log-format <FORMAT>  pretty or json (default: pretty)
+  -q, --quiet            Only print errors
+  --out <DIR>            Output directory for import/export (default: ./resources)
+  --from <URL>           Base URL of the instance to export from
+  --format <FORMAT>      Export format, mimic or wiremock (default: mimic)
+  --target <URL>         Base URL of the server to replay against
+  -h, --help             Print this help";
+
+/// Errors produced while parsing the command line
+#[derive(Debug, Error)]
+pub enum CliError {
+    #[error("missing value for {0}")]
+    MissingValue(String),
+
+    #[error("missing argument {0}")]
+    MissingArgument(&'static str),
+
+    #[error("unknown option {0}")]
+    UnknownOption(String),
+
+    #[error("unexpected argument {0}")]
+    UnexpectedArgument(String),
+
+    #[error("invalid port '{0}'")]
+    InvalidPort(String),
+
+    #[error("{0}")]
+    InvalidValue(String),
+}
+
+/// Parsed command line
+#[derive(Debug)]
+pub struct Cli {
+    pub log: LogConfig,
+
+    pub command: Command,
+}
+
+/// What the binary should do
+#[derive(Debug)]
+pub enum Command {
+    Serve(Box<ServeArgs>),
+
+    Import(ImportArgs),
+
+    Export(ExportArgs),
+
+    Replay(ReplayArgs),
+
+    Help,
+}
+
+/// Arguments for running the mock server
+#[derive(Debug)]
+pub struct ServeArgs {
+    pub port: u16,
+
+    pub resources_dir: String,
+
+    pub no_admin: bool,
+
+    pub allow_shutdown: bool,
+
+    pub no_method_not_allowed: bool,
+
+    pub explain_not_found: bool,
+
+    pub cors: bool,
+
+    pub tls_cert: Option<PathBuf>,
+
+    pub tls_key: Option<PathBuf>,
+
+    pub oidc: bool,
+
+    pub drain_timeout: Option<Duration>,
+
+    pub accept_delay: Option<Duration>,
+
+    pub tls_handshake_delay: Option<Duration>,
+
+    pub bandwidth: Option<u64>,
+
+    pub chaos: Option<PathBuf>,
+
+    pub seed: Option<u64>,
+
+    pub downtime: Option<PathBuf>,
+
+    pub strip_prefix: Option<String>,
+
+    pub dump_requests: Option<PathBuf>,
+
+    pub journal: Option<PathBuf>,
+
+    /// `-` writes the access log to stdout
+    pub access_log: Option<PathBuf>,
+
+    pub redact_headers: Vec<String>,
+
+    pub persist: Option<PathBuf>,
+
+    pub mappings: Option<PathBuf>,
+
+    pub watch: bool,
+
+    pub openapi: Option<PathBuf>,
+
+    pub validate: bool,
+
+    pub wiremock: Option<PathBuf>,
+
+    pub har: Option<PathBuf>,
+
+    pub har_latency: bool,
+
+    pub capture_dir: Option<PathBuf>,
+
+    pub idempotency_key: Option<String>,
+
+    pub peers: Vec<String>,
+
+    pub proxy_to: Option<String>,
+
+    pub fallback_proxy: Option<String>,
+
+    /// Path prefix and directory pairs from `--static`
+    pub static_dirs: Vec<(String, PathBuf)>,
+}
+
+impl Default for ServeArgs {
+    fn default() -> Self {
+        Self {
+            port: 8080,
+            resources_dir: "./resources".to_string(),
+            no_admin: false,
+            allow_shutdown: false,
+            no_method_not_allowed: false,
+            explain_not_found: false,
+            cors: false,
+            tls_cert: None,
+            tls_key: None,
+            oidc: false,
+            drain_timeout: None,
+            accept_delay: None,
+            tls_handshake_delay: None,
+            bandwidth: None,
+            chaos: None,
+            seed: None,
+            downtime: None,
+            strip_prefix: None,
+            dump_requests: None,
+            journal: None,
+            access_log: None,
+            redact_headers: Vec::new(),
+            persist: None,
+            mappings: None,
+            watch: false,
+            openapi: None,
+            validate: false,
+            wiremock: None,
+            har: None,
+            har_latency: false,
+            capture_dir: None,
+            idempotency_key: None,
+            peers: Vec::new(),
+            proxy_to: None,
+            fallback_proxy: None,
+            static_dirs: Vec::new(),
+        }
+    }
+}
+
+/// Arguments for the `import` subcommand
+#[derive(Debug)]
+pub struct ImportArgs {
+    pub format: ImportFormat,
+
+    pub file: PathBuf,
+
+    pub out: PathBuf,
+}
+
+/// Arguments for the `export` subcommand
+#[derive(Debug)]
+pub struct ExportArgs {
+    pub from: String,
+
+    pub out: PathBuf,
+
+    pub format: ExportFormat,
+}
+
+/// File format the `export` subcommand writes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Mapping files as read by `--mappings`
+    #[default]
+    Mimic,
+
+    /// A WireMock `mappings/` directory
+    WireMock,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mimic" => Ok(ExportFormat::Mimic),
+            "wiremock" => Ok(ExportFormat::WireMock),
+            other => Err(format!(
+                "unknown export format '{}', expected mimic or wiremock",
+                other
+            )),
+        }
+    }
+}
+
+/// Arguments for the `replay` subcommand
+#[derive(Debug)]
+pub struct ReplayArgs {
+    pub recordings: PathBuf,
+
+    pub target: String,
+}
+
+/// Options that only some commands accept
+#[derive(Debug, Default)]
+struct CommandOptions {
+    out: Option<String>,
+
+    from: Option<String>,
+
+    format: Option<ExportFormat>,
+
+    target: Option<String>,
+
+    no_admin: bool,
+
+    allow_shutdown: bool,
+
+    no_method_not_allowed: bool,
+
+    explain_not_found: bool,
+
+    cors: bool,
+
+    tls_cert: Option<PathBuf>,
+
+    tls_key: Option<PathBuf>,
+
+    oidc: bool,
+
+    drain_timeout: Option<Duration>,
+
+    accept_delay: Option<Duration>,
+
+    tls_handshake_delay: Option<Duration>,
+
+    bandwidth: Option<u64>,
+
+    chaos: Option<PathBuf>,
+
+    seed: Option<u64>,
+
+    downtime: Option<PathBuf>,
+
+    strip_prefix: Option<String>,
+
+    dump_requests: Option<PathBuf>,
+
+    journal: Option<PathBuf>,
+
+    access_log: Option<PathBuf>,
+
+    redact_headers: Vec<String>,
+
+    persist: Option<PathBuf>,
+
+    mappings: Option<PathBuf>,
+
+    watch: bool,
+
+    openapi: Option<PathBuf>,
+
+    validate: bool,
+
+    wiremock: Option<PathBuf>,
+
+    har: Option<PathBuf>,
+
+    har_latency: bool,
+
+    capture_dir: Option<PathBuf>,
+
+    idempotency_key: Option<String>,
+
+    peers: Vec<String>,
+
+    proxy_to: Option<String>,
+
+    fallback_proxy: Option<String>,
+
+    static_dirs: Vec<(String, PathBuf)>,
+}
+
+impl CommandOptions {
+    fn take_out(&mut self) -> PathBuf {
+        PathBuf::from(self.out.take().unwrap_or_else(|| "./resources".to_string()))
+    }
+
+    /// Fails on the first option the selected command did not consume
+    fn ensure_consumed(&self) -> Result<(), CliError> {
+        if self.out.is_some() {
+            return Err(CliError::UnknownOption("--out".to_string()));
+        }
+        if self.from.is_some() {
+            return Err(CliError::UnknownOption("--from".to_string()));
+        }
+        if self.format.is_some() {
+            return Err(CliError::UnknownOption("--format".to_string()));
+        }
+        if self.target.is_some() {
+            return Err(CliError::UnknownOption("--target".to_string()));
+        }
+        if self.no_admin {
+            return Err(CliError::UnknownOption("--no-admin".to_string()));
+        }
+        if self.allow_shutdown {
+            return Err(CliError::UnknownOption("--allow-shutdown".to_string()));
+        }
+        if self.no_method_not_allowed {
+            return Err(CliError::UnknownOption("--no-405".to_string()));
+        }
+        if self.explain_not_found {
+            return Err(CliError::UnknownOption("--explain-404".to_string()));
+        }
+        if self.cors {
+            return Err(CliError::UnknownOption("--cors".to_string()));
+        }
+        if self.tls_cert.is_some() {
+            return Err(CliError::UnknownOption("--tls-cert".to_string()));
+        }
+        if self.tls_key.is_some() {
+            return Err(CliError::UnknownOption("--tls-key".to_string()));
+        }
+        if self.oidc {
+            return Err(CliError::UnknownOption("--oidc".to_string()));
+        }
+        if self.drain_timeout.is_some() {
+            return Err(CliError::UnknownOption("--drain-timeout".to_string()));
+        }
+        if self.accept_delay.is_some() {
+            return Err(CliError::UnknownOption("--accept-delay".to_string()));
+        }
+        if self.tls_handshake_delay.is_some() {
+            return Err(CliError::UnknownOption("--tls-handshake-delay".to_string()));
+        }
+        if self.bandwidth.is_some() {
+            return Err(CliError::UnknownOption("--bandwidth".to_string()));
+        }
+        if self.chaos.is_some() {
+            return Err(CliError::UnknownOption("--chaos".to_string()));
+        }
+        if self.seed.is_some() {
+            return Err(CliError::UnknownOption("--seed".to_string()));
+        }
+        if self.downtime.is_some() {
+            return Err(CliError::UnknownOption("--downtime".to_string()));
+        }
+        if self.strip_prefix.is_some() {
+            return Err(CliError::UnknownOption("--strip-prefix".to_string()));
+        }
+        if self.dump_requests.is_some() {
+            return Err(CliError::UnknownOption("--dump-requests".to_string()));
+        }
+        if self.journal.is_some() {
+            return Err(CliError::UnknownOption("--journal".to_string()));
+        }
+        if self.access_log.is_some() {
+            return Err(CliError::UnknownOption("--access-log".to_string()));
+        }
+        if !self.redact_headers.is_empty() {
+            return Err(CliError::UnknownOption("--redact-header".to_string()));
+        }
+        if self.persist.is_some() {
+            return Err(CliError::UnknownOption("--persist".to_string()));
+        }
+        if self.mappings.is_some() {
+            return Err(CliError::UnknownOption("--mappings".to_string()));
+        }
+        if self.watch {
+            return Err(CliError::UnknownOption("--watch".to_string()));
+        }
+        if self.openapi.is_some() {
+            return Err(CliError::UnknownOption("--openapi".to_string()));
+        }
+        if self.validate {
+            return Err(CliError::UnknownOption("--validate".to_string()));
+        }
+        if self.wiremock.is_some() {
+            return Err(CliError::UnknownOption("--wiremock".to_string()));
+        }
+        if self.har.is_some() {
+            return Err(CliError::UnknownOption("--har".to_string()));
+        }
+        if self.har_latency {
+            return Err(CliError::UnknownOption("--har-latency".to_string()));
+        }
+        if self.capture_dir.is_some() {
+            return Err(CliError::UnknownOption("--capture-dir".to_string()));
+        }
+        if self.idempotency_key.is_some() {
+            return Err(CliError::UnknownOption("--idempotency-key".to_string()));
+        }
+        if !self.peers.is_empty() {
+            return Err(CliError::UnknownOption("--peer".to_string()));
+        }
+        if self.proxy_to.is_some() {
+            return Err(CliError::UnknownOption("--proxy-to".to_string()));
+        }
+        if self.fallback_proxy.is_some() {
+            return Err(CliError::UnknownOption("--fallback-proxy".to_string()));
+        }
+        if !self.static_dirs.is_empty() {
+            return Err(CliError::UnknownOption("--static".to_string()));
+        }
+        Ok(())
+    }
+}
+
+/// Parses the arguments (without the program name)
+pub fn parse<I>(args: I) -> Result<Cli, CliError>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut log = LogConfig::default();
+    let mut options = CommandOptions::default();
+    let mut positional = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => {
+                (name.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+
+        let mut value = |name: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| CliError::MissingValue(name.to_string()))
+        };
+
+        match name.as_str() {
+            "-h" | "--help" => {
+                return Ok(Cli {
+                    log,
+                    command: Command::Help,
+                });
+            }
+            "-q" | "--quiet" => log.quiet = true,
+            "--log-level" => {
+                log.level = logging::parse_level(&value(&name)?).map_err(CliError::InvalidValue)?;
+            }
+            "--log-format" => {
+                log.format = value(&name)?.parse().map_err(CliError::InvalidValue)?;
+            }
+            "--no-admin" => options.no_admin = true,
+            "--allow-shutdown" => options.allow_shutdown = true,
+            "--no-405" => options.no_method_not_allowed = true,
+            "--explain-404" => options.explain_not_found = true,
+            "--cors" => options.cors = true,
+            "--tls-cert" => options.tls_cert = Some(PathBuf::from(value(&name)?)),
+            "--tls-key" => options.tls_key = Some(PathBuf::from(value(&name)?)),
+            "--oidc" => options.oidc = true,
+            "--drain-timeout" => {
+                let secs = value(&name)?;
+                let secs: u64 = secs.parse().map_err(|_| {
+                    CliError::InvalidValue(format!("invalid drain timeout '{}'", secs))
+                })?;
+                options.drain_timeout = Some(Duration::from_secs(secs));
+            }
+            "--accept-delay" => {
+                let millis = value(&name)?;
+                let millis: u64 = millis.parse().map_err(|_| {
+                    CliError::InvalidValue(format!("invalid accept delay '{}'", millis))
+                })?;
+                options.accept_delay = Some(Duration::from_millis(millis));
+            }
+            "--tls-handshake-delay" => {
+                let millis = value(&name)?;
+                let millis: u64 = millis.parse().map_err(|_| {
+                    CliError::InvalidValue(format!("invalid TLS handshake delay '{}'", millis))
+                })?;
+                options.tls_handshake_delay = Some(Duration::from_millis(millis));
+            }
+            "--bandwidth" => options.bandwidth = Some(parse_rate(&value(&name)?)?),
+            "--chaos" => options.chaos = Some(PathBuf::from(value(&name)?)),
+            "--seed" => {
+                let seed = value(&name)?;
+                let seed: u64 = seed
+                    .parse()
+                    .map_err(|_| CliError::InvalidValue(format!("invalid seed '{}'", seed)))?;
+                options.seed = Some(seed);
+            }
+            "--downtime" => options.downtime = Some(PathBuf::from(value(&name)?)),
+            "--strip-prefix" => options.strip_prefix = Some(value(&name)?),
+            "--dump-requests" => options.dump_requests = Some(PathBuf::from(value(&name)?)),
+            "--journal" => options.journal = Some(PathBuf::from(value(&name)?)),
+            "--access-log" => options.access_log = Some(PathBuf::from(value(&name)?)),
+            "--redact-header" => options.redact_headers.push(value(&name)?),
+            "--persist" => options.persist = Some(PathBuf::from(value(&name)?)),
+            "--mappings" => options.mappings = Some(PathBuf::from(value(&name)?)),
+            "--watch" => options.watch = true,
+            "--openapi" => options.openapi = Some(PathBuf::from(value(&name)?)),
+            "--validate" => options.validate = true,
+            "--wiremock" => options.wiremock = Some(PathBuf::from(value(&name)?)),
+            "--har" => options.har = Some(PathBuf::from(value(&name)?)),
+            "--har-latency" => options.har_latency = true,
+            "--capture-dir" => options.capture_dir = Some(PathBuf::from(value(&name)?)),
+            "--idempotency-key" => options.idempotency_key = Some(value(&name)?),
+            "--peer" => options.peers.push(value(&name)?),
+            "--proxy-to" => options.proxy_to = Some(value(&name)?),
+            "--fallback-proxy" => options.fallback_proxy = Some(value(&name)?),
+            "--static" => {
+                let mapping = value(&name)?;
+                let (prefix, dir) = mapping.split_once('=').ok_or_else(|| {
+                    CliError::InvalidValue(format!(
+                        "invalid static mapping '{}', expected PREFIX=DIR",
+                        mapping
+                    ))
+                })?;
+                options
+                    .static_dirs
+                    .push((prefix.to_string(), PathBuf::from(dir)));
+            }
+            "--out" => options.out = Some(value(&name)?),
+            "--from" => options.from = Some(value(&name)?),
+            "--format" => {
+                options.format = Some(value(&name)?.parse().map_err(CliError::InvalidValue)?);
+            }
+            "--target" => options.target = Some(value(&name)?),
+            other if other.starts_with('-') => {
+                return Err(CliError::UnknownOption(other.to_string()));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let command = match positional.first().map(String::as_str) {
+        Some("import") => parse_import(positional.into_iter().skip(1), &mut options)?,
+        Some("export") => parse_export(positional.into_iter().skip(1), &mut options)?,
+        Some("replay") => parse_replay(positional.into_iter().skip(1), &mut options)?,
+        _ => parse_serve(positional.into_iter(), &mut options)?,
+    };
+
+    options.ensure_consumed()?;
+
+    Ok(Cli { log, command })
+}
+
+/// Parses a byte rate such as `512`, `256KB` or `1.5MB` (binary units)
+fn parse_rate(rate: &str) -> Result<u64, CliError> {
+    let lower = rate.trim().to_lowercase();
+    let lower = lower.strip_suffix("/s").unwrap_or(&lower);
+    let (number, unit) = lower
+        .find(|c: char| c.is_ascii_alphabetic())
+        .map_or((lower, ""), |i| lower.split_at(i));
+    let multiplier = match unit {
+        "" | "b" => 1.0,
+        "k" | "kb" => 1024.0,
+        "m" | "mb" => 1024.0 * 1024.0,
+        _ => {
+            return Err(CliError::InvalidValue(format!(
+                "invalid bandwidth '{}'",
+                rate
+            )));
+        }
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|n| *n > 0.0)
+        .map(|n| (n * multiplier) as u64)
+        .ok_or_else(|| CliError::InvalidValue(format!("invalid bandwidth '{}'", rate)))
+}
+
+fn parse_serve(
+    mut positional: impl Iterator<Item = String>,
+    options: &mut CommandOptions,
+) -> Result<Command, CliError> {
+    let mut serve = ServeArgs {
+        no_admin: std::mem::take(&mut options.no_admin),
+        allow_shutdown: std::mem::take(&mut options.allow_shutdown),
+        no_method_not_allowed: std::mem::take(&mut options.no_method_not_allowed),
+        explain_not_found: std::mem::take(&mut options.explain_not_found),
+        cors: std::mem::take(&mut options.cors),
+        tls_cert: options.tls_cert.take(),
+        tls_key: options.tls_key.take(),
+        oidc: std::mem::take(&mut options.oidc),
+        drain_timeout: options.drain_timeout.take(),
+        accept_delay: options.accept_delay.take(),
+        tls_handshake_delay: options.tls_handshake_delay.take(),
+        bandwidth: options.bandwidth.take(),
+        chaos: options.chaos.take(),
+        seed: options.seed.take(),
+        downtime: options.downtime.take(),
+        strip_prefix: options.strip_prefix.take(),
+        dump_requests: options.dump_requests.take(),
+        journal: options.journal.take(),
+        access_log: options.access_log.take(),
+        redact_headers: std::mem::take(&mut options.redact_headers),
+        persist: options.persist.take(),
+        mappings: options.mappings.take(),
+        watch: std::mem::take(&mut options.watch),
+        openapi: options.openapi.take(),
+        validate: std::mem::take(&mut options.validate),
+        wiremock: options.wiremock.take(),
+        har: options.har.take(),
+        har_latency: std::mem::take(&mut options.har_latency),
+        capture_dir: options.capture_dir.take(),
+        idempotency_key: options.idempotency_key.take(),
+        peers: std::mem::take(&mut options.peers),
+        proxy_to: options.proxy_to.take(),
+        fallback_proxy: options.fallback_proxy.take(),
+        static_dirs: std::mem::take(&mut options.static_dirs),
+        ..ServeArgs::default()
+    };
+
+    if serve.validate && serve.openapi.is_none() {
+        return Err(CliError::InvalidValue(
+            "--validate needs an --openapi document".to_string(),
+        ));
+    }
+
+    if serve.har_latency && serve.har.is_none() {
+        return Err(CliError::InvalidValue(
+            "--har-latency needs a --har archive".to_string(),
+        ));
+    }
+
+    if serve.tls_cert.is_some() != serve.tls_key.is_some() {
+        return Err(CliError::InvalidValue(
+            "--tls-cert and --tls-key must be given together".to_string(),
+        ));
+    }
+
+    if serve.tls_handshake_delay.is_some() && serve.tls_cert.is_none() {
+        return Err(CliError::InvalidValue(
+            "--tls-handshake-delay needs --tls-cert".to_string(),
+        ));
+    }
+
+    if let Some(port) = positional.next() {
+        serve.port = port.parse().map_err(|_| CliError::InvalidPort(port))?;
+    }
+    if let Some(dir) = positional.next() {
+        serve.resources_dir = dir;
+    }
+    if let Some(extra) = positional.next() {
+        return Err(CliError::UnexpectedArgument(extra));
+    }
+
+    Ok(Command::Serve(Box::new(serve)))
+}
+
+fn parse_import(
+    mut positional: impl Iterator<Item = String>,
+    options: &mut CommandOptions,
+) -> Result<Command, CliError> {
+    let format = positional
+        .next()
+        .ok_or(CliError::MissingArgument("<FORMAT>"))?
+        .parse()
+        .map_err(CliError::InvalidValue)?;
+    let file = positional
+        .next()
+        .ok_or(CliError::MissingArgument("<FILE>"))?;
+    if let Some(extra) = positional.next() {
+        return Err(CliError::UnexpectedArgument(extra));
+    }
+
+    Ok(Command::Import(ImportArgs {
+        format,
+        file: PathBuf::from(file),
+        out: options.take_out(),
+    }))
+}
+
+fn parse_export(
+    mut positional: impl Iterator<Item = String>,
+    options: &mut CommandOptions,
+) -> Result<Command, CliError> {
+    if let Some(extra) = positional.next() {
+        return Err(CliError::UnexpectedArgument(extra));
+    }
+
+    Ok(Command::Export(ExportArgs {
+        from: options
+            .from
+            .take()
+            .ok_or(CliError::MissingArgument("--from"))?,
+        out: options.take_out(),
+        format: options.format.take().unwrap_or_default(),
+    }))
+}
+
+fn parse_replay(
+    mut positional: impl Iterator<Item = String>,
+    options: &mut CommandOptions,
+) -> Result<Command, CliError> {
+    let recordings = positional
+        .next()
+        .ok_or(CliError::MissingArgument("<RECORDINGS_DIR>"))?;
+    if let Some(extra) = positional.next() {
+        return Err(CliError::UnexpectedArgument(extra));
+    }
+
+    Ok(Command::Replay(ReplayArgs {
+        recordings: PathBuf::from(recordings),
+        target: options
+            .target
+            .take()
+            .ok_or(CliError::MissingArgument("--target"))?,
+    }))
+}
@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::Path;
+
+use mimic_rs::mappings::{self, MAPPINGS_DIR};
+use mimic_rs::models::CreateExpectationRequest;
+use reqwest::{Client, Method};
+use serde_json::Value;
+use tracing::warn;
+
+use super::ReplayArgs;
+
+/// Re-sends recorded requests to a target and compares the responses
+///
+/// Returns `false` when at least one response differs from the recording
+/// or could not be fetched.
+pub async fn run(args: ReplayArgs) -> Result<bool, Box<dyn std::error::Error>> {
+    let mappings_dir = if args.recordings.join(MAPPINGS_DIR).is_dir() {
+        args.recordings.join(MAPPINGS_DIR)
+    } else {
+        args.recordings.clone()
+    };
+    let recordings = mappings::read_mappings(&mappings_dir)?;
+
+    let client = Client::new();
+    let target = args.target.trim_end_matches('/');
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+
+    for recording in &recordings {
+        if recording.path.contains(['*', '{']) {
+            warn!(
+                "Skipping {} {}: wildcard and template paths cannot be replayed",
+                recording.method, recording.path
+            );
+            skipped += 1;
+            continue;
+        }
+
+        match replay_one(&client, target, recording, &args.recordings).await {
+            Ok(differences) if differences.is_empty() => {
+                println!("OK       {} {}", recording.method, recording.path);
+                passed += 1;
+            }
+            Ok(differences) => {
+                println!("MISMATCH {} {}", recording.method, recording.path);
+                for difference in differences {
+                    println!("  {}", difference);
+                }
+                failed += 1;
+            }
+            Err(e) => {
+                println!("ERROR    {} {}", recording.method, recording.path);
+                println!("  {}", e);
+                failed += 1;
+            }
+        }
+    }
+
+    println!(
+        "\n{} passed, {} failed, {} skipped",
+        passed, failed, skipped
+    );
+
+    Ok(failed == 0)
+}
+
+async fn replay_one(
+    client: &Client,
+    target: &str,
+    recording: &CreateExpectationRequest,
+    resources_dir: &Path,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let method = Method::from_bytes(recording.method.as_bytes())?;
+    let mut request = client
+        .request(method, format!("{}{}", target, recording.path))
+        .query(&recording.query_params);
+    for (key, value) in &recording.headers {
+        request = request.header(key, value);
+    }
+    if let Some(body) = &recording.body {
+        request = request.body(body.clone());
+    }
+
+    let response = request.send().await?;
+    let mut differences = Vec::new();
+
+    let expected_status = recording.response.status_code;
+    if response.status().as_u16() != expected_status {
+        differences.push(format!(
+            "status: expected {}, got {}",
+            expected_status,
+            response.status().as_u16()
+        ));
+    }
+
+    for (key, expected) in &recording.response.headers {
+        match response.headers().get(key).and_then(|v| v.to_str().ok()) {
+            Some(actual) if actual == expected => {}
+            actual => differences.push(format!(
+                "header {}: expected {:?}, got {:?}",
+                key, expected, actual
+            )),
+        }
+    }
+
+    let actual_body = response.text().await?;
+    let expected_body = match (&recording.response.body, &recording.response.body_file) {
+        (Some(body), _) => Some(body.to_string()),
+        (None, Some(file_name)) => Some(fs::read_to_string(resources_dir.join(file_name))?),
+        (None, None) => None,
+    };
+
+    if let Some(expected_body) = expected_body {
+        match (
+            serde_json::from_str::<Value>(&expected_body),
+            serde_json::from_str::<Value>(&actual_body),
+        ) {
+            (Ok(expected), Ok(actual)) => diff_json(&expected, &actual, "", &mut differences),
+            _ if expected_body != actual_body => {
+                differences.push("body: content differs".to_string())
+            }
+            _ => {}
+        }
+    }
+
+    Ok(differences)
+}
+
+/// Collects differences between two JSON values as JSON pointer paths
+fn diff_json(expected: &Value, actual: &Value, pointer: &str, differences: &mut Vec<String>) {
+    match (expected, actual) {
+        (Value::Object(expected), Value::Object(actual)) => {
+            for (key, expected_value) in expected {
+                let child = format!("{}/{}", pointer, key);
+                match actual.get(key) {
+                    Some(actual_value) => {
+                        diff_json(expected_value, actual_value, &child, differences)
+                    }
+                    None => differences.push(format!("body {}: missing", child)),
+                }
+            }
+            for key in actual.keys().filter(|key| !expected.contains_key(*key)) {
+                differences.push(format!("body {}/{}: unexpected", pointer, key));
+            }
+        }
+        (Value::Array(expected), Value::Array(actual)) if expected.len() == actual.len() => {
+            for (index, (expected_value, actual_value)) in expected.iter().zip(actual).enumerate() {
+                diff_json(
+                    expected_value,
+                    actual_value,
+                    &format!("{}/{}", pointer, index),
+                    differences,
+                );
+            }
+        }
+        _ if expected != actual => differences.push(format!(
+            "body {}: expected {}, got {}",
+            if pointer.is_empty() { "/" } else { pointer },
+            expected,
+            actual
+        )),
+        _ => {}
+    }
+}
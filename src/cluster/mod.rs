@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use reqwest::Client;
+use tracing::{debug, info, warn};
+
+use crate::models::{ExpectationSource, MockExpectation};
+
+/// Keeps the expectations of several instances in sync
+///
+/// Expectations created, replaced or removed via the admin API and `/_reset`
+/// calls are forwarded to
+/// every peer's `/_cluster/*` endpoints, which apply them without forwarding
+/// them again. On start an instance copies the expectations of the first
+/// reachable peer, so nodes can join a running cluster.
+#[derive(Clone, Debug)]
+pub struct Cluster {
+    peers: Vec<String>,
+
+    client: Client,
+}
+
+impl Cluster {
+    /// Creates a cluster of this instance and `peers` (base URLs of other instances)
+    pub fn new<I, S>(peers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            peers: peers
+                .into_iter()
+                .map(|peer| peer.into().trim_end_matches('/').to_string())
+                .collect(),
+            client,
+        }
+    }
+
+    pub fn peers(&self) -> &[String] {
+        &self.peers
+    }
+
+    /// Sends a new expectation to all peers
+    pub(crate) async fn replicate_expectation(&self, expectation: &MockExpectation) {
+        for peer in &self.peers {
+            let result = self
+                .client
+                .post(format!("{}/_cluster/expectations", peer))
+                .json(expectation)
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status());
+            match result {
+                Ok(_) => debug!("Replicated expectation {} to {}", expectation.id, peer),
+                Err(e) => warn!("Failed to replicate expectation to {}: {}", peer, e),
+            }
+        }
+    }
+
+    /// Makes `expectations` the only expectations of all peers
+    pub(crate) async fn replicate_replace_all(&self, expectations: &[MockExpectation]) {
+        for peer in &self.peers {
+            let result = self
+                .client
+                .put(format!("{}/_cluster/expectations", peer))
+                .json(expectations)
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status());
+            match result {
+                Ok(_) => debug!("Replaced the expectations of {}", peer),
+                Err(e) => warn!("Failed to replace the expectations of {}: {}", peer, e),
+            }
+        }
+    }
+
+    /// Sends the replacement of the expectation with its id to all peers
+    pub(crate) async fn replicate_replace(&self, expectation: &MockExpectation) {
+        for peer in &self.peers {
+            let result = self
+                .client
+                .put(format!("{}/_cluster/expectations/{}", peer, expectation.id))
+                .json(expectation)
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status());
+            match result {
+                Ok(_) => debug!("Replicated replacement of {} to {}", expectation.id, peer),
+                Err(e) => warn!("Failed to replicate replacement to {}: {}", peer, e),
+            }
+        }
+    }
+
+    /// Removes the expectation with `id` from all peers
+    pub(crate) async fn replicate_remove(&self, id: &str) {
+        for peer in &self.peers {
+            let result = self
+                .client
+                .delete(format!("{}/_cluster/expectations/{}", peer, id))
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status());
+            match result {
+                Ok(_) => debug!("Replicated removal of {} to {}", id, peer),
+                Err(e) => warn!("Failed to replicate removal to {}: {}", peer, e),
+            }
+        }
+    }
+
+    /// Resets all peers
+    pub(crate) async fn replicate_reset(&self) {
+        for peer in &self.peers {
+            let result = self
+                .client
+                .post(format!("{}/_cluster/reset", peer))
+                .send()
+                .await
+                .and_then(|resp| resp.error_for_status());
+            if let Err(e) = result {
+                warn!("Failed to reset {}: {}", peer, e);
+            }
+        }
+    }
+
+    /// Fetches the runtime expectations of the first reachable peer
+    pub(crate) async fn pull(&self) -> Vec<MockExpectation> {
+        for peer in &self.peers {
+            let result = async {
+                self.client
+                    .get(format!("{}/_expectations", peer))
+                    .send()
+                    .await?
+                    .error_for_status()?
+                    .json::<Vec<MockExpectation>>()
+                    .await
+            }
+            .await;
+
+            match result {
+                Ok(expectations) => {
+                    let expectations: Vec<_> = expectations
+                        .into_iter()
+                        .filter(|e| e.source == ExpectationSource::SetupApi)
+                        .collect();
+                    info!(
+                        "Synchronized {} expectations from {}",
+                        expectations.len(),
+                        peer
+                    );
+                    return expectations;
+                }
+                Err(e) => debug!("Peer {} not available: {}", peer, e),
+            }
+        }
+
+        Vec::new()
+    }
+}
@@ -0,0 +1,39 @@
+use super::ReceivedRequest;
+use std::fmt;
+use std::sync::Arc;
+
+/// Custom matching logic, checked on top of method, path, query, headers and body
+///
+/// Implemented for closures, e.g. `|request: &ReceivedRequest| request.body.is_some()`.
+pub trait Matcher: Send + Sync {
+    fn matches(&self, request: &ReceivedRequest) -> bool;
+}
+
+impl<F> Matcher for F
+where
+    F: Fn(&ReceivedRequest) -> bool + Send + Sync,
+{
+    fn matches(&self, request: &ReceivedRequest) -> bool {
+        self(request)
+    }
+}
+
+/// A [`Matcher`] attached to an expectation
+#[derive(Clone)]
+pub struct CustomMatcher(pub Arc<dyn Matcher>);
+
+impl CustomMatcher {
+    pub fn new(matcher: impl Matcher + 'static) -> Self {
+        Self(Arc::new(matcher))
+    }
+
+    pub fn matches(&self, request: &ReceivedRequest) -> bool {
+        self.0.matches(request)
+    }
+}
+
+impl fmt::Debug for CustomMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomMatcher(..)")
+    }
+}
@@ -1,3 +1,9 @@
 mod handler;
+mod matcher;
+mod request;
+mod responder;
 
 pub use handler::*;
+pub use matcher::{CustomMatcher, Matcher};
+pub use request::ReceivedRequest;
+pub use responder::{AsyncResponderFn, Responder, ResponderFn};
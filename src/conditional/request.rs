@@ -0,0 +1,68 @@
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use crate::transform::IncomingRequest;
+
+/// The request a responder closure answers
+///
+/// Header names are lowercase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReceivedRequest {
+    pub method: String,
+
+    pub path: String,
+
+    /// Values captured by `{name}` segments of the expectation's path
+    pub path_params: HashMap<String, String>,
+
+    pub query_params: HashMap<String, String>,
+
+    pub headers: HashMap<String, String>,
+
+    /// `None` for bodies that are not UTF-8, see `raw_body`
+    pub body: Option<String>,
+
+    /// The body as received, also when it is binary
+    pub raw_body: Option<Bytes>,
+
+    /// When the request came in, by the server's clock
+    pub received_at: DateTime<Utc>,
+}
+
+impl ReceivedRequest {
+    pub(crate) fn new(request: &IncomingRequest, path_params: HashMap<String, String>) -> Self {
+        Self {
+            method: request.method.clone(),
+            path: request.path.clone(),
+            path_params,
+            query_params: request.query_params.clone(),
+            headers: request.headers.clone(),
+            body: request.body.clone(),
+            raw_body: request.raw_body.clone(),
+            received_at: request.received_at,
+        }
+    }
+
+    /// Value of a `{name}` path segment
+    pub fn path_param(&self, name: &str) -> Option<&str> {
+        self.path_params.get(name).map(String::as_str)
+    }
+
+    pub fn query_param(&self, name: &str) -> Option<&str> {
+        self.query_params.get(name).map(String::as_str)
+    }
+
+    /// Looks up a header, ignoring the case of `name`
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(&name.to_lowercase()).map(String::as_str)
+    }
+
+    /// The body parsed as JSON, `None` if it is missing or not JSON
+    pub fn json(&self) -> Option<Value> {
+        self.body
+            .as_deref()
+            .and_then(|body| serde_json::from_str(body).ok())
+    }
+}
@@ -0,0 +1,45 @@
+use super::ReceivedRequest;
+use crate::models::MockResponse;
+use futures_util::future::BoxFuture;
+use std::future::Future;
+use std::sync::Arc;
+
+/// Type of function building a response from the request
+pub type ResponderFn = Arc<dyn Fn(&ReceivedRequest) -> MockResponse + Send + Sync>;
+
+/// Type of function building a response asynchronously, e.g. from a database
+pub type AsyncResponderFn =
+    Arc<dyn Fn(ReceivedRequest) -> BoxFuture<'static, MockResponse> + Send + Sync>;
+
+/// Builds each response from the request it answers
+#[derive(Clone)]
+pub enum Responder {
+    Sync(ResponderFn),
+
+    Async(AsyncResponderFn),
+}
+
+impl Responder {
+    pub fn new<F>(handler: F) -> Self
+    where
+        F: Fn(&ReceivedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        Self::Sync(Arc::new(handler))
+    }
+
+    /// A responder whose closure returns a future, boxed here
+    pub fn new_async<F, Fut>(handler: F) -> Self
+    where
+        F: Fn(ReceivedRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = MockResponse> + Send + 'static,
+    {
+        Self::Async(Arc::new(move |request| Box::pin(handler(request))))
+    }
+
+    pub async fn respond(&self, request: ReceivedRequest) -> MockResponse {
+        match self {
+            Self::Sync(handler) => handler(&request),
+            Self::Async(handler) => handler(request).await,
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use regex::Regex;
+
+use crate::models::compile_wildcard;
+
+/// CORS rules applied to a set of paths
+#[derive(Clone, Debug)]
+pub struct CorsPolicy {
+    /// Allowed origins, `*` allows any origin
+    pub allowed_origins: Vec<String>,
+
+    pub allowed_methods: Vec<String>,
+
+    /// Allowed request headers, `*` allows whatever the preflight asks for
+    pub allowed_headers: Vec<String>,
+
+    pub exposed_headers: Vec<String>,
+
+    pub allow_credentials: bool,
+
+    pub max_age: Option<Duration>,
+}
+
+impl Default for CorsPolicy {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec!["*".to_string()],
+            allowed_methods: ["GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS"]
+                .iter()
+                .map(|m| m.to_string())
+                .collect(),
+            allowed_headers: vec!["*".to_string()],
+            exposed_headers: Vec::new(),
+            allow_credentials: false,
+            max_age: None,
+        }
+    }
+}
+
+impl CorsPolicy {
+    /// Allows any origin, the common methods and any request header
+    pub fn permissive() -> Self {
+        Self::default()
+    }
+
+    /// Restricts the allowed origins
+    pub fn allow_origins(mut self, origins: &[&str]) -> Self {
+        self.allowed_origins = origins.iter().map(|o| o.to_string()).collect();
+        self
+    }
+
+    /// Restricts the allowed methods
+    pub fn allow_methods(mut self, methods: &[&str]) -> Self {
+        self.allowed_methods = methods.iter().map(|m| m.to_uppercase()).collect();
+        self
+    }
+
+    /// Restricts the allowed request headers
+    pub fn allow_headers(mut self, headers: &[&str]) -> Self {
+        self.allowed_headers = headers.iter().map(|h| h.to_lowercase()).collect();
+        self
+    }
+
+    /// Sets the response headers readable by the browser
+    pub fn expose_headers(mut self, headers: &[&str]) -> Self {
+        self.exposed_headers = headers.iter().map(|h| h.to_string()).collect();
+        self
+    }
+
+    /// Allows cookies and authorization headers in cross-origin requests
+    pub fn allow_credentials(mut self) -> Self {
+        self.allow_credentials = true;
+        self
+    }
+
+    /// Sets how long browsers may cache preflight results
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn is_origin_allowed(&self, origin: &str) -> bool {
+        self.allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*" || allowed.eq_ignore_ascii_case(origin))
+    }
+
+    pub fn is_method_allowed(&self, method: &str) -> bool {
+        self.allowed_methods
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(method))
+    }
+
+    pub fn are_headers_allowed(&self, headers: &[String]) -> bool {
+        if self.allowed_headers.iter().any(|h| h == "*") {
+            return true;
+        }
+        headers
+            .iter()
+            .all(|header| self.allowed_headers.contains(&header.to_lowercase()))
+    }
+}
+
+/// Server wide CORS configuration
+///
+/// # Example
+/// ```
+/// # use mimic_rs::MockServer;
+/// # use mimic_rs::cors::{CorsConfig, CorsPolicy};
+/// let server = MockServer::new("./resources").with_cors(
+///     CorsConfig::new(CorsPolicy::permissive())
+///         .path(
+///             "/api/admin/*",
+///             CorsPolicy::default().allow_origins(&["https://admin.example.com"]),
+///         )
+///         .strict(),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct CorsConfig {
+    default_policy: CorsPolicy,
+
+    path_policies: Vec<(Regex, CorsPolicy)>,
+
+    strict: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self::new(CorsPolicy::default())
+    }
+}
+
+impl CorsConfig {
+    /// Creates a configuration applying `policy` to every path
+    pub fn new(policy: CorsPolicy) -> Self {
+        Self {
+            default_policy: policy,
+            path_policies: Vec::new(),
+            strict: false,
+        }
+    }
+
+    /// Uses a different policy for paths matching `pattern` (can contain wildcards '*')
+    ///
+    /// Path policies are checked in the order they were added.
+    pub fn path(mut self, pattern: &str, policy: CorsPolicy) -> Self {
+        match compile_wildcard(pattern) {
+            Ok(regex) => self.path_policies.push((regex, policy)),
+            Err(e) => tracing::error!("Failed to compile CORS path '{}': {}", pattern, e),
+        }
+        self
+    }
+
+    /// Rejects disallowed origins, methods and headers with 403
+    ///
+    /// Without strict mode the request is served without CORS headers and
+    /// the browser enforces the policy, like most real servers.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Returns the policy that applies to `path`
+    pub fn policy_for(&self, path: &str) -> &CorsPolicy {
+        self.path_policies
+            .iter()
+            .find(|(regex, _)| regex.is_match(path))
+            .map(|(_, policy)| policy)
+            .unwrap_or(&self.default_policy)
+    }
+}
@@ -0,0 +1,187 @@
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use super::CorsPolicy;
+use crate::server::MockServer;
+
+/// Marks a response with the CORS policy of the expectation that answered it
+#[derive(Clone)]
+pub(crate) struct ExpectationCors(pub CorsPolicy);
+
+/// Middleware answering preflights and adding CORS headers to responses
+///
+/// The policy of the answering expectation wins over the server wide one;
+/// strict mode only applies to the latter.
+pub(crate) async fn cors_middleware(
+    State(server): State<MockServer>,
+    req: Request,
+    next: Next,
+) -> Response {
+    // Requests without an Origin header are not cross-origin requests
+    let Some(origin) = req
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|v| v.to_str().ok())
+        .map(String::from)
+    else {
+        return next.run(req).await;
+    };
+
+    let path = req.uri().path().to_string();
+    let config = server.cors_config();
+    let strict = config.is_some_and(|config| config.is_strict());
+
+    let requested_method = (req.method() == Method::OPTIONS)
+        .then(|| req.headers().get(header::ACCESS_CONTROL_REQUEST_METHOD))
+        .flatten()
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    if let Some(requested_method) = requested_method {
+        let own_policy = server.expectation_cors(&requested_method, &path).await;
+        let strict = strict && own_policy.is_none();
+        let Some(policy) =
+            own_policy.or_else(|| config.map(|config| config.policy_for(&path).clone()))
+        else {
+            return next.run(req).await;
+        };
+        if !policy.is_origin_allowed(&origin) {
+            if strict {
+                return forbidden(format!("CORS origin {} is not allowed", origin));
+            }
+            return next.run(req).await;
+        }
+        return preflight_response(&policy, &origin, req.headers(), strict);
+    }
+
+    let server_policy = config.map(|config| config.policy_for(&path).clone());
+    if strict
+        && server_policy
+            .as_ref()
+            .is_some_and(|policy| !policy.is_origin_allowed(&origin))
+        && server
+            .expectation_cors(req.method().as_str(), &path)
+            .await
+            .is_none()
+    {
+        return forbidden(format!("CORS origin {} is not allowed", origin));
+    }
+
+    let mut response = next.run(req).await;
+    let policy = match response.extensions_mut().remove::<ExpectationCors>() {
+        Some(ExpectationCors(policy)) => policy,
+        None => match server_policy {
+            Some(policy) => policy,
+            None => return response,
+        },
+    };
+    if !policy.is_origin_allowed(&origin) {
+        return response;
+    }
+
+    apply_origin_headers(&policy, &origin, response.headers_mut());
+
+    if !policy.exposed_headers.is_empty()
+        && let Ok(value) = HeaderValue::from_str(&policy.exposed_headers.join(", "))
+    {
+        response
+            .headers_mut()
+            .insert(header::ACCESS_CONTROL_EXPOSE_HEADERS, value);
+    }
+
+    response
+}
+
+fn preflight_response(
+    policy: &CorsPolicy,
+    origin: &str,
+    request_headers: &HeaderMap,
+    strict: bool,
+) -> Response {
+    let requested_method = request_headers
+        .get(header::ACCESS_CONTROL_REQUEST_METHOD)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    let requested_headers: Vec<String> = request_headers
+        .get(header::ACCESS_CONTROL_REQUEST_HEADERS)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| {
+            v.split(',')
+                .map(|h| h.trim().to_lowercase())
+                .filter(|h| !h.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if strict && !policy.is_method_allowed(requested_method) {
+        return forbidden(format!("CORS method {} is not allowed", requested_method));
+    }
+    if strict && !policy.are_headers_allowed(&requested_headers) {
+        return forbidden(format!(
+            "CORS headers {} are not allowed",
+            requested_headers.join(", ")
+        ));
+    }
+
+    let mut response = Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .body(Body::empty())
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
+    let headers = response.headers_mut();
+
+    apply_origin_headers(policy, origin, headers);
+
+    if let Ok(value) = HeaderValue::from_str(&policy.allowed_methods.join(", ")) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_METHODS, value);
+    }
+
+    let allowed_headers = if policy.allowed_headers.iter().any(|h| h == "*") {
+        requested_headers.join(", ")
+    } else {
+        policy.allowed_headers.join(", ")
+    };
+    if !allowed_headers.is_empty()
+        && let Ok(value) = HeaderValue::from_str(&allowed_headers)
+    {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, value);
+    }
+
+    if let Some(max_age) = policy.max_age {
+        headers.insert(
+            header::ACCESS_CONTROL_MAX_AGE,
+            HeaderValue::from(max_age.as_secs()),
+        );
+    }
+
+    response
+}
+
+/// Adds `Access-Control-Allow-Origin` and related headers
+fn apply_origin_headers(policy: &CorsPolicy, origin: &str, headers: &mut HeaderMap) {
+    let any_origin = policy.allowed_origins.iter().any(|o| o == "*");
+
+    if any_origin && !policy.allow_credentials {
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_ORIGIN,
+            HeaderValue::from_static("*"),
+        );
+    } else if let Ok(value) = HeaderValue::from_str(origin) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, value);
+        headers.append(header::VARY, HeaderValue::from_static("Origin"));
+    }
+
+    if policy.allow_credentials {
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_CREDENTIALS,
+            HeaderValue::from_static("true"),
+        );
+    }
+}
+
+fn forbidden(message: String) -> Response {
+    (StatusCode::FORBIDDEN, message).into_response()
+}
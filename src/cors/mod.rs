@@ -0,0 +1,5 @@
+mod config;
+mod middleware;
+
+pub use config::*;
+pub(crate) use middleware::{ExpectationCors, cors_middleware};
@@ -0,0 +1,114 @@
+//! Extension points for downstream crates
+//!
+//! An [`Extension`] is registered on the server under its name and referenced
+//! from expectations by that name, either as an additional request matcher or
+//! as the generator of the response. Each reference carries a JSON `config`,
+//! so one extension can serve many stubs (e.g. one HMAC check, many secrets).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use tracing::warn;
+
+use crate::models::MockResponse;
+use crate::transform::IncomingRequest;
+
+/// Decides whether a request matches, on top of method, path, query, headers and body
+pub trait RequestMatcher: Send + Sync {
+    fn matches(&self, request: &IncomingRequest, config: &Value) -> bool;
+}
+
+/// Produces the response for a matched request
+pub trait ResponseGenerator: Send + Sync {
+    fn generate(&self, request: &IncomingRequest, config: &Value) -> MockResponse;
+}
+
+/// A named bundle of custom behavior
+///
+/// Implement [`RequestMatcher`] and/or [`ResponseGenerator`] and return
+/// `Some(self)` from the corresponding method.
+pub trait Extension: Send + Sync + 'static {
+    /// The name expectations use to refer to the extension
+    fn name(&self) -> &str;
+
+    fn as_matcher(&self) -> Option<&dyn RequestMatcher> {
+        None
+    }
+
+    fn as_generator(&self) -> Option<&dyn ResponseGenerator> {
+        None
+    }
+}
+
+/// Reference from an expectation to a registered extension
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExtensionRef {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Value::is_null", default)]
+    pub config: Value,
+}
+
+impl ExtensionRef {
+    pub fn new(name: &str, config: Value) -> Self {
+        Self {
+            name: name.to_string(),
+            config,
+        }
+    }
+}
+
+/// The extensions registered on a server, shared by its clones
+#[derive(Clone, Default)]
+pub struct ExtensionRegistry {
+    extensions: Arc<RwLock<HashMap<String, Arc<dyn Extension>>>>,
+}
+
+impl ExtensionRegistry {
+    /// Registers `extension`, replacing one with the same name
+    pub fn register(&self, extension: Arc<dyn Extension>) {
+        let name = extension.name().to_string();
+        let mut extensions = self.extensions.write().unwrap_or_else(|e| e.into_inner());
+        extensions.insert(name, extension);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let extensions = self.extensions.read().unwrap_or_else(|e| e.into_inner());
+        extensions.keys().cloned().collect()
+    }
+
+    fn get(&self, name: &str) -> Option<Arc<dyn Extension>> {
+        let extensions = self.extensions.read().unwrap_or_else(|e| e.into_inner());
+        extensions.get(name).cloned()
+    }
+
+    /// Runs the referenced matcher; unknown names never match
+    pub(crate) fn matches(&self, reference: &ExtensionRef, request: &IncomingRequest) -> bool {
+        let Some(extension) = self.get(&reference.name) else {
+            warn!(
+                "Unknown extension '{}' referenced as matcher",
+                reference.name
+            );
+            return false;
+        };
+        match extension.as_matcher() {
+            Some(matcher) => matcher.matches(request, &reference.config),
+            None => {
+                warn!("Extension '{}' is not a request matcher", reference.name);
+                false
+            }
+        }
+    }
+
+    /// Runs the referenced generator, `None` if it is not available
+    pub(crate) fn generate(
+        &self,
+        reference: &ExtensionRef,
+        request: &IncomingRequest,
+    ) -> Option<MockResponse> {
+        let extension = self.get(&reference.name)?;
+        let generator = extension.as_generator()?;
+        Some(generator.generate(request, &reference.config))
+    }
+}
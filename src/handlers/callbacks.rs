@@ -0,0 +1,18 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::server::MockServer;
+
+/// Handler for listing the callbacks sent after matches
+pub async fn handle_list_callbacks(State(server): State<MockServer>) -> Response {
+    Json(server.callback_deliveries().await).into_response()
+}
+
+/// Handler for forgetting the sent callbacks
+pub async fn handle_clear_callbacks(State(server): State<MockServer>) -> Response {
+    server.clear_callback_deliveries().await;
+    StatusCode::NO_CONTENT.into_response()
+}
@@ -0,0 +1,32 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::models::ChaosConfig;
+use crate::server::MockServer;
+
+/// Handler for reading the chaos configuration (`null` when disabled)
+pub async fn handle_get_chaos(State(server): State<MockServer>) -> Response {
+    Json(server.chaos().await).into_response()
+}
+
+/// Handler for enabling chaos mode
+pub async fn handle_set_chaos(
+    State(server): State<MockServer>,
+    Json(config): Json<ChaosConfig>,
+) -> Response {
+    if let Err(e) = config.validate() {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    server.set_chaos(Some(config)).await;
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// Handler for disabling chaos mode
+pub async fn handle_disable_chaos(State(server): State<MockServer>) -> Response {
+    server.set_chaos(None).await;
+    StatusCode::NO_CONTENT.into_response()
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+
+use crate::models::MockExpectation;
+use crate::server::MockServer;
+
+/// Handler for expectations replicated from a peer
+pub async fn handle_replicated_expectation(
+    State(server): State<MockServer>,
+    Json(expectation): Json<MockExpectation>,
+) -> impl IntoResponse {
+    server.add_replicated_expectation(expectation).await;
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for an import with `replace` replicated from a peer
+pub async fn handle_replicated_import(
+    State(server): State<MockServer>,
+    Json(expectations): Json<Vec<MockExpectation>>,
+) -> impl IntoResponse {
+    server.replace_replicated_expectations(expectations).await;
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for a replacement replicated from a peer
+///
+/// An expectation this node does not know yet is added, so nodes that
+/// missed its creation catch up.
+pub async fn handle_replicated_replace(
+    State(server): State<MockServer>,
+    Path(id): Path<String>,
+    Json(mut expectation): Json<MockExpectation>,
+) -> impl IntoResponse {
+    if !server.replace_expectation(&id, expectation.clone()).await {
+        expectation.id = id;
+        server.add_replicated_expectation(expectation).await;
+    }
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for a removal replicated from a peer
+pub async fn handle_replicated_remove(
+    State(server): State<MockServer>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    server.remove_expectation(&id).await;
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for a reset replicated from a peer
+pub async fn handle_replicated_reset(State(server): State<MockServer>) -> impl IntoResponse {
+    server.reset().await;
+
+    StatusCode::NO_CONTENT
+}
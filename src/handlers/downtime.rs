@@ -0,0 +1,32 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::models::DowntimeWindow;
+use crate::server::MockServer;
+
+/// Handler for listing the scheduled outages
+pub async fn handle_list_downtime(State(server): State<MockServer>) -> Response {
+    Json(server.downtime().await).into_response()
+}
+
+/// Handler for scheduling an outage
+pub async fn handle_add_downtime(
+    State(server): State<MockServer>,
+    Json(window): Json<DowntimeWindow>,
+) -> Response {
+    if let Err(e) = window.validate() {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    server.add_downtime(window).await;
+    StatusCode::NO_CONTENT.into_response()
+}
+
+/// Handler for removing all scheduled outages
+pub async fn handle_clear_downtime(State(server): State<MockServer>) -> Response {
+    server.clear_downtime().await;
+    StatusCode::NO_CONTENT.into_response()
+}
@@ -1,57 +1,451 @@
+use super::not_found;
+use crate::conditional::ReceivedRequest;
+use crate::cors::ExpectationCors;
+use crate::extension::ExtensionRegistry;
 use crate::models::MockExpectation;
 use crate::models::MockResponse;
-use crate::server::MockServer;
+use crate::models::ResponseScript;
+use crate::models::SCENARIO_STARTED;
+use crate::models::content_type_for;
+use crate::models::{
+    BodyVariant, GRPC_WEB_CONTENT_TYPE, GRPC_WEB_TEXT_CONTENT_TYPE, GrpcWebResponse, negotiate,
+    negotiate_language,
+};
+use crate::models::{
+    ChaosAction, ExpectContinue, MAX_RECORDED_BODY, RecordedResponse, SSE_CONTENT_TYPE,
+    SequenceEnd, SseStream, StreamBody,
+};
+use crate::openapi::ValidationReport;
+use crate::random::Random;
+use crate::server::{ConnectionFault, IdempotencyKey, InjectedFault, MockServer};
+use crate::state::{StateStore, StateUpdate};
+use crate::template::{self, TemplateContext};
+use crate::transform::IncomingRequest;
+use axum::body::HttpBody;
 use axum::{
     body::Body,
-    extract::State,
-    http::{HeaderMap, Request, StatusCode},
+    extract::{ConnectInfo, State},
+    http::{HeaderMap, Request, StatusCode, header},
     response::IntoResponse,
 };
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use futures_util::StreamExt;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path as FilePath;
+use std::time::{Duration, Instant};
 use tracing::{debug, error, info};
 
 /// Handler for processing dynamic requests
+///
+/// The response is attached to the request's log record before it is sent.
 pub async fn handle_dynamic_request(
     State(server): State<MockServer>,
     req: Request<Body>,
-) -> impl IntoResponse {
-    let method = req.method().clone();
-    let path = req.uri().path().to_string();
-    let query_string = req.uri().query();
-    let headers = req.headers().clone();
+) -> axum::response::Response {
+    let record_id = uuid::Uuid::new_v4().to_string();
+    let head = RequestHead {
+        method: req.method().to_string(),
+        path: req.uri().path().to_string(),
+        headers: req.headers().clone(),
+    };
+    let started = Instant::now();
+    let response = respond(server.clone(), req, &record_id).await;
+    record_response(&server, &record_id, &head, response, started.elapsed()).await
+}
 
-    info!("Received request: {} {}", method, path);
+/// Method, path and headers of a request as received, for the access log
+struct RequestHead {
+    method: String,
 
-    // Extract query params and headers
-    let query_params = extract_query_params(query_string);
-    let headers_map = extract_headers(&headers);
+    path: String,
 
-    // Now that we've extracted all needed data, we can consume req
-    let (_, body) = req.into_parts();
-    let body = extract_body_bytes(body).await;
+    headers: HeaderMap,
+}
 
-    // Record the request
+/// Marks a response as answered by the expectation with this id
+#[derive(Clone)]
+struct MatchedExpectation(String);
+
+/// Keeps status, headers, small text bodies and the answering expectation of
+/// `response` in the request log and writes the access log line
+///
+/// Only bodies of known size are read, streamed bodies pass through untouched.
+async fn record_response(
+    server: &MockServer,
+    record_id: &str,
+    head: &RequestHead,
+    response: axum::response::Response,
+    latency: Duration,
+) -> axum::response::Response {
+    let (mut parts, body) = response.into_parts();
+    let matched = parts.extensions.remove::<MatchedExpectation>();
+
+    let buffered = body
+        .size_hint()
+        .exact()
+        .is_some_and(|len| len <= MAX_RECORDED_BODY as u64);
+    let (body, text) = if buffered {
+        match axum::body::to_bytes(body, MAX_RECORDED_BODY).await {
+            Ok(bytes) => {
+                let text = (!bytes.is_empty())
+                    .then(|| String::from_utf8(bytes.to_vec()).ok())
+                    .flatten();
+                (Body::from(bytes), text)
+            }
+            Err(e) => {
+                error!("Failed to read response body for the request log: {}", e);
+                (Body::empty(), None)
+            }
+        }
+    } else {
+        (body, None)
+    };
+
+    let recorded = RecordedResponse {
+        expectation_id: matched.map(|MatchedExpectation(id)| id),
+        status: parts.status.as_u16(),
+        headers: extract_headers(&parts.headers),
+        body: text,
+        latency_ms: latency.as_secs_f64() * 1000.0,
+    };
+    server.log_access(
+        &head.method,
+        &head.path,
+        extract_headers(&head.headers),
+        &recorded,
+    );
     server
-        .record_request(
-            method.to_string(),
-            path.clone(),
-            &query_params,
-            &headers_map,
-            body.as_deref(),
-        )
+        .record_response(record_id, &head.method, recorded)
         .await;
 
-    let expectations = server.get_expectations_by_method(method.as_str()).await;
-    if let Some(expectation) = find_matching_expectation(
-        &expectations,
-        &path,
-        &query_params,
-        &headers_map,
-        body.as_deref(),
-    ) {
-        return create_response(expectation, &server, server.resource_dir()).await;
+    axum::response::Response::from_parts(parts, body)
+}
+
+async fn respond(
+    server: MockServer,
+    req: Request<Body>,
+    record_id: &str,
+) -> axum::response::Response {
+    let (parts, body) = req.into_parts();
+    let connection = parts
+        .extensions
+        .get::<ConnectInfo<ConnectionFault>>()
+        .map(|ConnectInfo(connection)| connection.clone());
+
+    info!("Received request: {} {}", parts.method, parts.uri.path());
+
+    // Extract query params and headers
+    let original = IncomingRequest {
+        method: parts.method.to_string(),
+        path: parts.uri.path().to_string(),
+        query_params: extract_query_params(parts.uri.query()),
+        headers: extract_headers(&parts.headers),
+        body: None,
+        raw_body: None,
+        received_at: server.clock().now(),
+    };
+
+    if original
+        .headers
+        .get("expect")
+        .is_some_and(|expect| expect.eq_ignore_ascii_case("100-continue"))
+    {
+        let mut request = original.clone();
+        server.transform_request(&mut request);
+
+        let expectations = server.get_expectations_by_method(&request.method).await;
+        let mode = expectations
+            .iter()
+            .filter(|exp| {
+                matches_request_line(exp, &request.path, &request.query_params, &request.headers)
+            })
+            .find_map(|exp| exp.expect_continue.clone());
+        match mode {
+            Some(ExpectContinue::Reject) => {
+                debug!(
+                    "Rejecting 100-continue for {} {}",
+                    request.method, request.path
+                );
+                server
+                    .record_request(
+                        record_id,
+                        request.method,
+                        request.path,
+                        &request.query_params,
+                        &request.headers,
+                        None,
+                    )
+                    .await;
+                return StatusCode::EXPECTATION_FAILED.into_response();
+            }
+            Some(ExpectContinue::Delay { delay_ms }) => {
+                debug!(
+                    "Delaying 100 Continue for {} {} by {}ms",
+                    request.method, request.path, delay_ms
+                );
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            }
+            Some(ExpectContinue::Accept) | None => {}
+        }
+    }
+
+    let bytes = extract_body_bytes(body).await;
+    let raw_body = bytes.clone();
+    let upload = match &bytes {
+        Some(bytes) => {
+            server
+                .capture_upload(
+                    original.headers.get("content-type").map(String::as_str),
+                    bytes,
+                )
+                .await
+        }
+        None => None,
+    };
+    let mut request = IncomingRequest {
+        body: bytes
+            .as_ref()
+            .and_then(|bytes| match String::from_utf8(bytes.to_vec()) {
+                Ok(body) => Some(body),
+                Err(_) => {
+                    debug!("Request body is not UTF-8, only body_bytes can match it");
+                    None
+                }
+            }),
+        raw_body: bytes,
+        ..original
+    };
+    server.transform_request(&mut request);
+    let method = &request.method;
+    let path = &request.path;
+    let query_params = &request.query_params;
+    let headers_map = &request.headers;
+    let body = &request.body;
+
+    // Record the request
+    match upload {
+        Some(upload) => {
+            server
+                .record_upload(
+                    record_id,
+                    method.clone(),
+                    path.clone(),
+                    query_params,
+                    headers_map,
+                    upload,
+                )
+                .await
+        }
+        None => {
+            server
+                .record_request(
+                    record_id,
+                    method.clone(),
+                    path.clone(),
+                    query_params,
+                    headers_map,
+                    body.as_deref(),
+                )
+                .await
+        }
+    }
+
+    if let Some(report) = server.validate_request(&request).await {
+        debug!(
+            "Rejecting {} {}: violates {}",
+            method, path, report.operation
+        );
+        return validation_error(report);
+    }
+
+    if let Some((window, remaining)) = server.active_downtime(path).await {
+        if window.drop_connections {
+            debug!("Downtime: dropping connection for {} {}", method, path);
+            return connection_fault();
+        }
+        debug!(
+            "Downtime: answering {} {} with {}",
+            method, path, window.status
+        );
+        return downtime_error(window.status, remaining);
+    }
+
+    if let Some(chaos) = server.chaos().await {
+        let outcome = chaos.roll(server.random());
+        if let Some(delay) = outcome.delay {
+            debug!("Chaos: delaying {} {} by {:?}", method, path, delay);
+            tokio::time::sleep(delay).await;
+        }
+        match outcome.action {
+            ChaosAction::Pass => {}
+            ChaosAction::Error(status) => {
+                debug!("Chaos: answering {} {} with {}", method, path, status);
+                return chaos_error(status);
+            }
+            ChaosAction::Fault => {
+                debug!("Chaos: dropping connection for {} {}", method, path);
+                return connection_fault();
+            }
+        }
+    }
+
+    // A repeated idempotency key gets the response stored for it
+    let idempotency_key = server.idempotency().and_then(|store| store.key(&request));
+    if let (Some(store), Some(key)) = (server.idempotency(), &idempotency_key)
+        && let Some(response) = store.replay(key).await
+    {
+        debug!("Replaying stored response for {} {}", method, path);
+        return response;
+    }
+
+    let expectations = server.get_expectations_by_method(method).await;
+    let mut candidates = find_matching_expectations(&expectations, &request, server.extensions());
+
+    // HEAD falls back to the GET stubs unless it has stubs of its own
+    let head_from_get = method == "HEAD" && candidates.is_empty();
+    if head_from_get {
+        let expectations = server.get_expectations_by_method("GET").await;
+        candidates = find_matching_expectations(&expectations, &request, server.extensions());
+    }
+
+    // Scenario expectations only match in their required state
+    if candidates.iter().any(|exp| exp.scenario.is_some()) {
+        let scenario_states = server.scenario_states().await;
+        candidates.retain(|exp| {
+            exp.scenario.as_ref().is_none_or(|rule| {
+                let current = scenario_states
+                    .get(&rule.name)
+                    .map_or(SCENARIO_STARTED, String::as_str);
+                rule.is_active(current)
+            })
+        });
+    }
+
+    // Exhausted sequences no longer match
+    if candidates
+        .iter()
+        .any(|exp| exp.rotate_end == SequenceEnd::NotFound)
+    {
+        let counters = server.response_counters.lock().await;
+        candidates.retain(|exp| {
+            exp.rotate_end != SequenceEnd::NotFound
+                || exp.rotate.is_empty()
+                || counters
+                    .get(&exp.id)
+                    .is_none_or(|&served| served < exp.rotate.len())
+        });
+    }
+
+    // Higher priority first, then the most specific; ties keep their order
+    candidates.sort_by_key(|exp| std::cmp::Reverse(exp.rank()));
+
+    // The first candidate accepting the bearer token wins; otherwise the
+    // rejection of the first token-protected candidate is returned
+    let mut rejection = None;
+    for expectation in candidates {
+        if let Some(requirement) = &expectation.jwt
+            && let Err(response) = requirement.validate_at(
+                headers_map.get("authorization").map(String::as_str),
+                server.oidc(),
+                server.clock().now(),
+            )
+        {
+            debug!("Bearer token rejected for {} {}", method, path);
+            rejection.get_or_insert(response);
+            continue;
+        }
+        // Expectations limited by `times` fall through once used up
+        if !server.claim_match(&expectation).await {
+            debug!("Expectation {} used up", expectation.id);
+            continue;
+        }
+
+        let expectation_id = expectation.id.clone();
+        let cors = expectation.cors.clone();
+        let response = create_response(expectation, &server, &request, connection.as_ref()).await;
+        let response = head_response(response, head_from_get);
+        let mut response = remember_response(&server, idempotency_key, response).await;
+        response
+            .extensions_mut()
+            .insert(MatchedExpectation(expectation_id));
+        if let Some(policy) = cors {
+            response.extensions_mut().insert(ExpectationCors(policy));
+        }
+        return response;
+    }
+
+    if let Some(response) = rejection {
+        return create_response_from_mock(*response, server.resource_dir()).await;
+    }
+
+    if matches!(method.as_str(), "GET" | "HEAD")
+        && let Some(file) = server.static_file(path)
+    {
+        let response = serve_static_file(&file).await;
+        return head_response(response, method == "HEAD");
+    }
+
+    if let Some(proxy) = server.proxy() {
+        return match proxy.forward(&request, parts.uri.query(), raw_body).await {
+            Ok(proxied) => (proxied.status, proxied.headers, proxied.body).into_response(),
+            Err(e) => {
+                error!(
+                    "Failed to proxy {} {} to {}: {}",
+                    method,
+                    path,
+                    proxy.base_url(),
+                    e
+                );
+                (
+                    StatusCode::BAD_GATEWAY,
+                    format!("Failed to proxy {} {}: {}", method, path, e),
+                )
+                    .into_response()
+            }
+        };
+    }
+
+    let expectations = server.get_expectations().await;
+    let allowed = allowed_methods(&expectations, path);
+    let near_misses = if method == "OPTIONS" && !allowed.is_empty() {
+        Vec::new()
+    } else {
+        let near_misses = not_found::near_misses(
+            &request,
+            &expectations,
+            &server.scenario_states().await,
+            server.extensions(),
+        );
+        server
+            .record_unmatched(&request, near_misses.first().cloned())
+            .await;
+        near_misses
+    };
+    if !allowed.is_empty() {
+        let allow = allowed.join(", ");
+        // Real servers answer OPTIONS for any path they serve
+        if method == "OPTIONS" {
+            debug!("Answering OPTIONS {} with Allow: {}", path, allow);
+            return (StatusCode::NO_CONTENT, [(header::ALLOW, allow)]).into_response();
+        }
+        // A stub for this very method only missed on query, headers or body
+        if server.method_not_allowed_enabled() && !allowed.contains(&method.as_str()) {
+            return (
+                StatusCode::METHOD_NOT_ALLOWED,
+                [(header::ALLOW, allow)],
+                format!("Method {} not allowed for {}", method, path),
+            )
+                .into_response();
+        }
+    }
+
+    if server.not_found_diagnostics_enabled() {
+        return not_found::diagnostic_response(&request, near_misses);
+    }
+    if let Some(response) = server.not_found_response() {
+        return create_response_from_mock(response.clone(), server.resource_dir()).await;
     }
 
     // If no matching expectation is found, return 404
@@ -95,25 +489,12 @@ fn extract_headers(headers: &HeaderMap) -> HashMap<String, String> {
 }
 
 /// Extracts request body from body parts
-async fn extract_body_bytes(body: Body) -> Option<String> {
+async fn extract_body_bytes(body: Body) -> Option<axum::body::Bytes> {
     // Set a reasonable limit (10MB)
     const MAX_SIZE: usize = 10 * 1024 * 1024;
 
     match axum::body::to_bytes(body, MAX_SIZE).await {
-        Ok(bytes) => {
-            if bytes.is_empty() {
-                None
-            } else {
-                // Convert bytes to string
-                match String::from_utf8(bytes.to_vec()) {
-                    Ok(body_string) => Some(body_string),
-                    Err(e) => {
-                        error!("Failed to convert request body to UTF-8: {}", e);
-                        None
-                    }
-                }
-            }
-        }
+        Ok(bytes) => (!bytes.is_empty()).then_some(bytes),
         Err(e) => {
             error!("Failed to read request body: {}", e);
             None
@@ -121,58 +502,166 @@ async fn extract_body_bytes(body: Body) -> Option<String> {
     }
 }
 
-/// Finds matching expectation - simplified because we already filtered by method
-fn find_matching_expectation(
-    expectations: &[MockExpectation],
+/// Checks everything but the body: path, query parameters and headers
+fn matches_request_line(
+    exp: &MockExpectation,
     path: &str,
     query_params: &HashMap<String, String>,
     headers: &HashMap<String, String>,
-    body: Option<&str>,
-) -> Option<MockExpectation> {
+) -> bool {
+    exp.matches_path(path)
+        && exp
+            .query_params
+            .iter()
+            .all(|(key, value)| query_params.get(key) == Some(value))
+        && exp
+            .headers
+            .iter()
+            .all(|(key, value)| headers.get(key) == Some(value))
+        && exp
+            .query_matchers
+            .iter()
+            .all(|(key, matcher)| matcher.matches(query_params.get(key).map(String::as_str)))
+        && exp.header_matchers.iter().all(|(name, matcher)| {
+            matcher.matches(headers.get(&name.to_lowercase()).map(String::as_str))
+        })
+}
+
+/// Finds matching expectations in order - simplified because we already filtered by method
+fn find_matching_expectations(
+    expectations: &[MockExpectation],
+    request: &IncomingRequest,
+    extensions: &ExtensionRegistry,
+) -> Vec<MockExpectation> {
+    let mut matching = Vec::new();
     for exp in expectations {
-        // Check path (supports regex)
-        let path_matches = if let Some(regex) = &exp.path_regex {
-            regex.is_match(path)
-        } else {
-            exp.path == path
-        };
+        if !matches_request_line(exp, &request.path, &request.query_params, &request.headers) {
+            continue;
+        }
 
-        if !path_matches {
+        if let Some(exp_body) = &exp.body
+            && request.body.as_deref() != Some(exp_body.as_str())
+        {
             continue;
         }
 
-        let mut query_params_match = true;
-        for (key, value) in &exp.query_params {
-            if query_params.get(key) != Some(value) {
-                query_params_match = false;
-                break;
-            }
+        if let Some(json_body) = &exp.json_body
+            && !json_body.matches(request.body.as_deref())
+        {
+            continue;
         }
-        if !query_params_match {
+
+        if exp.body_base64.is_some() && !exp.matches_body_bytes(request.raw_body.as_deref()) {
             continue;
         }
 
-        let mut headers_match = true;
-        for (key, value) in &exp.headers {
-            if headers.get(key) != Some(value) {
-                headers_match = false;
-                break;
-            }
+        if let Some(graphql) = &exp.graphql
+            && !graphql.matches(request.body.as_deref(), &request.query_params)
+        {
+            continue;
+        }
+
+        if !exp
+            .body_matchers
+            .iter()
+            .all(|matcher| matcher.matches(request.body.as_deref()))
+        {
+            continue;
         }
-        if !headers_match {
+
+        if !exp
+            .matchers
+            .iter()
+            .all(|matcher| extensions.matches(matcher, request))
+        {
             continue;
         }
 
-        if let Some(exp_body) = &exp.body {
-            if body != Some(exp_body.as_str()) {
+        if !exp.custom_matchers.is_empty() {
+            let received = ReceivedRequest::new(request, exp.path_params(&request.path));
+            if !exp
+                .custom_matchers
+                .iter()
+                .all(|matcher| matcher.matches(&received))
+            {
                 continue;
             }
         }
 
-        return Some(exp.clone());
+        matching.push(exp.clone());
+    }
+
+    matching
+}
+
+/// Drops the body of a GET response served for a HEAD request
+///
+/// The `Content-Length` of the GET body is kept so clients can probe the
+/// size of a resource when it is known up front.
+fn head_response(response: axum::response::Response, from_get: bool) -> axum::response::Response {
+    if !from_get {
+        return response;
+    }
+
+    // The body is dropped unread, a streamed one may never end
+    let (mut parts, body) = response.into_parts();
+    if !parts.headers.contains_key(header::CONTENT_LENGTH)
+        && let Some(length) = body.size_hint().exact()
+    {
+        parts.headers.insert(header::CONTENT_LENGTH, length.into());
+    }
+    axum::response::Response::from_parts(parts, Body::empty())
+}
+
+/// Answers with the content of a file from a static directory
+async fn serve_static_file(file: &FilePath) -> axum::response::Response {
+    match tokio::fs::read(file).await {
+        Ok(content) => {
+            debug!("Serving static file {}", file.display());
+            let content_type = content_type_for(&file.to_string_lossy());
+            ([(header::CONTENT_TYPE, content_type)], content).into_response()
+        }
+        Err(e) => {
+            error!("Error reading file {}: {}", file.display(), e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Error reading file: {}", e),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Stores a mocked response for the request's idempotency key, if it has one
+async fn remember_response(
+    server: &MockServer,
+    key: Option<IdempotencyKey>,
+    response: axum::response::Response,
+) -> axum::response::Response {
+    match (server.idempotency(), key) {
+        (Some(store), Some(key)) => store.store(key, response).await,
+        _ => response,
+    }
+}
+
+/// The methods listed in the `Allow` header for a path, empty without stubs
+fn allowed_methods<'a>(expectations: &'a [MockExpectation], path: &str) -> Vec<&'a str> {
+    let mut methods: Vec<&str> = expectations
+        .iter()
+        .filter(|exp| exp.matches_path(path))
+        .map(|exp| exp.method.as_str())
+        .collect();
+    if methods.is_empty() {
+        return methods;
     }
 
-    None
+    if methods.contains(&"GET") {
+        methods.push("HEAD");
+    }
+    methods.push("OPTIONS");
+    methods.sort_unstable();
+    methods.dedup();
+    methods
 }
 
 /// Create response from mock
@@ -187,22 +676,32 @@ async fn create_response_from_mock(
         builder = builder.header(key, value);
     }
 
-    if let Some(file_name) = &response.body_file {
-        let file_path = resource_dir.join(file_name);
-        match fs::read_to_string(&file_path) {
-            Ok(content) => {
-                debug!("Loaded file {} for response", file_path.display());
-                response.cache_file_content(content);
-            }
-            Err(e) => {
-                error!("Error reading file {}: {}", file_path.display(), e);
-                return (
-                    StatusCode::INTERNAL_SERVER_ERROR,
-                    format!("Error reading file: {}", e),
-                )
-                    .into_response();
-            }
+    if let Err(e) = load_body_file(&mut response, resource_dir) {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Error reading file: {}", e),
+        )
+            .into_response();
+    }
+
+    if let Some(encoded) = &response.body_base64 {
+        let Ok(bytes) = STANDARD.decode(encoded) else {
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Response body_base64 is not valid base64",
+            )
+                .into_response();
+        };
+        if !response
+            .headers
+            .keys()
+            .any(|key| key.eq_ignore_ascii_case("content-type"))
+        {
+            builder = builder.header("Content-Type", "application/octet-stream");
         }
+        return builder
+            .body(Body::from(bytes))
+            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
     }
 
     if let Some(json_str) = response.get_json_string() {
@@ -218,24 +717,583 @@ async fn create_response_from_mock(
             .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
     }
 
+    if let Some(content) = &response.cached_file_bytes {
+        return builder
+            .body(axum::body::Body::from(content.clone()))
+            .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response());
+    }
+
     builder
         .body(axum::body::Body::empty())
         .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
 }
 
+/// Whether the body or the loaded body file contains placeholders
+fn needs_rendering(response: &MockResponse) -> bool {
+    match (&response.body, &response.cached_json_content) {
+        (Some(body), _) => template::has_placeholders(body),
+        (None, Some(json)) => template::has_placeholders(json),
+        (None, None) => response
+            .cached_file_content
+            .as_ref()
+            .is_some_and(|content| content.contains("{{")),
+    }
+}
+
+/// Renders placeholders in the body, or in the content of the body file
+fn render_templates(response: &mut MockResponse, context: &TemplateContext) {
+    if let Some(body) = &response.body {
+        response.body = Some(template::render_value(body, context));
+    } else if let Some(json) = &response.cached_json_content {
+        let rendered = template::render_value(json, context);
+        response.cached_file_content = Some(rendered.to_string());
+        response.cached_json_content = Some(rendered);
+    } else if let Some(content) = &response.cached_file_content {
+        response.cached_file_content = Some(template::render_str(content, context));
+    }
+}
+
+/// Reads the body file into the response's cache and clears `body_file`
+fn load_body_file(response: &mut MockResponse, resource_dir: &FilePath) -> std::io::Result<()> {
+    let Some(file_name) = response.body_file.take() else {
+        return Ok(());
+    };
+
+    let file_path = resource_dir.join(&file_name);
+    match fs::read(&file_path) {
+        Ok(content) => {
+            debug!("Loaded file {} for response", file_path.display());
+            response.cache_file(content);
+            Ok(())
+        }
+        Err(e) => {
+            error!("Error reading file {}: {}", file_path.display(), e);
+            Err(e)
+        }
+    }
+}
+
 /// Creates HTTP response based on expectation
 async fn create_response(
     expectation: MockExpectation,
     server: &MockServer,
-    resource_dir: &FilePath,
+    request: &IncomingRequest,
+    connection: Option<&ConnectionFault>,
 ) -> axum::response::Response {
-    if let Some(cond_id) = &expectation.response.conditional_id {
+    let path = request.path.as_str();
+    let query_params = &request.query_params;
+    let headers = &request.headers;
+    let resource_dir = server.resource_dir();
+    let path_params = expectation.path_params(path);
+    let rate_limit_status = match &expectation.rate_limit {
+        Some(rate_limit) => {
+            let mut rate_limits = server.rate_limits.lock().await;
+            let status = rate_limits
+                .entry(expectation.id.clone())
+                .or_default()
+                .acquire(rate_limit, server.clock().now());
+            if status.is_exceeded() {
+                debug!("Rate limit exceeded for expectation {}", expectation.id);
+                return create_response_from_mock(status.too_many_requests(), resource_dir).await;
+            }
+            Some(status)
+        }
+        None => None,
+    };
+
+    if let Some(rule) = &expectation.scenario
+        && let Some(next_state) = &rule.new_state
+    {
+        debug!("Scenario {} moves to state {}", rule.name, next_state);
+        server.set_scenario_state(&rule.name, next_state).await;
+    }
+
+    if !expectation.state_updates.is_empty() {
+        apply_state_updates(
+            &expectation.state_updates,
+            server.state(),
+            server.random(),
+            request,
+            &path_params,
+        )
+        .await;
+    }
+
+    if !expectation.callbacks.is_empty() {
+        let state = server.state().snapshot().await;
+        let context = TemplateContext {
+            state: &state,
+            request,
+            path_params: &path_params,
+            random: server.random(),
+        };
+        for callback in &expectation.callbacks {
+            server.send_callback(&expectation.id, callback.render(&context));
+        }
+    }
+
+    let mut response = if expectation.rotate.is_empty() {
+        expectation.response
+    } else {
+        let mut counters = server.response_counters.lock().await;
+        let served = counters.entry(expectation.id.clone()).or_insert(0);
+        let last = expectation.rotate.len() - 1;
+        // A concurrent request may have used up the sequence since matching
+        let index = expectation
+            .rotate_end
+            .index(*served, expectation.rotate.len())
+            .unwrap_or(last);
+        *served += 1;
+        expectation.rotate[index].clone()
+    };
+    if let Some(cond_id) = &response.conditional_id {
         let mut conditional_responses = server.conditional_responses.write().await;
         if let Some(conditional) = conditional_responses.get_mut(cond_id) {
-            let response = conditional.generate_response();
-            return create_response_from_mock(response, resource_dir).await;
+            response = conditional.generate_response();
+        }
+    }
+    if let Some(responder_id) = &response.responder_id
+        && let Some(responder) = server.get_responder(responder_id).await
+    {
+        response = responder
+            .respond(ReceivedRequest::new(request, path_params.clone()))
+            .await;
+    }
+
+    if let Some(reference) = response.generator.clone() {
+        match server.extensions().generate(&reference, request) {
+            Some(generated) => response = generated,
+            None => {
+                error!(
+                    "Extension '{}' is not a registered generator",
+                    reference.name
+                );
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Unknown response generator '{}'", reference.name),
+                )
+                    .into_response();
+            }
+        }
+    }
+
+    if let Some(script) = response.script.take() {
+        let state = server.state().snapshot().await;
+        let context = TemplateContext {
+            state: &state,
+            request,
+            path_params: &path_params,
+            random: server.random(),
+        };
+        let outcome = match &script {
+            ResponseScript::Wasm(file) => {
+                server
+                    .scripts()
+                    .run_wasm(&resource_dir.join(file), &context)
+                    .await
+            }
+            script => match script.load(resource_dir) {
+                Ok(source) => server.scripts().run(&source, &context).await,
+                Err(e) => {
+                    error!("Error reading script: {}", e);
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        format!("Error reading script: {}", e),
+                    )
+                        .into_response();
+                }
+            },
+        };
+        match outcome {
+            Ok(outcome) => {
+                if !outcome.state_changes.is_empty() {
+                    server.state().apply(outcome.state_changes).await;
+                }
+                let mut scripted = outcome.response;
+                for (key, value) in std::mem::take(&mut response.headers) {
+                    if !scripted
+                        .headers
+                        .keys()
+                        .any(|k| k.eq_ignore_ascii_case(&key))
+                    {
+                        scripted.headers.insert(key, value);
+                    }
+                }
+                scripted.delay = response.delay.take();
+                response = scripted;
+            }
+            Err(e) => {
+                error!("Script for {} failed: {}", path, e);
+                return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response();
+            }
+        }
+    }
+
+    if let Some(delay) = &response.delay {
+        let delay = delay.pick(server.random());
+        debug!("Delaying response for {} by {:?}", path, delay);
+        tokio::time::sleep(delay).await;
+    }
+
+    // Nothing reaches the client, so headers are not worth transforming
+    if let Some(fault) = response.fault {
+        debug!("Injecting {:?} for {}", fault, path);
+        let mut response = match connection {
+            Some(connection) => {
+                connection.inject(fault);
+                StatusCode::OK.into_response()
+            }
+            // Served without the mock server's listener
+            None => connection_fault(),
+        };
+        response.extensions_mut().insert(InjectedFault);
+        return response;
+    }
+
+    if response.grpc_web.is_some() || response.stream.is_some() || response.sse.is_some() {
+        if let Some(status) = &rate_limit_status {
+            status.apply_headers(&mut response);
+        }
+        // These bodies are produced while sending, only headers can be transformed
+        for transform in &expectation.response_transforms {
+            if !transform.uses_body() {
+                transform.apply(&mut response);
+            }
+        }
+        server.transform_response_headers(&mut response);
+    }
+
+    if let Some(grpc_web) = &response.grpc_web {
+        return create_grpc_web_response(&response, grpc_web, headers);
+    }
+
+    if let Some(stream) = response.stream.take() {
+        return create_stream_response(&response, stream);
+    }
+
+    if let Some(sse) = response.sse.take() {
+        return create_sse_response(&response, sse);
+    }
+
+    if !response.languages.is_empty() {
+        let accept_language = headers.get("accept-language").map(String::as_str);
+        if let Some(variant) = negotiate_language(&response.languages, accept_language) {
+            debug!("Serving {} variant", variant.language);
+            response.body = variant.body.clone();
+            response.body_file = variant.body_file.clone();
+            response.cached_json_content = None;
+            response.cached_file_content = None;
+            response.cached_file_bytes = None;
+            response
+                .headers
+                .insert("Content-Language".to_string(), variant.language.clone());
+        }
+        add_vary(&mut response.headers, "Accept-Language");
+    }
+
+    if !response.variants.is_empty() {
+        match negotiate(
+            &response.variants,
+            headers.get("accept").map(String::as_str),
+        ) {
+            Some(variant) => {
+                let content_type = variant.content_type().to_string();
+                response.body = None;
+                response.body_file = None;
+                response.cached_json_content = None;
+                response.cached_file_content = Some(variant.render());
+                response.cached_file_bytes = None;
+                response
+                    .headers
+                    .retain(|key, _| !key.eq_ignore_ascii_case("content-type"));
+                response
+                    .headers
+                    .insert("Content-Type".to_string(), content_type);
+                add_vary(&mut response.headers, "Accept");
+            }
+            None => return not_acceptable(&response.variants),
+        }
+    }
+
+    if let Some(pagination) = &response.paginate {
+        let page = pagination.render(path, query_params);
+        response.body = Some(page.body);
+        response.headers.extend(page.headers);
+    }
+
+    if let Err(e) = load_body_file(&mut response, resource_dir) {
+        return (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Error reading file: {}", e),
+        )
+            .into_response();
+    }
+
+    if let Some(multipart) = response.multipart.take() {
+        match multipart.render(resource_dir) {
+            Ok((content_type, body)) => {
+                response.body = None;
+                response.cached_json_content = None;
+                response.cached_file_content = Some(body);
+                response.cached_file_bytes = None;
+                response
+                    .headers
+                    .retain(|key, _| !key.eq_ignore_ascii_case("content-type"));
+                response
+                    .headers
+                    .insert("Content-Type".to_string(), content_type);
+            }
+            Err(e) => {
+                error!("Error reading multipart file: {}", e);
+                return (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Error reading file: {}", e),
+                )
+                    .into_response();
+            }
         }
     }
 
-    create_response_from_mock(expectation.response, resource_dir).await
+    if needs_rendering(&response) {
+        let state = server.state().snapshot().await;
+        let context = TemplateContext {
+            state: &state,
+            request,
+            path_params: &path_params,
+            random: server.random(),
+        };
+        render_templates(&mut response, &context);
+    }
+
+    if let Some(status) = rate_limit_status {
+        status.apply_headers(&mut response);
+    }
+
+    if !expectation.response_transforms.is_empty() || server.has_response_transformers() {
+        for transform in &expectation.response_transforms {
+            transform.apply(&mut response);
+        }
+        server.transform_response(&mut response);
+    }
+
+    create_response_from_mock(response, resource_dir).await
+}
+
+/// Response injected by chaos mode instead of the mocked one
+fn chaos_error(status: u16) -> axum::response::Response {
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (
+        status,
+        [("X-Mimic-Chaos", "error")],
+        axum::Json(serde_json::json!({
+            "error": "chaos",
+            "status": status.as_u16(),
+        })),
+    )
+        .into_response()
+}
+
+/// Response for a request that violates the loaded OpenAPI document
+fn validation_error(report: ValidationReport) -> axum::response::Response {
+    (
+        StatusCode::BAD_REQUEST,
+        axum::Json(serde_json::json!({
+            "error": "Request does not match the OpenAPI document",
+            "operation": report.operation,
+            "errors": report.errors,
+        })),
+    )
+        .into_response()
+}
+
+/// Response answered during a scheduled outage
+fn downtime_error(status: u16, remaining: Duration) -> axum::response::Response {
+    let status = StatusCode::from_u16(status).unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+    let retry_after = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    (
+        status,
+        [
+            (header::RETRY_AFTER.as_str(), retry_after.to_string()),
+            ("X-Mimic-Downtime", "scheduled".to_string()),
+        ],
+        axum::Json(serde_json::json!({
+            "error": "downtime",
+            "status": status.as_u16(),
+        })),
+    )
+        .into_response()
+}
+
+/// A response whose body fails, making the server abort the connection
+fn connection_fault() -> axum::response::Response {
+    let failing = futures_util::stream::once(async {
+        Err::<axum::body::Bytes, _>(std::io::Error::new(
+            std::io::ErrorKind::ConnectionReset,
+            "connection fault injected by mimic-rs",
+        ))
+    });
+    axum::response::Response::new(Body::from_stream(failing))
+}
+
+/// Appends `field` to the `Vary` header
+fn add_vary(headers: &mut HashMap<String, String>, field: &str) {
+    headers
+        .entry("Vary".to_string())
+        .and_modify(|vary| {
+            vary.push_str(", ");
+            vary.push_str(field);
+        })
+        .or_insert_with(|| field.to_string());
+}
+
+/// 406 listing the media types the expectation can produce
+fn not_acceptable(variants: &[BodyVariant]) -> axum::response::Response {
+    let available: Vec<&str> = variants.iter().map(BodyVariant::content_type).collect();
+    debug!("No acceptable variant among {:?}", available);
+    (
+        StatusCode::NOT_ACCEPTABLE,
+        axum::Json(serde_json::json!({
+            "error": "not_acceptable",
+            "available": available,
+        })),
+    )
+        .into_response()
+}
+
+/// Frames a gRPC-Web answer, mirroring the binary or `-text` request format
+fn create_grpc_web_response(
+    response: &MockResponse,
+    grpc_web: &GrpcWebResponse,
+    headers: &HashMap<String, String>,
+) -> axum::response::Response {
+    let text = headers
+        .get("content-type")
+        .is_some_and(|ct| ct.starts_with("application/grpc-web-text"));
+
+    let body = match grpc_web.encode(text) {
+        Ok(body) => body,
+        Err(e) => {
+            error!("Invalid base64 gRPC-Web message: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Invalid gRPC-Web message: {}", e),
+            )
+                .into_response();
+        }
+    };
+
+    let content_type = if text {
+        GRPC_WEB_TEXT_CONTENT_TYPE
+    } else {
+        GRPC_WEB_CONTENT_TYPE
+    };
+    let status = StatusCode::from_u16(response.status_code).unwrap_or(StatusCode::OK);
+    let mut builder = axum::response::Response::builder()
+        .status(status)
+        .header("Content-Type", content_type);
+    for (key, value) in &response.headers {
+        builder = builder.header(key, value);
+    }
+
+    builder
+        .body(Body::from(body))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Writes the chunks of `stream` with its delay between them
+fn create_stream_response(response: &MockResponse, stream: StreamBody) -> axum::response::Response {
+    let delay = stream.chunk_delay();
+    let chunks = futures_util::stream::iter(stream.chunks.into_iter().enumerate()).then(
+        move |(index, chunk)| async move {
+            if index > 0 && !delay.is_zero() {
+                tokio::time::sleep(delay).await;
+            }
+            Ok::<_, std::convert::Infallible>(chunk)
+        },
+    );
+
+    let status = StatusCode::from_u16(response.status_code).unwrap_or(StatusCode::OK);
+    let mut builder = axum::response::Response::builder().status(status);
+    for (key, value) in &response.headers {
+        builder = builder.header(key, value);
+    }
+    builder
+        .body(Body::from_stream(chunks))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Sends the events of `sse`, keeping the connection open afterwards unless
+/// the script closes it
+fn create_sse_response(response: &MockResponse, sse: SseStream) -> axum::response::Response {
+    let SseStream {
+        events,
+        repeat,
+        close,
+    } = sse;
+    let script = futures_util::stream::unfold(0, move |index| {
+        let event = match events.get(index) {
+            Some(event) => Some(event.clone()),
+            None if repeat && !events.is_empty() => Some(events[0].clone()),
+            None => None,
+        };
+        let next = if index < events.len() { index + 1 } else { 1 };
+        async move {
+            let event = event?;
+            if event.delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(event.delay_ms)).await;
+            }
+            Some((Ok::<_, std::convert::Infallible>(event.encode()), next))
+        }
+    });
+    let body = if close {
+        Body::from_stream(script)
+    } else {
+        Body::from_stream(script.chain(futures_util::stream::pending()))
+    };
+
+    let status = StatusCode::from_u16(response.status_code).unwrap_or(StatusCode::OK);
+    let mut builder = axum::response::Response::builder()
+        .status(status)
+        .header("Cache-Control", "no-cache");
+    if response.content_type().is_none() {
+        builder = builder.header("Content-Type", SSE_CONTENT_TYPE);
+    }
+    for (key, value) in &response.headers {
+        builder = builder.header(key, value);
+    }
+    builder
+        .body(body)
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Applies the state changes of a matched expectation
+async fn apply_state_updates(
+    updates: &[StateUpdate],
+    state: &StateStore,
+    random: &Random,
+    request: &IncomingRequest,
+    path_params: &HashMap<String, String>,
+) {
+    for update in updates {
+        match update {
+            StateUpdate::Set { key, value } => {
+                let snapshot = state.snapshot().await;
+                let context = TemplateContext {
+                    state: &snapshot,
+                    request,
+                    path_params,
+                    random,
+                };
+                state
+                    .set(key, template::render_value(value, &context))
+                    .await;
+            }
+            StateUpdate::Increment { key, by } => {
+                state.increment(key, *by).await;
+            }
+            StateUpdate::Remove { key } => {
+                state.remove(key).await;
+            }
+        }
+    }
 }
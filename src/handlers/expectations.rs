@@ -0,0 +1,211 @@
+use axum::{
+    extract::{Json, Path, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::mappings;
+use crate::models::{CreateExpectationRequest, MockExpectation};
+use crate::server::MockServer;
+
+/// Query of `GET /_expectations/export`
+#[derive(Deserialize)]
+pub struct ExportExpectations {
+    #[serde(default = "default_document_format")]
+    format: String,
+}
+
+fn default_document_format() -> String {
+    "json".to_string()
+}
+
+/// Query of `POST /_expectations/import`
+#[derive(Deserialize)]
+pub struct ImportExpectations {
+    /// Remove the current expectations first
+    #[serde(default)]
+    replace: bool,
+}
+
+/// An expectation with the number of requests it answered
+#[derive(Serialize)]
+struct ExpectationInfo {
+    #[serde(flatten)]
+    expectation: MockExpectation,
+
+    hits: usize,
+}
+
+/// Handler for listing all registered expectations
+pub async fn handle_list_expectations(State(server): State<MockServer>) -> impl IntoResponse {
+    let hit_counts = server.hit_counts().await;
+    let expectations: Vec<ExpectationInfo> = server
+        .get_expectations()
+        .await
+        .into_iter()
+        .map(|expectation| ExpectationInfo {
+            hits: hit_counts.get(&expectation.id).copied().unwrap_or(0),
+            expectation,
+        })
+        .collect();
+
+    Json(expectations)
+}
+
+/// Handler for inspecting a single expectation
+pub async fn handle_get_expectation(
+    State(server): State<MockServer>,
+    Path(id): Path<String>,
+) -> Response {
+    match server.get_expectation(&id).await {
+        Some(expectation) => Json(ExpectationInfo {
+            hits: server.hit_count(&id).await,
+            expectation,
+        })
+        .into_response(),
+        None => (
+            StatusCode::NOT_FOUND,
+            format!("No expectation with id {}", id),
+        )
+            .into_response(),
+    }
+}
+
+/// Handler for replacing a single expectation, keeping its id
+pub async fn handle_replace_expectation(
+    State(server): State<MockServer>,
+    Path(id): Path<String>,
+    Json(request): Json<CreateExpectationRequest>,
+) -> Response {
+    if let Err(e) = request.validate() {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    let expectation: MockExpectation = request.into();
+
+    if server.replace_expectation(&id, expectation).await {
+        match server.get_expectation(&id).await {
+            Some(expectation) => {
+                if let Some(cluster) = server.cluster() {
+                    cluster.replicate_replace(&expectation).await;
+                }
+                Json(expectation).into_response()
+            }
+            None => StatusCode::NO_CONTENT.into_response(),
+        }
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            format!("No expectation with id {}", id),
+        )
+            .into_response()
+    }
+}
+
+/// Handler for removing a single expectation
+pub async fn handle_delete_expectation(
+    State(server): State<MockServer>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if server.remove_expectation(&id).await {
+        if let Some(cluster) = server.cluster() {
+            cluster.replicate_remove(&id).await;
+        }
+        StatusCode::NO_CONTENT
+    } else {
+        StatusCode::NOT_FOUND
+    }
+}
+
+/// Handler for downloading all expectations as one mapping document
+///
+/// `json` (the default) and `yaml` are supported.
+pub async fn handle_export_expectations(
+    State(server): State<MockServer>,
+    Query(export): Query<ExportExpectations>,
+) -> Response {
+    let expectations = server.export_expectations().await;
+
+    let (content_type, extension, document) = match export.format.to_lowercase().as_str() {
+        "json" => (
+            "application/json",
+            "json",
+            serde_json::to_string_pretty(&expectations).map_err(|e| e.to_string()),
+        ),
+        "yaml" | "yml" => (
+            "application/yaml",
+            "yaml",
+            serde_yaml::to_string(&expectations).map_err(|e| e.to_string()),
+        ),
+        other => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "Unsupported export format '{}', expected json or yaml",
+                    other
+                ),
+            )
+                .into_response();
+        }
+    };
+
+    match document {
+        Ok(document) => (
+            [
+                (header::CONTENT_TYPE, content_type.to_string()),
+                (
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"expectations.{}\"", extension),
+                ),
+            ],
+            document,
+        )
+            .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+    }
+}
+
+/// Handler for loading a document written by the export
+///
+/// The body is read as YAML when the `Content-Type` says so and as JSON
+/// otherwise. Either all expectations are registered or none.
+pub async fn handle_import_expectations(
+    State(server): State<MockServer>,
+    Query(import): Query<ImportExpectations>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let yaml = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| content_type.contains("yaml"));
+    let parsed = if yaml {
+        mappings::parse_yaml(&body).map_err(|e| e.to_string())
+    } else {
+        mappings::parse_json(&body).map_err(|e| e.to_string())
+    };
+    let requests = match parsed {
+        Ok(requests) => requests,
+        Err(e) => {
+            return (StatusCode::BAD_REQUEST, format!("Invalid document: {}", e)).into_response();
+        }
+    };
+
+    let imported = match server.import_expectations(requests, import.replace).await {
+        Ok(imported) => imported,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    if let Some(cluster) = server.cluster() {
+        if import.replace {
+            cluster.replicate_replace_all(&imported).await;
+        } else {
+            for expectation in &imported {
+                cluster.replicate_expectation(expectation).await;
+            }
+        }
+    }
+
+    (StatusCode::CREATED, Json(imported)).into_response()
+}
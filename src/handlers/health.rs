@@ -0,0 +1,17 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::server::MockServer;
+
+/// Handler for the liveness probe
+pub async fn handle_health() -> impl IntoResponse {
+    (StatusCode::OK, "ok")
+}
+
+/// Handler for the readiness probe
+pub async fn handle_ready(State(server): State<MockServer>) -> impl IntoResponse {
+    if server.is_ready() {
+        (StatusCode::OK, "ready")
+    } else {
+        (StatusCode::SERVICE_UNAVAILABLE, "starting")
+    }
+}
@@ -0,0 +1,55 @@
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+
+use crate::logging;
+use crate::models::{LogLevelRequest, LogLevelResponse};
+use crate::server::MockServer;
+
+/// Handler for reading the active log level
+pub async fn handle_get_log_level(State(server): State<MockServer>) -> Response {
+    let Some(handle) = server.log_handle() else {
+        return not_managed();
+    };
+
+    match handle.level() {
+        Some(level) => Json(LogLevelResponse {
+            level: level.to_string().to_lowercase(),
+        })
+        .into_response(),
+        None => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
+/// Handler for changing the log level without restarting the server
+pub async fn handle_set_log_level(
+    State(server): State<MockServer>,
+    Json(request): Json<LogLevelRequest>,
+) -> Response {
+    let Some(handle) = server.log_handle() else {
+        return not_managed();
+    };
+
+    let level = match logging::parse_level(&request.level) {
+        Ok(level) => level,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    match handle.set_level(level) {
+        Ok(()) => Json(LogLevelResponse {
+            level: level.to_string().to_lowercase(),
+        })
+        .into_response(),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+fn not_managed() -> Response {
+    (
+        StatusCode::NOT_IMPLEMENTED,
+        "Logging is not managed by this server",
+    )
+        .into_response()
+}
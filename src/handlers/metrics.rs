@@ -0,0 +1,19 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::server::MockServer;
+
+/// Content type of the Prometheus text exposition format
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Handler for the Prometheus metrics
+pub async fn handle_metrics(State(server): State<MockServer>) -> Response {
+    (
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        server.metrics().await,
+    )
+        .into_response()
+}
@@ -1,30 +1,159 @@
+mod callbacks;
+mod chaos;
+mod cluster;
+mod downtime;
 mod dynamic;
+mod expectations;
+mod health;
+mod log_level;
+mod metrics;
+mod not_found;
+mod oidc;
+mod recordings;
+mod requests;
 mod reset;
 mod setup;
+mod shutdown;
+mod state;
+mod stats;
+mod ui;
 mod verify;
 
 use axum::{
-    Router,
-    routing::{any, post},
+    Router, middleware,
+    routing::{any, get, post, put},
 };
 use tower_http::trace::TraceLayer;
 
+use crate::cors::cors_middleware;
 use crate::server::MockServer;
 
 /// Create a router for the server
 pub fn create_router(server: MockServer) -> Router {
     let api_router = Router::new()
         .route("/_setup", post(setup::handle_setup))
+        .route("/_setup/batch", post(setup::handle_setup_batch))
         .route("/_verify", post(verify::handle_verify))
-        .route("/_reset", post(reset::handle_reset));
+        .route("/_verify/sequence", post(verify::handle_verify_sequence))
+        .route("/_reset", post(reset::handle_reset))
+        .route(
+            "/_expectations",
+            get(expectations::handle_list_expectations),
+        )
+        .route(
+            "/_expectations/export",
+            get(expectations::handle_export_expectations),
+        )
+        .route(
+            "/_expectations/import",
+            post(expectations::handle_import_expectations),
+        )
+        .route(
+            "/_expectations/{id}",
+            get(expectations::handle_get_expectation)
+                .put(expectations::handle_replace_expectation)
+                .delete(expectations::handle_delete_expectation),
+        )
+        .route("/_requests", get(requests::handle_find_requests))
+        .route(
+            "/_requests/unmatched",
+            get(requests::handle_unmatched_requests),
+        )
+        .route("/_requests/export", get(requests::handle_export_requests))
+        .route("/_requests/stream", get(requests::handle_stream_requests))
+        .route(
+            "/_log_level",
+            get(log_level::handle_get_log_level).put(log_level::handle_set_log_level),
+        )
+        .route(
+            "/_chaos",
+            get(chaos::handle_get_chaos)
+                .post(chaos::handle_set_chaos)
+                .delete(chaos::handle_disable_chaos),
+        )
+        .route(
+            "/_downtime",
+            get(downtime::handle_list_downtime)
+                .post(downtime::handle_add_downtime)
+                .delete(downtime::handle_clear_downtime),
+        )
+        .route(
+            "/_recordings",
+            get(recordings::handle_list_recordings).delete(recordings::handle_clear_recordings),
+        )
+        .route(
+            "/_recordings/export",
+            post(recordings::handle_export_recordings),
+        )
+        .route(
+            "/_callbacks",
+            get(callbacks::handle_list_callbacks).delete(callbacks::handle_clear_callbacks),
+        )
+        .route("/_stats", get(stats::handle_stats))
+        .route("/_metrics", get(metrics::handle_metrics))
+        .route("/_ui", get(ui::handle_ui))
+        .route(
+            "/_state",
+            get(state::handle_get_state)
+                .put(state::handle_replace_state)
+                .delete(state::handle_clear_state),
+        )
+        .route(
+            "/_state/{key}",
+            get(state::handle_get_value)
+                .put(state::handle_set_value)
+                .delete(state::handle_delete_value),
+        );
 
     // Create wildcard router for dynamic requests
     let dynamic_router = any(dynamic::handle_dynamic_request);
 
+    let mut router = if server.admin_enabled() {
+        Router::new().merge(api_router)
+    } else {
+        Router::new()
+    };
+
+    if server.admin_enabled() && server.shutdown_endpoint_enabled() {
+        router = router.route("/_shutdown", post(shutdown::handle_shutdown));
+    }
+
+    // Liveness and readiness probes stay available without the admin API
+    router = router
+        .route("/_health", get(health::handle_health))
+        .route("/_ready", get(health::handle_ready));
+
+    if server.admin_enabled() && server.cluster().is_some() {
+        router = router
+            .route(
+                "/_cluster/expectations",
+                post(cluster::handle_replicated_expectation).put(cluster::handle_replicated_import),
+            )
+            .route(
+                "/_cluster/expectations/{id}",
+                put(cluster::handle_replicated_replace).delete(cluster::handle_replicated_remove),
+            )
+            .route("/_cluster/reset", post(cluster::handle_replicated_reset));
+    }
+
+    if server.oidc().is_some() {
+        router = router
+            .route(
+                "/.well-known/openid-configuration",
+                get(oidc::handle_discovery),
+            )
+            .route("/.well-known/jwks.json", get(oidc::handle_jwks))
+            .route("/authorize", get(oidc::handle_authorize))
+            .route("/token", post(oidc::handle_token));
+    }
+
     // Combine routers
-    Router::new()
-        .merge(api_router)
+    router
         .fallback(dynamic_router)
+        .layer(middleware::from_fn_with_state(
+            server.clone(),
+            cors_middleware,
+        ))
         .layer(TraceLayer::new_for_http())
         .with_state(server)
 }
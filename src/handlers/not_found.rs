@@ -0,0 +1,207 @@
+use axum::{
+    Json,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::json;
+use std::collections::HashMap;
+
+use crate::conditional::ReceivedRequest;
+use crate::extension::ExtensionRegistry;
+use crate::models::{MockExpectation, NearMiss, SCENARIO_STARTED};
+use crate::transform::IncomingRequest;
+
+/// How many near misses the diagnostic body lists
+const NEAR_MISS_LIMIT: usize = 3;
+
+/// Every expectation with the reasons it rejects the request, closest first
+pub(crate) fn near_misses(
+    request: &IncomingRequest,
+    expectations: &[MockExpectation],
+    scenario_states: &HashMap<String, String>,
+    extensions: &ExtensionRegistry,
+) -> Vec<NearMiss> {
+    let mut near_misses: Vec<NearMiss> = expectations
+        .iter()
+        .map(|exp| NearMiss {
+            id: exp.id.clone(),
+            method: exp.method.clone(),
+            path: exp.path.clone(),
+            mismatches: mismatches(exp, request, scenario_states, extensions),
+        })
+        .collect();
+    // Expectations for another path are the least likely to be meant
+    near_misses.sort_by_key(|miss| {
+        let other_path = miss.mismatches.iter().any(|m| m.starts_with("path"));
+        (other_path, miss.mismatches.len())
+    });
+    near_misses
+}
+
+/// JSON 404 explaining why no expectation matched the request
+pub(crate) fn diagnostic_response(
+    request: &IncomingRequest,
+    mut near_misses: Vec<NearMiss>,
+) -> Response {
+    near_misses.truncate(NEAR_MISS_LIMIT);
+
+    let body = json!({
+        "error": format!(
+            "No matching expectation found for {} {}",
+            request.method, request.path
+        ),
+        "request": {
+            "method": request.method,
+            "path": request.path,
+            "query_params": request.query_params,
+            "headers": request.headers,
+            "body": request.body,
+        },
+        "near_misses": near_misses,
+    });
+    (StatusCode::NOT_FOUND, Json(body)).into_response()
+}
+
+/// Describes every part of the request the expectation does not accept
+fn mismatches(
+    exp: &MockExpectation,
+    request: &IncomingRequest,
+    scenario_states: &HashMap<String, String>,
+    extensions: &ExtensionRegistry,
+) -> Vec<String> {
+    let mut mismatches = Vec::new();
+
+    if exp.method != request.method {
+        mismatches.push(format!("method: expected {}", exp.method));
+    }
+
+    let path_matches = match &exp.path_regex {
+        Some(regex) => regex.is_match(&request.path),
+        None => exp.path == request.path,
+    };
+    if !path_matches {
+        mismatches.push(format!("path: expected {}", exp.path));
+    }
+
+    let mut query: Vec<_> = exp.query_params.iter().collect();
+    query.sort();
+    for (key, expected) in query {
+        match request.query_params.get(key) {
+            Some(actual) if actual == expected => {}
+            Some(actual) => mismatches.push(format!(
+                "query parameter {}: expected {}, got {}",
+                key, expected, actual
+            )),
+            None => mismatches.push(format!(
+                "query parameter {}: expected {}, missing",
+                key, expected
+            )),
+        }
+    }
+
+    let mut headers: Vec<_> = exp.headers.iter().collect();
+    headers.sort();
+    for (name, expected) in headers {
+        match request.headers.get(name) {
+            Some(actual) if actual == expected => {}
+            Some(actual) => mismatches.push(format!(
+                "header {}: expected {}, got {}",
+                name, expected, actual
+            )),
+            None => mismatches.push(format!("header {}: expected {}, missing", name, expected)),
+        }
+    }
+
+    let mut query_matchers: Vec<_> = exp.query_matchers.iter().collect();
+    query_matchers.sort_by_key(|(key, _)| key.as_str());
+    for (key, matcher) in query_matchers {
+        let actual = request.query_params.get(key);
+        if !matcher.matches(actual.map(String::as_str)) {
+            mismatches.push(format!(
+                "query parameter {}: expected {}, {}",
+                key,
+                matcher,
+                describe(actual)
+            ));
+        }
+    }
+
+    let mut header_matchers: Vec<_> = exp.header_matchers.iter().collect();
+    header_matchers.sort_by_key(|(name, _)| name.as_str());
+    for (name, matcher) in header_matchers {
+        let actual = request.headers.get(&name.to_lowercase());
+        if !matcher.matches(actual.map(String::as_str)) {
+            mismatches.push(format!(
+                "header {}: expected {}, {}",
+                name,
+                matcher,
+                describe(actual)
+            ));
+        }
+    }
+
+    if let Some(body) = &exp.body
+        && request.body.as_deref() != Some(body.as_str())
+    {
+        mismatches.push("body: differs".to_string());
+    }
+
+    if let Some(json_body) = &exp.json_body
+        && !json_body.matches(request.body.as_deref())
+    {
+        mismatches.push("body: JSON differs".to_string());
+    }
+
+    if exp.body_base64.is_some() && !exp.matches_body_bytes(request.raw_body.as_deref()) {
+        mismatches.push("body: bytes differ".to_string());
+    }
+
+    if let Some(graphql) = &exp.graphql
+        && !graphql.matches(request.body.as_deref(), &request.query_params)
+    {
+        mismatches.push("graphql: operation differs".to_string());
+    }
+
+    for matcher in &exp.body_matchers {
+        if !matcher.matches(request.body.as_deref()) {
+            mismatches.push(format!("body: expected {}", matcher));
+        }
+    }
+
+    for matcher in &exp.matchers {
+        if !extensions.matches(matcher, request) {
+            mismatches.push(format!("matcher {}: rejected", matcher.name));
+        }
+    }
+
+    if !exp.custom_matchers.is_empty() {
+        let received = ReceivedRequest::new(request, exp.path_params(&request.path));
+        let rejected = exp
+            .custom_matchers
+            .iter()
+            .filter(|matcher| !matcher.matches(&received))
+            .count();
+        if rejected > 0 {
+            mismatches.push(format!("custom matchers: {} rejected", rejected));
+        }
+    }
+
+    if let Some(rule) = &exp.scenario {
+        let current = scenario_states
+            .get(&rule.name)
+            .map_or(SCENARIO_STARTED, String::as_str);
+        if !rule.is_active(current) {
+            mismatches.push(format!("scenario {}: in state {}", rule.name, current));
+        }
+    }
+
+    mismatches
+}
+
+/// `got <value>` or `missing` for a mismatch message
+fn describe(actual: Option<&String>) -> String {
+    match actual {
+        Some(actual) => format!("got {}", actual),
+        None => "missing".to_string(),
+    }
+}
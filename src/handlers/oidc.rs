@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Form, Json, Query, State},
+    http::{HeaderMap, StatusCode, header},
+    response::{IntoResponse, Redirect, Response},
+};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::{Map, Value, json};
+
+use crate::auth::{Grant, OidcProvider};
+use crate::server::MockServer;
+
+/// Handler for `/.well-known/openid-configuration`
+pub async fn handle_discovery(State(server): State<MockServer>, headers: HeaderMap) -> Response {
+    let Some(provider) = server.oidc() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let issuer = provider.issuer(host(&headers));
+    Json(json!({
+        "issuer": issuer,
+        "authorization_endpoint": format!("{}/authorize", issuer),
+        "token_endpoint": format!("{}/token", issuer),
+        "jwks_uri": format!("{}/.well-known/jwks.json", issuer),
+        "response_types_supported": ["code"],
+        "subject_types_supported": ["public"],
+        "id_token_signing_alg_values_supported": ["ES256"],
+        "grant_types_supported": [
+            "authorization_code",
+            "client_credentials",
+            "password",
+            "refresh_token"
+        ],
+        "token_endpoint_auth_methods_supported": ["client_secret_basic", "client_secret_post"],
+    }))
+    .into_response()
+}
+
+/// Handler for the JWKS document with the public signing key
+pub async fn handle_jwks(State(server): State<MockServer>) -> Response {
+    match server.oidc() {
+        Some(provider) => Json(json!({"keys": [provider.signing_key().jwk()]})).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Handler for `/authorize`, approves every request and redirects back with a code
+pub async fn handle_authorize(
+    State(server): State<MockServer>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Response {
+    let Some(provider) = server.oidc() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let Some(redirect_uri) = params.get("redirect_uri") else {
+        return oauth_error("invalid_request", "redirect_uri is required");
+    };
+
+    let code = provider.store_code(Grant {
+        client_id: params.get("client_id").cloned().unwrap_or_default(),
+        subject: params
+            .get("login_hint")
+            .cloned()
+            .unwrap_or_else(|| provider.config().subject.clone()),
+        scope: params.get("scope").cloned().unwrap_or_default(),
+        nonce: params.get("nonce").cloned(),
+    });
+
+    let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+    let mut location = format!("{}{}code={}", redirect_uri, separator, code);
+    if let Some(state) = params.get("state") {
+        location.push_str(&format!("&state={}", state));
+    }
+
+    Redirect::to(&location).into_response()
+}
+
+/// Handler for `/token`
+pub async fn handle_token(
+    State(server): State<MockServer>,
+    headers: HeaderMap,
+    Form(form): Form<HashMap<String, String>>,
+) -> Response {
+    let Some(provider) = server.oidc() else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let client_id = basic_auth_client(&headers)
+        .or_else(|| form.get("client_id").cloned())
+        .unwrap_or_default();
+    let scope = form.get("scope").cloned().unwrap_or_default();
+
+    let grant = match form.get("grant_type").map(String::as_str) {
+        Some("client_credentials") => Grant {
+            subject: client_id.clone(),
+            client_id,
+            scope,
+            nonce: None,
+        },
+        Some("password") => Grant {
+            subject: form
+                .get("username")
+                .cloned()
+                .unwrap_or_else(|| provider.config().subject.clone()),
+            client_id,
+            scope,
+            nonce: None,
+        },
+        Some("authorization_code") => {
+            match form.get("code").and_then(|code| provider.take_code(code)) {
+                Some(grant) => grant,
+                None => return oauth_error("invalid_grant", "unknown or used code"),
+            }
+        }
+        Some("refresh_token") => {
+            match form
+                .get("refresh_token")
+                .and_then(|token| provider.refresh_grant(token))
+            {
+                Some(grant) => grant,
+                None => return oauth_error("invalid_grant", "unknown refresh token"),
+            }
+        }
+        _ => return oauth_error("unsupported_grant_type", "grant type is not supported"),
+    };
+
+    token_response(provider, &grant, host(&headers))
+}
+
+fn token_response(provider: &OidcProvider, grant: &Grant, host: Option<&str>) -> Response {
+    let issuer = provider.issuer(host);
+    let audience = provider
+        .config()
+        .audience
+        .clone()
+        .unwrap_or_else(|| grant.client_id.clone());
+
+    let mut access_claims = Map::new();
+    if !grant.scope.is_empty() {
+        access_claims.insert("scope".to_string(), json!(grant.scope));
+    }
+    if !grant.client_id.is_empty() {
+        access_claims.insert("client_id".to_string(), json!(grant.client_id));
+    }
+
+    let access_token =
+        match provider.issue_token(&issuer, &grant.subject, &audience, &access_claims) {
+            Ok(token) => token,
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        };
+
+    let mut body = json!({
+        "access_token": access_token,
+        "token_type": "Bearer",
+        "expires_in": provider.config().expires_in.as_secs(),
+        "refresh_token": provider.store_refresh_token(grant.clone()),
+    });
+    if !grant.scope.is_empty() {
+        body["scope"] = json!(grant.scope);
+    }
+
+    if grant.scope.split(' ').any(|s| s == "openid") {
+        let mut id_claims = Map::new();
+        if let Some(nonce) = &grant.nonce {
+            id_claims.insert("nonce".to_string(), json!(nonce));
+        }
+        let id_audience = if grant.client_id.is_empty() {
+            audience.clone()
+        } else {
+            grant.client_id.clone()
+        };
+        match provider.issue_token(&issuer, &grant.subject, &id_audience, &id_claims) {
+            Ok(id_token) => body["id_token"] = Value::String(id_token),
+            Err(e) => return (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+
+    ([(header::CACHE_CONTROL, "no-store")], Json(body)).into_response()
+}
+
+fn host(headers: &HeaderMap) -> Option<&str> {
+    headers.get(header::HOST).and_then(|v| v.to_str().ok())
+}
+
+/// Reads the client id from `Authorization: Basic ...`
+fn basic_auth_client(headers: &HeaderMap) -> Option<String> {
+    let encoded = headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?;
+    decoded.split(':').next().map(String::from)
+}
+
+fn oauth_error(error: &str, description: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({"error": error, "error_description": description})),
+    )
+        .into_response()
+}
@@ -0,0 +1,53 @@
+use std::path::PathBuf;
+
+use axum::{
+    extract::{Json, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use serde_json::json;
+use tracing::error;
+
+use crate::server::MockServer;
+
+/// Where `POST /_recordings/export` writes the mapping files
+#[derive(Deserialize)]
+pub struct ExportRecordings {
+    out: PathBuf,
+}
+
+/// Handler for listing the exchanges recorded in proxy mode as expectations
+pub async fn handle_list_recordings(State(server): State<MockServer>) -> Response {
+    Json(server.export_recordings().expectations).into_response()
+}
+
+/// Handler for writing the recordings as mapping and body files
+pub async fn handle_export_recordings(
+    State(server): State<MockServer>,
+    Json(request): Json<ExportRecordings>,
+) -> Response {
+    match server.export_recordings().write_to(&request.out) {
+        Ok(written) => Json(json!({ "mappings": written })).into_response(),
+        Err(e) => {
+            error!(
+                "Failed to export recordings to {}: {}",
+                request.out.display(),
+                e
+            );
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to export recordings: {}", e),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Handler for discarding the recordings
+pub async fn handle_clear_recordings(State(server): State<MockServer>) -> Response {
+    if let Some(proxy) = server.proxy() {
+        proxy.clear_recordings();
+    }
+    StatusCode::NO_CONTENT.into_response()
+}
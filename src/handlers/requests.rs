@@ -0,0 +1,110 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Json, Query, State},
+    http::{StatusCode, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures_util::Stream;
+use serde::Deserialize;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::warn;
+
+use crate::models::RequestQuery;
+use crate::server::MockServer;
+
+/// Query of `GET /_requests/export`
+#[derive(Deserialize)]
+pub struct ExportRequests {
+    #[serde(default = "default_export_format")]
+    format: String,
+}
+
+fn default_export_format() -> String {
+    "har".to_string()
+}
+
+/// Handler for searching the request log
+///
+/// The number of matching requests before pagination is sent in `X-Total-Count`.
+pub async fn handle_find_requests(
+    State(server): State<MockServer>,
+    Query(query): Query<RequestQuery>,
+) -> impl IntoResponse {
+    let (requests, total) = server.query_requests(&query).await;
+
+    ([("x-total-count", total.to_string())], Json(requests))
+}
+
+/// Handler for listing the requests no expectation answered
+pub async fn handle_unmatched_requests(State(server): State<MockServer>) -> impl IntoResponse {
+    Json(server.unmatched_requests().await)
+}
+
+/// Handler for downloading the request log with the served responses
+///
+/// `har` is the only format so far and the default.
+pub async fn handle_export_requests(
+    State(server): State<MockServer>,
+    Query(export): Query<ExportRequests>,
+) -> Response {
+    if !export.format.eq_ignore_ascii_case("har") {
+        return (
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Unsupported export format '{}', expected har",
+                export.format
+            ),
+        )
+            .into_response();
+    }
+
+    (
+        [(
+            header::CONTENT_DISPOSITION,
+            "attachment; filename=\"requests.har\"",
+        )],
+        Json(server.export_har().await),
+    )
+        .into_response()
+}
+
+/// Handler for tailing the request log as server-sent events
+///
+/// Every request is sent as a `request` event with the JSON record, once its
+/// response is ready. The stream ends when the server shuts down.
+pub async fn handle_stream_requests(
+    State(server): State<MockServer>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let requests = server.subscribe_requests();
+    let events = futures_util::stream::unfold(requests, move |mut requests| {
+        let server = server.clone();
+        async move {
+            loop {
+                let record = tokio::select! {
+                    record = requests.recv() => record,
+                    _ = server.stopping() => return None,
+                };
+                match record {
+                    Ok(record) => {
+                        let event = Event::default()
+                            .event("request")
+                            .id(record.id.clone())
+                            .json_data(&record)
+                            .unwrap_or_else(|_| Event::default().comment("unserializable record"));
+                        return Some((Ok(event), requests));
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        warn!("Request stream fell behind, skipped {} requests", skipped);
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        }
+    });
+
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -5,5 +5,9 @@ use crate::server::MockServer;
 pub async fn handle_reset(State(server): State<MockServer>) -> impl IntoResponse {
     server.reset().await;
 
+    if let Some(cluster) = server.cluster() {
+        cluster.replicate_reset().await;
+    }
+
     StatusCode::OK
 }
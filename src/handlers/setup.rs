@@ -1,7 +1,7 @@
 use axum::{
     extract::{Json, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
 
 use crate::models::{CreateExpectationRequest, MockExpectation};
@@ -11,10 +11,44 @@ use crate::server::MockServer;
 pub async fn handle_setup(
     State(server): State<MockServer>,
     Json(request): Json<CreateExpectationRequest>,
-) -> impl IntoResponse {
+) -> Response {
+    if let Err(e) = request.validate() {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
     let expectation: MockExpectation = request.into();
 
     server.add_expectation(expectation.clone()).await;
 
-    (StatusCode::CREATED, Json(expectation))
+    if let Some(cluster) = server.cluster() {
+        cluster.replicate_expectation(&expectation).await;
+    }
+
+    (StatusCode::CREATED, Json(expectation)).into_response()
+}
+
+/// Handler for setting up several expectations in one call
+///
+/// Either all expectations are registered or, if one is invalid, none.
+/// Answers with the ids of the new expectations in request order.
+pub async fn handle_setup_batch(
+    State(server): State<MockServer>,
+    Json(requests): Json<Vec<CreateExpectationRequest>>,
+) -> Response {
+    let created = match server.import_expectations(requests, false).await {
+        Ok(created) => created,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+
+    if let Some(cluster) = server.cluster() {
+        for expectation in &created {
+            cluster.replicate_expectation(expectation).await;
+        }
+    }
+
+    let ids: Vec<String> = created
+        .into_iter()
+        .map(|expectation| expectation.id)
+        .collect();
+    (StatusCode::CREATED, Json(ids)).into_response()
 }
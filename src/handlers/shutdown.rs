@@ -0,0 +1,10 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+
+use crate::server::MockServer;
+
+/// Handler for stopping the server once in-flight requests are done
+pub async fn handle_shutdown(State(server): State<MockServer>) -> impl IntoResponse {
+    server.shutdown();
+
+    StatusCode::ACCEPTED
+}
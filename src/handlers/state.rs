@@ -0,0 +1,63 @@
+use axum::{
+    extract::{Json, Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde_json::{Map, Value};
+
+use crate::server::MockServer;
+
+/// Handler for inspecting the whole state store
+pub async fn handle_get_state(State(server): State<MockServer>) -> impl IntoResponse {
+    Json(server.state().snapshot().await)
+}
+
+/// Handler for seeding the state store, replacing all values
+pub async fn handle_replace_state(
+    State(server): State<MockServer>,
+    Json(values): Json<Map<String, Value>>,
+) -> impl IntoResponse {
+    server.state().replace(values).await;
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for clearing the state store
+pub async fn handle_clear_state(State(server): State<MockServer>) -> impl IntoResponse {
+    server.state().clear().await;
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for reading a single state value
+pub async fn handle_get_value(
+    State(server): State<MockServer>,
+    Path(key): Path<String>,
+) -> Response {
+    match server.state().get(&key).await {
+        Some(value) => Json(value).into_response(),
+        None => (StatusCode::NOT_FOUND, format!("No state value for {}", key)).into_response(),
+    }
+}
+
+/// Handler for setting a single state value
+pub async fn handle_set_value(
+    State(server): State<MockServer>,
+    Path(key): Path<String>,
+    Json(value): Json<Value>,
+) -> impl IntoResponse {
+    server.state().set(&key, value).await;
+
+    StatusCode::NO_CONTENT
+}
+
+/// Handler for removing a single state value
+pub async fn handle_delete_value(
+    State(server): State<MockServer>,
+    Path(key): Path<String>,
+) -> impl IntoResponse {
+    match server.state().remove(&key).await {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
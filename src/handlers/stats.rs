@@ -0,0 +1,11 @@
+use axum::{
+    extract::{Json, State},
+    response::{IntoResponse, Response},
+};
+
+use crate::server::MockServer;
+
+/// Handler for the hits and latencies per expectation
+pub async fn handle_stats(State(server): State<MockServer>) -> Response {
+    Json(server.stats().await).into_response()
+}
@@ -0,0 +1,9 @@
+use axum::response::Html;
+
+/// The dashboard, a single page using the admin API from the browser
+const DASHBOARD: &str = include_str!("../../assets/ui.html");
+
+/// Handler for the admin web UI
+pub async fn handle_ui() -> Html<&'static str> {
+    Html(DASHBOARD)
+}
@@ -1,24 +1,66 @@
 use axum::{
     extract::{Json, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
 };
+use serde_json::json;
 
-use crate::models::{VerifyRequest, VerifyResponse};
-use crate::server::MockServer;
+use crate::models::{VerifyRequest, VerifyResponse, VerifySequenceRequest};
+use crate::server::{MockServer, VerificationError};
 
 /// Handler for verifying the number of calls to an endpoint
 pub async fn handle_verify(
     State(server): State<MockServer>,
     Json(request): Json<VerifyRequest>,
-) -> impl IntoResponse {
-    let actual = server.count_calls(&request.method, &request.path).await;
+) -> Response {
+    if let Err(e) = request
+        .times
+        .validate()
+        .and_then(|()| request.conditions.validate())
+    {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    let actual = server
+        .count_calls_matching(&request.method, &request.path, &request.conditions)
+        .await;
 
     let response = VerifyResponse::new(request.method, request.path, request.times, actual);
 
     if response.success {
-        (StatusCode::OK, Json(response))
+        (StatusCode::OK, Json(response)).into_response()
     } else {
-        (StatusCode::BAD_REQUEST, Json(response))
+        (StatusCode::BAD_REQUEST, Json(response)).into_response()
+    }
+}
+
+/// Handler for verifying that calls happened in the given order
+///
+/// Answers 400 with the first step that has no matching request after the previous ones.
+pub async fn handle_verify_sequence(
+    State(server): State<MockServer>,
+    Json(request): Json<VerifySequenceRequest>,
+) -> Response {
+    if let Err(e) = request
+        .steps
+        .iter()
+        .try_for_each(|step| step.conditions.validate())
+    {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+
+    let steps = request.steps.len();
+    match server.verify_sequence(request.steps).await {
+        Ok(()) => Json(json!({ "success": true, "matched": steps })).into_response(),
+        Err(error @ VerificationError::OutOfOrder { step, .. }) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "success": false,
+                "matched": step - 1,
+                "error": error.to_string(),
+            })),
+        )
+            .into_response(),
+        Err(error) => (StatusCode::BAD_REQUEST, error.to_string()).into_response(),
     }
 }
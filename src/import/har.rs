@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use std::collections::HashMap;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::{Value, json};
+
+use super::{Import, ImportError, expectation, is_transfer_header, split_url};
+use crate::models::{MockResponse, RequestRecord, ResponseDelay, SequenceEnd};
+
+/// Converts the entries of a HAR archive into expectations
+///
+/// Each entry is matched on method, path and query string and answers with
+/// the recorded status, headers and body. Repeated requests answer with
+/// their recorded responses in turn, the last one once all were served.
+/// With `keep_latency` every response is delayed by the recorded server
+/// wait time (`timings.wait`, or the total `time` if that is missing).
+pub fn convert(document: &Value, keep_latency: bool) -> Result<Import, ImportError> {
+    let entries = document
+        .pointer("/log/entries")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ImportError::Invalid("missing 'log.entries' array".to_string()))?;
+
+    let mut import = Import::default();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let request = entry
+            .get("request")
+            .ok_or_else(|| ImportError::Invalid(format!("entry {} has no request", index)))?;
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("GET");
+        let url = request
+            .get("url")
+            .and_then(Value::as_str)
+            .ok_or_else(|| ImportError::Invalid(format!("entry {} has no request url", index)))?;
+        let (path, query_params) = split_url(url);
+
+        let recorded = entry.get("response").unwrap_or(&Value::Null);
+        let status = recorded
+            .get("status")
+            .and_then(Value::as_u64)
+            .and_then(|status| u16::try_from(status).ok())
+            .filter(|status| *status > 0)
+            .unwrap_or(200);
+
+        let mut response = MockResponse::new(status);
+        for header in recorded
+            .get("headers")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            if let (Some(name), Some(value)) = (
+                header.get("name").and_then(Value::as_str),
+                header.get("value").and_then(Value::as_str),
+            ) && !is_transfer_header(name)
+            {
+                response.headers.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        let content = recorded.get("content").unwrap_or(&Value::Null);
+        let mime_type = content.get("mimeType").and_then(Value::as_str);
+        if let Some(text) = content.get("text").and_then(Value::as_str) {
+            let bytes = if content.get("encoding").and_then(Value::as_str) == Some("base64") {
+                STANDARD.decode(text).map_err(|e| {
+                    ImportError::Invalid(format!("entry {} has invalid base64 body: {}", index, e))
+                })?
+            } else {
+                text.as_bytes().to_vec()
+            };
+            import.set_body(&mut response, bytes, mime_type);
+        }
+
+        if keep_latency {
+            response.delay = latency(entry).map(ResponseDelay::fixed);
+        }
+
+        let repeated = import.expectations.iter_mut().find(|exp| {
+            exp.method.eq_ignore_ascii_case(method)
+                && exp.path == path
+                && exp.query_params == query_params
+        });
+        match repeated {
+            Some(exp) => {
+                if exp.rotate.is_empty() {
+                    exp.rotate.push(exp.response.clone());
+                    exp.rotate_end = SequenceEnd::RepeatLast;
+                }
+                exp.rotate.push(response);
+            }
+            None => {
+                let mut exp = expectation(method, path, response);
+                exp.query_params = query_params;
+                import.expectations.push(exp);
+            }
+        }
+    }
+
+    Ok(import)
+}
+
+/// Recorded server wait time of an entry; HAR uses -1 for unknown timings
+fn latency(entry: &Value) -> Option<Duration> {
+    [
+        entry.pointer("/timings/wait").and_then(Value::as_f64),
+        entry.get("time").and_then(Value::as_f64),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|millis| *millis >= 0.0)
+    .filter(|millis| *millis > 0.0)
+    .map(|millis| Duration::from_millis(millis.round() as u64))
+}
+
+/// Converts logged requests and their responses into a HAR 1.2 archive
+///
+/// The host comes from the `Host` header. Requests logged without a
+/// response get status 0, which HAR uses for aborted requests.
+pub fn export(records: &[RequestRecord]) -> Value {
+    let entries: Vec<Value> = records.iter().map(export_entry).collect();
+
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "mimic-rs", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries
+        }
+    })
+}
+
+fn export_entry(record: &RequestRecord) -> Value {
+    let host = record
+        .headers
+        .get("host")
+        .map_or("localhost", String::as_str);
+    let mut url = format!("http://{}{}", host, record.path);
+    if !record.query_params.is_empty() {
+        let query: Vec<String> = sorted(&record.query_params)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        url = format!("{}?{}", url, query.join("&"));
+    }
+
+    let mut request = json!({
+        "method": record.method,
+        "url": url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": name_value_list(&record.headers),
+        "queryString": name_value_list(&record.query_params),
+        "headersSize": -1,
+        "bodySize": record.body.as_ref().map_or(0, String::len),
+    });
+    if let Some(body) = &record.body {
+        request["postData"] = json!({
+            "mimeType": record.headers.get("content-type").map_or("", String::as_str),
+            "text": body,
+        });
+    }
+
+    let response = match &record.response {
+        Some(response) => {
+            let mime_type = response
+                .headers
+                .get("content-type")
+                .map_or("", String::as_str);
+            let mut content = json!({
+                "size": response.body.as_ref().map_or(0, String::len),
+                "mimeType": mime_type,
+            });
+            if let Some(body) = &response.body {
+                content["text"] = json!(body);
+            }
+            json!({
+                "status": response.status,
+                "statusText": StatusCode::from_u16(response.status)
+                    .ok()
+                    .and_then(|status| status.canonical_reason())
+                    .unwrap_or_default(),
+                "httpVersion": "HTTP/1.1",
+                "cookies": [],
+                "headers": name_value_list(&response.headers),
+                "content": content,
+                "redirectURL": response.headers.get("location").map_or("", String::as_str),
+                "headersSize": -1,
+                "bodySize": -1,
+            })
+        }
+        None => json!({
+            "status": 0,
+            "statusText": "",
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": [],
+            "content": { "size": 0, "mimeType": "" },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": -1,
+        }),
+    };
+
+    let latency = record
+        .response
+        .as_ref()
+        .map_or(0.0, |response| response.latency_ms);
+    let mut entry = json!({
+        "startedDateTime": record.timestamp.to_rfc3339(),
+        "time": latency,
+        "request": request,
+        "response": response,
+        "cache": {},
+        "timings": { "send": 0, "wait": latency, "receive": 0 },
+    });
+    if let Some(id) = record
+        .response
+        .as_ref()
+        .and_then(|response| response.expectation_id.as_ref())
+    {
+        entry["_expectationId"] = json!(id);
+    }
+    entry
+}
+
+/// HAR `[{"name": ..., "value": ...}]` list, sorted by name
+fn name_value_list(values: &HashMap<String, String>) -> Value {
+    sorted(values)
+        .map(|(name, value)| json!({ "name": name, "value": value }))
+        .collect()
+}
+
+fn sorted(values: &HashMap<String, String>) -> impl Iterator<Item = (&String, &String)> {
+    let mut pairs: Vec<_> = values.iter().collect();
+    pairs.sort();
+    pairs.into_iter()
+}
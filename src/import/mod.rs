@@ -0,0 +1,273 @@
+mod har;
+pub(crate) mod openapi;
+mod postman;
+mod wiremock;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use serde_json::Value;
+use thiserror::Error;
+
+use crate::mappings::{self, MAPPINGS_DIR};
+use crate::models::{CreateExpectationRequest, MockResponse, RequestRecord, SequenceEnd};
+
+/// Errors produced while converting external definitions
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("failed to read input: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("input is not valid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("input is not valid YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("invalid document: {0}")]
+    Invalid(String),
+}
+
+/// Supported external formats
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportFormat {
+    OpenApi,
+    Har,
+    Postman,
+    WireMock,
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "openapi" => Ok(ImportFormat::OpenApi),
+            "har" => Ok(ImportFormat::Har),
+            "postman" => Ok(ImportFormat::Postman),
+            "wiremock" => Ok(ImportFormat::WireMock),
+            other => Err(format!(
+                "unknown import format '{}', expected openapi, har, postman or wiremock",
+                other
+            )),
+        }
+    }
+}
+
+/// A response body that is written next to the mappings instead of inline
+#[derive(Clone, Debug)]
+pub struct Fixture {
+    /// Path relative to the resources directory
+    pub name: String,
+
+    pub content: Vec<u8>,
+}
+
+/// Result of converting an external definition
+#[derive(Clone, Debug, Default)]
+pub struct Import {
+    pub expectations: Vec<CreateExpectationRequest>,
+
+    pub fixtures: Vec<Fixture>,
+}
+
+impl Import {
+    /// Writes fixtures into `out_dir` and mapping files into `out_dir/mappings`
+    ///
+    /// Returns the paths of the written mapping files.
+    pub fn write_to(&self, out_dir: &Path) -> io::Result<Vec<PathBuf>> {
+        for fixture in &self.fixtures {
+            let file_path = out_dir.join(&fixture.name);
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(file_path, &fixture.content)?;
+        }
+
+        mappings::write_mappings(&out_dir.join(MAPPINGS_DIR), &self.expectations)
+    }
+
+    /// Sets the response body, keeping non-JSON content in a fixture file
+    pub(crate) fn set_body(
+        &mut self,
+        response: &mut MockResponse,
+        content: Vec<u8>,
+        mime_type: Option<&str>,
+    ) {
+        if content.is_empty() {
+            return;
+        }
+
+        if let Ok(json) = serde_json::from_slice::<Value>(&content) {
+            response.body = Some(json);
+            return;
+        }
+
+        let name = format!(
+            "bodies/{:03}.{}",
+            self.fixtures.len() + 1,
+            extension_for(mime_type)
+        );
+        response.body_file = Some(name.clone());
+        self.fixtures.push(Fixture { name, content });
+    }
+
+    /// Moves the fixture a response refers to into its cached content
+    ///
+    /// Used when serving an import directly instead of writing it out.
+    pub(crate) fn inline_fixture(&self, response: &mut MockResponse) {
+        let Some(fixture) = response
+            .body_file
+            .as_ref()
+            .and_then(|name| self.fixtures.iter().find(|f| &f.name == name))
+        else {
+            return;
+        };
+        response.body_file = None;
+        response.cache_file(fixture.content.clone());
+    }
+}
+
+/// Reads `path` and converts it from the given format
+///
+/// Files ending in `.yaml` or `.yml` are parsed as YAML, all others as JSON.
+/// For WireMock `path` may also be a directory holding `mappings/` and `__files/`.
+pub fn import_file(format: ImportFormat, path: &Path) -> Result<Import, ImportError> {
+    if format == ImportFormat::WireMock && path.is_dir() {
+        return wiremock::convert_dir(path);
+    }
+
+    let document = read_document(path)?;
+
+    match format {
+        ImportFormat::OpenApi => openapi::convert(&document),
+        ImportFormat::Har => har::convert(&document, false),
+        ImportFormat::Postman => postman::convert(&document),
+        ImportFormat::WireMock => {
+            let base_dir = wiremock::root_dir(path);
+            wiremock::convert(&document, &base_dir)
+        }
+    }
+}
+
+/// Reads a HAR archive, optionally delaying responses by the recorded latency
+///
+/// Same as `import_file(ImportFormat::Har, path)` when `keep_latency` is false.
+pub fn import_har(path: &Path, keep_latency: bool) -> Result<Import, ImportError> {
+    har::convert(&read_document(path)?, keep_latency)
+}
+
+/// Converts logged requests and their responses into a HAR archive
+pub fn to_har(records: &[RequestRecord]) -> Value {
+    har::export(records)
+}
+
+/// Converts expectations into a WireMock `{"mappings": [...]}` document
+///
+/// Response files are referenced via `bodyFileName` and have to be copied
+/// into WireMock's `__files` directory.
+pub fn to_wiremock(expectations: &[CreateExpectationRequest]) -> Value {
+    wiremock::export(expectations)
+}
+
+/// Parses a JSON or YAML document, chosen by the file extension
+pub(crate) fn read_document(path: &Path) -> Result<Value, ImportError> {
+    let content = fs::read_to_string(path)?;
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml" | "yml") => Ok(serde_yaml::from_str(&content)?),
+        _ => Ok(serde_json::from_str(&content)?),
+    }
+}
+
+/// Splits a URL (absolute or relative) into its path and query parameters
+fn split_url(url: &str) -> (String, HashMap<String, String>) {
+    let without_fragment = url.split('#').next().unwrap_or_default();
+
+    let relative = match without_fragment.split_once("://") {
+        Some((_, rest)) => rest.find('/').map(|i| &rest[i..]).unwrap_or("/"),
+        None => without_fragment,
+    };
+
+    let (path, query) = match relative.split_once('?') {
+        Some((path, query)) => (path, Some(query)),
+        None => (relative, None),
+    };
+
+    let mut params = HashMap::new();
+    if let Some(query) = query {
+        for pair in query.split('&') {
+            if let Some((key, value)) = pair.split_once('=') {
+                params.insert(key.to_string(), value.to_string());
+            }
+        }
+    }
+
+    let path = if path.is_empty() {
+        "/".to_string()
+    } else if path.starts_with('/') {
+        path.to_string()
+    } else {
+        format!("/{}", path)
+    };
+
+    (path, params)
+}
+
+/// Headers that describe the original transfer and must not be replayed
+pub(crate) fn is_transfer_header(name: &str) -> bool {
+    matches!(
+        name.to_lowercase().as_str(),
+        "content-length" | "content-encoding" | "transfer-encoding" | "connection"
+    )
+}
+
+fn extension_for(mime_type: Option<&str>) -> &'static str {
+    let mime_type = mime_type.unwrap_or_default().to_lowercase();
+    if mime_type.contains("html") {
+        "html"
+    } else if mime_type.contains("xml") {
+        "xml"
+    } else if mime_type.contains("csv") {
+        "csv"
+    } else if mime_type.starts_with("text/") {
+        "txt"
+    } else {
+        "bin"
+    }
+}
+
+pub(crate) fn expectation(
+    method: &str,
+    path: String,
+    response: MockResponse,
+) -> CreateExpectationRequest {
+    CreateExpectationRequest {
+        method: method.to_uppercase(),
+        path,
+        query_params: HashMap::new(),
+        headers: HashMap::new(),
+        query_matchers: HashMap::new(),
+        header_matchers: HashMap::new(),
+        body: None,
+        json_body: None,
+        body_matchers: Vec::new(),
+        body_base64: None,
+        graphql: None,
+        jwt: None,
+        rate_limit: None,
+        state_updates: Vec::new(),
+        callbacks: Vec::new(),
+        scenario: None,
+        rotate: Vec::new(),
+        rotate_end: SequenceEnd::StartOver,
+        expect_continue: None,
+        matchers: Vec::new(),
+        priority: None,
+        times: None,
+        response_transforms: Vec::new(),
+        response,
+    }
+}
@@ -0,0 +1,236 @@
+use serde_json::{Map, Value};
+
+use super::{Import, ImportError, expectation, split_url};
+use crate::models::MockResponse;
+
+pub(crate) const METHODS: [&str; 7] = ["get", "put", "post", "delete", "patch", "head", "options"];
+
+/// Maximum nesting when synthesizing sample data from schemas
+const MAX_DEPTH: usize = 8;
+
+/// Converts an OpenAPI 3 document into expectations
+///
+/// Every operation gets one expectation answering with its first successful
+/// response. The body comes from `example`/`examples` or is synthesized from
+/// the schema. Path templates such as `{id}` become wildcards.
+pub fn convert(document: &Value) -> Result<Import, ImportError> {
+    let version = document
+        .get("openapi")
+        .and_then(Value::as_str)
+        .ok_or_else(|| ImportError::Invalid("missing 'openapi' version field".to_string()))?;
+    if !version.starts_with('3') {
+        return Err(ImportError::Invalid(format!(
+            "unsupported OpenAPI version {}, expected 3.x",
+            version
+        )));
+    }
+
+    let paths = document
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| ImportError::Invalid("missing 'paths' object".to_string()))?;
+
+    let base_path = base_path(document);
+    let mut import = Import::default();
+
+    for (path, item) in paths {
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+
+            let mut response = MockResponse::default();
+            if let Some((status, definition)) = pick_response(operation) {
+                response.status_code = status;
+                if let Some(body) = response_example(definition, document) {
+                    response = response.with_json_body(body);
+                }
+            }
+
+            let full_path = format!("{}{}", base_path, to_wildcard_path(path));
+            import
+                .expectations
+                .push(expectation(method, full_path, response));
+        }
+    }
+
+    Ok(import)
+}
+
+/// Path prefix of the first server, e.g. `/v1` for `https://api.example.com/v1`
+pub(crate) fn base_path(document: &Value) -> String {
+    document
+        .pointer("/servers/0/url")
+        .and_then(Value::as_str)
+        .map(|url| split_url(url).0)
+        .map(|path| path.trim_end_matches('/').to_string())
+        .unwrap_or_default()
+}
+
+/// Replaces `{param}` segments with wildcards
+pub(crate) fn to_wildcard_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut in_param = false;
+
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_param = true;
+                result.push('*');
+            }
+            '}' => in_param = false,
+            _ if !in_param => result.push(c),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+/// Picks the lowest 2xx response, falling back to `default`
+fn pick_response(operation: &Value) -> Option<(u16, &Value)> {
+    let responses = operation.get("responses")?.as_object()?;
+
+    let success = responses
+        .iter()
+        .filter_map(|(code, definition)| code.parse::<u16>().ok().map(|c| (c, definition)))
+        .filter(|(code, _)| (200..300).contains(code))
+        .min_by_key(|(code, _)| *code);
+
+    success.or_else(|| responses.get("default").map(|definition| (200, definition)))
+}
+
+fn response_example(definition: &Value, document: &Value) -> Option<Value> {
+    let definition = resolve(definition, document);
+    let content = definition.get("content")?.as_object()?;
+
+    let media = content
+        .iter()
+        .find(|(media_type, _)| media_type.contains("json"))
+        .map(|(_, media)| media)?;
+
+    if let Some(example) = media.get("example") {
+        return Some(example.clone());
+    }
+
+    if let Some(value) = media
+        .get("examples")
+        .and_then(Value::as_object)
+        .and_then(|examples| examples.values().next())
+        .map(|example| resolve(example, document))
+        .and_then(|example| example.get("value"))
+    {
+        return Some(value.clone());
+    }
+
+    media
+        .get("schema")
+        .map(|schema| sample_from_schema(schema, document, 0))
+}
+
+/// Follows a local `$ref` such as `#/components/schemas/User`
+pub(crate) fn resolve<'a>(value: &'a Value, document: &'a Value) -> &'a Value {
+    let mut current = value;
+    for _ in 0..MAX_DEPTH {
+        match current
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| document.pointer(pointer))
+        {
+            Some(target) => current = target,
+            None => break,
+        }
+    }
+    current
+}
+
+/// Synthesizes sample data matching a JSON schema
+pub(crate) fn sample_from_schema(schema: &Value, document: &Value, depth: usize) -> Value {
+    if depth > MAX_DEPTH {
+        return Value::Null;
+    }
+
+    let schema = resolve(schema, document);
+
+    for key in ["example", "default"] {
+        if let Some(value) = schema.get(key) {
+            return value.clone();
+        }
+    }
+
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in all_of {
+            if let Value::Object(object) = sample_from_schema(part, document, depth + 1) {
+                merged.extend(object);
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|variants| variants.first())
+        {
+            return sample_from_schema(first, document, depth + 1);
+        }
+    }
+
+    match schema_type(schema) {
+        Some("object") => {
+            let mut object = Map::new();
+            if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+                for (name, property) in properties {
+                    object.insert(
+                        name.clone(),
+                        sample_from_schema(property, document, depth + 1),
+                    );
+                }
+            }
+            Value::Object(object)
+        }
+        Some("array") => match schema.get("items") {
+            Some(items) => Value::Array(vec![sample_from_schema(items, document, depth + 1)]),
+            None => Value::Array(Vec::new()),
+        },
+        Some("string") => Value::String(
+            match schema.get("format").and_then(Value::as_str) {
+                Some("date-time") => "2024-01-01T00:00:00Z",
+                Some("date") => "2024-01-01",
+                Some("uuid") => "00000000-0000-0000-0000-000000000000",
+                Some("email") => "user@example.com",
+                Some("uri") | Some("url") => "https://example.com",
+                _ => "string",
+            }
+            .to_string(),
+        ),
+        Some("integer") => Value::from(0),
+        Some("number") => Value::from(0.0),
+        Some("boolean") => Value::Bool(true),
+        _ => Value::Null,
+    }
+}
+
+/// Reads `type`, accepting the OpenAPI 3.1 array form
+pub(crate) fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::String(t)) => Some(t.as_str()),
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        _ if schema.get("properties").is_some() => Some("object"),
+        _ => None,
+    }
+}
@@ -0,0 +1,127 @@
+use serde_json::Value;
+
+use super::{Import, ImportError, expectation, is_transfer_header, split_url};
+use crate::models::MockResponse;
+
+/// Converts a Postman collection (v2.x) into expectations
+///
+/// Folders are flattened. The first saved example response of a request is
+/// used as the mock response, requests without examples answer 200.
+/// Path variables (`:id`, `{{id}}`) become wildcards.
+pub fn convert(document: &Value) -> Result<Import, ImportError> {
+    let items = document
+        .get("item")
+        .and_then(Value::as_array)
+        .ok_or_else(|| ImportError::Invalid("missing collection 'item' array".to_string()))?;
+
+    let mut import = Import::default();
+    collect_items(items, &mut import);
+    Ok(import)
+}
+
+fn collect_items(items: &[Value], import: &mut Import) {
+    for item in items {
+        if let Some(children) = item.get("item").and_then(Value::as_array) {
+            collect_items(children, import);
+            continue;
+        }
+
+        let Some(request) = item.get("request") else {
+            continue;
+        };
+
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("GET");
+        let path = request_path(request);
+
+        let mut response = MockResponse::default();
+        if let Some(example) = item
+            .get("response")
+            .and_then(Value::as_array)
+            .and_then(|responses| responses.first())
+        {
+            if let Some(code) = example
+                .get("code")
+                .and_then(Value::as_u64)
+                .and_then(|code| u16::try_from(code).ok())
+            {
+                response.status_code = code;
+            }
+
+            let mut content_type = None;
+            for header in example
+                .get("header")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if let (Some(key), Some(value)) = (
+                    header.get("key").and_then(Value::as_str),
+                    header.get("value").and_then(Value::as_str),
+                ) && !is_transfer_header(key)
+                {
+                    if key.eq_ignore_ascii_case("content-type") {
+                        content_type = Some(value.to_string());
+                    }
+                    response.headers.insert(key.to_string(), value.to_string());
+                }
+            }
+
+            if let Some(body) = example.get("body").and_then(Value::as_str) {
+                import.set_body(
+                    &mut response,
+                    body.as_bytes().to_vec(),
+                    content_type.as_deref(),
+                );
+            }
+        }
+
+        import
+            .expectations
+            .push(expectation(method, path, response));
+    }
+}
+
+/// Extracts the path from either the string or the object form of `url`
+fn request_path(request: &Value) -> String {
+    match request.get("url") {
+        Some(Value::String(raw)) => wildcard_variables(&split_url(strip_host_variable(raw)).0),
+        Some(url) => match url.get("path").and_then(Value::as_array) {
+            Some(segments) => {
+                let segments: Vec<&str> = segments.iter().filter_map(Value::as_str).collect();
+                wildcard_variables(&format!("/{}", segments.join("/")))
+            }
+            None => url
+                .get("raw")
+                .and_then(Value::as_str)
+                .map(|raw| wildcard_variables(&split_url(strip_host_variable(raw)).0))
+                .unwrap_or_else(|| "/".to_string()),
+        },
+        None => "/".to_string(),
+    }
+}
+
+/// Removes a leading `{{baseUrl}}` style host variable
+fn strip_host_variable(raw: &str) -> &str {
+    if raw.starts_with("{{")
+        && let Some(end) = raw.find("}}")
+    {
+        return &raw[end + 2..];
+    }
+    raw
+}
+
+fn wildcard_variables(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            if segment.starts_with(':') || (segment.starts_with("{{") && segment.ends_with("}}")) {
+                "*"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
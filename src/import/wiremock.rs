@@ -0,0 +1,467 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::{Map, Value, json};
+use tracing::warn;
+
+use super::{
+    Fixture, Import, ImportError, expectation, is_transfer_header, read_document, split_url,
+};
+use crate::mappings::MAPPINGS_DIR;
+use crate::models::{
+    CreateExpectationRequest, JsonBodyMatch, MockResponse, ResponseDelay, ScenarioRule,
+};
+
+/// Directory WireMock keeps the files referenced by `bodyFileName` in
+const FILES_DIR: &str = "__files";
+
+/// Converts WireMock stub mappings into expectations
+///
+/// Accepts a single mapping or a `{"mappings": [...]}` document. Only
+/// `equalTo` and `equalToJson` matchers can be translated; other matchers
+/// are skipped with a warning. Files referenced by `bodyFileName` are copied from `__files`.
+/// Scenario states and fixed or uniform delays carry over.
+pub fn convert(document: &Value, root_dir: &Path) -> Result<Import, ImportError> {
+    let mut import = Import::default();
+    convert_into(document, root_dir, &mut import)?;
+    Ok(import)
+}
+
+/// Converts every mapping file below `root_dir/mappings`, in file name order
+pub fn convert_dir(root_dir: &Path) -> Result<Import, ImportError> {
+    let mappings_dir = root_dir.join(MAPPINGS_DIR);
+    if !mappings_dir.is_dir() {
+        return Err(ImportError::Invalid(format!(
+            "{} has no {} directory",
+            root_dir.display(),
+            MAPPINGS_DIR
+        )));
+    }
+
+    let mut files = Vec::new();
+    collect_mapping_files(&mappings_dir, &mut files)?;
+    files.sort();
+
+    let mut import = Import::default();
+    for file in files {
+        let document = read_document(&file)?;
+        convert_into(&document, root_dir, &mut import)
+            .map_err(|e| ImportError::Invalid(format!("{}: {}", file.display(), e)))?;
+    }
+    Ok(import)
+}
+
+fn collect_mapping_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_mapping_files(&path, files)?;
+        } else if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn convert_into(document: &Value, root_dir: &Path, import: &mut Import) -> Result<(), ImportError> {
+    let mappings = match document.get("mappings").and_then(Value::as_array) {
+        Some(mappings) => mappings.iter().collect(),
+        None if document.get("request").is_some() => vec![document],
+        None => {
+            return Err(ImportError::Invalid(
+                "expected a stub mapping or a 'mappings' array".to_string(),
+            ));
+        }
+    };
+
+    for (index, mapping) in mappings.into_iter().enumerate() {
+        let request = mapping.get("request").unwrap_or(&Value::Null);
+
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or("GET");
+        if method.eq_ignore_ascii_case("ANY") {
+            warn!("Skipping mapping {}: method ANY is not supported", index);
+            continue;
+        }
+
+        let Some((path, query_params)) = request_url(request) else {
+            warn!("Skipping mapping {}: no url matcher", index);
+            continue;
+        };
+
+        let mut response = MockResponse::default();
+        let definition = mapping.get("response").unwrap_or(&Value::Null);
+        if let Some(status) = definition
+            .get("status")
+            .and_then(Value::as_u64)
+            .and_then(|status| u16::try_from(status).ok())
+        {
+            response.status_code = status;
+        }
+
+        let mut content_type = None;
+        for (name, value) in definition
+            .get("headers")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            if let Some(value) = value.as_str()
+                && !is_transfer_header(name)
+            {
+                if name.eq_ignore_ascii_case("content-type") {
+                    content_type = Some(value.to_string());
+                }
+                response.headers.insert(name.clone(), value.to_string());
+            }
+        }
+
+        if let Some(json) = definition.get("jsonBody") {
+            response = response.with_json_body(json.clone());
+        } else if let Some(encoded) = definition.get("base64Body").and_then(Value::as_str) {
+            response.body_base64 = Some(encoded.to_string());
+        } else if let Some(body) = definition.get("body").and_then(Value::as_str) {
+            import.set_body(
+                &mut response,
+                body.as_bytes().to_vec(),
+                content_type.as_deref(),
+            );
+        } else if let Some(file_name) = definition.get("bodyFileName").and_then(Value::as_str) {
+            let source = root_dir.join(FILES_DIR).join(file_name);
+            match fs::read(&source) {
+                Ok(content) => import.fixtures.push(Fixture {
+                    name: file_name.to_string(),
+                    content,
+                }),
+                Err(e) => warn!("Could not copy body file {}: {}", source.display(), e),
+            }
+            response.body_file = Some(file_name.to_string());
+        }
+        response.delay = response_delay(definition);
+
+        let mut exp = expectation(method, path, response);
+        exp.query_params.extend(query_params);
+
+        for (name, matcher) in request
+            .get("queryParameters")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            match matcher.get("equalTo").and_then(Value::as_str) {
+                Some(value) => {
+                    exp.query_params.insert(name.clone(), value.to_string());
+                }
+                None => warn!("Skipping unsupported query matcher for '{}'", name),
+            }
+        }
+
+        for (name, matcher) in request
+            .get("headers")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            match matcher.get("equalTo").and_then(Value::as_str) {
+                Some(value) => {
+                    exp.headers.insert(name.to_lowercase(), value.to_string());
+                }
+                None => warn!("Skipping unsupported header matcher for '{}'", name),
+            }
+        }
+
+        if let Some(patterns) = request.get("bodyPatterns").and_then(Value::as_array) {
+            for pattern in patterns {
+                if let Some(body) = pattern.get("equalTo").and_then(Value::as_str) {
+                    exp.body = Some(body.to_string());
+                } else if let Some(encoded) = pattern.get("binaryEqualTo").and_then(Value::as_str) {
+                    exp.body_base64 = Some(encoded.to_string());
+                } else if let Some(json) = pattern.get("equalToJson").and_then(json_pattern) {
+                    let partial = pattern.get("ignoreExtraElements") == Some(&Value::Bool(true));
+                    exp.json_body = Some(if partial {
+                        JsonBodyMatch::Contains(json)
+                    } else {
+                        JsonBodyMatch::Equals(json)
+                    });
+                } else {
+                    warn!("Skipping unsupported body pattern in mapping {}", index);
+                }
+            }
+        }
+
+        if let Some(name) = mapping.get("scenarioName").and_then(Value::as_str) {
+            let state = |key: &str| mapping.get(key).and_then(Value::as_str).map(String::from);
+            exp.scenario = Some(ScenarioRule {
+                name: name.to_string(),
+                required_state: state("requiredScenarioState"),
+                new_state: state("newScenarioState"),
+            });
+        }
+
+        import.expectations.push(exp);
+    }
+
+    Ok(())
+}
+
+/// `fixedDelayMilliseconds` or a uniform `delayDistribution`
+fn response_delay(definition: &Value) -> Option<ResponseDelay> {
+    if let Some(millis) = definition
+        .get("fixedDelayMilliseconds")
+        .and_then(Value::as_u64)
+    {
+        return Some(ResponseDelay::fixed(Duration::from_millis(millis)));
+    }
+
+    let distribution = definition.get("delayDistribution")?;
+    if distribution.get("type").and_then(Value::as_str) != Some("uniform") {
+        warn!("Skipping unsupported delay distribution");
+        return None;
+    }
+    let bound = |key: &str| distribution.get(key).and_then(Value::as_u64);
+    Some(ResponseDelay::between(
+        Duration::from_millis(bound("lower")?),
+        Duration::from_millis(bound("upper")?),
+    ))
+}
+
+/// Converts expectations into a WireMock `{"mappings": [...]}` document
+///
+/// Parts WireMock has no equivalent for, such as token requirements,
+/// rotating responses or extension matchers, are left out with a warning.
+/// Response files are referenced via `bodyFileName` and have to be placed
+/// in `__files`.
+pub fn export(expectations: &[CreateExpectationRequest]) -> Value {
+    let mappings: Vec<Value> = expectations.iter().map(export_mapping).collect();
+    json!({ "mappings": mappings })
+}
+
+fn export_mapping(exp: &CreateExpectationRequest) -> Value {
+    let mut request = Map::new();
+    request.insert("method".to_string(), json!(exp.method));
+    let (url_key, url) = url_matcher(&exp.path);
+    request.insert(url_key.to_string(), json!(url));
+
+    if !exp.query_params.is_empty() {
+        request.insert(
+            "queryParameters".to_string(),
+            equal_to_matchers(exp.query_params.iter()),
+        );
+    }
+    if !exp.headers.is_empty() {
+        request.insert("headers".to_string(), equal_to_matchers(exp.headers.iter()));
+    }
+
+    let mut body_patterns = Vec::new();
+    if let Some(body) = &exp.body {
+        body_patterns.push(json!({ "equalTo": body }));
+    }
+    if let Some(encoded) = &exp.body_base64 {
+        body_patterns.push(json!({ "binaryEqualTo": encoded }));
+    }
+    match &exp.json_body {
+        Some(JsonBodyMatch::Equals(json)) => body_patterns.push(json!({ "equalToJson": json })),
+        Some(JsonBodyMatch::Contains(json)) => body_patterns.push(json!({
+            "equalToJson": json,
+            "ignoreExtraElements": true,
+            "ignoreArrayOrder": true
+        })),
+        None => {}
+    }
+    if !body_patterns.is_empty() {
+        request.insert("bodyPatterns".to_string(), Value::Array(body_patterns));
+    }
+
+    let unsupported = [
+        ("jwt", exp.jwt.is_some()),
+        ("rate_limit", exp.rate_limit.is_some()),
+        ("state_updates", !exp.state_updates.is_empty()),
+        ("callbacks", !exp.callbacks.is_empty()),
+        ("rotate", !exp.rotate.is_empty()),
+        ("expect_continue", exp.expect_continue.is_some()),
+        ("response_transforms", !exp.response_transforms.is_empty()),
+        ("matchers", !exp.matchers.is_empty()),
+        ("query_matchers", !exp.query_matchers.is_empty()),
+        ("header_matchers", !exp.header_matchers.is_empty()),
+        ("body_matchers", !exp.body_matchers.is_empty()),
+        ("graphql", exp.graphql.is_some()),
+        ("times", exp.times.is_some()),
+    ];
+    for (field, _) in unsupported.iter().filter(|(_, set)| *set) {
+        warn!(
+            "{} {}: '{}' has no WireMock equivalent and is left out",
+            exp.method, exp.path, field
+        );
+    }
+
+    let mut mapping = Map::new();
+    mapping.insert("request".to_string(), Value::Object(request));
+    mapping.insert("response".to_string(), export_response(&exp.response));
+    if let Some(scenario) = &exp.scenario {
+        mapping.insert("scenarioName".to_string(), json!(scenario.name));
+        if let Some(state) = &scenario.required_state {
+            mapping.insert("requiredScenarioState".to_string(), json!(state));
+        }
+        if let Some(state) = &scenario.new_state {
+            mapping.insert("newScenarioState".to_string(), json!(state));
+        }
+    }
+    Value::Object(mapping)
+}
+
+fn export_response(response: &MockResponse) -> Value {
+    let mut definition = Map::new();
+    definition.insert("status".to_string(), json!(response.status_code));
+    if !response.headers.is_empty() {
+        definition.insert("headers".to_string(), json!(response.headers));
+    }
+    if let Some(body) = &response.body {
+        definition.insert("jsonBody".to_string(), body.clone());
+    } else if let Some(encoded) = &response.body_base64 {
+        definition.insert("base64Body".to_string(), json!(encoded));
+    } else if let Some(file_name) = &response.body_file {
+        definition.insert("bodyFileName".to_string(), json!(file_name));
+    }
+    match &response.delay {
+        Some(delay) if delay.min_ms == delay.max_ms => {
+            definition.insert("fixedDelayMilliseconds".to_string(), json!(delay.min_ms));
+        }
+        Some(delay) => {
+            definition.insert(
+                "delayDistribution".to_string(),
+                json!({ "type": "uniform", "lower": delay.min_ms, "upper": delay.max_ms }),
+            );
+        }
+        None => {}
+    }
+    Value::Object(definition)
+}
+
+/// The WireMock URL matcher for a path with optional `{name}` and `*` parts
+fn url_matcher(path: &str) -> (&'static str, String) {
+    if path.contains('*') {
+        let mut pattern = String::new();
+        let mut rest = path;
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+                break;
+            };
+            pattern.push_str(&wildcard_regex(&rest[..start]));
+            pattern.push_str("[^/]+");
+            rest = &rest[end + 1..];
+        }
+        pattern.push_str(&wildcard_regex(rest));
+        ("urlPathPattern", pattern)
+    } else if path.contains('{') {
+        ("urlPathTemplate", path.to_string())
+    } else {
+        ("urlPath", path.to_string())
+    }
+}
+
+fn wildcard_regex(literal: &str) -> String {
+    literal
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*")
+}
+
+fn equal_to_matchers<'a>(values: impl Iterator<Item = (&'a String, &'a String)>) -> Value {
+    values
+        .map(|(name, value)| (name.clone(), json!({ "equalTo": value })))
+        .collect::<Map<_, _>>()
+        .into()
+}
+
+/// Directory containing `mappings/` and `__files/` for a mapping file
+pub fn root_dir(mapping_file: &Path) -> PathBuf {
+    let dir = mapping_file.parent().unwrap_or(Path::new("."));
+    if dir.file_name().and_then(|name| name.to_str()) == Some("mappings") {
+        dir.parent().unwrap_or(Path::new(".")).to_path_buf()
+    } else {
+        dir.to_path_buf()
+    }
+}
+
+/// `equalToJson` holds the JSON itself or the JSON as a string
+fn json_pattern(value: &Value) -> Option<Value> {
+    match value {
+        Value::String(json) => serde_json::from_str(json).ok(),
+        json => Some(json.clone()),
+    }
+}
+
+fn request_url(request: &Value) -> Option<(String, std::collections::HashMap<String, String>)> {
+    if let Some(url) = request.get("url").and_then(Value::as_str) {
+        return Some(split_url(url));
+    }
+    if let Some(path) = ["urlPath", "urlPathTemplate"]
+        .iter()
+        .find_map(|key| request.get(*key).and_then(Value::as_str))
+    {
+        return Some((path.to_string(), Default::default()));
+    }
+
+    ["urlPathPattern", "urlPattern"]
+        .iter()
+        .find_map(|key| request.get(*key).and_then(Value::as_str))
+        .map(|pattern| (regex_to_wildcard(pattern), Default::default()))
+}
+
+/// Best-effort translation of a URL regex into a wildcard path
+///
+/// Character classes, `.` and `\d` followed by a quantifier become `*`.
+fn regex_to_wildcard(pattern: &str) -> String {
+    let pattern = pattern.trim_start_matches('^').trim_end_matches('$');
+    let chars: Vec<char> = pattern.chars().collect();
+    let mut result = String::with_capacity(pattern.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let token_end = match chars[i] {
+            '[' => chars[i..].iter().position(|c| *c == ']').map(|p| i + p + 1),
+            '.' => Some(i + 1),
+            '\\' if matches!(chars.get(i + 1), Some('d') | Some('w')) => Some(i + 2),
+            '\\' => {
+                if let Some(c) = chars.get(i + 1) {
+                    result.push(*c);
+                }
+                i += 2;
+                continue;
+            }
+            c => {
+                result.push(c);
+                i += 1;
+                continue;
+            }
+        };
+
+        let Some(mut end) = token_end else {
+            result.push(chars[i]);
+            i += 1;
+            continue;
+        };
+
+        if matches!(chars.get(end), Some('*') | Some('+') | Some('?')) {
+            end += 1;
+        } else if chars.get(end) == Some(&'{') {
+            end = chars[end..]
+                .iter()
+                .position(|c| *c == '}')
+                .map(|p| end + p + 1)
+                .unwrap_or(end);
+        }
+
+        if !result.ends_with('*') {
+            result.push('*');
+        }
+        i = end;
+    }
+
+    result
+}
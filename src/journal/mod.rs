@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, Row, params, params_from_iter};
+use tracing::warn;
+
+use crate::models::{RecordedResponse, RequestQuery, RequestRecord, SortOrder};
+
+/// Tables and indexes of the journal, created on open if missing
+const SCHEMA: &str = "
+    PRAGMA journal_mode = WAL;
+    PRAGMA synchronous = NORMAL;
+    CREATE TABLE IF NOT EXISTS requests (
+        seq INTEGER PRIMARY KEY AUTOINCREMENT,
+        id TEXT NOT NULL,
+        method TEXT NOT NULL,
+        path TEXT NOT NULL,
+        timestamp TEXT NOT NULL,
+        record TEXT NOT NULL,
+        response TEXT,
+        expectation_id TEXT
+    );
+    CREATE INDEX IF NOT EXISTS requests_id ON requests (id);
+    CREATE INDEX IF NOT EXISTS requests_method ON requests (method COLLATE NOCASE);
+    CREATE INDEX IF NOT EXISTS requests_path ON requests (path);
+    CREATE INDEX IF NOT EXISTS requests_timestamp ON requests (timestamp);
+    CREATE INDEX IF NOT EXISTS requests_expectation ON requests (expectation_id);
+";
+
+/// Request log on disk in an SQLite database
+///
+/// Unlike the in-memory log it survives restarts and is not trimmed to the
+/// maximum log size, so call counts cover the whole history of the file.
+/// Responses are attached to their requests once they are ready.
+/// Method, path, time and answering expectation are indexed, queries on
+/// them do not read the whole journal.
+///
+/// All methods do blocking I/O.
+#[derive(Debug)]
+pub struct RequestJournal {
+    path: PathBuf,
+
+    connection: Mutex<Connection>,
+}
+
+impl RequestJournal {
+    /// Opens the journal at `path`, creating it if needed
+    pub fn open<P: Into<PathBuf>>(path: P) -> io::Result<Self> {
+        let path = path.into();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let connection = Connection::open(&path).map_err(io::Error::other)?;
+        connection.execute_batch(SCHEMA).map_err(io::Error::other)?;
+
+        Ok(Self {
+            path,
+            connection: Mutex::new(connection),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, record: &RequestRecord) -> io::Result<()> {
+        let response = record
+            .response
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()?;
+        let expectation_id = record
+            .response
+            .as_ref()
+            .and_then(|response| response.expectation_id.as_deref());
+        let record_json = serde_json::to_string(&RequestRecord {
+            response: None,
+            ..record.clone()
+        })?;
+
+        self.with_connection(|connection| {
+            connection.execute(
+                "INSERT INTO requests (id, method, path, timestamp, record, response, expectation_id)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    record.id,
+                    record.method,
+                    record.path,
+                    sortable(record.timestamp),
+                    record_json,
+                    response,
+                    expectation_id
+                ],
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Attaches the response sent for the request with `id`
+    pub fn append_response(&self, id: &str, response: &RecordedResponse) -> io::Result<()> {
+        let response_json = serde_json::to_string(response)?;
+        self.with_connection(|connection| {
+            connection.execute(
+                "UPDATE requests SET response = ?1, expectation_id = ?2 WHERE id = ?3",
+                params![response_json, response.expectation_id, id],
+            )
+        })?;
+        Ok(())
+    }
+
+    /// Reads all records with their responses, oldest first
+    pub fn read_all(&self) -> io::Result<Vec<RequestRecord>> {
+        self.read_where(None, |_| true)
+    }
+
+    /// Reads the records accepted by `predicate` with their responses, oldest first
+    ///
+    /// With a `method` only the records for it (case-insensitive) are
+    /// looked at. Only the accepted records are kept in memory.
+    pub fn read_where(
+        &self,
+        method: Option<&str>,
+        predicate: impl Fn(&RequestRecord) -> bool,
+    ) -> io::Result<Vec<RequestRecord>> {
+        let mut records = Vec::new();
+        self.for_each(method, |record| {
+            if predicate(&record) {
+                records.push(record);
+            }
+        })?;
+        Ok(records)
+    }
+
+    /// Counts the records for `method` and `path`
+    pub fn count(&self, method: &str, path: &str) -> io::Result<usize> {
+        let count: i64 = self.with_connection(|connection| {
+            connection.query_row(
+                "SELECT COUNT(*) FROM requests WHERE method = ?1 AND path = ?2",
+                params![method, path],
+                |row| row.get(0),
+            )
+        })?;
+        Ok(count as usize)
+    }
+
+    /// Counts the records accepted by `predicate` without keeping them in memory
+    ///
+    /// With a `method` only the records for it (case-insensitive) are looked at.
+    pub fn count_where(
+        &self,
+        method: Option<&str>,
+        predicate: impl Fn(&RequestRecord) -> bool,
+    ) -> io::Result<usize> {
+        let mut count = 0;
+        self.for_each(method, |record| {
+            if predicate(&record) {
+                count += 1;
+            }
+        })?;
+        Ok(count)
+    }
+
+    /// The page of records selected by `query` and the number of matching records
+    pub fn query(&self, query: &RequestQuery) -> io::Result<(Vec<RequestRecord>, usize)> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(method) = &query.method {
+            values.push(SqlValue::Text(method.clone()));
+            conditions.push(format!("method = ?{} COLLATE NOCASE", values.len()));
+        }
+        if let Some(prefix) = &query.path_prefix {
+            values.push(SqlValue::Text(prefix.clone()));
+            let n = values.len();
+            conditions.push(format!("substr(path, 1, length(?{n})) = ?{n}"));
+        }
+        if let Some(since) = query.since {
+            values.push(SqlValue::Text(sortable(since)));
+            conditions.push(format!("timestamp >= ?{}", values.len()));
+        }
+        let filter = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let order = match query.order {
+            SortOrder::Asc => "ASC",
+            SortOrder::Desc => "DESC",
+        };
+        let limit = query
+            .limit
+            .map_or(-1, |limit| limit.min(i64::MAX as usize) as i64);
+
+        self.with_connection(|connection| {
+            let total: i64 = connection.query_row(
+                &format!("SELECT COUNT(*) FROM requests {filter}"),
+                params_from_iter(&values),
+                |row| row.get(0),
+            )?;
+
+            let mut statement = connection.prepare(&format!(
+                "SELECT seq, record, response FROM requests {filter}
+                 ORDER BY seq {order} LIMIT {limit} OFFSET {}",
+                query.offset
+            ))?;
+            let mut rows = statement.query(params_from_iter(&values))?;
+            let mut page = Vec::new();
+            while let Some(row) = rows.next()? {
+                if let Some(record) = self.parse(row) {
+                    page.push(record);
+                }
+            }
+            Ok((page, total as usize))
+        })
+    }
+
+    /// Counts the responses per answering expectation id
+    pub fn hit_counts(&self) -> io::Result<HashMap<String, usize>> {
+        self.with_connection(|connection| {
+            let mut statement = connection.prepare(
+                "SELECT expectation_id, COUNT(*) FROM requests
+                 WHERE expectation_id IS NOT NULL GROUP BY expectation_id",
+            )?;
+            statement
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as usize))
+                })?
+                .collect()
+        })
+    }
+
+    /// Removes all records
+    pub fn clear(&self) -> io::Result<()> {
+        self.with_connection(|connection| connection.execute("DELETE FROM requests", []))?;
+        Ok(())
+    }
+
+    /// Passes the records, optionally only those for `method`, to `f` oldest first
+    fn for_each(&self, method: Option<&str>, mut f: impl FnMut(RequestRecord)) -> io::Result<()> {
+        self.with_connection(|connection| {
+            let mut statement;
+            let mut rows = match method {
+                Some(method) => {
+                    statement = connection.prepare(
+                        "SELECT seq, record, response FROM requests
+                         WHERE method = ?1 COLLATE NOCASE ORDER BY seq",
+                    )?;
+                    statement.query([method])?
+                }
+                None => {
+                    statement = connection
+                        .prepare("SELECT seq, record, response FROM requests ORDER BY seq")?;
+                    statement.query([])?
+                }
+            };
+            while let Some(row) = rows.next()? {
+                if let Some(record) = self.parse(row) {
+                    f(record);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// The record in `row`, `None` with a warning if it cannot be parsed
+    fn parse(&self, row: &Row) -> Option<RequestRecord> {
+        let parsed = (|| -> Result<RequestRecord, Box<dyn std::error::Error>> {
+            let mut record: RequestRecord = serde_json::from_str(&row.get::<_, String>(1)?)?;
+            if let Some(response) = row.get::<_, Option<String>>(2)? {
+                record.response = Some(serde_json::from_str(&response)?);
+            }
+            Ok(record)
+        })();
+
+        match parsed {
+            Ok(record) => Some(record),
+            Err(e) => {
+                warn!(
+                    "Skipping invalid journal entry {}#{}: {}",
+                    self.path.display(),
+                    row.get::<_, i64>(0).unwrap_or_default(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    fn with_connection<T>(
+        &self,
+        f: impl FnOnce(&Connection) -> rusqlite::Result<T>,
+    ) -> io::Result<T> {
+        let connection = self.connection.lock().unwrap_or_else(|e| e.into_inner());
+        f(&connection).map_err(io::Error::other)
+    }
+}
+
+/// `time` in a fixed-width UTC format whose text order is the time order
+fn sortable(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Nanos, true)
+}
@@ -1,10 +1,30 @@
+pub mod auth;
+pub mod clock;
+pub mod cluster;
 pub mod conditional;
+pub mod cors;
+pub mod extension;
 pub mod handlers;
+pub mod import;
+pub mod journal;
+pub mod logging;
+pub mod mappings;
 pub mod models;
+pub mod openapi;
+pub mod proxy;
+pub mod random;
+pub(crate) mod script;
 pub mod server;
+pub mod state;
+pub(crate) mod template;
+pub mod tls;
+pub mod transform;
 
 // Re-export modules
-pub use conditional::ConditionalResponse;
+pub use conditional::{ConditionalResponse, Matcher, ReceivedRequest};
 pub use models::MockResponse;
-pub use server::MockServer;
-pub use server::expectation_builder::{ExpectationBuilder, ResponseBuilder};
+pub use random::Random;
+pub use server::expectation_builder::{ExpectationBuilder, ResponseBuilder, SequenceBuilder};
+pub use server::scenario_builder::ScenarioBuilder;
+pub use server::verification_builder::VerificationBuilder;
+pub use server::{Mock, MockServer, ServerHandle};
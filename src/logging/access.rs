@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Replaces the values of redacted headers
+pub const REDACTED: &str = "[REDACTED]";
+
+/// One line of the access log
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AccessLogEntry {
+    /// When the response was ready, by the server's clock
+    pub timestamp: DateTime<Utc>,
+
+    pub method: String,
+
+    pub path: String,
+
+    pub status: u16,
+
+    pub latency_ms: f64,
+
+    /// The expectation that answered, absent for 404s and other fallbacks
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expectation_id: Option<String>,
+
+    /// Request headers, with redacted values replaced by [`REDACTED`]
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub headers: HashMap<String, String>,
+}
+
+/// Destination of the access log, one JSON object per served request
+pub struct AccessLog {
+    out: Mutex<Box<dyn Write + Send>>,
+}
+
+impl fmt::Debug for AccessLog {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AccessLog").finish_non_exhaustive()
+    }
+}
+
+impl AccessLog {
+    /// Writes the lines to standard output
+    pub fn stdout() -> Self {
+        Self {
+            out: Mutex::new(Box::new(io::stdout())),
+        }
+    }
+
+    /// Appends the lines to the file at `path`, creating it if needed
+    pub fn file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(Self {
+            out: Mutex::new(Box::new(file)),
+        })
+    }
+
+    pub(crate) fn write(&self, entry: &AccessLogEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let mut out = self.out.lock().unwrap_or_else(|e| e.into_inner());
+        out.write_all(&line)?;
+        out.flush()
+    }
+}
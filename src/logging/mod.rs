@@ -0,0 +1,130 @@
+mod access;
+mod json;
+
+use std::fmt;
+use std::str::FromStr;
+
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+use tracing_subscriber::{Registry, fmt as tracing_fmt, reload};
+
+pub use access::{AccessLog, AccessLogEntry, REDACTED};
+pub use json::JsonFormat;
+
+/// Output format of the log lines
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable, colored output
+    #[default]
+    Pretty,
+
+    /// One JSON object per line
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            other => Err(format!(
+                "unknown log format '{}', expected 'json' or 'pretty'",
+                other
+            )),
+        }
+    }
+}
+
+/// Logging configuration used when installing the global subscriber
+#[derive(Clone, Debug)]
+pub struct LogConfig {
+    pub level: LevelFilter,
+
+    pub format: LogFormat,
+
+    /// Only errors are printed, overrides `level`
+    pub quiet: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::INFO,
+            format: LogFormat::Pretty,
+            quiet: false,
+        }
+    }
+}
+
+impl LogConfig {
+    /// Returns the level the subscriber starts with
+    pub fn effective_level(&self) -> LevelFilter {
+        if self.quiet {
+            LevelFilter::ERROR
+        } else {
+            self.level
+        }
+    }
+}
+
+/// Handle for changing the log level of a running process
+#[derive(Clone)]
+pub struct LogHandle {
+    inner: reload::Handle<LevelFilter, Registry>,
+}
+
+impl fmt::Debug for LogHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogHandle")
+            .field("level", &self.level())
+            .finish()
+    }
+}
+
+impl LogHandle {
+    /// Returns the currently active level
+    pub fn level(&self) -> Option<LevelFilter> {
+        self.inner.clone_current()
+    }
+
+    /// Changes the active level without restarting the process
+    pub fn set_level(&self, level: LevelFilter) -> Result<(), reload::Error> {
+        self.inner.reload(level)
+    }
+}
+
+/// Installs the global tracing subscriber and returns a handle to its level
+///
+/// # Example
+/// ```no_run
+/// # use mimic_rs::MockServer;
+/// # use mimic_rs::logging::{self, LogConfig};
+/// let handle = logging::init(&LogConfig::default()).unwrap();
+/// let server = MockServer::new("./resources").with_log_handle(handle);
+/// ```
+pub fn init(config: &LogConfig) -> Result<LogHandle, TryInitError> {
+    let (filter, handle) = reload::Layer::new(config.effective_level());
+    let registry = tracing_subscriber::registry().with(filter);
+
+    match config.format {
+        LogFormat::Pretty => registry.with(tracing_fmt::layer()).try_init()?,
+        LogFormat::Json => registry
+            .with(tracing_fmt::layer().event_format(JsonFormat))
+            .try_init()?,
+    }
+
+    Ok(LogHandle { inner: handle })
+}
+
+/// Parses a level name such as `info` or `off`
+pub fn parse_level(level: &str) -> Result<LevelFilter, String> {
+    LevelFilter::from_str(level).map_err(|_| {
+        format!(
+            "unknown log level '{}', expected one of off, error, warn, info, debug, trace",
+            level
+        )
+    })
+}
@@ -1,31 +1,183 @@
+mod cli;
+
+use cli::Command;
 use mimic_rs::MockServer;
+use mimic_rs::auth::OidcConfig;
+use mimic_rs::cors::CorsConfig;
+use mimic_rs::logging::{self, AccessLog};
+use mimic_rs::models::{ChaosConfig, DowntimeWindow};
+use mimic_rs::tls::TlsConfig;
+use mimic_rs::transform::RequestTransform;
 use std::env;
+use std::process::ExitCode;
 use tracing::info;
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialization of the logger
-    tracing_subscriber::fmt::init();
+async fn main() -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let cli = match cli::parse(env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, cli::USAGE);
+            return Ok(ExitCode::from(2));
+        }
+    };
 
-    // Get the port from the arguments or use the default 8080
-    let port = env::args()
-        .nth(1)
-        .and_then(|arg| arg.parse::<u16>().ok())
-        .unwrap_or(8080);
+    let args = match cli.command {
+        Command::Serve(args) => *args,
+        Command::Import(args) => {
+            logging::init(&cli.log)?;
+            cli::import::run(args)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Command::Export(args) => {
+            logging::init(&cli.log)?;
+            cli::export::run(args).await?;
+            return Ok(ExitCode::SUCCESS);
+        }
+        Command::Replay(args) => {
+            logging::init(&cli.log)?;
+            let passed = cli::replay::run(args).await?;
+            return Ok(if passed {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            });
+        }
+        Command::Help => {
+            println!("{}", cli::USAGE);
+            return Ok(ExitCode::SUCCESS);
+        }
+    };
 
-    // Get the resources directory from the arguments or use the default "./resources"
-    let resources_dir = env::args()
-        .nth(2)
-        .unwrap_or_else(|| "./resources".to_string());
+    // Initialization of the logger
+    let log_handle = logging::init(&cli.log)?;
 
     info!(
         "MockServer is starting on port {} with resources in {}",
-        port, resources_dir
+        args.port, args.resources_dir
     );
 
     // Create and start the server
-    let server = MockServer::new(resources_dir);
-    server.start(port).await?;
+    let mut server = MockServer::new(args.resources_dir).with_log_handle(log_handle);
+    if args.no_admin {
+        info!("Admin API is disabled");
+        server = server.without_admin();
+    }
+    if args.allow_shutdown {
+        server = server.with_shutdown_endpoint();
+    }
+    if args.no_method_not_allowed {
+        server = server.without_method_not_allowed();
+    }
+    if args.explain_not_found {
+        server = server.with_not_found_diagnostics();
+    }
+    if args.cors {
+        server = server.with_cors(CorsConfig::default());
+    }
+    if args.oidc {
+        server = server.with_oidc(OidcConfig::default());
+    }
+    if let Some(timeout) = args.drain_timeout {
+        server = server.with_drain_timeout(timeout);
+    }
+    if let Some(delay) = args.accept_delay {
+        server = server.with_accept_delay(delay);
+    }
+    if let Some(delay) = args.tls_handshake_delay {
+        server = server.with_tls_handshake_delay(delay);
+    }
+    if let Some(bytes_per_sec) = args.bandwidth {
+        server = server.with_bandwidth_limit(bytes_per_sec);
+    }
+    if let Some(seed) = args.seed {
+        server = server.with_seed(seed);
+    }
+    if let Some(path) = args.chaos {
+        let config: ChaosConfig = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        config.validate()?;
+        server = server.with_chaos(config);
+    }
+    if let Some(path) = args.downtime {
+        let windows: Vec<DowntimeWindow> = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+        for window in windows {
+            window.validate()?;
+            server = server.with_downtime(window);
+        }
+    }
+    if let Some(prefix) = args.strip_prefix {
+        server = server.with_request_transform(RequestTransform::StripPathPrefix { prefix });
+    }
+    if let Some(path) = args.dump_requests {
+        server = server.with_request_log_dump(path);
+    }
+    if let Some(path) = args.journal {
+        server = server.with_request_journal(path)?;
+    }
+    if let Some(path) = args.access_log {
+        let log = if path.as_os_str() == "-" {
+            AccessLog::stdout()
+        } else {
+            AccessLog::file(&path)?
+        };
+        server = server.with_access_log(log);
+    }
+    if !args.redact_headers.is_empty() {
+        server = server.with_redacted_headers(args.redact_headers);
+    }
+    if let Some(dir) = args.persist {
+        server = server.with_persistence(dir);
+    }
+    if let Some(dir) = args.mappings {
+        server = server.with_mappings_dir(dir);
+    }
+    if args.watch {
+        server = server.with_hot_reload();
+    }
+    if let Some(dir) = args.capture_dir {
+        server = server.with_upload_capture(dir);
+    }
+    if let Some(header) = args.idempotency_key {
+        server = server.with_idempotency_key(&header);
+    }
+    if !args.peers.is_empty() {
+        server = server.with_peers(args.peers);
+    }
+    for (prefix, dir) in args.static_dirs {
+        server = server.serve_static(&prefix, dir);
+    }
+    if let Some(url) = args.proxy_to {
+        server = server.proxy_to(&url);
+    }
+    if let Some(url) = args.fallback_proxy {
+        server = server.fallback_proxy(&url);
+    }
+    if args.validate {
+        server = server.with_request_validation();
+    }
+    if let Some(path) = args.openapi {
+        server.load_openapi(path).await?;
+    }
+    if let Some(dir) = args.wiremock {
+        server.load_wiremock(dir).await?;
+    }
+    match args.har {
+        Some(path) if args.har_latency => {
+            server.load_har_with_latency(path).await?;
+        }
+        Some(path) => {
+            server.load_har(path).await?;
+        }
+        None => {}
+    }
+    match (args.tls_cert, args.tls_key) {
+        (Some(cert), Some(key)) => {
+            server
+                .start_tls(args.port, TlsConfig::from_files(cert, key)?)
+                .await?
+        }
+        _ => server.start(args.port).await?,
+    }
 
-    Ok(())
+    Ok(ExitCode::SUCCESS)
 }
@@ -0,0 +1,141 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use thiserror::Error;
+
+use crate::models::CreateExpectationRequest;
+
+/// Name of the directory with mapping files inside a resources directory
+pub const MAPPINGS_DIR: &str = "mappings";
+
+/// Errors produced while reading mapping files
+#[derive(Debug, Error)]
+pub enum MappingError {
+    #[error("failed to read {path}: {source}")]
+    Io { path: PathBuf, source: io::Error },
+
+    #[error("invalid mapping file {path}: {source}")]
+    Json {
+        path: PathBuf,
+        source: serde_json::Error,
+    },
+
+    #[error("invalid mapping file {path}: {source}")]
+    Yaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+}
+
+/// A mapping file holds either a single expectation or a list of them
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum MappingFile {
+    Single(Box<CreateExpectationRequest>),
+    Many(Vec<CreateExpectationRequest>),
+}
+
+impl MappingFile {
+    fn into_vec(self) -> Vec<CreateExpectationRequest> {
+        match self {
+            MappingFile::Single(expectation) => vec![*expectation],
+            MappingFile::Many(many) => many,
+        }
+    }
+}
+
+/// Parses a JSON mapping document holding one expectation or a list of them
+pub fn parse_json(content: &str) -> Result<Vec<CreateExpectationRequest>, serde_json::Error> {
+    serde_json::from_str::<MappingFile>(content).map(MappingFile::into_vec)
+}
+
+/// Parses a YAML mapping document holding one expectation or a list of them
+pub fn parse_yaml(content: &str) -> Result<Vec<CreateExpectationRequest>, serde_yaml::Error> {
+    serde_yaml::from_str::<MappingFile>(content).map(MappingFile::into_vec)
+}
+
+/// Reads all `*.json`, `*.yaml` and `*.yml` mapping files in `dir`, ordered by file name
+pub fn read_mappings(dir: &Path) -> Result<Vec<CreateExpectationRequest>, MappingError> {
+    let io_error = |source| MappingError::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        let extension = path.extension().and_then(|e| e.to_str());
+        if path.is_file() && matches!(extension, Some("json" | "yaml" | "yml")) {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    let mut expectations = Vec::new();
+    for path in files {
+        let content = fs::read_to_string(&path).map_err(|source| MappingError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let file = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+            parse_json(&content).map_err(|source| MappingError::Json {
+                path: path.clone(),
+                source,
+            })?
+        } else {
+            parse_yaml(&content).map_err(|source| MappingError::Yaml {
+                path: path.clone(),
+                source,
+            })?
+        };
+        expectations.extend(file);
+    }
+
+    Ok(expectations)
+}
+
+/// Writes each expectation as a separate mapping file into `dir`
+///
+/// Returns the paths of the written files.
+pub fn write_mappings(
+    dir: &Path,
+    expectations: &[CreateExpectationRequest],
+) -> io::Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir)?;
+
+    let mut written = Vec::with_capacity(expectations.len());
+    for (index, expectation) in expectations.iter().enumerate() {
+        let file_path = dir.join(mapping_file_name(index + 1, expectation));
+        let content = serde_json::to_string_pretty(expectation)?;
+        fs::write(&file_path, content)?;
+        written.push(file_path);
+    }
+
+    Ok(written)
+}
+
+/// Builds a readable file name such as `001-get-api-users.json`
+pub fn mapping_file_name(index: usize, expectation: &CreateExpectationRequest) -> String {
+    let mut slug = String::with_capacity(expectation.path.len());
+    for c in expectation.path.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+
+    if slug.is_empty() {
+        format!("{:03}-{}.json", index, expectation.method.to_lowercase())
+    } else {
+        format!(
+            "{:03}-{}-{}.json",
+            index,
+            expectation.method.to_lowercase(),
+            slug
+        )
+    }
+}
@@ -0,0 +1,103 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::template::{self, TemplateContext};
+
+/// An outbound request fired after an expectation matched, e.g. a webhook
+///
+/// The URL, header values and body may contain templates such as
+/// `"{{request.body.order_id}}"`, rendered against the incoming request.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Callback {
+    pub url: String,
+
+    #[serde(default = "default_method")]
+    pub method: String,
+
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub headers: HashMap<String, String>,
+
+    /// Sent as JSON, or as is if it renders to a string
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<Value>,
+
+    /// Wait after the response before the callback is sent
+    #[serde(skip_serializing_if = "is_zero", default)]
+    pub delay_ms: u64,
+}
+
+fn default_method() -> String {
+    "POST".to_string()
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+impl Callback {
+    pub fn new(url: &str, method: &str, body: Option<Value>, delay: Duration) -> Self {
+        Self {
+            url: url.to_string(),
+            method: method.to_uppercase(),
+            headers: HashMap::new(),
+            body,
+            delay_ms: delay.as_millis() as u64,
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn delay(&self) -> Duration {
+        Duration::from_millis(self.delay_ms)
+    }
+
+    /// The callback with the templates in its URL, headers and body rendered
+    pub(crate) fn render(&self, context: &TemplateContext) -> Self {
+        Self {
+            url: template::render_str(&self.url, context),
+            method: self.method.clone(),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), template::render_str(value, context)))
+                .collect(),
+            body: self
+                .body
+                .as_ref()
+                .map(|body| template::render_value(body, context)),
+            delay_ms: self.delay_ms,
+        }
+    }
+}
+
+/// The outcome of sending a [`Callback`]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CallbackDelivery {
+    /// The expectation whose match fired the callback
+    pub expectation_id: String,
+
+    pub method: String,
+
+    /// The rendered URL
+    pub url: String,
+
+    /// The rendered body
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<String>,
+
+    pub timestamp: DateTime<Utc>,
+
+    /// Status the receiver answered with, `None` if it could not be reached
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub status: Option<u16>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub error: Option<String>,
+}
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::random::Random;
+
+/// Random faults injected into every mocked endpoint
+///
+/// Each probability is between 0.0 and 1.0 and rolled independently per
+/// request. A connection fault takes precedence over an error response; a
+/// delay can be combined with either.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ChaosConfig {
+    #[serde(default)]
+    pub delay_probability: f64,
+
+    #[serde(default)]
+    pub min_delay_ms: u64,
+
+    #[serde(default)]
+    pub max_delay_ms: u64,
+
+    #[serde(default)]
+    pub error_probability: f64,
+
+    /// Statuses picked at random for injected errors
+    #[serde(default = "default_error_statuses")]
+    pub error_statuses: Vec<u16>,
+
+    /// Chance of closing the connection without a complete response
+    #[serde(default)]
+    pub fault_probability: f64,
+}
+
+fn default_error_statuses() -> Vec<u16> {
+    vec![500, 502, 503, 504]
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        Self {
+            delay_probability: 0.0,
+            min_delay_ms: 0,
+            max_delay_ms: 0,
+            error_probability: 0.0,
+            error_statuses: default_error_statuses(),
+            fault_probability: 0.0,
+        }
+    }
+}
+
+/// What chaos mode decided for a single request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ChaosOutcome {
+    pub delay: Option<Duration>,
+
+    pub action: ChaosAction,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ChaosAction {
+    /// Answer normally
+    Pass,
+
+    /// Answer with this status instead
+    Error(u16),
+
+    /// Drop the connection
+    Fault,
+}
+
+impl ChaosConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Delays requests with `probability` by a random duration between `min`
+    /// and `max`, swapped if given in reverse
+    pub fn delay(mut self, probability: f64, min: Duration, max: Duration) -> Self {
+        let (min, max) = (min.min(max), min.max(max));
+        self.delay_probability = probability;
+        self.min_delay_ms = min.as_millis() as u64;
+        self.max_delay_ms = max.as_millis() as u64;
+        self
+    }
+
+    /// Answers with a 5xx status with `probability`
+    pub fn errors(mut self, probability: f64) -> Self {
+        self.error_probability = probability;
+        self
+    }
+
+    /// Sets the statuses injected errors are picked from
+    pub fn error_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.error_statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Drops the connection with `probability`
+    pub fn connection_faults(mut self, probability: f64) -> Self {
+        self.fault_probability = probability;
+        self
+    }
+
+    /// Checks probabilities, delay range and statuses
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, probability) in [
+            ("delay_probability", self.delay_probability),
+            ("error_probability", self.error_probability),
+            ("fault_probability", self.fault_probability),
+        ] {
+            if !(0.0..=1.0).contains(&probability) {
+                return Err(format!("{} must be between 0 and 1", name));
+            }
+        }
+        if self.min_delay_ms > self.max_delay_ms {
+            return Err("min_delay_ms must not exceed max_delay_ms".to_string());
+        }
+        if self.error_probability > 0.0 && self.error_statuses.is_empty() {
+            return Err("error_statuses must not be empty".to_string());
+        }
+        if let Some(status) = self
+            .error_statuses
+            .iter()
+            .find(|status| !(100..=999).contains(*status))
+        {
+            return Err(format!("invalid status {}", status));
+        }
+        Ok(())
+    }
+
+    /// Rolls the dice for one request
+    ///
+    /// Tolerates a reversed delay range, the fields are public and not every
+    /// configuration goes through `validate`.
+    pub(crate) fn roll(&self, random: &Random) -> ChaosOutcome {
+        let (min, max) = (
+            self.min_delay_ms.min(self.max_delay_ms),
+            self.min_delay_ms.max(self.max_delay_ms),
+        );
+        let delay = random
+            .chance(self.delay_probability)
+            .then(|| Duration::from_millis(random.range(min..=max)));
+
+        let action = if random.chance(self.fault_probability) {
+            ChaosAction::Fault
+        } else if let Some(&status) = random
+            .chance(self.error_probability)
+            .then(|| random.pick(&self.error_statuses))
+            .flatten()
+        {
+            ChaosAction::Error(status)
+        } else {
+            ChaosAction::Pass
+        };
+
+        ChaosOutcome { delay, action }
+    }
+}
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use super::compile_wildcard;
+
+/// A period during which mocked endpoints are unavailable
+///
+/// Requests to an affected path are answered with `status` (503 by default)
+/// and a `Retry-After` header, or have their connection dropped.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DowntimeWindow {
+    #[serde(flatten)]
+    pub schedule: DowntimeSchedule,
+
+    /// Paths with `*` wildcards that go down; every path when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub paths: Vec<String>,
+
+    #[serde(default = "default_status")]
+    pub status: u16,
+
+    /// Close connections instead of answering with `status`
+    #[serde(default)]
+    pub drop_connections: bool,
+}
+
+/// When a [`DowntimeWindow`] is active
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DowntimeSchedule {
+    /// Once, from `start` until `end`
+    Between {
+        start: DateTime<Utc>,
+
+        end: DateTime<Utc>,
+    },
+
+    /// For `duration_ms` at the start of every `every_ms` period
+    ///
+    /// Periods are counted from `from`, which defaults to the moment the
+    /// window is added to the server.
+    Every {
+        every_ms: u64,
+
+        duration_ms: u64,
+
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        from: Option<DateTime<Utc>>,
+    },
+}
+
+fn default_status() -> u16 {
+    503
+}
+
+impl DowntimeWindow {
+    /// A single outage from `start` until `end`
+    pub fn between(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self::new(DowntimeSchedule::Between { start, end })
+    }
+
+    /// A recurring outage lasting `duration` at the start of every `period`
+    pub fn every(period: Duration, duration: Duration) -> Self {
+        Self::new(DowntimeSchedule::Every {
+            every_ms: period.as_millis() as u64,
+            duration_ms: duration.as_millis() as u64,
+            from: None,
+        })
+    }
+
+    fn new(schedule: DowntimeSchedule) -> Self {
+        Self {
+            schedule,
+            paths: Vec::new(),
+            status: default_status(),
+            drop_connections: false,
+        }
+    }
+
+    /// Limits the outage to these paths (with `*` wildcards)
+    pub fn paths<I, S>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.paths = paths.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Answers with `status` instead of 503
+    pub fn status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Drops connections instead of answering
+    pub fn drop_connections(mut self) -> Self {
+        self.drop_connections = true;
+        self
+    }
+
+    /// Checks the schedule and status
+    pub fn validate(&self) -> Result<(), String> {
+        match &self.schedule {
+            DowntimeSchedule::Between { start, end } if start >= end => {
+                return Err("start must be before end".to_string());
+            }
+            DowntimeSchedule::Every {
+                every_ms,
+                duration_ms,
+                ..
+            } => {
+                if *every_ms == 0 {
+                    return Err("every_ms must be greater than 0".to_string());
+                }
+                if duration_ms > every_ms {
+                    return Err("duration_ms must not exceed every_ms".to_string());
+                }
+            }
+            DowntimeSchedule::Between { .. } => {}
+        }
+        if !(100..=999).contains(&self.status) {
+            return Err(format!("invalid status {}", self.status));
+        }
+        Ok(())
+    }
+
+    /// Starts a recurring schedule without `from` at `now`
+    pub(crate) fn anchored(mut self, now: DateTime<Utc>) -> Self {
+        if let DowntimeSchedule::Every { from, .. } = &mut self.schedule {
+            from.get_or_insert(now);
+        }
+        self
+    }
+
+    /// How long the outage for `path` still lasts, `None` when it is up
+    pub(crate) fn remaining(&self, path: &str, now: DateTime<Utc>) -> Option<Duration> {
+        if !self.paths.is_empty()
+            && !self.paths.iter().any(|pattern| {
+                compile_wildcard(pattern).is_ok_and(|pattern| pattern.is_match(path))
+            })
+        {
+            return None;
+        }
+
+        match &self.schedule {
+            DowntimeSchedule::Between { start, end } => {
+                (*start <= now && now < *end).then(|| (*end - now).to_std().unwrap_or_default())
+            }
+            DowntimeSchedule::Every {
+                every_ms,
+                duration_ms,
+                from,
+            } => {
+                let elapsed = (now - (*from)?).num_milliseconds();
+                if elapsed < 0 || *every_ms == 0 {
+                    return None;
+                }
+                let phase = elapsed as u64 % every_ms;
+                (phase < *duration_ms).then(|| Duration::from_millis(duration_ms - phase))
+            }
+        }
+    }
+}
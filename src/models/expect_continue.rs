@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// How to answer a request carrying `Expect: 100-continue`
+///
+/// The interim `100 Continue` is sent once the server starts reading the
+/// body, so delaying the read delays it and rejecting skips the body entirely.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum ExpectContinue {
+    /// Send `100 Continue` right away
+    Accept,
+
+    /// Wait before sending `100 Continue`
+    Delay { delay_ms: u64 },
+
+    /// Answer `417 Expectation Failed` without reading the body
+    Reject,
+}
+
+impl ExpectContinue {
+    pub fn delay(delay: Duration) -> Self {
+        Self::Delay {
+            delay_ms: delay.as_millis() as u64,
+        }
+    }
+}
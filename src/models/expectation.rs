@@ -1,9 +1,27 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 
+use super::callback::Callback;
+use super::expect_continue::ExpectContinue;
+use super::graphql::GraphqlMatch;
+use super::json_body::JsonBodyMatch;
+use super::rate_limit::RateLimit;
 use super::response::MockResponse;
+use super::scenario::ScenarioRule;
+use super::script::ResponseScript;
+use super::sequence::SequenceEnd;
+use super::verify::ValueMatcher;
+use crate::auth::JwtRequirement;
+use crate::conditional::CustomMatcher;
+use crate::cors::CorsPolicy;
+use crate::extension::ExtensionRef;
+use crate::script;
+use crate::state::StateUpdate;
+use crate::transform::ResponseTransform;
 
 /// Represents an expectation that the server should fulfill
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -23,10 +41,108 @@ pub struct MockExpectation {
     #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub headers: HashMap<String, String>,
 
+    /// Query parameters checked with a matcher instead of an exact value
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub query_matchers: HashMap<String, ValueMatcher>,
+
+    /// Headers checked with a matcher instead of an exact value
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub header_matchers: HashMap<String, ValueMatcher>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
 
+    /// Structural match of a JSON body, independent of key order and whitespace
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub json_body: Option<JsonBodyMatch>,
+
+    /// Matchers the body has to pass, e.g. `{"not": {"contains": "password"}}`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub body_matchers: Vec<ValueMatcher>,
+
+    /// Exact binary body, base64 encoded, e.g. a serialized protobuf message
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_base64: Option<String>,
+
+    /// Operation name, query fragments or variables of a GraphQL request
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub graphql: Option<GraphqlMatch>,
+
+    /// Bearer token the request has to carry
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub jwt: Option<JwtRequirement>,
+
+    /// Budget after which the expectation answers with 429
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub rate_limit: Option<RateLimit>,
+
+    /// Changes applied to the server state whenever the expectation matches
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub state_updates: Vec<StateUpdate>,
+
+    /// Outbound requests fired after the expectation matched, e.g. webhooks
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub callbacks: Vec<Callback>,
+
+    /// Scenario state machine the expectation takes part in
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub scenario: Option<ScenarioRule>,
+
+    /// Responses returned in turn instead of `response`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub rotate: Vec<MockResponse>,
+
+    /// What happens once every response in `rotate` was returned
+    #[serde(skip_serializing_if = "SequenceEnd::is_start_over", default)]
+    pub rotate_end: SequenceEnd,
+
+    /// Answer to `Expect: 100-continue`, decided before the body is read
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expect_continue: Option<ExpectContinue>,
+
+    /// Applied to the response before it is sent, ahead of global transformations
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub response_transforms: Vec<ResponseTransform>,
+
+    /// Registered extensions that all have to accept the request
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub matchers: Vec<ExtensionRef>,
+
+    /// Matchers added in Rust with `ExpectationBuilder::matches`
+    #[serde(skip)]
+    pub custom_matchers: Vec<CustomMatcher>,
+
+    /// CORS policy for this endpoint, used instead of the server wide one
+    #[serde(skip)]
+    pub cors: Option<CorsPolicy>,
+
+    /// Expectations with a higher priority are tried first
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub priority: Option<u32>,
+
+    /// Number of matches after which the expectation stops matching
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub times: Option<usize>,
+
     pub response: MockResponse,
+
+    #[serde(default)]
+    pub source: ExpectationSource,
+}
+
+/// Where an expectation was defined
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectationSource {
+    /// `MockServer::expect()` in Rust code
+    #[default]
+    Builder,
+
+    /// The `/_setup` endpoint
+    SetupApi,
+
+    /// A file in the mappings directory
+    Mapping,
 }
 
 impl MockExpectation {
@@ -39,22 +155,39 @@ impl MockExpectation {
             path_regex: None,
             query_params: HashMap::new(),
             headers: HashMap::new(),
+            query_matchers: HashMap::new(),
+            header_matchers: HashMap::new(),
             body: None,
+            json_body: None,
+            body_matchers: Vec::new(),
+            body_base64: None,
+            graphql: None,
+            jwt: None,
+            rate_limit: None,
+            state_updates: Vec::new(),
+            callbacks: Vec::new(),
+            scenario: None,
+            rotate: Vec::new(),
+            rotate_end: SequenceEnd::StartOver,
+            expect_continue: None,
+            response_transforms: Vec::new(),
+            matchers: Vec::new(),
+            custom_matchers: Vec::new(),
+            cors: None,
+            priority: None,
+            times: None,
             response: MockResponse::default(),
+            source: ExpectationSource::Builder,
         };
 
         exp.compile_regex_if_needed();
         exp
     }
 
-    /// Compiles the regex if the path contains wildcards
+    /// Compiles the regex if the path contains wildcards or `{name}` parameters
     pub fn compile_regex_if_needed(&mut self) {
-        if self.path.contains('*') {
-            // Escape regex special characters except our wildcard
-            let escaped_path = regex::escape(&self.path.replace("*", "WILDCARD_PLACEHOLDER"));
-            let regex_path = escaped_path.replace("WILDCARD_PLACEHOLDER", ".*");
-
-            match Regex::new(&format!("^{}$", regex_path)) {
+        if self.path.contains(['*', '{']) {
+            match compile_path_template(&self.path) {
                 Ok(re) => {
                     self.path_regex = Some(re);
                 }
@@ -64,23 +197,239 @@ impl MockExpectation {
             }
         }
     }
+
+    /// Order in which matching expectations are tried, highest first
+    ///
+    /// The explicit priority comes first. Among equal priorities an exact path
+    /// beats a pattern, a longer literal path beats a shorter one and more
+    /// query, header, body, token and extension conditions beat fewer.
+    pub fn rank(&self) -> (u32, bool, usize, usize) {
+        let literal_len = self
+            .path
+            .split('/')
+            .filter(|segment| !segment.contains(['*', '{']))
+            .map(str::len)
+            .sum();
+        let conditions = self.query_params.len()
+            + self.headers.len()
+            + self.query_matchers.len()
+            + self.header_matchers.len()
+            + usize::from(self.body.is_some())
+            + usize::from(self.json_body.is_some())
+            + self.body_matchers.len()
+            + usize::from(self.body_base64.is_some())
+            + self.graphql.as_ref().map_or(0, GraphqlMatch::conditions)
+            + usize::from(self.jwt.is_some())
+            + self.matchers.len()
+            + self.custom_matchers.len();
+
+        (
+            self.priority.unwrap_or(0),
+            self.path_regex.is_none(),
+            literal_len,
+            conditions,
+        )
+    }
+
+    /// Whether `body` equals `body_base64`; an invalid encoding never matches
+    pub fn matches_body_bytes(&self, body: Option<&[u8]>) -> bool {
+        let Some(encoded) = &self.body_base64 else {
+            return true;
+        };
+        STANDARD
+            .decode(encoded)
+            .is_ok_and(|expected| body.unwrap_or_default() == expected.as_slice())
+    }
+
+    /// Whether `path` is the expectation's path or matches its pattern
+    pub fn matches_path(&self, path: &str) -> bool {
+        match &self.path_regex {
+            Some(regex) => regex.is_match(path),
+            None => self.path == path,
+        }
+    }
+
+    /// Values of the `{name}` path parameters captured from `path`
+    pub fn path_params(&self, path: &str) -> HashMap<String, String> {
+        let Some(regex) = &self.path_regex else {
+            return HashMap::new();
+        };
+        let Some(captures) = regex.captures(path) else {
+            return HashMap::new();
+        };
+
+        regex
+            .capture_names()
+            .flatten()
+            .filter_map(|name| {
+                let value = captures.name(name)?.as_str();
+                Some((name.to_string(), value.to_string()))
+            })
+            .collect()
+    }
+}
+
+/// Compiles a path with `*` wildcards into an anchored regex
+pub(crate) fn compile_wildcard(path: &str) -> Result<Regex, regex::Error> {
+    // Escape regex special characters except our wildcard
+    let escaped_path = regex::escape(&path.replace("*", "WILDCARD_PLACEHOLDER"));
+    let regex_path = escaped_path.replace("WILDCARD_PLACEHOLDER", ".*");
+
+    Regex::new(&format!("^{}$", regex_path))
+}
+
+/// Compiles a path such as `/api/users/{id}/*` into an anchored regex
+///
+/// Every `{name}` segment matches a single path segment and is captured
+/// under `name`; `*` matches anything, as in [`compile_wildcard`].
+pub(crate) fn compile_path_template(path: &str) -> Result<Regex, regex::Error> {
+    let mut pattern = String::from("^");
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        pattern.push_str(&wildcard_pattern(&rest[..start]));
+        pattern.push_str(&format!("(?P<{}>[^/]+)", &rest[start + 1..end]));
+        rest = &rest[end + 1..];
+    }
+    pattern.push_str(&wildcard_pattern(rest));
+    pattern.push('$');
+
+    Regex::new(&pattern)
+}
+
+/// Checks the `{name}` parameters of a path template
+///
+/// Names must be identifiers (letters, digits and `_`, not starting with a
+/// digit) and unique within the path, anything else would fail to compile.
+pub(crate) fn check_path_template(path: &str) -> Result<(), String> {
+    let mut names = HashSet::new();
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        let name = &rest[start + 1..end];
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid {
+            return Err(format!(
+                "invalid path parameter '{{{}}}' in '{}', use letters, digits and _",
+                name, path
+            ));
+        }
+        if !names.insert(name) {
+            return Err(format!(
+                "duplicate path parameter '{{{}}}' in '{}'",
+                name, path
+            ));
+        }
+        rest = &rest[end + 1..];
+    }
+    Ok(())
+}
+
+fn wildcard_pattern(literal: &str) -> String {
+    literal
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*")
 }
 
 /// Represents a request to create an expectation
-#[derive(Debug, Deserialize)]
+///
+/// This is also the format of the mapping files read and written by the CLI.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CreateExpectationRequest {
     pub method: String,
 
     pub path: String,
 
-    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub query_params: HashMap<String, String>,
 
-    #[serde(default)]
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
     pub headers: HashMap<String, String>,
 
+    /// Query parameters checked with a matcher, e.g. `{"page": {"matches": "^[0-9]+$"}}`
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub query_matchers: HashMap<String, ValueMatcher>,
+
+    /// Headers checked with a matcher, e.g. `{"x-request-id": {"present": true}}`
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub header_matchers: HashMap<String, ValueMatcher>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
 
+    /// Structural match of a JSON body, independent of key order and whitespace
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub json_body: Option<JsonBodyMatch>,
+
+    /// Matchers the body has to pass, e.g. `{"not": {"contains": "password"}}`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub body_matchers: Vec<ValueMatcher>,
+
+    /// Exact binary body, base64 encoded, e.g. a serialized protobuf message
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_base64: Option<String>,
+
+    /// Operation name, query fragments or variables of a GraphQL request
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub graphql: Option<GraphqlMatch>,
+
+    /// Bearer token the request has to carry
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub jwt: Option<JwtRequirement>,
+
+    /// Budget after which the expectation answers with 429
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub rate_limit: Option<RateLimit>,
+
+    /// Changes applied to the server state whenever the expectation matches
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub state_updates: Vec<StateUpdate>,
+
+    /// Outbound requests fired after the expectation matched, e.g. webhooks
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub callbacks: Vec<Callback>,
+
+    /// Scenario state machine the expectation takes part in
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub scenario: Option<ScenarioRule>,
+
+    /// Responses returned in turn instead of `response`
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub rotate: Vec<MockResponse>,
+
+    /// What happens once every response in `rotate` was returned
+    #[serde(skip_serializing_if = "SequenceEnd::is_start_over", default)]
+    pub rotate_end: SequenceEnd,
+
+    /// Answer to `Expect: 100-continue`, decided before the body is read
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expect_continue: Option<ExpectContinue>,
+
+    /// Applied to the response before it is sent, ahead of global transformations
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub response_transforms: Vec<ResponseTransform>,
+
+    /// Registered extensions that all have to accept the request
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub matchers: Vec<ExtensionRef>,
+
+    /// Expectations with a higher priority are tried first
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub priority: Option<u32>,
+
+    /// Number of matches after which the expectation stops matching
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub times: Option<usize>,
+
     pub response: MockResponse,
 }
 
@@ -93,8 +442,29 @@ impl From<CreateExpectationRequest> for MockExpectation {
             path_regex: None,
             query_params: req.query_params,
             headers: req.headers,
+            query_matchers: req.query_matchers,
+            header_matchers: req.header_matchers,
             body: req.body,
+            json_body: req.json_body,
+            body_matchers: req.body_matchers,
+            body_base64: req.body_base64,
+            graphql: req.graphql,
+            jwt: req.jwt,
+            rate_limit: req.rate_limit,
+            state_updates: req.state_updates,
+            callbacks: req.callbacks,
+            scenario: req.scenario,
+            rotate: req.rotate,
+            rotate_end: req.rotate_end,
+            expect_continue: req.expect_continue,
+            response_transforms: req.response_transforms,
+            matchers: req.matchers,
+            custom_matchers: Vec::new(),
+            cors: None,
+            priority: req.priority,
+            times: req.times,
             response: req.response,
+            source: ExpectationSource::SetupApi,
         };
 
         // Compile regex for paths with wildcards
@@ -102,3 +472,60 @@ impl From<CreateExpectationRequest> for MockExpectation {
         exp
     }
 }
+
+impl CreateExpectationRequest {
+    /// Fails on the first matcher with an invalid regex or JSONPath
+    pub fn validate(&self) -> Result<(), String> {
+        check_path_template(&self.path)?;
+        self.query_matchers
+            .values()
+            .chain(self.header_matchers.values())
+            .chain(&self.body_matchers)
+            .try_for_each(ValueMatcher::validate)?;
+        for encoded in [&self.body_base64, &self.response.body_base64]
+            .into_iter()
+            .flatten()
+        {
+            STANDARD
+                .decode(encoded)
+                .map_err(|e| format!("invalid body_base64: {}", e))?;
+        }
+        for response in std::iter::once(&self.response).chain(&self.rotate) {
+            if let Some(ResponseScript::Source(source)) = &response.script {
+                script::check(source).map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<MockExpectation> for CreateExpectationRequest {
+    fn from(exp: MockExpectation) -> Self {
+        Self {
+            method: exp.method,
+            path: exp.path,
+            query_params: exp.query_params,
+            headers: exp.headers,
+            query_matchers: exp.query_matchers,
+            header_matchers: exp.header_matchers,
+            body: exp.body,
+            json_body: exp.json_body,
+            body_matchers: exp.body_matchers,
+            body_base64: exp.body_base64,
+            graphql: exp.graphql,
+            jwt: exp.jwt,
+            rate_limit: exp.rate_limit,
+            state_updates: exp.state_updates,
+            callbacks: exp.callbacks,
+            scenario: exp.scenario,
+            rotate: exp.rotate,
+            rotate_end: exp.rotate_end,
+            expect_continue: exp.expect_continue,
+            response_transforms: exp.response_transforms,
+            matchers: exp.matchers,
+            priority: exp.priority,
+            times: exp.times,
+            response: exp.response,
+        }
+    }
+}
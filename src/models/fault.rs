@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+/// A broken answer sent instead of a well-formed HTTP response
+///
+/// Faults act on the TCP connection, so the client sees the same errors it
+/// would get from a crashing or misbehaving upstream.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Fault {
+    /// Abort the connection with a TCP reset
+    ConnectionReset,
+
+    /// Close the connection without sending anything
+    EmptyResponse,
+
+    /// Send a chunked response whose chunk size is not a number, then close
+    MalformedChunk,
+}
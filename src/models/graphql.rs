@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::json_body::contains;
+
+/// Conditions on a GraphQL operation
+///
+/// The operation is read from a POST body `{"query", "operationName",
+/// "variables"}` or from the query parameters of the same names of a GET
+/// request. A batch (a JSON array of operations) matches if one of them does.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphqlMatch {
+    /// `operationName`, or the name of the first operation in the query
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub operation_name: Option<String>,
+
+    /// Fragments the query has to contain; whitespace and commas are ignored
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub query_contains: Vec<String>,
+
+    /// Variables the operation has to carry, extra ones are fine
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub variables: Option<Value>,
+}
+
+/// An operation as sent by a GraphQL client
+struct Operation {
+    query: String,
+
+    operation_name: Option<String>,
+
+    variables: Value,
+}
+
+impl GraphqlMatch {
+    /// Whether the request carries an operation satisfying all conditions
+    pub fn matches(&self, body: Option<&str>, query_params: &HashMap<String, String>) -> bool {
+        operations(body, query_params)
+            .iter()
+            .any(|operation| self.matches_operation(operation))
+    }
+
+    /// Number of conditions, for ranking expectations
+    pub(crate) fn conditions(&self) -> usize {
+        usize::from(self.operation_name.is_some())
+            + self.query_contains.len()
+            + usize::from(self.variables.is_some())
+    }
+
+    fn matches_operation(&self, operation: &Operation) -> bool {
+        if let Some(expected) = &self.operation_name {
+            let actual = operation
+                .operation_name
+                .clone()
+                .or_else(|| operation_name(&operation.query));
+            if actual.as_deref() != Some(expected.as_str()) {
+                return false;
+            }
+        }
+
+        let query = compact(&operation.query);
+        if !self
+            .query_contains
+            .iter()
+            .all(|fragment| query.contains(&compact(fragment)))
+        {
+            return false;
+        }
+
+        self.variables
+            .as_ref()
+            .is_none_or(|expected| contains(&operation.variables, expected))
+    }
+}
+
+/// The operations of a POST body or of the query parameters of a GET request
+fn operations(body: Option<&str>, query_params: &HashMap<String, String>) -> Vec<Operation> {
+    // Query parameters are kept percent-encoded in the request
+    if let Some(query) = query_params.get("query") {
+        return vec![Operation {
+            query: decode_component(query),
+            operation_name: query_params
+                .get("operationName")
+                .map(|name| decode_component(name)),
+            variables: query_params
+                .get("variables")
+                .and_then(|variables| serde_json::from_str(&decode_component(variables)).ok())
+                .unwrap_or(Value::Null),
+        }];
+    }
+
+    let Some(body) = body.and_then(|body| serde_json::from_str::<Value>(body).ok()) else {
+        return Vec::new();
+    };
+    let operations = match body {
+        Value::Array(batch) => batch,
+        single => vec![single],
+    };
+    operations
+        .into_iter()
+        .filter_map(|mut operation| {
+            Some(Operation {
+                query: operation.get("query")?.as_str()?.to_string(),
+                operation_name: operation
+                    .get("operationName")
+                    .and_then(Value::as_str)
+                    .map(String::from),
+                variables: operation
+                    .get_mut("variables")
+                    .map(Value::take)
+                    .unwrap_or(Value::Null),
+            })
+        })
+        .collect()
+}
+
+/// Name of the first operation defined in `query`, `None` if it is anonymous
+fn operation_name(query: &str) -> Option<String> {
+    let mut depth = 0usize;
+    let mut word = String::new();
+    let mut expect_name = false;
+
+    let text = query.lines().flat_map(|line| {
+        line.split('#')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .chain([' '])
+    });
+    for c in text {
+        if is_name_char(c) {
+            if depth == 0 {
+                word.push(c);
+            }
+            continue;
+        }
+        if !word.is_empty() {
+            if expect_name {
+                return Some(word);
+            }
+            expect_name = matches!(word.as_str(), "query" | "mutation" | "subscription");
+            word.clear();
+        }
+        match c {
+            '{' | '(' if depth == 0 && expect_name => return None,
+            '{' | '(' => depth += 1,
+            '}' | ')' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Drops insignificant whitespace and commas so formatting does not matter
+fn compact(text: &str) -> String {
+    let mut compacted = String::with_capacity(text.len());
+    let mut separated = false;
+    for c in text.chars() {
+        if c.is_whitespace() || c == ',' {
+            separated = true;
+            continue;
+        }
+        if separated && is_name_char(c) && compacted.chars().last().is_some_and(is_name_char) {
+            compacted.push(' ');
+        }
+        separated = false;
+        compacted.push(c);
+    }
+    compacted
+}
+
+/// Decodes a `application/x-www-form-urlencoded` query component
+fn decode_component(component: &str) -> String {
+    let bytes = component.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |byte: u8| (byte as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push((high * 16 + low) as u8);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
@@ -0,0 +1,100 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Content type for binary gRPC-Web responses
+pub const GRPC_WEB_CONTENT_TYPE: &str = "application/grpc-web+proto";
+
+/// Content type for base64 framed (`-text`) gRPC-Web responses
+pub const GRPC_WEB_TEXT_CONTENT_TYPE: &str = "application/grpc-web-text+proto";
+
+/// A unary gRPC-Web answer: one message frame followed by a trailer frame
+///
+/// The message is the already serialized protobuf, base64 encoded so it fits
+/// into mapping files. It is only sent when `status` is 0 (OK).
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GrpcWebResponse {
+    /// Serialized protobuf message, base64 encoded
+    #[serde(default)]
+    pub message: String,
+
+    /// gRPC status code (0 = OK)
+    #[serde(default)]
+    pub status: u32,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub status_message: Option<String>,
+
+    /// Additional trailers, e.g. `grpc-status-details-bin`
+    #[serde(skip_serializing_if = "BTreeMap::is_empty", default)]
+    pub trailers: BTreeMap<String, String>,
+}
+
+impl GrpcWebResponse {
+    /// A successful response carrying `message` (serialized protobuf bytes)
+    pub fn ok(message: &[u8]) -> Self {
+        Self {
+            message: STANDARD.encode(message),
+            ..Default::default()
+        }
+    }
+
+    /// A failed call with the given gRPC status
+    pub fn error(status: u32, status_message: &str) -> Self {
+        Self {
+            status,
+            status_message: Some(status_message.to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// Encodes the frames; `text` selects the base64 `grpc-web-text` format
+    pub(crate) fn encode(&self, text: bool) -> Result<Vec<u8>, base64::DecodeError> {
+        let mut body = Vec::new();
+
+        if self.status == 0 {
+            let message = STANDARD.decode(&self.message)?;
+            push_frame(&mut body, 0x00, &message);
+        }
+
+        let mut trailers = format!("grpc-status:{}\r\n", self.status);
+        if let Some(status_message) = &self.status_message {
+            trailers.push_str(&format!(
+                "grpc-message:{}\r\n",
+                percent_encode(status_message)
+            ));
+        }
+        for (key, value) in &self.trailers {
+            trailers.push_str(&format!("{}:{}\r\n", key.to_lowercase(), value));
+        }
+        push_frame(&mut body, 0x80, trailers.as_bytes());
+
+        if text {
+            Ok(STANDARD.encode(body).into_bytes())
+        } else {
+            Ok(body)
+        }
+    }
+}
+
+/// Percent-encodes a `grpc-message` as the gRPC spec requires: UTF-8 bytes
+/// outside printable ASCII and `%` itself become `%XX`
+fn percent_encode(message: &str) -> String {
+    let mut encoded = String::with_capacity(message.len());
+    for byte in message.bytes() {
+        if (0x20..=0x7e).contains(&byte) && byte != b'%' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Appends a frame: flag byte, big-endian length, payload
+fn push_frame(body: &mut Vec<u8>, flag: u8, payload: &[u8]) {
+    body.push(flag);
+    body.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    body.extend_from_slice(payload);
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Structural comparison of a JSON request body
+///
+/// Key order and whitespace do not matter; a body that is not JSON never
+/// matches.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonBodyMatch {
+    /// The body has to equal the value
+    Equals(Value),
+
+    /// The body has to contain the value: objects may carry extra keys and
+    /// every expected array element has to match one of the actual elements
+    Contains(Value),
+}
+
+impl JsonBodyMatch {
+    /// Whether the raw request body satisfies the match
+    pub fn matches(&self, body: Option<&str>) -> bool {
+        let Some(actual) = body.and_then(|body| serde_json::from_str::<Value>(body).ok()) else {
+            return false;
+        };
+
+        match self {
+            JsonBodyMatch::Equals(expected) => actual == *expected,
+            JsonBodyMatch::Contains(expected) => contains(&actual, expected),
+        }
+    }
+}
+
+pub(crate) fn contains(actual: &Value, expected: &Value) -> bool {
+    match (actual, expected) {
+        (Value::Object(actual), Value::Object(expected)) => expected
+            .iter()
+            .all(|(key, value)| actual.get(key).is_some_and(|field| contains(field, value))),
+        (Value::Array(actual), Value::Array(expected)) => expected
+            .iter()
+            .all(|value| actual.iter().any(|item| contains(item, value))),
+        _ => actual == expected,
+    }
+}
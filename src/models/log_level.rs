@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Request for changing the log level at runtime
+#[derive(Debug, Deserialize)]
+pub struct LogLevelRequest {
+    pub level: String,
+}
+
+/// Response describing the active log level
+#[derive(Debug, Serialize)]
+pub struct LogLevelResponse {
+    pub level: String,
+}
@@ -1,9 +1,51 @@
+mod callback;
+mod chaos;
+mod downtime;
+mod expect_continue;
 mod expectation;
+mod fault;
+mod graphql;
+mod grpc_web;
+mod json_body;
+mod log_level;
+mod multipart;
+mod negotiation;
+mod pagination;
+mod rate_limit;
 mod record;
+mod request_query;
 mod response;
+mod scenario;
+mod script;
+mod sequence;
+mod sse;
+mod stats;
+mod stream;
+mod unmatched;
 mod verify;
 
+pub use callback::*;
+pub use chaos::*;
+pub use downtime::*;
+pub use expect_continue::*;
 pub use expectation::*;
+pub use fault::*;
+pub use graphql::*;
+pub use grpc_web::*;
+pub use json_body::*;
+pub use log_level::*;
+pub use multipart::*;
+pub use negotiation::*;
+pub use pagination::*;
+pub use rate_limit::*;
 pub use record::*;
+pub use request_query::*;
 pub use response::*;
+pub use scenario::*;
+pub use script::*;
+pub use sequence::*;
+pub use sse::*;
+pub use stats::*;
+pub use stream::*;
+pub use unmatched::*;
 pub use verify::*;
@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A `multipart/*` response body made of independently typed parts
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Multipart {
+    /// `mixed`, `related`, ...
+    #[serde(default = "default_subtype")]
+    pub subtype: String,
+
+    /// Generated when not set
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub boundary: Option<String>,
+
+    pub parts: Vec<MultipartPart>,
+}
+
+fn default_subtype() -> String {
+    "mixed".to_string()
+}
+
+/// One part of a multipart response
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultipartPart {
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+
+    /// Strings are sent verbatim, anything else is serialized as JSON
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<Value>,
+
+    /// File in the resource directory, used when `body` is not set
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_file: Option<String>,
+}
+
+impl Multipart {
+    /// `multipart/mixed`, e.g. for batches
+    pub fn mixed() -> Self {
+        Self {
+            subtype: "mixed".to_string(),
+            boundary: None,
+            parts: Vec::new(),
+        }
+    }
+
+    /// `multipart/related`, e.g. a document with its attachments (MTOM-like)
+    pub fn related() -> Self {
+        Self {
+            subtype: "related".to_string(),
+            ..Self::mixed()
+        }
+    }
+
+    pub fn boundary(mut self, boundary: &str) -> Self {
+        self.boundary = Some(boundary.to_string());
+        self
+    }
+
+    pub fn part(mut self, part: MultipartPart) -> Self {
+        self.parts.push(part);
+        self
+    }
+
+    /// Renders the body and returns it with the matching `Content-Type`
+    pub(crate) fn render(&self, resource_dir: &Path) -> io::Result<(String, String)> {
+        let boundary = self
+            .boundary
+            .clone()
+            .unwrap_or_else(|| format!("mimic-{}", uuid::Uuid::new_v4().simple()));
+
+        let mut body = String::new();
+        for part in &self.parts {
+            body.push_str(&format!("--{}\r\n", boundary));
+            for (name, value) in &part.headers {
+                body.push_str(&format!("{}: {}\r\n", name, value));
+            }
+            body.push_str("\r\n");
+            match (&part.body, &part.body_file) {
+                (Some(Value::String(text)), _) => body.push_str(text),
+                (Some(value), _) => body.push_str(&value.to_string()),
+                (None, Some(file)) => body.push_str(&fs::read_to_string(resource_dir.join(file))?),
+                (None, None) => {}
+            }
+            body.push_str("\r\n");
+        }
+        body.push_str(&format!("--{}--\r\n", boundary));
+
+        let mut content_type = format!("multipart/{}; boundary=\"{}\"", self.subtype, boundary);
+        // multipart/related names the type of its root part (RFC 2387)
+        if self.subtype == "related"
+            && let Some(root_type) = self
+                .parts
+                .first()
+                .and_then(|part| part.header_value("Content-Type"))
+        {
+            content_type.push_str(&format!("; type=\"{}\"", root_type));
+        }
+
+        Ok((content_type, body))
+    }
+}
+
+impl MultipartPart {
+    /// A part with the given content type and body
+    pub fn new(content_type: &str, body: impl Into<Value>) -> Self {
+        Self {
+            headers: BTreeMap::from([("Content-Type".to_string(), content_type.to_string())]),
+            body: Some(body.into()),
+            body_file: None,
+        }
+    }
+
+    /// A JSON part
+    pub fn json(body: Value) -> Self {
+        Self::new("application/json", body)
+    }
+
+    /// A part loaded from a file in the resource directory
+    pub fn file(content_type: &str, file_path: &str) -> Self {
+        Self {
+            headers: BTreeMap::from([("Content-Type".to_string(), content_type.to_string())]),
+            body: None,
+            body_file: Some(file_path.to_string()),
+        }
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// One representation of a response body, chosen by the request's `Accept` header
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BodyVariant {
+    /// A media type (`application/xml`) or a short name (`json`, `xml`, `csv`)
+    pub media_type: String,
+
+    /// Strings are sent verbatim, anything else is serialized as JSON
+    pub body: Value,
+}
+
+impl BodyVariant {
+    pub fn new(media_type: &str, body: impl Into<Value>) -> Self {
+        Self {
+            media_type: media_type.to_string(),
+            body: body.into(),
+        }
+    }
+
+    /// The full media type, expanding short names
+    pub fn content_type(&self) -> &str {
+        match self.media_type.as_str() {
+            "json" => "application/json",
+            "xml" => "application/xml",
+            "csv" => "text/csv",
+            "html" => "text/html",
+            "text" => "text/plain",
+            "yaml" => "application/yaml",
+            other => other,
+        }
+    }
+
+    /// The body as sent over the wire
+    pub fn render(&self) -> String {
+        match &self.body {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// A localized body, chosen by the request's `Accept-Language` header
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LanguageVariant {
+    /// A language tag such as `de` or `pt-BR`
+    pub language: String,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<Value>,
+
+    /// File in the resource directory, used when `body` is not set
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_file: Option<String>,
+}
+
+impl LanguageVariant {
+    pub fn body(language: &str, body: impl Into<Value>) -> Self {
+        Self {
+            language: language.to_string(),
+            body: Some(body.into()),
+            body_file: None,
+        }
+    }
+
+    pub fn file(language: &str, file_path: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            body: None,
+            body_file: Some(file_path.to_string()),
+        }
+    }
+}
+
+/// Picks the variant preferred by `accept`
+///
+/// Each variant gets the quality of the most specific media range matching
+/// it; the highest non-zero quality wins, ties going to the variant declared
+/// first. Without an `Accept` header the first variant is used. `None` means
+/// that nothing is acceptable.
+pub(crate) fn negotiate<'a>(
+    variants: &'a [BodyVariant],
+    accept: Option<&str>,
+) -> Option<&'a BodyVariant> {
+    let Some(accept) = accept.filter(|a| !a.trim().is_empty()) else {
+        return variants.first();
+    };
+
+    let ranges = parse_quality_list(accept);
+
+    let mut best: Option<(&BodyVariant, f32)> = None;
+    for variant in variants {
+        let content_type = variant.content_type().to_lowercase();
+        let quality = ranges
+            .iter()
+            .filter_map(|(range, quality)| {
+                specificity(range, &content_type).map(|specificity| (specificity, *quality))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(0.0, |(_, quality)| quality);
+        if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+            best = Some((variant, quality));
+        }
+    }
+    best.map(|(variant, _)| variant)
+}
+
+/// Picks the language variant preferred by `accept_language`
+///
+/// A range matches a tag exactly, as a prefix (`en` matches `en-US`) or, as a
+/// fallback, the other way round (`en-US` is served by `en`). `None` means
+/// the default body should be kept.
+pub(crate) fn negotiate_language<'a>(
+    variants: &'a [LanguageVariant],
+    accept_language: Option<&str>,
+) -> Option<&'a LanguageVariant> {
+    let ranges = parse_quality_list(accept_language?);
+
+    let mut best: Option<(&LanguageVariant, f32)> = None;
+    for variant in variants {
+        let language = variant.language.to_lowercase();
+        let quality = ranges
+            .iter()
+            .filter_map(|(range, quality)| {
+                language_specificity(range, &language).map(|specificity| (specificity, *quality))
+            })
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(0.0, |(_, quality)| quality);
+        if quality > 0.0 && best.is_none_or(|(_, best_quality)| quality > best_quality) {
+            best = Some((variant, quality));
+        }
+    }
+    best.map(|(variant, _)| variant)
+}
+
+/// Splits a header like `Accept` into lowercase ranges and their `q` values
+fn parse_quality_list(header: &str) -> Vec<(String, f32)> {
+    header
+        .split(',')
+        .filter_map(|range| {
+            let mut parts = range.split(';');
+            let value = parts.next()?.trim().to_lowercase();
+            let quality = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!value.is_empty()).then_some((value, quality))
+        })
+        .collect()
+}
+
+fn language_specificity(range: &str, language: &str) -> Option<u8> {
+    if range == "*" {
+        Some(0)
+    } else if range == language {
+        Some(3)
+    } else if language.starts_with(&format!("{}-", range)) {
+        Some(2)
+    } else if range.starts_with(&format!("{}-", language)) {
+        Some(1)
+    } else {
+        None
+    }
+}
+
+/// How specifically `range` matches `content_type`, `None` if it does not
+fn specificity(range: &str, content_type: &str) -> Option<u8> {
+    match range.strip_suffix("/*") {
+        Some("*") => Some(0),
+        Some(main_type) => content_type
+            .split_once('/')
+            .filter(|(main, _)| *main == main_type)
+            .map(|_| 1),
+        None => (content_type == range).then_some(2),
+    }
+}
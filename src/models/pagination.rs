@@ -0,0 +1,135 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::HashMap;
+
+/// Serves a JSON array page by page
+///
+/// Pages are selected with `?page=2&limit=10` (1-based) or with the opaque
+/// `?cursor=...` returned as `next_cursor` by the previous page. The body is
+/// `{"data": [...], "page", "limit", "total", "total_pages", "next_page",
+/// "next_cursor"}`; `X-Total-Count` and `Link` headers are added as well.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Pagination {
+    pub items: Vec<Value>,
+
+    pub page_size: usize,
+
+    /// Largest page a client may request via `limit`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub max_page_size: Option<usize>,
+}
+
+/// A rendered page: JSON body plus headers
+pub(crate) struct Page {
+    pub body: Value,
+
+    pub headers: Vec<(String, String)>,
+}
+
+impl Pagination {
+    /// Pages over `dataset`, which should be a JSON array
+    ///
+    /// Any other value is served as a dataset with a single item.
+    pub fn new(dataset: Value, page_size: usize) -> Self {
+        let items = match dataset {
+            Value::Array(items) => items,
+            other => vec![other],
+        };
+
+        Self {
+            items,
+            page_size: page_size.max(1),
+            max_page_size: None,
+        }
+    }
+
+    /// Renders the page requested by `query_params` for a request to `path`
+    pub(crate) fn render(&self, path: &str, query_params: &HashMap<String, String>) -> Page {
+        let mut limit = query_params
+            .get("limit")
+            .and_then(|v| v.parse::<usize>().ok())
+            .filter(|limit| *limit > 0)
+            .unwrap_or(self.page_size);
+        if let Some(max) = self.max_page_size {
+            limit = limit.min(max);
+        }
+
+        let offset = match query_params.get("cursor").and_then(|c| decode_cursor(c)) {
+            Some(offset) => offset,
+            None => {
+                let page = query_params
+                    .get("page")
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .max(1);
+                (page - 1).saturating_mul(limit)
+            }
+        };
+
+        let total = self.items.len();
+        let total_pages = total.div_ceil(limit);
+        let page = offset / limit + 1;
+        let end = offset.saturating_add(limit).min(total);
+        let data = self.items.get(offset..end).unwrap_or_default().to_vec();
+
+        let has_next = end < total;
+        let next_page = has_next.then_some(page + 1);
+        let next_cursor = has_next.then(|| encode_cursor(end));
+
+        let mut links = Vec::new();
+        if has_next {
+            links.push(format!(
+                "<{}?page={}&limit={}>; rel=\"next\"",
+                path,
+                page + 1,
+                limit
+            ));
+        }
+        if page > 1 {
+            links.push(format!(
+                "<{}?page={}&limit={}>; rel=\"prev\"",
+                path,
+                page - 1,
+                limit
+            ));
+        }
+        links.push(format!("<{}?page=1&limit={}>; rel=\"first\"", path, limit));
+        links.push(format!(
+            "<{}?page={}&limit={}>; rel=\"last\"",
+            path,
+            total_pages.max(1),
+            limit
+        ));
+
+        Page {
+            body: json!({
+                "data": data,
+                "page": page,
+                "limit": limit,
+                "total": total,
+                "total_pages": total_pages,
+                "next_page": next_page,
+                "next_cursor": next_cursor,
+            }),
+            headers: vec![
+                ("X-Total-Count".to_string(), total.to_string()),
+                ("Link".to_string(), links.join(", ")),
+            ],
+        }
+    }
+}
+
+fn encode_cursor(offset: usize) -> String {
+    URL_SAFE_NO_PAD.encode(format!("offset:{}", offset))
+}
+
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    String::from_utf8(decoded)
+        .ok()?
+        .strip_prefix("offset:")?
+        .parse()
+        .ok()
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Maximum number of requests an expectation answers within a sliding window
+///
+/// Requests beyond the budget get `429 Too Many Requests` until enough of the
+/// earlier requests have left the window.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "RateLimitFields")]
+pub struct RateLimit {
+    pub limit: u32,
+
+    pub window_ms: u64,
+}
+
+/// `RateLimit` as written in JSON, with the window in seconds or milliseconds
+#[derive(Deserialize)]
+struct RateLimitFields {
+    limit: u32,
+
+    #[serde(default)]
+    window_secs: Option<u64>,
+
+    #[serde(default)]
+    window_ms: Option<u64>,
+}
+
+impl TryFrom<RateLimitFields> for RateLimit {
+    type Error = String;
+
+    fn try_from(fields: RateLimitFields) -> Result<Self, Self::Error> {
+        let window_ms = match (fields.window_ms, fields.window_secs) {
+            (Some(ms), None) => ms,
+            (None, Some(secs)) => secs.saturating_mul(1000),
+            (Some(_), Some(_)) => {
+                return Err("rate_limit takes either window_secs or window_ms".to_string());
+            }
+            (None, None) => return Err("rate_limit needs window_secs or window_ms".to_string()),
+        };
+        if window_ms == 0 {
+            return Err("rate_limit window must not be zero".to_string());
+        }
+
+        Ok(Self {
+            limit: fields.limit,
+            window_ms,
+        })
+    }
+}
+
+impl RateLimit {
+    /// `per` is kept to the millisecond, windows under 1ms count as 1ms
+    pub fn new(limit: u32, per: Duration) -> Self {
+        Self {
+            limit,
+            window_ms: u64::try_from(per.as_millis()).unwrap_or(u64::MAX).max(1),
+        }
+    }
+
+    pub fn window(&self) -> Duration {
+        Duration::from_millis(self.window_ms)
+    }
+}
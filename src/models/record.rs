@@ -1,10 +1,15 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 
 /// Represents a record of a request that the mock server received
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RequestRecord {
+    /// Unique per request, empty in records written by older versions
+    #[serde(default)]
+    pub id: String,
+
     pub method: String,
 
     pub path: String,
@@ -18,7 +23,53 @@ pub struct RequestRecord {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body: Option<String>,
 
+    /// Where an uploaded body was saved instead of keeping it in `body`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub upload: Option<CapturedUpload>,
+
     pub timestamp: DateTime<Utc>,
+
+    /// What the server answered, `None` while the request is being served
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub response: Option<RecordedResponse>,
+}
+
+/// The response sent for a recorded request
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    /// The expectation that answered, `None` for 404s, proxied requests and other fallbacks
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expectation_id: Option<String>,
+
+    pub status: u16,
+
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub headers: HashMap<String, String>,
+
+    /// Text bodies up to [`MAX_RECORDED_BODY`] bytes; streamed and binary bodies are not kept
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<String>,
+
+    /// Time from receiving the request until the response was ready, including delays
+    #[serde(default)]
+    pub latency_ms: f64,
+}
+
+/// Largest response body kept in the request log
+pub const MAX_RECORDED_BODY: usize = 1024 * 1024;
+
+/// A multipart or binary request body written to the capture directory
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapturedUpload {
+    pub path: PathBuf,
+
+    /// Hex encoded SHA-256 of the bytes
+    pub sha256: String,
+
+    pub size: u64,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub content_type: Option<String>,
 }
 
 impl RequestRecord {
@@ -31,12 +82,15 @@ impl RequestRecord {
         body: Option<String>,
     ) -> Self {
         Self {
+            id: uuid::Uuid::new_v4().to_string(),
             method,
             path,
             query_params,
             headers,
             body,
+            upload: None,
             timestamp: Utc::now(),
+            response: None,
         }
     }
 }
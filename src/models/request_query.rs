@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::RequestRecord;
+
+/// Filters and pagination for searching the request log
+///
+/// Every filter that is set must match. Without a limit all matching
+/// requests are returned, oldest first unless `order` is `desc`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RequestQuery {
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub method: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub path_prefix: Option<String>,
+
+    /// Only requests received at or after this time
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Number of matching requests to skip
+    #[serde(default)]
+    pub offset: usize,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub limit: Option<usize>,
+
+    #[serde(default)]
+    pub order: SortOrder,
+}
+
+/// Order of the requests returned by a [`RequestQuery`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortOrder {
+    /// Oldest first
+    #[default]
+    Asc,
+
+    /// Newest first
+    Desc,
+}
+
+impl RequestQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only requests with this method (case-insensitive)
+    pub fn method(mut self, method: &str) -> Self {
+        self.method = Some(method.to_string());
+        self
+    }
+
+    /// Only requests whose path starts with `prefix`
+    pub fn path_prefix(mut self, prefix: &str) -> Self {
+        self.path_prefix = Some(prefix.to_string());
+        self
+    }
+
+    /// Only requests received at or after `since`
+    pub fn since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Returns the most recent requests first
+    pub fn newest_first(mut self) -> Self {
+        self.order = SortOrder::Desc;
+        self
+    }
+
+    /// Checks the filters, ignoring pagination
+    pub fn matches(&self, record: &RequestRecord) -> bool {
+        self.method
+            .as_ref()
+            .is_none_or(|method| record.method.eq_ignore_ascii_case(method))
+            && self
+                .path_prefix
+                .as_ref()
+                .is_none_or(|prefix| record.path.starts_with(prefix.as_str()))
+            && self.since.is_none_or(|since| record.timestamp >= since)
+    }
+
+    /// Filters, sorts and pages `records`, which must be in the order received
+    ///
+    /// Returns the selected page and the number of matching requests.
+    pub fn apply(&self, records: Vec<RequestRecord>) -> (Vec<RequestRecord>, usize) {
+        let mut matching: Vec<RequestRecord> = records
+            .into_iter()
+            .filter(|record| self.matches(record))
+            .collect();
+        let total = matching.len();
+
+        if self.order == SortOrder::Desc {
+            matching.reverse();
+        }
+
+        let page = matching
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit.unwrap_or(usize::MAX))
+            .collect();
+        (page, total)
+    }
+}
@@ -1,6 +1,21 @@
+use axum::body::Bytes;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::time::Duration;
+
+use super::fault::Fault;
+use super::grpc_web::GrpcWebResponse;
+use super::multipart::Multipart;
+use super::negotiation::{BodyVariant, LanguageVariant};
+use super::pagination::Pagination;
+use super::script::ResponseScript;
+use super::sse::SseStream;
+use super::stream::StreamBody;
+use crate::extension::ExtensionRef;
+use crate::random::Random;
 
 /// Represents the response that the mock server returns when matching an expectation
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -16,14 +31,71 @@ pub struct MockResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub body_file: Option<String>,
 
+    /// Binary body, base64 encoded; served as `application/octet-stream`
+    /// unless a `Content-Type` header is set
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body_base64: Option<String>,
+
+    /// Alternative bodies selected by the `Accept` header
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub variants: Vec<BodyVariant>,
+
+    /// Localized bodies selected by the `Accept-Language` header
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub languages: Vec<LanguageVariant>,
+
+    /// Registered extension producing the response instead
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub generator: Option<ExtensionRef>,
+
+    /// Rhai script producing the response instead
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub script: Option<ResponseScript>,
+
+    /// Sends a `multipart/*` body instead of `body`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub multipart: Option<Multipart>,
+
+    /// Answers as a gRPC-Web call instead of a plain HTTP body
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub grpc_web: Option<GrpcWebResponse>,
+
+    /// Serves the body as pages of a dataset
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub paginate: Option<Pagination>,
+
+    /// Writes the body in chunks with pauses in between instead of `body`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub stream: Option<StreamBody>,
+
+    /// Answers with a stream of server-sent events instead of `body`
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub sse: Option<SseStream>,
+
+    /// Holds the response before it is sent
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub delay: Option<ResponseDelay>,
+
+    /// Breaks the connection instead of answering
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub fault: Option<Fault>,
+
     #[serde(skip)]
     pub cached_file_content: Option<String>,
 
     #[serde(skip)]
     pub cached_json_content: Option<Value>,
 
+    /// Content of a body file that is not UTF-8, served as is
+    #[serde(skip)]
+    pub cached_file_bytes: Option<Bytes>,
+
     #[serde(skip)]
     pub conditional_id: Option<String>,
+
+    /// Closure registered with `respond_with` that builds the response instead
+    #[serde(skip)]
+    pub responder_id: Option<String>,
 }
 
 impl Default for MockResponse {
@@ -33,9 +105,23 @@ impl Default for MockResponse {
             headers: HashMap::new(),
             body: None,
             body_file: None,
+            body_base64: None,
+            variants: Vec::new(),
+            languages: Vec::new(),
+            generator: None,
+            script: None,
+            multipart: None,
+            grpc_web: None,
+            paginate: None,
+            stream: None,
+            sse: None,
+            delay: None,
+            fault: None,
             cached_file_content: None,
             cached_json_content: None,
+            cached_file_bytes: None,
             conditional_id: None,
+            responder_id: None,
         }
     }
 }
@@ -76,16 +162,51 @@ impl MockResponse {
         self
     }
 
+    /// Sets any file as the body, with a `Content-Type` inferred from its extension
+    ///
+    /// An explicitly set `Content-Type` header is kept.
+    pub fn with_file(mut self, file_path: &str) -> Self {
+        self.body_file = Some(file_path.to_string());
+        if self.content_type().is_none() {
+            self.headers.insert(
+                "Content-Type".to_string(),
+                content_type_for(file_path).to_string(),
+            );
+        }
+        self
+    }
+
+    /// The `Content-Type` header, looked up case-insensitively
+    pub fn content_type(&self) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .map(|(_, value)| value.as_str())
+    }
+
     /// Cache the content of the file to avoid repeated disk reads
+    ///
+    /// Only parsed as JSON when the response has no `Content-Type` or a JSON one,
+    /// so text fixtures such as `42` or `true` are served verbatim.
     pub fn cache_file_content(&mut self, content: String) {
-        // Try to parse as JSON first
-        if let Ok(json_value) = serde_json::from_str::<Value>(&content) {
+        let json_allowed = self
+            .content_type()
+            .is_none_or(|content_type| content_type.contains("json"));
+        if json_allowed && let Ok(json_value) = serde_json::from_str::<Value>(&content) {
             self.cached_json_content = Some(json_value);
         }
 
         self.cached_file_content = Some(content);
     }
 
+    /// Caches a file read from disk, as text if it is UTF-8 and as bytes otherwise
+    pub fn cache_file(&mut self, content: Vec<u8>) {
+        match String::from_utf8(content) {
+            Ok(text) => self.cache_file_content(text),
+            Err(e) => self.cached_file_bytes = Some(Bytes::from(e.into_bytes())),
+        }
+    }
+
     /// Get the pre-serialized JSON string if available
     pub fn get_json_string(&self) -> Option<String> {
         match (&self.body, &self.cached_json_content) {
@@ -95,8 +216,121 @@ impl MockResponse {
         }
     }
 
+    /// Adds a body variant for content negotiation
+    pub fn with_variant(mut self, media_type: &str, body: impl Into<Value>) -> Self {
+        self.variants.push(BodyVariant::new(media_type, body));
+        self
+    }
+
+    /// Holds the response for `delay` before sending it
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = Some(ResponseDelay::fixed(delay));
+        self
+    }
+
+    /// Holds the response for a random duration between `min` and `max`
+    pub fn with_random_delay(mut self, min: Duration, max: Duration) -> Self {
+        self.delay = Some(ResponseDelay::between(min, max));
+        self
+    }
+
+    /// Breaks the connection with `fault` instead of answering
+    pub fn with_fault(mut self, fault: Fault) -> Self {
+        self.fault = Some(fault);
+        self
+    }
+
+    /// Sends `bytes` as the body, e.g. an image or a protobuf message
+    pub fn with_body_bytes(mut self, bytes: impl AsRef<[u8]>) -> Self {
+        self.body = None;
+        self.body_base64 = Some(STANDARD.encode(bytes));
+        self
+    }
+
+    /// Streams `stream` as chunked body instead of sending `body`
+    pub fn with_stream(mut self, stream: StreamBody) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    /// Answers with the server-sent events of `sse`
+    pub fn with_sse(mut self, sse: SseStream) -> Self {
+        self.sse = Some(sse);
+        self
+    }
+
+    /// Lets a Rhai script produce the response
+    pub fn with_script(mut self, script: ResponseScript) -> Self {
+        self.script = Some(script);
+        self
+    }
+
     pub fn with_conditional_id(mut self, id: String) -> Self {
         self.conditional_id = Some(id);
         self
     }
 }
+
+/// How long a response is held before it is sent
+///
+/// A fixed delay has `min_ms` equal to `max_ms`; otherwise a duration in the
+/// range is picked for each response.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResponseDelay {
+    pub min_ms: u64,
+
+    pub max_ms: u64,
+}
+
+impl ResponseDelay {
+    pub fn fixed(delay: Duration) -> Self {
+        Self::between(delay, delay)
+    }
+
+    /// A random delay between `min` and `max`, swapped if given in reverse
+    pub fn between(min: Duration, max: Duration) -> Self {
+        let (min, max) = (min.min(max), min.max(max));
+        Self {
+            min_ms: min.as_millis() as u64,
+            max_ms: max.as_millis() as u64,
+        }
+    }
+
+    /// Picks the delay for one response
+    pub(crate) fn pick(&self, random: &Random) -> Duration {
+        if self.min_ms >= self.max_ms {
+            return Duration::from_millis(self.min_ms);
+        }
+        Duration::from_millis(random.range(self.min_ms..=self.max_ms))
+    }
+}
+
+/// Guesses a media type from the extension of `file_path`
+pub(crate) fn content_type_for(file_path: &str) -> &'static str {
+    let extension = file_path
+        .rsplit_once('.')
+        .map(|(_, extension)| extension.to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "html" | "htm" => "text/html; charset=utf-8",
+        "txt" => "text/plain; charset=utf-8",
+        "csv" => "text/csv; charset=utf-8",
+        "css" => "text/css; charset=utf-8",
+        "js" | "mjs" => "text/javascript; charset=utf-8",
+        "yaml" | "yml" => "application/yaml",
+        "svg" => "image/svg+xml",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "wasm" => "application/wasm",
+        "woff2" => "font/woff2",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// State every scenario is in before any transition
+pub const SCENARIO_STARTED: &str = "Started";
+
+/// Ties an expectation to a named state machine
+///
+/// The expectation only matches while the scenario is in `required_state`
+/// (any state if `None`) and moves it to `new_state` after responding.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioRule {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub required_state: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub new_state: Option<String>,
+}
+
+impl ScenarioRule {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            required_state: None,
+            new_state: None,
+        }
+    }
+
+    /// Whether the rule allows matching while the scenario is in `current`
+    pub fn is_active(&self, current: &str) -> bool {
+        self.required_state
+            .as_deref()
+            .is_none_or(|required| required == current)
+    }
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::io;
+use std::path::Path;
+
+/// A Rhai script computing the response, e.g. `{"source": "..."}` or
+/// `{"file": "scripts/order.rhai"}`, or a WebAssembly module such as
+/// `{"wasm": "modules/order.wasm"}`
+///
+/// Mapping files may also give the source as a plain string.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", from = "ScriptField")]
+pub enum ResponseScript {
+    /// The script itself
+    Source(String),
+
+    /// A script file, relative to the resource directory
+    File(String),
+
+    /// A WASI module (`.wasm`, or `.wat` text), relative to the resource directory
+    Wasm(String),
+}
+
+/// The accepted spellings of a `script` field
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ScriptField {
+    Inline(String),
+
+    Tagged(TaggedScript),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TaggedScript {
+    Source(String),
+
+    File(String),
+
+    Wasm(String),
+}
+
+impl From<ScriptField> for ResponseScript {
+    fn from(field: ScriptField) -> Self {
+        match field {
+            ScriptField::Inline(source) | ScriptField::Tagged(TaggedScript::Source(source)) => {
+                ResponseScript::Source(source)
+            }
+            ScriptField::Tagged(TaggedScript::File(file)) => ResponseScript::File(file),
+            ScriptField::Tagged(TaggedScript::Wasm(file)) => ResponseScript::Wasm(file),
+        }
+    }
+}
+
+impl ResponseScript {
+    /// The source of the script, reading the file if needed
+    pub(crate) fn load(&self, resource_dir: &Path) -> io::Result<Cow<'_, str>> {
+        match self {
+            ResponseScript::Source(source) => Ok(Cow::Borrowed(source)),
+            ResponseScript::File(file) => {
+                std::fs::read_to_string(resource_dir.join(file)).map(Cow::Owned)
+            }
+            ResponseScript::Wasm(_) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "a WebAssembly module has no script source",
+            )),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+/// What a response sequence (`rotate`) does after its last response
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SequenceEnd {
+    /// Start over with the first response
+    #[default]
+    StartOver,
+
+    /// Keep returning the last response
+    RepeatLast,
+
+    /// Stop matching, so requests fall through to other expectations or 404
+    NotFound,
+}
+
+impl SequenceEnd {
+    pub fn is_start_over(&self) -> bool {
+        *self == SequenceEnd::StartOver
+    }
+
+    /// Index of the response for the call after `served` earlier ones,
+    /// `None` once the sequence is exhausted
+    pub fn index(self, served: usize, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        match self {
+            SequenceEnd::StartOver => Some(served % len),
+            SequenceEnd::RepeatLast => Some(served.min(len - 1)),
+            SequenceEnd::NotFound => (served < len).then_some(served),
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Content type of an event stream
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// One server-sent event
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SseEvent {
+    /// Event type, `message` when absent
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub event: Option<String>,
+
+    /// Payload; every line becomes its own `data:` field
+    #[serde(default)]
+    pub data: String,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub id: Option<String>,
+
+    /// Reconnection time the client should use, in milliseconds
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub retry_ms: Option<u64>,
+
+    /// Pause before the event is sent, in milliseconds
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+impl SseEvent {
+    /// An unnamed event carrying `data`
+    pub fn new(data: impl Into<String>) -> Self {
+        Self {
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the event type, the `event:` field
+    pub fn event(mut self, event: &str) -> Self {
+        self.event = Some(event.to_string());
+        self
+    }
+
+    /// Sets the event id, sent back by clients as `Last-Event-ID` on reconnect
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Asks the client to wait `retry` before reconnecting
+    pub fn retry(mut self, retry: Duration) -> Self {
+        self.retry_ms = Some(retry.as_millis() as u64);
+        self
+    }
+
+    /// Waits `delay` before sending the event
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay_ms = delay.as_millis() as u64;
+        self
+    }
+
+    /// The event in wire format, terminated by a blank line
+    pub(crate) fn encode(&self) -> String {
+        let mut encoded = String::new();
+        if let Some(id) = &self.id {
+            encoded.push_str(&format!("id: {}\n", id));
+        }
+        if let Some(event) = &self.event {
+            encoded.push_str(&format!("event: {}\n", event));
+        }
+        if let Some(retry) = self.retry_ms {
+            encoded.push_str(&format!("retry: {}\n", retry));
+        }
+        for line in self.data.split('\n') {
+            encoded.push_str(&format!("data: {}\n", line));
+        }
+        encoded.push('\n');
+        encoded
+    }
+}
+
+/// A script of server-sent events
+///
+/// The events are sent in order, each after its own delay. Afterwards the
+/// connection stays open until the client leaves, unless `close` is set;
+/// with `repeat` the script starts over instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SseStream {
+    pub events: Vec<SseEvent>,
+
+    /// Loop over the events until the client disconnects
+    #[serde(default)]
+    pub repeat: bool,
+
+    /// End the response after the last event instead of keeping it open
+    #[serde(default)]
+    pub close: bool,
+}
+
+impl SseStream {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends an event to the script
+    pub fn event(mut self, event: SseEvent) -> Self {
+        self.events.push(event);
+        self
+    }
+
+    /// Starts over after the last event
+    pub fn repeat(mut self) -> Self {
+        self.repeat = true;
+        self
+    }
+
+    /// Ends the response after the last event
+    pub fn close(mut self) -> Self {
+        self.close = true;
+        self
+    }
+}
@@ -0,0 +1,23 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How often and how fast an expectation answered
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExpectationStats {
+    pub id: String,
+
+    pub method: String,
+
+    pub path: String,
+
+    /// Requests the expectation answered
+    pub hits: usize,
+
+    /// When the last response was sent, by the server's clock
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub last_hit: Option<DateTime<Utc>>,
+
+    /// Mean time from receiving a request until its response was ready
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub avg_latency_ms: Option<f64>,
+}
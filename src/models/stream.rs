@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A body written chunk by chunk with chunked transfer encoding
+///
+/// The first chunk is sent right away, each following one after
+/// `chunk_delay_ms`, e.g. to feed NDJSON lines to a client that processes
+/// them incrementally or to simulate a slow download.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamBody {
+    pub chunks: Vec<String>,
+
+    /// Pause between two chunks in milliseconds
+    #[serde(default)]
+    pub chunk_delay_ms: u64,
+}
+
+impl StreamBody {
+    pub fn new<I, S>(chunks: I, chunk_delay: Duration) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            chunks: chunks.into_iter().map(Into::into).collect(),
+            chunk_delay_ms: chunk_delay.as_millis() as u64,
+        }
+    }
+
+    pub fn chunk_delay(&self) -> Duration {
+        Duration::from_millis(self.chunk_delay_ms)
+    }
+}
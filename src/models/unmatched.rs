@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+use super::RequestRecord;
+
+/// An expectation that almost matched, with the reasons it did not
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NearMiss {
+    pub id: String,
+
+    pub method: String,
+
+    pub path: String,
+
+    /// One entry per rejected part, e.g. `header accept: expected ..., missing`
+    pub mismatches: Vec<String>,
+}
+
+/// A request no expectation answered
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UnmatchedRequest {
+    pub request: RequestRecord,
+
+    /// The expectation with the fewest mismatches when the request came in
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub closest: Option<NearMiss>,
+}
@@ -1,4 +1,12 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::RequestRecord;
+use crate::template::jsonpath;
 
 /// Request for verifying the number of endpoint calls
 #[derive(Debug, Deserialize)]
@@ -7,7 +15,346 @@ pub struct VerifyRequest {
 
     pub path: String,
 
-    pub times: usize,
+    pub times: CallTimes,
+
+    /// Only requests meeting these conditions are counted
+    #[serde(flatten)]
+    pub conditions: RequestConditions,
+}
+
+/// Request for verifying that calls happened in a given order
+#[derive(Debug, Deserialize)]
+pub struct VerifySequenceRequest {
+    pub steps: Vec<SequenceStep>,
+}
+
+/// One request of an ordered verification
+///
+/// `path` may contain `*` wildcards and `{name}` segments, the method `*`
+/// matches any method.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SequenceStep {
+    pub method: String,
+
+    pub path: String,
+
+    #[serde(flatten)]
+    pub conditions: RequestConditions,
+}
+
+impl SequenceStep {
+    pub fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: method.to_string(),
+            path: path.to_string(),
+            conditions: RequestConditions::default(),
+        }
+    }
+
+    /// Only requests meeting `conditions` count for this step
+    pub fn with_conditions(mut self, conditions: RequestConditions) -> Self {
+        self.conditions = conditions;
+        self
+    }
+}
+
+impl From<(&str, &str)> for SequenceStep {
+    fn from((method, path): (&str, &str)) -> Self {
+        Self::new(method, path)
+    }
+}
+
+/// How many calls a verification expects
+///
+/// Deserializes from a number (`3`) or a range object
+/// (`{"at_least": 1, "at_most": 3}`, either bound may be left out).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CallTimes {
+    Exactly(usize),
+
+    Range {
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        at_least: Option<usize>,
+
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        at_most: Option<usize>,
+    },
+}
+
+impl CallTimes {
+    pub fn at_least(min: usize) -> Self {
+        Self::Range {
+            at_least: Some(min),
+            at_most: None,
+        }
+    }
+
+    pub fn at_most(max: usize) -> Self {
+        Self::Range {
+            at_least: None,
+            at_most: Some(max),
+        }
+    }
+
+    /// Between `min` and `max` calls, both inclusive
+    pub fn between(min: usize, max: usize) -> Self {
+        Self::Range {
+            at_least: Some(min),
+            at_most: Some(max),
+        }
+    }
+
+    pub fn accepts(&self, actual: usize) -> bool {
+        match *self {
+            Self::Exactly(expected) => actual == expected,
+            Self::Range { at_least, at_most } => {
+                at_least.is_none_or(|min| actual >= min) && at_most.is_none_or(|max| actual <= max)
+            }
+        }
+    }
+
+    /// Fails for ranges without bounds or with `at_least` above `at_most`
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Self::Range {
+                at_least: None,
+                at_most: None,
+            } => Err("times needs at_least or at_most".to_string()),
+            Self::Range {
+                at_least: Some(min),
+                at_most: Some(max),
+            } if min > max => Err(format!(
+                "times at_least ({}) is greater than at_most ({})",
+                min, max
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl From<usize> for CallTimes {
+    fn from(times: usize) -> Self {
+        Self::Exactly(times)
+    }
+}
+
+impl fmt::Display for CallTimes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Exactly(times) => write!(f, "{}", times),
+            Self::Range {
+                at_least: Some(min),
+                at_most: Some(max),
+            } => write!(f, "between {} and {}", min, max),
+            Self::Range {
+                at_least: Some(min),
+                at_most: None,
+            } => write!(f, "at least {}", min),
+            Self::Range {
+                at_least: None,
+                at_most: Some(max),
+            } => write!(f, "at most {}", max),
+            Self::Range { .. } => write!(f, "any number of"),
+        }
+    }
+}
+
+/// Conditions on query parameters, headers and body a recorded request has to meet
+///
+/// Header names are compared case-insensitively.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct RequestConditions {
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub query_params: HashMap<String, ValueMatcher>,
+
+    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
+    pub headers: HashMap<String, ValueMatcher>,
+
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub body: Option<ValueMatcher>,
+}
+
+impl RequestConditions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn query_param(mut self, name: &str, matcher: ValueMatcher) -> Self {
+        self.query_params.insert(name.to_string(), matcher);
+        self
+    }
+
+    pub fn header(mut self, name: &str, matcher: ValueMatcher) -> Self {
+        self.headers.insert(name.to_lowercase(), matcher);
+        self
+    }
+
+    pub fn body(mut self, matcher: ValueMatcher) -> Self {
+        self.body = Some(matcher);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.query_params.is_empty() && self.headers.is_empty() && self.body.is_none()
+    }
+
+    /// Whether `record` meets every condition; missing values only pass `present: false` and `not`
+    pub fn matches(&self, record: &RequestRecord) -> bool {
+        let query_matches = self.query_params.iter().all(|(name, matcher)| {
+            matcher.matches(record.query_params.get(name).map(String::as_str))
+        });
+        let headers_match = self.headers.iter().all(|(name, matcher)| {
+            matcher.matches(record.headers.get(&name.to_lowercase()).map(String::as_str))
+        });
+        let body_matches = self
+            .body
+            .as_ref()
+            .is_none_or(|matcher| matcher.matches(record.body.as_deref()));
+
+        query_matches && headers_match && body_matches
+    }
+
+    /// Fails on the first matcher with an invalid regex or JSONPath
+    pub fn validate(&self) -> Result<(), String> {
+        self.query_params
+            .values()
+            .chain(self.headers.values())
+            .chain(self.body.as_ref())
+            .try_for_each(ValueMatcher::validate)
+    }
+}
+
+/// How a query parameter, header or body value is compared
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueMatcher {
+    /// The value equals the string
+    Equals(String),
+
+    /// The value contains the string
+    Contains(String),
+
+    /// The value matches the regex anywhere, anchor it with `^...$` for a full match
+    Matches(String),
+
+    /// The value is JSON in which the path selects something, equal to `value` if given
+    JsonPath {
+        path: String,
+
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        value: Option<Value>,
+    },
+
+    /// The value is present with any content, or absent with `false`
+    Present(bool),
+
+    /// The inner matcher rejects the value, e.g. `{"not": {"contains": "password"}}`
+    Not(Box<ValueMatcher>),
+}
+
+impl ValueMatcher {
+    pub fn equals(value: &str) -> Self {
+        Self::Equals(value.to_string())
+    }
+
+    pub fn contains(value: &str) -> Self {
+        Self::Contains(value.to_string())
+    }
+
+    pub fn matches_regex(pattern: &str) -> Self {
+        Self::Matches(pattern.to_string())
+    }
+
+    /// Requires `path` to select something in the JSON value
+    pub fn json_path(path: &str) -> Self {
+        Self::JsonPath {
+            path: path.to_string(),
+            value: None,
+        }
+    }
+
+    /// Requires `path` to select `value` in the JSON value
+    pub fn json_path_equals(path: &str, value: impl Into<Value>) -> Self {
+        Self::JsonPath {
+            path: path.to_string(),
+            value: Some(value.into()),
+        }
+    }
+
+    pub fn present() -> Self {
+        Self::Present(true)
+    }
+
+    pub fn absent() -> Self {
+        Self::Present(false)
+    }
+
+    pub fn matches(&self, actual: Option<&str>) -> bool {
+        let actual = match (self, actual) {
+            (Self::Present(present), actual) => return actual.is_some() == *present,
+            (Self::Not(matcher), actual) => return !matcher.matches(actual),
+            (_, None) => return false,
+            (_, Some(actual)) => actual,
+        };
+        match self {
+            Self::Equals(expected) => actual == expected,
+            Self::Contains(expected) => actual.contains(expected.as_str()),
+            Self::Matches(pattern) => Regex::new(pattern).is_ok_and(|re| re.is_match(actual)),
+            Self::JsonPath { path, value } => {
+                let Ok(json) = serde_json::from_str::<Value>(actual) else {
+                    return false;
+                };
+                match (jsonpath::select(&json, path), value) {
+                    (Some(selected), Some(expected)) => selected == *expected,
+                    (selected, None) => selected.is_some(),
+                    (None, Some(_)) => false,
+                }
+            }
+            Self::Present(_) | Self::Not(_) => true,
+        }
+    }
+
+    /// Fails for an invalid regex or JSONPath
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::Matches(pattern) => Regex::new(pattern)
+                .map(|_| ())
+                .map_err(|e| format!("invalid regex '{}': {}", pattern, e)),
+            Self::JsonPath { path, .. } if !jsonpath::is_valid(path) => {
+                Err(format!("invalid JSONPath '{}'", path))
+            }
+            Self::Not(matcher) => matcher.validate(),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// `!matcher` accepts what `matcher` rejects, including a missing value
+impl std::ops::Not for ValueMatcher {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::Not(Box::new(self))
+    }
+}
+
+impl fmt::Display for ValueMatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Equals(expected) => write!(f, "{}", expected),
+            Self::Contains(expected) => write!(f, "containing {}", expected),
+            Self::Matches(pattern) => write!(f, "matching {}", pattern),
+            Self::JsonPath {
+                path,
+                value: Some(value),
+            } => write!(f, "{} = {}", path, value),
+            Self::JsonPath { path, value: None } => write!(f, "JSON with {}", path),
+            Self::Present(true) => f.write_str("present"),
+            Self::Present(false) => f.write_str("absent"),
+            Self::Not(matcher) => write!(f, "not {}", matcher),
+        }
+    }
 }
 
 /// Response for verifying the number of calls
@@ -17,7 +364,7 @@ pub struct VerifyResponse {
 
     pub method: String,
 
-    pub expected: usize,
+    pub expected: CallTimes,
 
     pub actual: usize,
 
@@ -26,13 +373,13 @@ pub struct VerifyResponse {
 
 impl VerifyResponse {
     /// Creates a new verification response
-    pub fn new(method: String, path: String, expected: usize, actual: usize) -> Self {
+    pub fn new(method: String, path: String, expected: CallTimes, actual: usize) -> Self {
         Self {
             method,
             path,
             expected,
             actual,
-            success: expected == actual,
+            success: expected.accepts(actual),
         }
     }
 }
@@ -0,0 +1,290 @@
+mod schema;
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::import::ImportError;
+use crate::import::openapi::{METHODS, base_path, resolve};
+use crate::transform::IncomingRequest;
+
+/// Checks incoming requests against the operations of an OpenAPI 3 document
+///
+/// Validates path, query and header parameters and JSON request bodies.
+/// Requests for paths or methods the document does not describe are not
+/// judged, they are left to the expectations.
+#[derive(Clone, Debug)]
+pub struct OpenApiValidator {
+    document: Value,
+
+    operations: Vec<Operation>,
+}
+
+/// An operation with its path template compiled for matching
+#[derive(Clone, Debug)]
+struct Operation {
+    method: String,
+
+    /// Full path template including the server base path
+    path: String,
+
+    regex: Regex,
+
+    /// Names of the path parameters in the order of the regex groups
+    path_params: Vec<String>,
+
+    /// Resolved parameter objects, operation-level ones override path-level ones
+    parameters: Vec<Value>,
+
+    request_body: Option<Value>,
+}
+
+/// Why a request violates the document
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    /// The violated operation, e.g. `POST /pets/{petId}`
+    pub operation: String,
+
+    pub errors: Vec<String>,
+}
+
+impl OpenApiValidator {
+    pub fn new(document: Value) -> Result<Self, ImportError> {
+        let paths = document
+            .get("paths")
+            .and_then(Value::as_object)
+            .ok_or_else(|| ImportError::Invalid("missing 'paths' object".to_string()))?;
+
+        let base_path = base_path(&document);
+        let mut operations = Vec::new();
+        for (path, item) in paths {
+            let item = resolve(item, &document);
+            let shared = item.get("parameters").and_then(Value::as_array);
+
+            for method in METHODS {
+                let Some(operation) = item.get(method) else {
+                    continue;
+                };
+
+                let mut parameters: Vec<Value> = Vec::new();
+                let own = operation.get("parameters").and_then(Value::as_array);
+                for parameter in shared.into_iter().chain(own).flatten() {
+                    let parameter = resolve(parameter, &document).clone();
+                    parameters
+                        .retain(|p| p["name"] != parameter["name"] || p["in"] != parameter["in"]);
+                    parameters.push(parameter);
+                }
+
+                let full_path = format!("{}{}", base_path, path);
+                let (regex, path_params) = compile_template(&full_path)
+                    .map_err(|e| ImportError::Invalid(format!("invalid path {}: {}", path, e)))?;
+                operations.push(Operation {
+                    method: method.to_uppercase(),
+                    path: full_path,
+                    regex,
+                    path_params,
+                    parameters,
+                    request_body: operation
+                        .get("requestBody")
+                        .map(|body| resolve(body, &document).clone()),
+                });
+            }
+        }
+
+        // Literal segments win over templates, e.g. /pets/mine over /pets/{id}
+        operations.sort_by_key(|op| std::cmp::Reverse(literal_len(&op.path)));
+
+        Ok(Self {
+            document,
+            operations,
+        })
+    }
+
+    /// Reports the violations of `request`, `None` if it is valid or not described
+    pub fn validate(&self, request: &IncomingRequest) -> Option<ValidationReport> {
+        let (operation, captures) = self.operations.iter().find_map(|op| {
+            if op.method != request.method {
+                return None;
+            }
+            op.regex
+                .captures(&request.path)
+                .map(|captures| (op, captures))
+        })?;
+
+        let mut errors = Vec::new();
+        for parameter in &operation.parameters {
+            let (Some(name), Some(location)) = (
+                parameter.get("name").and_then(Value::as_str),
+                parameter.get("in").and_then(Value::as_str),
+            ) else {
+                continue;
+            };
+
+            let value = match location {
+                "path" => operation
+                    .path_params
+                    .iter()
+                    .position(|param| param == name)
+                    .and_then(|index| captures.get(index + 1))
+                    .map(|m| m.as_str()),
+                "query" => request.query_params.get(name).map(String::as_str),
+                "header" => request
+                    .headers
+                    .get(&name.to_lowercase())
+                    .map(String::as_str),
+                _ => continue,
+            };
+
+            let label = format!("{} parameter {}", location, name);
+            match value {
+                None => {
+                    let required = location == "path"
+                        || parameter.get("required").and_then(Value::as_bool) == Some(true);
+                    if required {
+                        errors.push(format!("{}: required", label));
+                    }
+                }
+                Some(raw) => {
+                    if let Some(schema) = parameter.get("schema") {
+                        let value = coerce(raw, resolve(schema, &self.document));
+                        schema::validate(schema, &value, &self.document, &label, &mut errors);
+                    }
+                }
+            }
+        }
+
+        if let Some(definition) = &operation.request_body {
+            self.validate_body(definition, request, &mut errors);
+        }
+
+        if errors.is_empty() {
+            None
+        } else {
+            Some(ValidationReport {
+                operation: format!("{} {}", operation.method, operation.path),
+                errors,
+            })
+        }
+    }
+
+    fn validate_body(
+        &self,
+        definition: &Value,
+        request: &IncomingRequest,
+        errors: &mut Vec<String>,
+    ) {
+        let body = request.body.as_deref().filter(|body| !body.is_empty());
+        let Some(body) = body else {
+            if definition.get("required").and_then(Value::as_bool) == Some(true) {
+                errors.push("body: required".to_string());
+            }
+            return;
+        };
+
+        let Some(content) = definition.get("content").and_then(Value::as_object) else {
+            return;
+        };
+
+        if let Some(content_type) = request.headers.get("content-type") {
+            let essence = content_type
+                .split(';')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase();
+            let accepted = content.keys().any(|media| media_matches(media, &essence));
+            if !accepted {
+                let declared: Vec<&str> = content.keys().map(String::as_str).collect();
+                errors.push(format!(
+                    "header parameter content-type: {} is not one of {}",
+                    essence,
+                    declared.join(", ")
+                ));
+                return;
+            }
+        }
+
+        let Some(schema) = content
+            .iter()
+            .find(|(media, _)| media.contains("json"))
+            .and_then(|(_, media)| media.get("schema"))
+        else {
+            return;
+        };
+
+        match serde_json::from_str::<Value>(body) {
+            Ok(value) => schema::validate(schema, &value, &self.document, "body", errors),
+            Err(e) => errors.push(format!("body: not valid JSON ({})", e)),
+        }
+    }
+}
+
+/// Compiles `/pets/{petId}` into a regex with one group per parameter
+fn compile_template(path: &str) -> Result<(Regex, Vec<String>), regex::Error> {
+    let mut pattern = String::from("^");
+    let mut names = Vec::new();
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+            break;
+        };
+        pattern.push_str(&regex::escape(&rest[..start]));
+        pattern.push_str("([^/]+)");
+        names.push(rest[start + 1..end].to_string());
+        rest = &rest[end + 1..];
+    }
+    pattern.push_str(&regex::escape(rest));
+    pattern.push('$');
+
+    Ok((Regex::new(&pattern)?, names))
+}
+
+fn literal_len(path: &str) -> usize {
+    let mut len = 0;
+    let mut in_param = false;
+    for c in path.chars() {
+        match c {
+            '{' => in_param = true,
+            '}' => in_param = false,
+            _ if !in_param => len += 1,
+            _ => {}
+        }
+    }
+    len
+}
+
+/// Turns a parameter string into the JSON type its schema asks for
+///
+/// Values that do not parse stay strings, so the type check reports them.
+fn coerce(raw: &str, schema: &Value) -> Value {
+    match schema.get("type").and_then(Value::as_str) {
+        Some("integer") => raw
+            .parse::<i64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| raw.into()),
+        Some("number") => raw
+            .parse::<f64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| raw.into()),
+        Some("boolean") => raw
+            .parse::<bool>()
+            .map(Value::from)
+            .unwrap_or_else(|_| raw.into()),
+        Some("array") => {
+            let items = schema.get("items").cloned().unwrap_or(Value::Null);
+            Value::Array(raw.split(',').map(|item| coerce(item, &items)).collect())
+        }
+        _ => Value::String(raw.to_string()),
+    }
+}
+
+/// Whether a declared media type such as `application/*` accepts `essence`
+fn media_matches(declared: &str, essence: &str) -> bool {
+    let declared = declared.to_lowercase();
+    match declared.split_once('/') {
+        _ if declared == "*/*" || declared == essence => true,
+        Some((kind, "*")) => essence.split('/').next() == Some(kind),
+        _ => false,
+    }
+}
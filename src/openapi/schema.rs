@@ -0,0 +1,248 @@
+use regex::Regex;
+use serde_json::Value;
+
+use crate::import::openapi::{resolve, schema_type};
+
+/// Maximum nesting followed while validating
+const MAX_DEPTH: usize = 32;
+
+/// Checks `value` against a JSON schema, adding one message per violation
+///
+/// Supports the keywords mock clients typically trip over: `type`,
+/// `nullable`, `enum`, `required`, `properties`, `additionalProperties`,
+/// `items`, length and range limits, `pattern` and the `allOf`/`anyOf`/`oneOf`
+/// combinators. `location` prefixes the messages, e.g. `body.items[0]`.
+pub(crate) fn validate(
+    schema: &Value,
+    value: &Value,
+    document: &Value,
+    location: &str,
+    errors: &mut Vec<String>,
+) {
+    validate_at(schema, value, document, location, errors, 0);
+}
+
+fn validate_at(
+    schema: &Value,
+    value: &Value,
+    document: &Value,
+    location: &str,
+    errors: &mut Vec<String>,
+    depth: usize,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let schema = resolve(schema, document);
+
+    if value.is_null() && is_nullable(schema) {
+        return;
+    }
+
+    if let Some(all_of) = schema.get("allOf").and_then(Value::as_array) {
+        for part in all_of {
+            validate_at(part, value, document, location, errors, depth + 1);
+        }
+    }
+    for key in ["anyOf", "oneOf"] {
+        if let Some(variants) = schema.get(key).and_then(Value::as_array) {
+            let matching = variants
+                .iter()
+                .filter(|variant| {
+                    let mut variant_errors = Vec::new();
+                    validate_at(
+                        variant,
+                        value,
+                        document,
+                        location,
+                        &mut variant_errors,
+                        depth + 1,
+                    );
+                    variant_errors.is_empty()
+                })
+                .count();
+            let accepted = if key == "oneOf" {
+                matching == 1
+            } else {
+                matching > 0
+            };
+            if !accepted {
+                errors.push(format!("{}: does not match {}", location, key));
+            }
+        }
+    }
+
+    if let Some(expected) = schema_type(schema)
+        && !has_type(value, expected)
+    {
+        errors.push(format!(
+            "{}: expected {}, got {}",
+            location,
+            expected,
+            type_name(value)
+        ));
+        return;
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        errors.push(format!(
+            "{}: {} is not one of {}",
+            location,
+            value,
+            Value::from(allowed.clone())
+        ));
+    }
+
+    match value {
+        Value::Object(object) => {
+            if let Some(required) = schema.get("required").and_then(Value::as_array) {
+                for name in required.iter().filter_map(Value::as_str) {
+                    if !object.contains_key(name) {
+                        errors.push(format!("{}.{}: required", location, name));
+                    }
+                }
+            }
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for (name, property) in object {
+                let property_location = format!("{}.{}", location, name);
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(property_schema) => validate_at(
+                        property_schema,
+                        property,
+                        document,
+                        &property_location,
+                        errors,
+                        depth + 1,
+                    ),
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            errors.push(format!("{}: not allowed", property_location));
+                        }
+                        Some(additional @ Value::Object(_)) => validate_at(
+                            additional,
+                            property,
+                            document,
+                            &property_location,
+                            errors,
+                            depth + 1,
+                        ),
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            check_limit(
+                schema,
+                "minItems",
+                items.len(),
+                location,
+                errors,
+                |min, len| len >= min,
+            );
+            check_limit(
+                schema,
+                "maxItems",
+                items.len(),
+                location,
+                errors,
+                |max, len| len <= max,
+            );
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    let item_location = format!("{}[{}]", location, index);
+                    validate_at(
+                        item_schema,
+                        item,
+                        document,
+                        &item_location,
+                        errors,
+                        depth + 1,
+                    );
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count();
+            check_limit(schema, "minLength", length, location, errors, |min, len| {
+                len >= min
+            });
+            check_limit(schema, "maxLength", length, location, errors, |max, len| {
+                len <= max
+            });
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str)
+                && let Ok(regex) = Regex::new(pattern)
+                && !regex.is_match(text)
+            {
+                errors.push(format!("{}: does not match {}", location, pattern));
+            }
+        }
+        Value::Number(number) => {
+            let Some(number) = number.as_f64() else {
+                return;
+            };
+            if let Some(minimum) = schema.get("minimum").and_then(Value::as_f64)
+                && number < minimum
+            {
+                errors.push(format!("{}: must be at least {}", location, minimum));
+            }
+            if let Some(maximum) = schema.get("maximum").and_then(Value::as_f64)
+                && number > maximum
+            {
+                errors.push(format!("{}: must be at most {}", location, maximum));
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Reports a violated `minLength`/`maxLength`/`minItems`/`maxItems`
+fn check_limit(
+    schema: &Value,
+    keyword: &str,
+    actual: usize,
+    location: &str,
+    errors: &mut Vec<String>,
+    accepts: impl Fn(usize, usize) -> bool,
+) {
+    if let Some(limit) = schema.get(keyword).and_then(Value::as_u64)
+        && !accepts(limit as usize, actual)
+    {
+        errors.push(format!("{}: violates {} {}", location, keyword, limit));
+    }
+}
+
+/// `nullable: true` (OpenAPI 3.0) or `null` in the type list (3.1)
+fn is_nullable(schema: &Value) -> bool {
+    schema.get("nullable").and_then(Value::as_bool) == Some(true)
+        || schema
+            .get("type")
+            .and_then(Value::as_array)
+            .is_some_and(|types| types.iter().any(|t| t == "null"))
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "integer" => value.is_i64() || value.is_u64(),
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Mutex;
+use std::time::Duration;
+
+use axum::body::Bytes;
+use axum::http::{HeaderMap, StatusCode};
+use reqwest::Client;
+use tracing::debug;
+
+use crate::import::{self, Import};
+use crate::models::MockResponse;
+use crate::transform::IncomingRequest;
+
+/// Forwards unmatched requests to a real backend and records the exchanges
+///
+/// Every forwarded request and its response is kept as an expectation, so
+/// the traffic can be written as mapping files with [`Import::write_to`] and
+/// replayed offline. A [`passthrough`](Self::passthrough) proxy forwards
+/// without recording.
+#[derive(Debug)]
+pub struct Proxy {
+    base_url: String,
+
+    client: Client,
+
+    recordings: Mutex<Import>,
+
+    /// Whether exchanges are kept as recordings
+    record: bool,
+}
+
+/// The upstream's answer to a forwarded request
+pub(crate) struct ProxiedResponse {
+    pub status: StatusCode,
+
+    pub headers: HeaderMap,
+
+    pub body: Bytes,
+}
+
+impl Proxy {
+    /// Creates a recording proxy to `base_url`, e.g. `https://api.example.com`
+    pub fn new(base_url: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            client,
+            recordings: Mutex::new(Import::default()),
+            record: true,
+        }
+    }
+
+    /// Creates a proxy to `base_url` that forwards without recording
+    ///
+    /// Responses, including redirects and compressed bodies, reach the
+    /// client as the backend sent them.
+    pub fn passthrough(base_url: &str) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(30))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            record: false,
+            ..Self::new(base_url)
+        }
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The exchanges recorded so far
+    pub fn recordings(&self) -> Import {
+        self.lock().clone()
+    }
+
+    pub fn clear_recordings(&self) {
+        *self.lock() = Import::default();
+    }
+
+    /// Sends `request` upstream and records the exchange
+    ///
+    /// `query` is the raw query string and `body` the raw request body, so
+    /// both reach the backend unchanged.
+    pub(crate) async fn forward(
+        &self,
+        request: &IncomingRequest,
+        query: Option<&str>,
+        body: Option<Bytes>,
+    ) -> Result<ProxiedResponse, reqwest::Error> {
+        let mut url = format!("{}{}", self.base_url, request.path);
+        if let Some(query) = query {
+            url.push('?');
+            url.push_str(query);
+        }
+        let method =
+            reqwest::Method::from_bytes(request.method.as_bytes()).unwrap_or(reqwest::Method::GET);
+
+        // Host is set from the base URL; the original one is kept as X-Forwarded-Host
+        let mut upstream = self.client.request(method, &url);
+        for (name, value) in &request.headers {
+            // A recording upstream has to answer uncompressed so the body can be kept
+            let skipped = name == "host" || (self.record && name == "accept-encoding");
+            if !skipped && !import::is_transfer_header(name) {
+                upstream = upstream.header(name, value);
+            }
+        }
+        if let Some(host) = request.headers.get("host")
+            && !request.headers.contains_key("x-forwarded-host")
+        {
+            upstream = upstream.header("X-Forwarded-Host", host);
+        }
+        if let Some(body) = body {
+            upstream = upstream.body(body);
+        }
+
+        debug!("Proxying {} {} to {}", request.method, request.path, url);
+        let response = upstream.send().await?;
+        let status = response.status();
+        let mut headers = HeaderMap::with_capacity(response.headers().len());
+        for (name, value) in response.headers() {
+            if !import::is_transfer_header(name.as_str()) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+        let body = response.bytes().await?;
+
+        if self.record {
+            self.record(request, status, &headers, &body);
+        }
+
+        Ok(ProxiedResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+
+    fn record(
+        &self,
+        request: &IncomingRequest,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) {
+        let mut response = MockResponse::new(status.as_u16());
+        for (name, value) in headers {
+            if let Ok(value) = value.to_str() {
+                response.headers.insert(name.to_string(), value.to_string());
+            }
+        }
+
+        let mut recordings = self.lock();
+        let content_type = headers
+            .get("content-type")
+            .and_then(|value| value.to_str().ok());
+        recordings.set_body(&mut response, body.to_vec(), content_type);
+
+        let mut expectation = import::expectation(&request.method, request.path.clone(), response);
+        expectation.query_params = request.query_params.clone();
+        expectation.body = request.body.clone();
+        recordings.expectations.push(expectation);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Import> {
+        self.recordings.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
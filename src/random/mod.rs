@@ -0,0 +1,88 @@
+//! The source of all randomness of a server
+//!
+//! Chaos mode, random response delays and the template data generators draw
+//! from the server's [`Random`]. Seeded with [`MockServer::with_seed`], the
+//! same requests sent in the same order get the same random answers, so tests
+//! relying on them are reproducible in CI.
+//!
+//! [`MockServer::with_seed`]: crate::MockServer::with_seed
+
+use rand::distr::uniform::{SampleRange, SampleUniform};
+use rand::rngs::StdRng;
+use rand::seq::IndexedRandom;
+use rand::{Rng, RngCore, SeedableRng};
+use std::sync::{Arc, Mutex};
+
+/// Random numbers, from the thread-local generator or a seeded one
+#[derive(Clone, Debug, Default)]
+pub struct Random {
+    seeded: Option<Arc<Mutex<Seeded>>>,
+}
+
+#[derive(Debug)]
+struct Seeded {
+    seed: u64,
+
+    rng: StdRng,
+}
+
+impl Random {
+    /// A generator producing the same sequence for the same `seed`
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seeded: Some(Arc::new(Mutex::new(Seeded {
+                seed,
+                rng: StdRng::seed_from_u64(seed),
+            }))),
+        }
+    }
+
+    /// The seed, `None` if the values are not reproducible
+    pub fn seed(&self) -> Option<u64> {
+        self.seeded
+            .as_ref()
+            .map(|seeded| seeded.lock().unwrap_or_else(|e| e.into_inner()).seed)
+    }
+
+    /// Starts the sequence of a seeded generator over
+    pub fn restart(&self) {
+        if let Some(seeded) = &self.seeded {
+            let mut seeded = seeded.lock().unwrap_or_else(|e| e.into_inner());
+            seeded.rng = StdRng::seed_from_u64(seeded.seed);
+        }
+    }
+
+    /// Runs `f` with the generator, e.g. in a `respond_with` closure
+    pub fn with_rng<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        match &self.seeded {
+            Some(seeded) => f(&mut seeded.lock().unwrap_or_else(|e| e.into_inner()).rng),
+            None => f(&mut rand::rng()),
+        }
+    }
+
+    /// A value in `range`, e.g. `1..=100`
+    pub fn range<T, R>(&self, range: R) -> T
+    where
+        T: SampleUniform,
+        R: SampleRange<T>,
+    {
+        self.with_rng(|rng| rng.random_range(range))
+    }
+
+    /// `true` with the given probability
+    pub fn chance(&self, probability: f64) -> bool {
+        self.with_rng(|rng| rng.random::<f64>() < probability)
+    }
+
+    /// A random element of `items`, `None` if it is empty
+    pub fn pick<'a, T>(&self, items: &'a [T]) -> Option<&'a T> {
+        self.with_rng(|rng| items.choose(rng))
+    }
+
+    /// A random UUID v4
+    pub fn uuid(&self) -> uuid::Uuid {
+        let mut bytes = [0; 16];
+        self.with_rng(|rng| rng.fill_bytes(&mut bytes));
+        uuid::Builder::from_random_bytes(bytes).into_uuid()
+    }
+}
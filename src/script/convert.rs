@@ -0,0 +1,108 @@
+//! Conversions between requests, responses and the JSON scripts exchange
+
+use serde_json::{Map, Value};
+
+use super::ScriptError;
+use crate::models::MockResponse;
+use crate::template::TemplateContext;
+
+/// The request as scripts see it
+pub(super) fn request_value(context: &TemplateContext) -> Value {
+    let request = context.request;
+    let json = request
+        .body
+        .as_deref()
+        .and_then(|body| serde_json::from_str(body).ok())
+        .unwrap_or(Value::Null);
+    serde_json::json!({
+        "method": request.method,
+        "path": request.path,
+        "query": request.query_params,
+        "headers": request.headers,
+        "body": request.body,
+        "json": json,
+        "received_at": request.received_at.to_rfc3339(),
+    })
+}
+
+/// Reads the value a script returned as a response
+pub(super) fn into_response(value: Value) -> Result<MockResponse, ScriptError> {
+    let Value::Object(mut map) = value else {
+        return Ok(with_body(MockResponse::new(200), value));
+    };
+
+    let status = match map.remove("status") {
+        None => 200,
+        Some(status) => status
+            .as_u64()
+            .and_then(|status| u16::try_from(status).ok())
+            .filter(|status| (100..1000).contains(status))
+            .ok_or_else(|| ScriptError::InvalidResult(format!("status {}", status)))?,
+    };
+    let mut response = MockResponse::new(status);
+
+    match map.remove("headers") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(headers)) => {
+            for (name, value) in headers {
+                let value = match value {
+                    Value::String(value) => value,
+                    other => other.to_string(),
+                };
+                response.headers.insert(name, value);
+            }
+        }
+        Some(other) => {
+            return Err(ScriptError::InvalidResult(format!(
+                "headers must be a map, got {}",
+                other
+            )));
+        }
+    }
+
+    let body = map.remove("body").unwrap_or(Value::Null);
+    if let Some(key) = map.keys().next() {
+        return Err(ScriptError::InvalidResult(format!(
+            "unexpected key '{}', expected status, headers and body",
+            key
+        )));
+    }
+    Ok(with_body(response, body))
+}
+
+/// Sends strings as text and other values as JSON
+fn with_body(mut response: MockResponse, body: Value) -> MockResponse {
+    match body {
+        Value::Null => {}
+        Value::String(text) => {
+            if response.content_type().is_none() {
+                response.headers.insert(
+                    "Content-Type".to_string(),
+                    "text/plain; charset=utf-8".to_string(),
+                );
+            }
+            response.cached_file_content = Some(text);
+        }
+        json => response.body = Some(json),
+    }
+    response
+}
+
+/// Keys the script set or changed, with `None` for removed ones
+pub(super) fn state_changes(
+    before: &Map<String, Value>,
+    after: Map<String, Value>,
+) -> Vec<(String, Option<Value>)> {
+    let mut changes: Vec<(String, Option<Value>)> = before
+        .keys()
+        .filter(|key| !after.contains_key(*key))
+        .map(|key| (key.clone(), None))
+        .collect();
+    changes.extend(
+        after
+            .into_iter()
+            .filter(|(key, value)| before.get(key) != Some(value))
+            .map(|(key, value)| (key, Some(value))),
+    );
+    changes
+}
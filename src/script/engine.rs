@@ -0,0 +1,115 @@
+use rhai::serde::{from_dynamic, to_dynamic};
+use rhai::{AST, Dynamic, Engine, Scope};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use super::convert::{into_response, request_value, state_changes};
+use super::{ScriptError, ScriptOutcome};
+use crate::template::TemplateContext;
+
+/// Upper bound on the operations a script may run, against endless loops
+const MAX_OPERATIONS: u64 = 1_000_000;
+
+/// Fails if `source` is not a valid script
+pub(crate) fn check(source: &str) -> Result<(), ScriptError> {
+    engine()
+        .compile(source)
+        .map(|_| ())
+        .map_err(|e| ScriptError::Compile(e.to_string()))
+}
+
+/// Compiles scripts once and runs them off the async workers
+pub(crate) struct RhaiScripts {
+    engine: Arc<Engine>,
+
+    /// Compiled scripts by source, shared by all expectations using them
+    compiled: Mutex<HashMap<String, Arc<AST>>>,
+}
+
+impl RhaiScripts {
+    pub(crate) fn new() -> Self {
+        Self {
+            engine: Arc::new(engine()),
+            compiled: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Runs `source` against the request of `context`
+    ///
+    /// Returns the response and the changes the script made to `state`, which
+    /// the caller applies to the store only when the script succeeded.
+    pub(crate) async fn run(
+        &self,
+        source: &str,
+        context: &TemplateContext<'_>,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        let ast = self.compile(source)?;
+        let engine = self.engine.clone();
+        let request = request_value(context);
+        let path = serde_json::to_value(context.path_params).unwrap_or_default();
+        let state = context.state.clone();
+
+        // Up to MAX_OPERATIONS of work, too much for an async worker
+        tokio::task::spawn_blocking(move || {
+            let mut scope = Scope::new();
+            scope.push_constant_dynamic("request", variable(request)?);
+            scope.push_constant_dynamic("path", variable(path)?);
+            scope.push_dynamic("state", variable(Value::Object(state.clone()))?);
+
+            let result = engine
+                .eval_ast_with_scope::<Dynamic>(&mut scope, &ast)
+                .map_err(|e| ScriptError::Runtime(e.to_string()))?;
+            let value: Value =
+                from_dynamic(&result).map_err(|e| ScriptError::InvalidResult(e.to_string()))?;
+            let updated = scope
+                .get("state")
+                .map(from_dynamic::<Value>)
+                .transpose()
+                .map_err(|e| ScriptError::InvalidResult(format!("state: {}", e)))?;
+            let updated = match updated {
+                Some(Value::Object(updated)) => updated,
+                Some(other) => {
+                    return Err(ScriptError::InvalidResult(format!(
+                        "state must stay a map, got {}",
+                        other
+                    )));
+                }
+                None => state.clone(),
+            };
+
+            Ok(ScriptOutcome {
+                response: into_response(value)?,
+                state_changes: state_changes(&state, updated),
+            })
+        })
+        .await
+        .map_err(|e| ScriptError::Runtime(e.to_string()))?
+    }
+
+    fn compile(&self, source: &str) -> Result<Arc<AST>, ScriptError> {
+        let mut compiled = self.compiled.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(ast) = compiled.get(source) {
+            return Ok(ast.clone());
+        }
+        let ast = Arc::new(
+            self.engine
+                .compile(source)
+                .map_err(|e| ScriptError::Compile(e.to_string()))?,
+        );
+        compiled.insert(source.to_string(), ast.clone());
+        Ok(ast)
+    }
+}
+
+fn engine() -> Engine {
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.on_print(|text| tracing::info!("script: {}", text));
+    engine.on_debug(|text, _, _| tracing::debug!("script: {}", text));
+    engine
+}
+
+fn variable(value: Value) -> Result<Dynamic, ScriptError> {
+    to_dynamic(value).map_err(|e| ScriptError::Runtime(e.to_string()))
+}
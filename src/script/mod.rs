@@ -0,0 +1,129 @@
+//! Responses computed by embedded Rhai scripts
+//!
+//! A script sees the incoming request as `request` (`method`, `path`, `query`,
+//! `headers`, `body`, `json`, the body parsed as JSON, and `received_at` by the
+//! server's clock in RFC 3339), the captured path parameters as `path` and the
+//! shared state as `state`. Changes to `state` are written back to the store
+//! once the script succeeded. It returns a map with `status`, `headers` and
+//! `body`; any other value becomes the body of a `200` response. String bodies
+//! are sent as text, everything else as JSON.
+//!
+//! ```rhai
+//! state.orders = (state.orders ?? 0) + 1;
+//! if request.json.amount > 1000 {
+//!     #{ status: 402, body: #{ error: "limit exceeded" } }
+//! } else {
+//!     #{ status: 201, headers: #{ "X-Order": path.id }, body: #{ id: path.id } }
+//! }
+//! ```
+//!
+//! Scripts need the `scripting` feature, which is enabled by default. The
+//! `wasm` feature, also on by default, runs sandboxed WebAssembly modules
+//! instead, see [`wasm`](self::wasm) for what they exchange.
+
+use serde_json::Value;
+use std::path::Path;
+use thiserror::Error;
+
+use crate::models::MockResponse;
+use crate::template::TemplateContext;
+
+#[cfg(any(feature = "scripting", feature = "wasm"))]
+mod convert;
+#[cfg(feature = "scripting")]
+mod engine;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "scripting")]
+pub(crate) use engine::check;
+
+/// What a script produced for one request
+#[derive(Debug)]
+pub(crate) struct ScriptOutcome {
+    pub response: MockResponse,
+
+    /// State keys the script set, `None` for removed ones
+    pub state_changes: Vec<(String, Option<Value>)>,
+}
+
+#[derive(Debug, Error)]
+pub(crate) enum ScriptError {
+    #[cfg(not(all(feature = "scripting", feature = "wasm")))]
+    #[error("not available, mimic-rs was built without the `{0}` feature")]
+    Disabled(&'static str),
+
+    #[cfg(feature = "wasm")]
+    #[error("module cannot be read: {0}")]
+    Load(std::io::Error),
+
+    #[cfg(any(feature = "scripting", feature = "wasm"))]
+    #[error("script does not compile: {0}")]
+    Compile(String),
+
+    #[cfg(any(feature = "scripting", feature = "wasm"))]
+    #[error("script failed: {0}")]
+    Runtime(String),
+
+    #[cfg(any(feature = "scripting", feature = "wasm"))]
+    #[error("script returned an invalid response: {0}")]
+    InvalidResult(String),
+}
+
+/// Fails if `source` is not a valid script
+#[cfg(not(feature = "scripting"))]
+pub(crate) fn check(_source: &str) -> Result<(), ScriptError> {
+    Err(ScriptError::Disabled("scripting"))
+}
+
+/// Runs Rhai scripts and WebAssembly modules, as far as they are built in
+pub(crate) struct ScriptRunner {
+    #[cfg(feature = "scripting")]
+    rhai: engine::RhaiScripts,
+
+    #[cfg(feature = "wasm")]
+    wasm: wasm::WasmModules,
+}
+
+impl ScriptRunner {
+    pub(crate) fn new() -> Self {
+        Self {
+            #[cfg(feature = "scripting")]
+            rhai: engine::RhaiScripts::new(),
+            #[cfg(feature = "wasm")]
+            wasm: wasm::WasmModules::new(),
+        }
+    }
+
+    /// Runs the Rhai `source` against the request of `context`
+    pub(crate) async fn run(
+        &self,
+        source: &str,
+        context: &TemplateContext<'_>,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        #[cfg(feature = "scripting")]
+        return self.rhai.run(source, context).await;
+
+        #[cfg(not(feature = "scripting"))]
+        {
+            let _ = (source, context);
+            Err(ScriptError::Disabled("scripting"))
+        }
+    }
+
+    /// Runs the WebAssembly module in `file` against the request of `context`
+    pub(crate) async fn run_wasm(
+        &self,
+        file: &Path,
+        context: &TemplateContext<'_>,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        #[cfg(feature = "wasm")]
+        return self.wasm.run(file, context).await;
+
+        #[cfg(not(feature = "wasm"))]
+        {
+            let _ = (file, context);
+            Err(ScriptError::Disabled("wasm"))
+        }
+    }
+}
@@ -0,0 +1,380 @@
+//! Responses computed by sandboxed WebAssembly modules
+//!
+//! A module is a WASI command: its `_start` reads one JSON document from
+//! stdin and writes the response to stdout. Only the WASI calls for stdio,
+//! arguments, environment, clock and randomness are provided, so a module
+//! cannot reach files or the network. Fuel and memory are limited against
+//! runaway modules.
+
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use wasmi::{
+    Caller, Config, Engine, Error, Extern, Linker, Memory, Module, Store, StoreLimits,
+    StoreLimitsBuilder, TrapCode,
+};
+
+use super::convert::{into_response, request_value, state_changes};
+use super::{ScriptError, ScriptOutcome};
+use crate::random::Random;
+use crate::template::TemplateContext;
+
+/// Fuel for one run, roughly the number of executed instructions
+const MAX_FUEL: u64 = 10_000_000;
+
+/// Upper bound on the linear memory of a module
+const MAX_MEMORY: usize = 64 * 1024 * 1024;
+
+/// Upper bound on what a module may write to stdout or stderr
+const MAX_OUTPUT: usize = 10 * 1024 * 1024;
+
+const WASI: &str = "wasi_snapshot_preview1";
+
+// WASI errno values
+const SUCCESS: i32 = 0;
+const EBADF: i32 = 8;
+const EFAULT: i32 = 21;
+const EFBIG: i32 = 22;
+const EINVAL: i32 = 28;
+const ESPIPE: i32 = 70;
+
+/// Compiled modules by path, with the modification time they were read at
+type CompiledModules = Mutex<HashMap<PathBuf, (Option<SystemTime>, Module)>>;
+
+/// Compiles modules once per file version and runs them off the async workers
+pub(crate) struct WasmModules {
+    engine: Engine,
+
+    compiled: Arc<CompiledModules>,
+}
+
+/// What a module sees through WASI
+struct Wasi {
+    stdin: Vec<u8>,
+
+    /// Read position in `stdin`
+    consumed: usize,
+
+    stdout: Vec<u8>,
+
+    stderr: Vec<u8>,
+
+    /// Request time by the server's clock, in nanoseconds since the epoch
+    now: u64,
+
+    random: Random,
+
+    limits: StoreLimits,
+}
+
+impl WasmModules {
+    pub(crate) fn new() -> Self {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        Self {
+            engine: Engine::new(&config),
+            compiled: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Runs the module in `file` against the request of `context`
+    ///
+    /// The module reads `{"request": .., "path": .., "state": ..}` and writes
+    /// a response like a Rhai script returns, optionally with the new `state`.
+    pub(crate) async fn run(
+        &self,
+        file: &Path,
+        context: &TemplateContext<'_>,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        let input = json!({
+            "request": request_value(context),
+            "path": context.path_params,
+            "state": context.state,
+        });
+        let state = context.state.clone();
+        let wasi = Wasi {
+            stdin: input.to_string().into_bytes(),
+            consumed: 0,
+            stdout: Vec::new(),
+            stderr: Vec::new(),
+            now: context
+                .request
+                .received_at
+                .timestamp_nanos_opt()
+                .unwrap_or_default()
+                .max(0) as u64,
+            random: context.random.clone(),
+            limits: StoreLimitsBuilder::new().memory_size(MAX_MEMORY).build(),
+        };
+        let engine = self.engine.clone();
+        let compiled = self.compiled.clone();
+        let file = file.to_path_buf();
+
+        // Compiling and running may take long, too long for an async worker
+        tokio::task::spawn_blocking(move || {
+            let module = load(&engine, &compiled, &file)?;
+            let output = execute(&engine, &module, wasi)?;
+            let value: Value = serde_json::from_slice(&output).map_err(|e| {
+                ScriptError::InvalidResult(format!("module output is not JSON: {}", e))
+            })?;
+            outcome(value, &state)
+        })
+        .await
+        .map_err(|e| ScriptError::Runtime(e.to_string()))?
+    }
+}
+
+/// The compiled module in `file`, compiled again when the file changed
+fn load(engine: &Engine, compiled: &CompiledModules, file: &Path) -> Result<Module, ScriptError> {
+    let modified = std::fs::metadata(file)
+        .map_err(ScriptError::Load)?
+        .modified()
+        .ok();
+    if let Some((when, module)) = compiled.lock().unwrap_or_else(|e| e.into_inner()).get(file)
+        && *when == modified
+    {
+        return Ok(module.clone());
+    }
+
+    let bytes = std::fs::read(file).map_err(ScriptError::Load)?;
+    let module = Module::new(engine, bytes).map_err(|e| ScriptError::Compile(e.to_string()))?;
+    compiled
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(file.to_path_buf(), (modified, module.clone()));
+    Ok(module)
+}
+
+/// Runs the `_start` function of `module` and returns what it wrote to stdout
+fn execute(engine: &Engine, module: &Module, wasi: Wasi) -> Result<Vec<u8>, ScriptError> {
+    let mut store = Store::new(engine, wasi);
+    store.limiter(|wasi| &mut wasi.limits);
+    store
+        .set_fuel(MAX_FUEL)
+        .map_err(|e| ScriptError::Runtime(e.to_string()))?;
+
+    let linker = linker(engine).map_err(|e| ScriptError::Runtime(e.to_string()))?;
+    let result = linker
+        .instantiate_and_start(&mut store, module)
+        .and_then(|instance| instance.get_typed_func::<(), ()>(&store, "_start"))
+        .and_then(|start| start.call(&mut store, ()));
+
+    let stderr = String::from_utf8_lossy(&store.data().stderr);
+    for line in stderr.lines() {
+        tracing::info!("wasm: {}", line);
+    }
+
+    match result {
+        Ok(()) => {}
+        Err(e) if e.i32_exit_status() == Some(0) => {}
+        Err(e) => return Err(ScriptError::Runtime(failure(&e))),
+    }
+    Ok(store.into_data().stdout)
+}
+
+fn failure(error: &Error) -> String {
+    if let Some(status) = error.i32_exit_status() {
+        return format!("module exited with status {}", status);
+    }
+    match error.as_trap_code() {
+        Some(TrapCode::OutOfFuel) => "module ran out of fuel".to_string(),
+        _ => error.to_string(),
+    }
+}
+
+/// Splits the module output into the response and the state changes
+fn outcome(value: Value, state: &Map<String, Value>) -> Result<ScriptOutcome, ScriptError> {
+    let (value, updated) = match value {
+        Value::Object(mut map) => {
+            let updated = map.remove("state");
+            (Value::Object(map), updated)
+        }
+        other => (other, None),
+    };
+    let state_changes = match updated {
+        None => Vec::new(),
+        Some(Value::Object(updated)) => state_changes(state, updated),
+        Some(other) => {
+            return Err(ScriptError::InvalidResult(format!(
+                "state must be a map, got {}",
+                other
+            )));
+        }
+    };
+    Ok(ScriptOutcome {
+        response: into_response(value)?,
+        state_changes,
+    })
+}
+
+/// The WASI calls a module may import
+fn linker(engine: &Engine) -> Result<Linker<Wasi>, wasmi::Error> {
+    let mut linker = Linker::new(engine);
+    linker
+        .func_wrap(WASI, "fd_read", fd_read)?
+        .func_wrap(WASI, "fd_write", fd_write)?
+        .func_wrap(
+            WASI,
+            "proc_exit",
+            |_: Caller<'_, Wasi>, status: i32| -> Result<(), Error> {
+                Err(Error::i32_exit(status))
+            },
+        )?
+        .func_wrap(WASI, "fd_close", |_: Caller<'_, Wasi>, _: i32| SUCCESS)?
+        .func_wrap(WASI, "fd_fdstat_get", fd_fdstat_get)?
+        .func_wrap(
+            WASI,
+            "fd_seek",
+            |_: Caller<'_, Wasi>, _: i32, _: i64, _: i32, _: i32| ESPIPE,
+        )?
+        // No preopened directories
+        .func_wrap(
+            WASI,
+            "fd_prestat_get",
+            |_: Caller<'_, Wasi>, _: i32, _: i32| EBADF,
+        )?
+        .func_wrap(
+            WASI,
+            "fd_prestat_dir_name",
+            |_: Caller<'_, Wasi>, _: i32, _: i32, _: i32| EBADF,
+        )?
+        // No arguments and no environment
+        .func_wrap(WASI, "args_sizes_get", empty_sizes)?
+        .func_wrap(WASI, "environ_sizes_get", empty_sizes)?
+        .func_wrap(WASI, "args_get", |_: Caller<'_, Wasi>, _: i32, _: i32| {
+            SUCCESS
+        })?
+        .func_wrap(
+            WASI,
+            "environ_get",
+            |_: Caller<'_, Wasi>, _: i32, _: i32| SUCCESS,
+        )?
+        .func_wrap(WASI, "clock_time_get", clock_time_get)?
+        .func_wrap(WASI, "random_get", random_get)?
+        .func_wrap(WASI, "sched_yield", |_: Caller<'_, Wasi>| SUCCESS)?;
+    Ok(linker)
+}
+
+fn memory(caller: &Caller<'_, Wasi>) -> Result<Memory, i32> {
+    caller
+        .get_export("memory")
+        .and_then(Extern::into_memory)
+        .ok_or(EINVAL)
+}
+
+/// `len` bytes of `data` at `offset`
+fn slice(data: &mut [u8], offset: u32, len: u32) -> Result<&mut [u8], i32> {
+    let start = offset as usize;
+    let end = start.checked_add(len as usize).ok_or(EFAULT)?;
+    data.get_mut(start..end).ok_or(EFAULT)
+}
+
+fn read_u32(data: &mut [u8], offset: u32) -> Result<u32, i32> {
+    let bytes = slice(data, offset, 4)?;
+    Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn write_bytes(data: &mut [u8], offset: u32, bytes: &[u8]) -> Result<(), i32> {
+    slice(data, offset, bytes.len() as u32)?.copy_from_slice(bytes);
+    Ok(())
+}
+
+/// The `(buffer, length)` pairs of an iovec array
+fn iovecs(data: &mut [u8], iovs: u32, count: u32) -> Result<Vec<(u32, u32)>, i32> {
+    (0..count)
+        .map(|index| {
+            let iov = iovs.checked_add(index.checked_mul(8).ok_or(EFAULT)?);
+            let iov = iov.ok_or(EFAULT)?;
+            Ok((read_u32(data, iov)?, read_u32(data, iov.wrapping_add(4))?))
+        })
+        .collect()
+}
+
+fn errno(result: Result<(), i32>) -> i32 {
+    result.err().unwrap_or(SUCCESS)
+}
+
+fn fd_read(mut caller: Caller<'_, Wasi>, fd: i32, iovs: i32, count: i32, nread: i32) -> i32 {
+    errno((|| {
+        if fd != 0 {
+            return Err(EBADF);
+        }
+        let memory = memory(&caller)?;
+        let (data, wasi) = memory.data_and_store_mut(&mut caller);
+        let mut read = 0u32;
+        for (buf, len) in iovecs(data, iovs as u32, count as u32)? {
+            let remaining = &wasi.stdin[wasi.consumed..];
+            let chunk = &remaining[..remaining.len().min(len as usize)];
+            write_bytes(data, buf, chunk)?;
+            wasi.consumed += chunk.len();
+            read += chunk.len() as u32;
+        }
+        write_bytes(data, nread as u32, &read.to_le_bytes())
+    })())
+}
+
+fn fd_write(mut caller: Caller<'_, Wasi>, fd: i32, iovs: i32, count: i32, nwritten: i32) -> i32 {
+    errno((|| {
+        let memory = memory(&caller)?;
+        let (data, wasi) = memory.data_and_store_mut(&mut caller);
+        let output = match fd {
+            1 => &mut wasi.stdout,
+            2 => &mut wasi.stderr,
+            _ => return Err(EBADF),
+        };
+        let mut written = 0u32;
+        for (buf, len) in iovecs(data, iovs as u32, count as u32)? {
+            let bytes = slice(data, buf, len)?;
+            if output.len() + bytes.len() > MAX_OUTPUT {
+                return Err(EFBIG);
+            }
+            output.extend_from_slice(bytes);
+            written += len;
+        }
+        write_bytes(data, nwritten as u32, &written.to_le_bytes())
+    })())
+}
+
+fn fd_fdstat_get(mut caller: Caller<'_, Wasi>, fd: i32, stat: i32) -> i32 {
+    errno((|| {
+        if !(0..=2).contains(&fd) {
+            return Err(EBADF);
+        }
+        let memory = memory(&caller)?;
+        let data = memory.data_mut(&mut caller);
+        // Character device, no flags, all rights
+        let mut fdstat = [0u8; 24];
+        fdstat[0] = 2;
+        fdstat[8..24].fill(0xff);
+        write_bytes(data, stat as u32, &fdstat)
+    })())
+}
+
+fn empty_sizes(mut caller: Caller<'_, Wasi>, count: i32, size: i32) -> i32 {
+    errno((|| {
+        let memory = memory(&caller)?;
+        let data = memory.data_mut(&mut caller);
+        write_bytes(data, count as u32, &0u32.to_le_bytes())?;
+        write_bytes(data, size as u32, &0u32.to_le_bytes())
+    })())
+}
+
+fn clock_time_get(mut caller: Caller<'_, Wasi>, _clock: i32, _precision: i64, time: i32) -> i32 {
+    errno((|| {
+        let memory = memory(&caller)?;
+        let (data, wasi) = memory.data_and_store_mut(&mut caller);
+        write_bytes(data, time as u32, &wasi.now.to_le_bytes())
+    })())
+}
+
+fn random_get(mut caller: Caller<'_, Wasi>, buf: i32, len: i32) -> i32 {
+    errno((|| {
+        let memory = memory(&caller)?;
+        let (data, wasi) = memory.data_and_store_mut(&mut caller);
+        let target = slice(data, buf as u32, len as u32)?;
+        wasi.random.with_rng(|rng| rng.fill_bytes(target));
+        Ok(())
+    })())
+}
@@ -0,0 +1,38 @@
+use std::io;
+use std::path::Path;
+use tokio::fs;
+use uuid::Uuid;
+
+use crate::models::CapturedUpload;
+use crate::template::sha256_hex;
+
+/// Whether a body should go to the capture directory instead of the log
+///
+/// Multipart and octet-stream bodies are captured, as is anything that is
+/// not valid UTF-8.
+pub(crate) fn is_upload(content_type: Option<&str>, bytes: &[u8]) -> bool {
+    let is_binary_type = content_type.is_some_and(|content_type| {
+        let content_type = content_type.to_lowercase();
+        content_type.starts_with("multipart/")
+            || content_type.starts_with("application/octet-stream")
+    });
+    !bytes.is_empty() && (is_binary_type || std::str::from_utf8(bytes).is_err())
+}
+
+/// Writes `bytes` to a new file in `dir`
+pub(crate) async fn save(
+    dir: &Path,
+    content_type: Option<&str>,
+    bytes: &[u8],
+) -> io::Result<CapturedUpload> {
+    fs::create_dir_all(dir).await?;
+    let path = dir.join(format!("{}.bin", Uuid::new_v4()));
+    fs::write(&path, bytes).await?;
+
+    Ok(CapturedUpload {
+        path,
+        sha256: sha256_hex(bytes),
+        size: bytes.len() as u64,
+        content_type: content_type.map(String::from),
+    })
+}
@@ -0,0 +1,148 @@
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use thiserror::Error;
+
+use crate::mappings::MappingError;
+use crate::models::{CallTimes, RequestRecord};
+use crate::tls::TlsError;
+
+/// Problems that keep the server from starting
+#[derive(Debug, Error)]
+pub enum StartupError {
+    #[error("{} response file(s) cannot be loaded:\n{}", .0.len(), list(.0))]
+    Fixtures(Vec<FixtureError>),
+
+    #[error("failed to bind {addr}: {source}")]
+    Bind { addr: SocketAddr, source: io::Error },
+
+    #[error("invalid TLS configuration: {0}")]
+    Tls(#[from] TlsError),
+
+    #[error("failed to load mappings: {0}")]
+    Mappings(#[from] MappingError),
+}
+
+/// A response file referenced by an expectation that cannot be read
+#[derive(Debug)]
+pub struct FixtureError {
+    pub method: String,
+
+    pub path: String,
+
+    pub file: PathBuf,
+
+    pub source: io::Error,
+}
+
+impl fmt::Display for FixtureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: {}: {}",
+            self.method,
+            self.path,
+            self.file.display(),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for FixtureError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+/// An assertion on the recorded requests that does not hold
+#[derive(Debug, Error)]
+pub enum VerificationError {
+    #[error("expected no {method} {path} requests, got {}:\n{}", .requests.len(), list_requests(.requests))]
+    UnwantedRequests {
+        method: String,
+        path: String,
+        requests: Vec<RequestRecord>,
+    },
+
+    #[error("{} request(s) outside the allow-list:\n{}", .0.len(), list_requests(.0))]
+    UnexpectedRequests(Vec<RequestRecord>),
+
+    #[error("expected {expected} {method} {path} request(s), got {}:\n{}", .requests.len(), list_requests(.requests))]
+    CallCount {
+        method: String,
+        path: String,
+        expected: usize,
+        requests: Vec<RequestRecord>,
+    },
+
+    #[error("expected {expected} {method} {path} request(s), got {}:\n{}", .requests.len(), list_requests(.requests))]
+    CallRange {
+        method: String,
+        path: String,
+        expected: CallTimes,
+        requests: Vec<RequestRecord>,
+    },
+
+    #[error("expected {method} {path} (step {step} of the sequence) after the previous steps, matched {}:\n{}", .matched.len(), list_requests(.matched))]
+    OutOfOrder {
+        /// 1-based position of the first step without a matching request
+        step: usize,
+        method: String,
+        path: String,
+        /// The requests that satisfied the steps before it
+        matched: Vec<RequestRecord>,
+    },
+
+    #[error("expected {method} {path} requests within {window:?}, they spanned {elapsed:?}:\n{}", list_requests(.requests))]
+    OutsideWindow {
+        method: String,
+        path: String,
+        window: Duration,
+        elapsed: Duration,
+        requests: Vec<RequestRecord>,
+    },
+
+    #[error(
+        "expected {method} {path} ({id}) to answer {expected} request(s), it answered {actual}"
+    )]
+    Hits {
+        id: String,
+        method: String,
+        path: String,
+        expected: usize,
+        actual: usize,
+    },
+
+    #[error("expected {method} {path} requests at least {min_gap:?} apart, two were {gap:?} apart:\n{}", list_requests(.requests))]
+    TooClose {
+        method: String,
+        path: String,
+        min_gap: Duration,
+        gap: Duration,
+        requests: Vec<RequestRecord>,
+    },
+}
+
+/// Every failed check of `verify_all`
+#[derive(Debug, Error)]
+#[error("{} unmet expectation(s):\n{}", .0.len(), list(.0))]
+pub struct VerificationErrors(pub Vec<VerificationError>);
+
+fn list_requests(requests: &[RequestRecord]) -> String {
+    requests
+        .iter()
+        .map(|r| format!("  {} {} at {}", r.method, r.path, r.timestamp))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn list(errors: &[impl fmt::Display]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("  {}", e))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,7 +1,23 @@
-use super::MockServer;
+use super::{Mock, MockServer};
 use crate::ConditionalResponse;
-use crate::models::{MockExpectation, MockResponse};
+use crate::auth::JwtRequirement;
+use crate::conditional::{CustomMatcher, Matcher, ReceivedRequest, Responder};
+use crate::cors::CorsPolicy;
+use crate::extension::ExtensionRef;
+use crate::models::{
+    BodyVariant, Callback, ExpectContinue, Fault, GraphqlMatch, GrpcWebResponse, JsonBodyMatch,
+    LanguageVariant, MockExpectation, MockResponse, Multipart, Pagination, RateLimit,
+    ResponseDelay, ResponseScript, ScenarioRule, SequenceEnd, SseStream, StreamBody, ValueMatcher,
+    content_type_for,
+};
+use crate::state::StateUpdate;
+use crate::transform::ResponseTransform;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
 use serde_json::Value;
+use std::future::Future;
+use std::time::Duration;
+use tracing::warn;
 
 /// Builder for defining expectations
 pub struct ExpectationBuilder {
@@ -61,6 +77,58 @@ impl ExpectationBuilder {
         self
     }
 
+    /// Requires a query parameter matching a regex, e.g. `^[0-9]+$`
+    ///
+    /// The regex may match anywhere in the value unless it is anchored.
+    pub fn query_param_matches(self, key: &str, pattern: &str) -> Self {
+        self.query_matcher(key, ValueMatcher::matches_regex(pattern))
+    }
+
+    /// Only matches requests without the query parameter
+    pub fn query_param_missing(self, key: &str) -> Self {
+        self.query_matcher(key, ValueMatcher::absent())
+    }
+
+    /// Requires a query parameter accepted by `matcher`
+    pub fn query_matcher(mut self, key: &str, matcher: ValueMatcher) -> Self {
+        warn_if_invalid(&matcher);
+        self.expectation
+            .query_matchers
+            .insert(key.to_string(), matcher);
+        self
+    }
+
+    /// Only matches requests without the query parameter, same as `query_param_missing`
+    pub fn without_query_param(self, key: &str) -> Self {
+        self.query_param_missing(key)
+    }
+
+    /// Requires a header matching a regex, e.g. `^Bearer .+`
+    ///
+    /// The regex may match anywhere in the value unless it is anchored.
+    pub fn header_matches(self, key: &str, pattern: &str) -> Self {
+        self.header_matcher(key, ValueMatcher::matches_regex(pattern))
+    }
+
+    /// Requires the header with any value
+    pub fn header_exists(self, key: &str) -> Self {
+        self.header_matcher(key, ValueMatcher::present())
+    }
+
+    /// Only matches requests without the header, e.g. unauthenticated ones
+    pub fn without_header(self, key: &str) -> Self {
+        self.header_matcher(key, ValueMatcher::absent())
+    }
+
+    /// Requires a header accepted by `matcher`
+    pub fn header_matcher(mut self, key: &str, matcher: ValueMatcher) -> Self {
+        warn_if_invalid(&matcher);
+        self.expectation
+            .header_matchers
+            .insert(key.to_lowercase(), matcher);
+        self
+    }
+
     /// Sets the expected request body
     ///
     /// # Arguments
@@ -70,21 +138,400 @@ impl ExpectationBuilder {
         self
     }
 
+    /// Sets the expected binary body, compared byte for byte
+    ///
+    /// Matches bodies that are not UTF-8, such as protobuf messages or images.
+    pub fn body_bytes(mut self, body: impl AsRef<[u8]>) -> Self {
+        self.expectation.body_base64 = Some(STANDARD.encode(body));
+        self
+    }
+
+    /// Answers cross-origin requests for this endpoint according to `policy`
+    ///
+    /// Works with or without `MockServer::with_cors` and wins over its
+    /// policies: preflights for the expectation's method and path are
+    /// answered, and its responses get the `Access-Control-*` headers.
+    pub fn cors(mut self, policy: CorsPolicy) -> Self {
+        self.expectation.cors = Some(policy);
+        self
+    }
+
+    /// Only matches GraphQL requests for the operation `name`
+    ///
+    /// Compared with `operationName`, or with the name of the first operation
+    /// in the query if the request does not send one.
+    pub fn graphql_operation(mut self, name: &str) -> Self {
+        self.graphql().operation_name = Some(name.to_string());
+        self
+    }
+
+    /// Only matches GraphQL requests whose query contains `fragment`
+    ///
+    /// Whitespace and commas are ignored, so `user(id: 1) { name }` matches
+    /// however a client formats the query.
+    pub fn graphql_query_contains(mut self, fragment: &str) -> Self {
+        self.graphql().query_contains.push(fragment.to_string());
+        self
+    }
+
+    /// Only matches GraphQL requests carrying `variables`; extra variables are fine
+    pub fn graphql_variables(mut self, variables: Value) -> Self {
+        self.graphql().variables = Some(variables);
+        self
+    }
+
+    fn graphql(&mut self) -> &mut GraphqlMatch {
+        self.expectation
+            .graphql
+            .get_or_insert_with(Default::default)
+    }
+
+    /// Only matches requests whose body does not contain `text`
+    ///
+    /// Requests without a body match too.
+    pub fn body_not_contains(self, text: &str) -> Self {
+        self.body_matcher(!ValueMatcher::contains(text))
+    }
+
+    /// Requires the body to be accepted by `matcher`
+    pub fn body_matcher(mut self, matcher: ValueMatcher) -> Self {
+        warn_if_invalid(&matcher);
+        self.expectation.body_matchers.push(matcher);
+        self
+    }
+
+    /// Sets the expected JSON body, ignoring key order and whitespace
+    ///
+    /// # Arguments
+    /// * `body` - The JSON value the request body has to equal
+    pub fn body_json(mut self, body: Value) -> Self {
+        self.expectation.json_body = Some(JsonBodyMatch::Equals(body));
+        self
+    }
+
+    /// Requires the JSON body to contain the given subset
+    ///
+    /// Objects in the request may carry extra keys and arrays extra elements.
+    ///
+    /// # Arguments
+    /// * `body` - The JSON value the request body has to contain
+    pub fn body_json_partial(mut self, body: Value) -> Self {
+        self.expectation.json_body = Some(JsonBodyMatch::Contains(body));
+        self
+    }
+
+    /// Sets the priority among expectations matching the same request
+    ///
+    /// Higher values are tried first; expectations without a priority count
+    /// as 0. Among equal priorities the most specific expectation wins.
+    ///
+    /// # Arguments
+    /// * `priority` - The priority of the expectation
+    pub fn priority(mut self, priority: u32) -> Self {
+        self.expectation.priority = Some(priority);
+        self
+    }
+
+    /// Stops matching after `times` matches
+    ///
+    /// Later requests fall through to the next matching expectation or 404.
+    /// `MockServer::verify_all` reports the expectation until it answered `times` requests.
+    ///
+    /// # Arguments
+    /// * `times` - How often the expectation answers
+    pub fn times(mut self, times: usize) -> Self {
+        self.expectation.times = Some(times);
+        self
+    }
+
+    /// Answers a single request, e.g. for one-time tokens
+    pub fn once(self) -> Self {
+        self.times(1)
+    }
+
+    /// Requires a valid bearer token
+    ///
+    /// Requests without an acceptable token are rejected with 401 or 403.
+    ///
+    /// # Arguments
+    /// * `requirement` - How the token is verified and which claims it needs
+    pub fn jwt(mut self, requirement: JwtRequirement) -> Self {
+        self.expectation.jwt = Some(requirement);
+        self
+    }
+
+    /// Limits how many requests the expectation answers per time window
+    ///
+    /// Further requests get 429 with `Retry-After` until the window frees up.
+    /// All responses carry `X-RateLimit-Limit`, `-Remaining` and `-Reset` headers.
+    ///
+    /// # Arguments
+    /// * `limit` - Number of requests allowed per window
+    /// * `per` - Length of the sliding window, to the millisecond
+    pub fn rate_limit(mut self, limit: u32, per: Duration) -> Self {
+        self.expectation.rate_limit = Some(RateLimit::new(limit, per));
+        self
+    }
+
+    /// Stores a value in the server state whenever the expectation matches
+    ///
+    /// String values may contain templates such as `"{{request.body}}"`.
+    ///
+    /// # Arguments
+    /// * `key` - The state key
+    /// * `value` - The value to store
+    pub fn set_state(mut self, key: &str, value: Value) -> Self {
+        self.expectation.state_updates.push(StateUpdate::Set {
+            key: key.to_string(),
+            value,
+        });
+        self
+    }
+
+    /// Increments a numeric state value by one whenever the expectation matches
+    ///
+    /// # Arguments
+    /// * `key` - The state key
+    pub fn increment_state(mut self, key: &str) -> Self {
+        self.expectation.state_updates.push(StateUpdate::Increment {
+            key: key.to_string(),
+            by: 1,
+        });
+        self
+    }
+
+    /// Removes a state value whenever the expectation matches
+    ///
+    /// # Arguments
+    /// * `key` - The state key
+    pub fn remove_state(mut self, key: &str) -> Self {
+        self.expectation.state_updates.push(StateUpdate::Remove {
+            key: key.to_string(),
+        });
+        self
+    }
+
+    /// Sends a request after the expectation matched, e.g. to simulate a webhook
+    ///
+    /// The URL and the body may contain templates such as
+    /// `"{{request.body.order_id}}"`. A string body is sent as is, anything
+    /// else as JSON. The response is not delayed by the callback.
+    ///
+    /// # Arguments
+    /// * `url` - Where the callback is sent
+    /// * `method` - The HTTP method, e.g. `"POST"`
+    /// * `body_template` - The body, `None` for an empty one
+    /// * `delay` - Wait before the callback is sent
+    pub fn then_callback(
+        mut self,
+        url: &str,
+        method: &str,
+        body_template: Option<Value>,
+        delay: Duration,
+    ) -> Self {
+        self.expectation
+            .callbacks
+            .push(Callback::new(url, method, body_template, delay));
+        self
+    }
+
+    /// Additionally requires a registered extension to accept the request
+    ///
+    /// # Arguments
+    /// * `name` - Name the extension was registered under
+    /// * `config` - Passed to the matcher, `Value::Null` if unused
+    pub fn matcher(mut self, name: &str, config: Value) -> Self {
+        self.expectation
+            .matchers
+            .push(ExtensionRef::new(name, config));
+        self
+    }
+
+    /// Additionally requires `matcher` to accept the request
+    ///
+    /// Takes a [`Matcher`] or a closure over the [`ReceivedRequest`], for
+    /// logic the built-in conditions cannot express. Custom matchers are not
+    /// serialized, so exported mappings do not contain them.
+    ///
+    /// # Example
+    /// ```
+    /// # use mimic_rs::{MockServer, ReceivedRequest};
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let server = MockServer::new("./resources");
+    ///
+    /// server.expect()
+    ///     .path("/webhooks")
+    ///     .method("POST")
+    ///     .matches(|request: &ReceivedRequest| {
+    ///         request
+    ///             .header("X-Signature")
+    ///             .is_some_and(|signature| signature.starts_with("sha256="))
+    ///     })
+    ///     .respond()
+    ///     .status(204)
+    ///     .build().await;
+    /// # }
+    /// ```
+    pub fn matches(mut self, matcher: impl Matcher + 'static) -> Self {
+        self.expectation
+            .custom_matchers
+            .push(CustomMatcher::new(matcher));
+        self
+    }
+
+    /// Controls the answer to `Expect: 100-continue` requests
+    ///
+    /// Only the method, path, query parameters and headers are considered
+    /// when picking the expectation, as the body has not been sent yet.
+    ///
+    /// # Arguments
+    /// * `mode` - Send `100 Continue`, delay it, or reject with 417
+    pub fn expect_continue(mut self, mode: ExpectContinue) -> Self {
+        self.expectation.expect_continue = Some(mode);
+        self
+    }
+
+    /// Matches a unary gRPC(-Web) call to `service`/`method`
+    ///
+    /// Sets the method to POST and the path to `/{service}/{method}`.
+    ///
+    /// # Arguments
+    /// * `service` - Fully qualified service name, e.g. `helloworld.Greeter`
+    /// * `method` - The RPC name, e.g. `SayHello`
+    pub fn grpc(self, service: &str, method: &str) -> Self {
+        self.method("POST")
+            .path(&format!("/{}/{}", service, method))
+    }
+
+    /// Makes the expectation part of a scenario
+    ///
+    /// Every scenario starts in `"Started"`; see [`MockServer::scenario`] to
+    /// inspect or choose its state.
+    ///
+    /// # Arguments
+    /// * `name` - The scenario name, shared by all its expectations
+    pub fn in_scenario(mut self, name: &str) -> Self {
+        self.expectation.scenario = Some(ScenarioRule::new(name));
+        self
+    }
+
+    /// Only matches while the scenario is in `state`
+    ///
+    /// Has no effect on expectations outside a scenario.
+    ///
+    /// # Arguments
+    /// * `state` - The required scenario state
+    pub fn in_state(mut self, state: &str) -> Self {
+        match &mut self.expectation.scenario {
+            Some(rule) => rule.required_state = Some(state.to_string()),
+            None => warn!(
+                "in_state(\"{}\") ignored: expectation is not in a scenario",
+                state
+            ),
+        }
+        self
+    }
+
+    /// Only matches while the scenario is in `state`, same as [`Self::in_state`]
+    ///
+    /// # Arguments
+    /// * `state` - The required scenario state
+    pub fn when_state(self, state: &str) -> Self {
+        self.in_state(state)
+    }
+
+    /// Moves the scenario to `state` after responding
+    ///
+    /// Has no effect on expectations outside a scenario.
+    ///
+    /// # Arguments
+    /// * `state` - The next scenario state
+    pub fn will_set_state(mut self, state: &str) -> Self {
+        match &mut self.expectation.scenario {
+            Some(rule) => rule.new_state = Some(state.to_string()),
+            None => warn!(
+                "will_set_state(\"{}\") ignored: expectation is not in a scenario",
+                state
+            ),
+        }
+        self
+    }
+
+    /// Adds the expectation to the server
+    async fn register(self) -> Mock {
+        let exp = &self.expectation;
+        let mock = Mock::new(self.server.clone(), &exp.id, &exp.method, &exp.path);
+        self.server.add_expectation(self.expectation).await;
+        mock
+    }
+
     /// Starts defining the response
     pub fn respond(self) -> ResponseBuilder {
         ResponseBuilder::new(self)
     }
+
+    /// Starts defining responses returned one after another
+    ///
+    /// After the last one the sequence starts over unless
+    /// [`SequenceBuilder::when_exhausted`] says otherwise.
+    pub fn respond_sequence(self) -> SequenceBuilder {
+        SequenceBuilder {
+            expectation_builder: self,
+        }
+    }
+}
+
+/// Builder for an ordered list of responses, e.g. first 201, then 409
+pub struct SequenceBuilder {
+    expectation_builder: ExpectationBuilder,
+}
+
+impl SequenceBuilder {
+    /// Appends the next response of the sequence
+    pub fn then(mut self, response: MockResponse) -> Self {
+        self.expectation_builder.expectation.rotate.push(response);
+        self
+    }
+
+    /// Chooses what happens after the last response
+    ///
+    /// # Arguments
+    /// * `end` - Start over, repeat the last response or stop matching
+    pub fn when_exhausted(mut self, end: SequenceEnd) -> Self {
+        self.expectation_builder.expectation.rotate_end = end;
+        self
+    }
+
+    /// Completes the expectation definition and adds it to the server
+    ///
+    /// The returned [`Mock`] can verify and remove the expectation.
+    pub async fn build(self) -> Mock {
+        if self.expectation_builder.expectation.rotate.is_empty() {
+            warn!("respond_sequence() without responses: the default response is used");
+        }
+        self.expectation_builder.register().await
+    }
 }
 
 /// Builder for defining responses
 pub struct ResponseBuilder {
     expectation_builder: ExpectationBuilder,
+
+    /// Registered together with the expectation in `build()`
+    responder: Option<(String, Responder)>,
+
+    /// Registered together with the expectation in `build()`
+    conditional: Option<(String, ConditionalResponse)>,
 }
 
 impl ResponseBuilder {
     fn new(expectation_builder: ExpectationBuilder) -> Self {
         Self {
             expectation_builder,
+            responder: None,
+            conditional: None,
         }
     }
 
@@ -137,6 +584,20 @@ impl ResponseBuilder {
         self
     }
 
+    /// Sends binary content as the response body
+    ///
+    /// Served as `application/octet-stream` unless a `Content-Type` header
+    /// is set, e.g. `.header("Content-Type", "image/png")`.
+    ///
+    /// # Arguments
+    /// * `body` - The bytes to send, e.g. an image or a serialized protobuf
+    pub fn body_bytes(mut self, body: impl AsRef<[u8]>) -> Self {
+        let response = &mut self.expectation_builder.expectation.response;
+        response.body = None;
+        response.body_base64 = Some(STANDARD.encode(body));
+        self
+    }
+
     /// Sets the path to a JSON file as the response body
     ///
     /// # Arguments
@@ -147,33 +608,412 @@ impl ResponseBuilder {
         self
     }
 
+    /// Serves any file from the resources directory as the response body
+    ///
+    /// The file is read as bytes and cached, the `Content-Type` is inferred
+    /// from its extension unless already set. UTF-8 files are rendered for
+    /// placeholders like other body files.
+    ///
+    /// # Arguments
+    /// * `file_path` - The relative path to the file in the resources directory, e.g. `report.pdf`
+    pub fn file(mut self, file_path: &str) -> Self {
+        let response = &mut self.expectation_builder.expectation.response;
+        *response = std::mem::take(response).with_file(file_path);
+        self
+    }
+
+    /// Serves a text file as the response body
+    ///
+    /// Like [`file`](Self::file), but files with an unknown extension are sent
+    /// as `text/plain` instead of `application/octet-stream`.
+    ///
+    /// # Arguments
+    /// * `file_path` - The relative path to the text file in the resources directory
+    pub fn text_file(mut self, file_path: &str) -> Self {
+        let response = &mut self.expectation_builder.expectation.response;
+        if response.content_type().is_none() && content_type_for(file_path).contains("octet") {
+            response.headers.insert(
+                "Content-Type".to_string(),
+                "text/plain; charset=utf-8".to_string(),
+            );
+        }
+        self.file(file_path)
+    }
+
+    /// Sets the path to an XML file as the response body
+    ///
+    /// Placeholders in the file are rendered per request; use `xmlEscape`
+    /// for values that may contain markup and `xmlElements` to turn JSON
+    /// data into elements.
+    ///
+    /// # Arguments
+    /// * `file_path` - The relative path to the XML file in the resources directory
+    pub fn xml_file(mut self, file_path: &str) -> Self {
+        self.expectation_builder.expectation.response.body_file = Some(file_path.to_string());
+        self.expectation_builder
+            .expectation
+            .response
+            .headers
+            .insert("Content-Type".to_string(), "application/xml".to_string());
+        self
+    }
+
+    /// Answers with a `multipart/mixed` or `multipart/related` body
+    ///
+    /// # Arguments
+    /// * `multipart` - The parts, each with its own headers and body
+    pub fn multipart(mut self, multipart: Multipart) -> Self {
+        self.expectation_builder.expectation.response.multipart = Some(multipart);
+        self
+    }
+
+    /// Serves a JSON array as paged responses
+    ///
+    /// Honors `page`/`limit` or `cursor` query parameters and wraps each page
+    /// with `total`, `total_pages`, `next_page` and `next_cursor` metadata.
+    ///
+    /// # Arguments
+    /// * `dataset` - The JSON array to page over
+    /// * `page_size` - Items per page when the request has no `limit`
+    pub fn paginate(mut self, dataset: Value, page_size: usize) -> Self {
+        self.expectation_builder.expectation.response.paginate =
+            Some(Pagination::new(dataset, page_size));
+        self.ensure_content_type();
+        self
+    }
+
+    /// Adds a body variant chosen by the request's `Accept` header
+    ///
+    /// Without an `Accept` header the first variant is served; when no
+    /// variant is acceptable the server answers 406.
+    ///
+    /// # Arguments
+    /// * `media_type` - A media type or a short name (`json`, `xml`, `csv`)
+    /// * `body` - Strings are sent verbatim, other values as JSON
+    pub fn variant(mut self, media_type: &str, body: impl Into<Value>) -> Self {
+        self.expectation_builder
+            .expectation
+            .response
+            .variants
+            .push(BodyVariant::new(media_type, body));
+        self
+    }
+
+    /// Adds a localized body chosen by the request's `Accept-Language` header
+    ///
+    /// Requests without an acceptable language get the default body.
+    ///
+    /// # Arguments
+    /// * `language` - A language tag such as `de` or `pt-BR`
+    /// * `body` - The localized JSON body
+    pub fn localized(mut self, language: &str, body: Value) -> Self {
+        self.expectation_builder
+            .expectation
+            .response
+            .languages
+            .push(LanguageVariant::body(language, body));
+        self.ensure_content_type();
+        self
+    }
+
+    /// Adds a localized body loaded from a file in the resource directory
+    ///
+    /// # Arguments
+    /// * `language` - A language tag such as `de` or `pt-BR`
+    /// * `file_path` - Path relative to the resource directory
+    pub fn localized_file(mut self, language: &str, file_path: &str) -> Self {
+        self.expectation_builder
+            .expectation
+            .response
+            .languages
+            .push(LanguageVariant::file(language, file_path));
+        self.ensure_content_type();
+        self
+    }
+
+    /// Answers with a gRPC-Web message
+    ///
+    /// The framing (binary or base64 `grpc-web-text`) follows the request's
+    /// `Content-Type`.
+    ///
+    /// # Arguments
+    /// * `message` - The serialized protobuf response message
+    pub fn grpc_web(mut self, message: &[u8]) -> Self {
+        self.expectation_builder.expectation.response.grpc_web = Some(GrpcWebResponse::ok(message));
+        self
+    }
+
+    /// Answers a gRPC-Web call with a non-OK status and no message
+    ///
+    /// # Arguments
+    /// * `status` - The gRPC status code, e.g. 5 for NOT_FOUND
+    /// * `message` - The `grpc-message` trailer, percent-encoded when sent
+    pub fn grpc_web_error(mut self, status: u32, message: &str) -> Self {
+        self.expectation_builder.expectation.response.grpc_web =
+            Some(GrpcWebResponse::error(status, message));
+        self
+    }
+
+    /// Streams the body as chunked transfer encoding
+    ///
+    /// The first chunk is sent immediately, the others after `inter_chunk_delay`
+    /// each. Placeholders in the chunks are not rendered.
+    ///
+    /// # Arguments
+    /// * `chunks` - The body parts in order, e.g. NDJSON lines
+    /// * `inter_chunk_delay` - Pause between two chunks
+    pub fn stream_body<I, S>(mut self, chunks: I, inter_chunk_delay: Duration) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.expectation_builder.expectation.response.stream =
+            Some(StreamBody::new(chunks, inter_chunk_delay));
+        self
+    }
+
+    /// Answers with a stream of server-sent events
+    ///
+    /// Sets `Content-Type: text/event-stream` and keeps the connection open
+    /// after the last event unless the script closes or repeats it.
+    ///
+    /// # Arguments
+    /// * `sse` - The events with their delays
+    pub fn sse(mut self, sse: SseStream) -> Self {
+        self.expectation_builder.expectation.response.sse = Some(sse);
+        self
+    }
+
+    /// Holds the response for `delay` before sending it
+    ///
+    /// # Arguments
+    /// * `delay` - How long to wait once the expectation matched
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.expectation_builder.expectation.response.delay = Some(ResponseDelay::fixed(delay));
+        self
+    }
+
+    /// Holds the response for a random duration before sending it
+    ///
+    /// # Arguments
+    /// * `min` - The shortest delay
+    /// * `max` - The longest delay
+    pub fn random_delay(mut self, min: Duration, max: Duration) -> Self {
+        self.expectation_builder.expectation.response.delay =
+            Some(ResponseDelay::between(min, max));
+        self
+    }
+
+    /// Breaks the connection instead of sending the response
+    ///
+    /// # Arguments
+    /// * `fault` - Reset the connection, close it silently or send a malformed chunk
+    pub fn fault(mut self, fault: Fault) -> Self {
+        self.expectation_builder.expectation.response.fault = Some(fault);
+        self
+    }
+
+    /// Lets a registered extension produce the response
+    ///
+    /// # Arguments
+    /// * `name` - Name the extension was registered under
+    /// * `config` - Passed to the generator, `Value::Null` if unused
+    pub fn generate(mut self, name: &str, config: Value) -> Self {
+        self.expectation_builder.expectation.response.generator =
+            Some(ExtensionRef::new(name, config));
+        self
+    }
+
+    /// Lets a Rhai script produce the response from the request
+    ///
+    /// The script sees `request`, `path` and `state` and returns a map with
+    /// `status`, `headers` and `body`. Headers set on this builder are kept
+    /// unless the script sets them too.
+    ///
+    /// # Arguments
+    /// * `source` - The script, e.g. `#{ status: 201, body: #{ id: path.id } }`
+    pub fn script(mut self, source: &str) -> Self {
+        self.expectation_builder.expectation.response.script =
+            Some(ResponseScript::Source(source.to_string()));
+        self
+    }
+
+    /// Lets a Rhai script file produce the response, read on every request
+    ///
+    /// # Arguments
+    /// * `file_path` - Path relative to the resource directory
+    pub fn script_file(mut self, file_path: &str) -> Self {
+        self.expectation_builder.expectation.response.script =
+            Some(ResponseScript::File(file_path.to_string()));
+        self
+    }
+
+    /// Lets a sandboxed WebAssembly module produce the response
+    ///
+    /// The module is a WASI command reading the request as JSON from stdin
+    /// and writing the response to stdout. It is compiled again when the
+    /// file changes.
+    ///
+    /// # Arguments
+    /// * `file_path` - A `.wasm` or `.wat` file relative to the resource directory
+    pub fn wasm_module(mut self, file_path: &str) -> Self {
+        self.expectation_builder.expectation.response.script =
+            Some(ResponseScript::Wasm(file_path.to_string()));
+        self
+    }
+
+    /// Post-processes the response before it is sent
+    ///
+    /// Runs before the server-wide response transformations. Streamed, SSE
+    /// and gRPC-Web responses only get the header transformations.
+    ///
+    /// # Arguments
+    /// * `transform` - E.g. a header to add or an envelope to wrap the body in
+    pub fn transform(mut self, transform: ResponseTransform) -> Self {
+        self.expectation_builder
+            .expectation
+            .response_transforms
+            .push(transform);
+        self
+    }
+
+    /// Cycles through `responses` on successive matching calls
+    ///
+    /// After the last response the rotation starts over with the first one.
+    /// Replaces the response defined with the other methods.
+    ///
+    /// # Arguments
+    /// * `responses` - The responses in the order they are returned
+    pub fn rotate<I>(mut self, responses: I) -> Self
+    where
+        I: IntoIterator<Item = MockResponse>,
+    {
+        self.expectation_builder.expectation.rotate = responses.into_iter().collect();
+        self
+    }
+
+    /// Moves the scenario to `state` after this response was sent
+    ///
+    /// Has no effect on expectations outside a scenario.
+    ///
+    /// # Arguments
+    /// * `state` - The next scenario state
+    pub fn will_transition_to(mut self, state: &str) -> Self {
+        match &mut self.expectation_builder.expectation.scenario {
+            Some(rule) => rule.new_state = Some(state.to_string()),
+            None => warn!(
+                "will_transition_to(\"{}\") ignored: expectation is not in a scenario",
+                state
+            ),
+        }
+        self
+    }
+
     /// Completes the expectation definition and adds it to the server
-    pub async fn build(self) {
-        let server = self.expectation_builder.server.clone();
-        let expectation = self.expectation_builder.expectation;
+    ///
+    /// The returned [`Mock`] can verify and remove the expectation.
+    pub async fn build(self) -> Mock {
+        let server = &self.expectation_builder.server;
+        if let Some((id, conditional)) = self.conditional {
+            server.add_conditional_response(id, conditional).await;
+        }
+        if let Some((id, responder)) = self.responder {
+            server.add_responder(id, responder).await;
+        }
+        self.expectation_builder.register().await
+    }
+
+    /// Builds every response with `handler` from the request it answers
+    ///
+    /// The closure sees the method, path, path parameters, query, headers
+    /// and body, e.g. to echo an id back without one expectation per id.
+    /// Its response replaces the one defined on this builder; delays,
+    /// faults and transformations of the returned response still apply.
+    ///
+    /// # Example
+    /// ```
+    /// # use mimic_rs::{MockResponse, MockServer};
+    /// # use serde_json::json;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let server = MockServer::new("./resources");
+    ///
+    /// server.expect()
+    ///     .path("/api/users/{id}")
+    ///     .respond()
+    ///     .respond_with(|request| {
+    ///         MockResponse::new(200).with_json_body(json!({"id": request.path_param("id")}))
+    ///     })
+    ///     .build().await;
+    /// # }
+    /// ```
+    pub fn respond_with<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ReceivedRequest) -> MockResponse + Send + Sync + 'static,
+    {
+        let responder_id = format!("responder_{}", uuid::Uuid::new_v4());
+        self.expectation_builder.expectation.response.responder_id = Some(responder_id.clone());
+        self.responder = Some((responder_id, Responder::new(handler)));
+        self
+    }
 
-        server.add_expectation(expectation).await;
+    /// Like [`respond_with`](Self::respond_with), with a closure returning a future
+    ///
+    /// Lets the response depend on async work such as a database query,
+    /// `tokio::fs` or a call to another service. The request is passed by
+    /// value so the future can keep it.
+    ///
+    /// # Example
+    /// ```
+    /// # use mimic_rs::{MockResponse, MockServer};
+    /// # use serde_json::json;
+    /// #
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let server = MockServer::new("./resources");
+    ///
+    /// server.expect()
+    ///     .path("/api/reports/{name}")
+    ///     .respond()
+    ///     .respond_with_async(|request| async move {
+    ///         let name = request.path_param("name").unwrap_or_default().to_string();
+    ///         match tokio::fs::read_to_string(format!("./reports/{}.csv", name)).await {
+    ///             Ok(csv) => MockResponse::new(200).with_json_body(json!({"csv": csv})),
+    ///             Err(_) => MockResponse::new(404),
+    ///         }
+    ///     })
+    ///     .build().await;
+    /// # }
+    /// ```
+    pub fn respond_with_async<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(ReceivedRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = MockResponse> + Send + 'static,
+    {
+        let responder_id = format!("responder_{}", uuid::Uuid::new_v4());
+        self.expectation_builder.expectation.response.responder_id = Some(responder_id.clone());
+        self.responder = Some((responder_id, Responder::new_async(handler)));
+        self
     }
 
     /// Adds a conditional response to the expectation
+    ///
+    /// The handler is registered by `build()`, before the expectation can match.
     pub fn conditional<F>(mut self, handler: F) -> Self
     where
         F: Fn(usize) -> MockResponse + Send + Sync + 'static,
     {
         let conditional_id = format!("cond_{}", uuid::Uuid::new_v4());
-
         self.expectation_builder.expectation.response.conditional_id = Some(conditional_id.clone());
-
-        let conditional = ConditionalResponse::new(handler);
-
-        let server = self.expectation_builder.server.clone();
-        let cond_id = conditional_id.clone();
-
-        // Spawn task to add the conditional response
-        tokio::spawn(async move {
-            server.add_conditional_response(cond_id, conditional).await;
-        });
-
+        self.conditional = Some((conditional_id, ConditionalResponse::new(handler)));
         self
     }
 }
+
+/// An invalid regex or JSONPath never matches, which is easy to miss in a test
+fn warn_if_invalid(matcher: &ValueMatcher) {
+    if let Err(e) = matcher.validate() {
+        warn!("Matcher never matches: {}", e);
+    }
+}
@@ -0,0 +1,49 @@
+use super::MockServer;
+
+/// Panics when dropped while expectations built with `times` are unmet
+///
+/// Created by [`MockServer::verify_on_drop`].
+///
+/// # Example
+/// ```should_panic
+/// # use mimic_rs::MockServer;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let server = MockServer::new("./resources");
+/// let _guard = server.verify_on_drop();
+///
+/// server.expect().path("/api/ping").once().respond().build().await;
+///
+/// // The test ends without calling /api/ping, dropping the guard panics
+/// # }
+/// ```
+#[must_use = "the guard verifies when it is dropped"]
+pub struct VerifyOnDrop {
+    server: MockServer,
+}
+
+impl VerifyOnDrop {
+    pub(super) fn new(server: MockServer) -> Self {
+        Self { server }
+    }
+}
+
+impl Drop for VerifyOnDrop {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let Some(unmet) = self.server.try_unmet_expectations() else {
+            return;
+        };
+        if !unmet.is_empty() {
+            let messages: Vec<String> = unmet.iter().map(|e| format!("  {}", e)).collect();
+            panic!(
+                "{} unmet expectation(s):\n{}",
+                unmet.len(),
+                messages.join("\n")
+            );
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::net::{Ipv4Addr, SocketAddr};
+
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+/// A server running in the background, returned by
+/// [`MockServer::start_background`](super::MockServer::start_background),
+/// [`MockServer::start_random_port`](super::MockServer::start_random_port) and
+/// [`MockServer::start_tls_background`](super::MockServer::start_tls_background)
+///
+/// # Example
+/// ```
+/// # use mimic_rs::MockServer;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let server = MockServer::new("./resources");
+/// let running = server.start_random_port().await.unwrap();
+///
+/// let url = running.url("/api/users");
+/// assert_eq!(url, format!("http://127.0.0.1:{}/api/users", running.port()));
+///
+/// running.shutdown().await;
+/// # }
+/// ```
+pub struct ServerHandle {
+    address: SocketAddr,
+
+    /// `http`, or `https` for a TLS server
+    scheme: &'static str,
+
+    stop: oneshot::Sender<()>,
+
+    task: JoinHandle<()>,
+}
+
+impl ServerHandle {
+    pub(crate) fn new(
+        port: u16,
+        scheme: &'static str,
+        stop: oneshot::Sender<()>,
+        task: JoinHandle<()>,
+    ) -> Self {
+        Self {
+            address: SocketAddr::from((Ipv4Addr::LOCALHOST, port)),
+            scheme,
+            stop,
+            task,
+        }
+    }
+
+    /// The port the server listens on
+    pub fn port(&self) -> u16 {
+        self.address.port()
+    }
+
+    /// Loopback address clients can connect to
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// Base URL of the server, e.g. `http://127.0.0.1:49152`
+    pub fn base_url(&self) -> String {
+        format!("{}://{}", self.scheme, self.address)
+    }
+
+    /// Absolute URL for `path`
+    pub fn url(&self, path: &str) -> String {
+        if path.starts_with('/') {
+            format!("{}{}", self.base_url(), path)
+        } else {
+            format!("{}/{}", self.base_url(), path)
+        }
+    }
+
+    /// Whether the server stopped, e.g. after a termination signal
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+
+    /// Stops the server gracefully and waits until it is down
+    ///
+    /// In-flight requests get the drain timeout to complete. Dropping the
+    /// handle instead leaves the server running.
+    pub async fn shutdown(self) {
+        // Fails if the server already stopped on its own
+        let _ = self.stop.send(());
+        if let Err(e) = self.task.await {
+            tracing::error!("MockServer task failed: {}", e);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+use axum::body::{Body, Bytes, HttpBody};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::Response;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::transform::IncomingRequest;
+
+/// Header added to responses replayed for a repeated idempotency key
+const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Responses served for idempotency keys, keyed by method, path and key
+#[derive(Debug)]
+pub(crate) struct IdempotencyStore {
+    /// Lowercase name of the header carrying the key
+    header: String,
+
+    responses: Mutex<HashMap<IdempotencyKey, StoredResponse>>,
+}
+
+/// Marks a response whose connection gets broken, it is never stored
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct InjectedFault;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct IdempotencyKey {
+    method: String,
+
+    path: String,
+
+    key: String,
+}
+
+#[derive(Debug)]
+struct StoredResponse {
+    status: StatusCode,
+
+    headers: HeaderMap,
+
+    body: Bytes,
+}
+
+impl IdempotencyStore {
+    pub(crate) fn new(header: &str) -> Self {
+        Self {
+            header: header.to_lowercase(),
+            responses: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The key `request` carries for its endpoint, if any
+    pub(crate) fn key(&self, request: &IncomingRequest) -> Option<IdempotencyKey> {
+        let key = request.headers.get(&self.header)?;
+        Some(IdempotencyKey {
+            method: request.method.clone(),
+            path: request.path.clone(),
+            key: key.clone(),
+        })
+    }
+
+    /// A copy of the response stored for `key`, marked as replayed
+    pub(crate) async fn replay(&self, key: &IdempotencyKey) -> Option<Response> {
+        let responses = self.responses.lock().await;
+        let stored = responses.get(key)?;
+        let mut response = Response::new(Body::from(stored.body.clone()));
+        *response.status_mut() = stored.status;
+        *response.headers_mut() = stored.headers.clone();
+        response
+            .headers_mut()
+            .insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+        Some(response)
+    }
+
+    /// Buffers `response`, stores it for `key` and returns it unchanged
+    ///
+    /// Streamed bodies of unknown size and injected faults pass through
+    /// unstored, buffering them would never finish or replay a broken
+    /// connection as a success.
+    pub(crate) async fn store(&self, key: IdempotencyKey, response: Response) -> Response {
+        if response.extensions().get::<InjectedFault>().is_some()
+            || response.body().size_hint().exact().is_none()
+        {
+            return response;
+        }
+
+        let (parts, body) = response.into_parts();
+        let body = match axum::body::to_bytes(body, usize::MAX).await {
+            Ok(body) => body,
+            Err(e) => {
+                error!(
+                    "Failed to buffer response for idempotency key {}: {}",
+                    key.key, e
+                );
+                return Response::from_parts(parts, Body::empty());
+            }
+        };
+        self.responses.lock().await.insert(
+            key,
+            StoredResponse {
+                status: parts.status,
+                headers: parts.headers.clone(),
+                body: body.clone(),
+            },
+        );
+        Response::from_parts(parts, Body::from(body))
+    }
+
+    pub(crate) async fn clear(&self) {
+        self.responses.lock().await.clear();
+    }
+}
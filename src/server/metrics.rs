@@ -0,0 +1,153 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::models::{ExpectationStats, RecordedResponse};
+
+/// Upper bounds in seconds of the latency histogram buckets
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Counters behind `GET /_metrics`
+///
+/// They cover the requests sent to mocked paths and keep counting across
+/// resets, as Prometheus expects from counters.
+#[derive(Debug, Default)]
+pub(crate) struct RequestMetrics {
+    /// Responses per method and status
+    requests: BTreeMap<(String, u16), u64>,
+
+    matched: u64,
+
+    unmatched: u64,
+
+    /// Responses at or below each of [`LATENCY_BUCKETS`]
+    latency_buckets: [u64; LATENCY_BUCKETS.len()],
+
+    latency_count: u64,
+
+    latency_sum_seconds: f64,
+}
+
+impl RequestMetrics {
+    /// Counts a response to a `method` request
+    pub(crate) fn observe(&mut self, method: &str, response: &RecordedResponse) {
+        *self
+            .requests
+            .entry((method.to_string(), response.status))
+            .or_default() += 1;
+
+        if response.expectation_id.is_some() {
+            self.matched += 1;
+        } else {
+            self.unmatched += 1;
+        }
+
+        let seconds = response.latency_ms / 1000.0;
+        for (count, bound) in self.latency_buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= bound {
+                *count += 1;
+            }
+        }
+        self.latency_count += 1;
+        self.latency_sum_seconds += seconds;
+    }
+
+    /// The counters and the hits of `expectations` in the Prometheus text format
+    pub(crate) fn render(&self, expectations: &[ExpectationStats]) -> String {
+        let mut out = String::new();
+
+        header(
+            &mut out,
+            "mimic_requests_total",
+            "counter",
+            "Requests to mocked paths by method and response status",
+        );
+        for ((method, status), count) in &self.requests {
+            let _ = writeln!(
+                out,
+                "mimic_requests_total{{method=\"{}\",status=\"{}\"}} {}",
+                escape_label(method),
+                status,
+                count
+            );
+        }
+
+        header(
+            &mut out,
+            "mimic_requests_matched_total",
+            "counter",
+            "Requests answered by an expectation",
+        );
+        let _ = writeln!(out, "mimic_requests_matched_total {}", self.matched);
+
+        header(
+            &mut out,
+            "mimic_requests_unmatched_total",
+            "counter",
+            "Requests no expectation answered",
+        );
+        let _ = writeln!(out, "mimic_requests_unmatched_total {}", self.unmatched);
+
+        header(
+            &mut out,
+            "mimic_response_latency_seconds",
+            "histogram",
+            "Time from receiving a request until its response was ready",
+        );
+        for (count, bound) in self.latency_buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(
+                out,
+                "mimic_response_latency_seconds_bucket{{le=\"{}\"}} {}",
+                bound, count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "mimic_response_latency_seconds_bucket{{le=\"+Inf\"}} {}",
+            self.latency_count
+        );
+        let _ = writeln!(
+            out,
+            "mimic_response_latency_seconds_sum {}",
+            self.latency_sum_seconds
+        );
+        let _ = writeln!(
+            out,
+            "mimic_response_latency_seconds_count {}",
+            self.latency_count
+        );
+
+        header(
+            &mut out,
+            "mimic_expectation_hits_total",
+            "counter",
+            "Requests answered per registered expectation",
+        );
+        for stats in expectations {
+            let _ = writeln!(
+                out,
+                "mimic_expectation_hits_total{{id=\"{}\",method=\"{}\",path=\"{}\"}} {}",
+                escape_label(&stats.id),
+                escape_label(&stats.method),
+                escape_label(&stats.path),
+                stats.hits
+            );
+        }
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escapes backslashes, quotes and newlines in a label value
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,78 @@
+use super::MockServer;
+use super::error::VerificationError;
+
+/// Handle to a registered expectation, returned by `build()`
+///
+/// # Example
+/// ```
+/// # use mimic_rs::MockServer;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let server = MockServer::new("./resources");
+///
+/// let mock = server.expect()
+///     .path("/api/orders")
+///     .method("POST")
+///     .respond()
+///     .status(201)
+///     .build().await;
+///
+/// // ...exercise the code under test...
+///
+/// assert_eq!(mock.calls().await, 0);
+/// assert!(mock.verify(0).await.is_ok());
+/// assert!(mock.remove().await);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Mock {
+    server: MockServer,
+
+    id: String,
+
+    method: String,
+
+    path: String,
+}
+
+impl Mock {
+    pub(crate) fn new(server: MockServer, id: &str, method: &str, path: &str) -> Self {
+        Self {
+            server,
+            id: id.to_string(),
+            method: method.to_string(),
+            path: path.to_string(),
+        }
+    }
+
+    /// Id of the expectation, as listed by the admin API
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// How many requests the expectation answered
+    pub async fn calls(&self) -> usize {
+        self.server.hit_count(&self.id).await
+    }
+
+    /// Checks that the expectation answered exactly `times` requests
+    pub async fn verify(&self, times: usize) -> Result<(), VerificationError> {
+        let actual = self.calls().await;
+        if actual == times {
+            return Ok(());
+        }
+        Err(VerificationError::Hits {
+            id: self.id.clone(),
+            method: self.method.clone(),
+            path: self.path.clone(),
+            expected: times,
+            actual,
+        })
+    }
+
+    /// Unregisters the expectation, `false` if it was already gone
+    pub async fn remove(&self) -> bool {
+        self.server.remove_expectation(&self.id).await
+    }
+}
@@ -1,16 +1,80 @@
+mod capture;
+mod error;
 pub mod expectation_builder;
+mod guard;
+mod handle;
+mod idempotency;
+mod metrics;
+mod mock;
+mod network;
+mod persistence;
+pub(crate) mod rate_limit;
+mod reload;
+pub mod scenario_builder;
+mod shutdown;
+mod static_files;
+mod stats;
+mod verification;
+pub mod verification_builder;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::future::Future;
+use std::io;
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use axum::Router;
-use tokio::sync::RwLock;
-use tracing::info;
+use axum::extract::connect_info::Connected;
+use axum::serve::{IncomingStream, Listener};
+pub(crate) use network::ConnectionFault;
+use network::{FaultyListener, TlsListener};
+use tokio::net::TcpListener;
+use tokio::sync::{Mutex, Notify, RwLock, broadcast, oneshot, watch};
+use tracing::{error, info, warn};
 
+pub use self::error::{FixtureError, StartupError, VerificationError, VerificationErrors};
 use self::expectation_builder::ExpectationBuilder;
-use crate::models::{MockExpectation, RequestRecord};
+pub use self::guard::VerifyOnDrop;
+pub use self::handle::ServerHandle;
+use self::idempotency::IdempotencyStore;
+pub(crate) use self::idempotency::{IdempotencyKey, InjectedFault};
+use self::metrics::RequestMetrics;
+pub use self::mock::Mock;
+use self::rate_limit::RateLimitWindow;
+use self::scenario_builder::ScenarioBuilder;
+use self::static_files::StaticDir;
+use self::stats::ServeTimes;
+use self::verification::RequestPattern;
+use self::verification_builder::VerificationBuilder;
+use crate::auth::{OidcConfig, OidcProvider};
+use crate::clock::{Clock, SystemClock};
+use crate::cluster::Cluster;
+use crate::conditional::Responder;
+use crate::cors::{CorsConfig, CorsPolicy};
+use crate::extension::{Extension, ExtensionRegistry};
+use crate::import::{self, Import, ImportError, ImportFormat};
+use crate::journal::RequestJournal;
+use crate::logging::{AccessLog, AccessLogEntry, LogHandle, REDACTED};
+use crate::mappings::{self, MAPPINGS_DIR, MappingError};
+use crate::models::{
+    Callback, CallbackDelivery, CapturedUpload, ChaosConfig, CreateExpectationRequest,
+    DowntimeWindow, ExpectationSource, ExpectationStats, MockExpectation, MockResponse, NearMiss,
+    RecordedResponse, RequestConditions, RequestQuery, RequestRecord, SCENARIO_STARTED,
+    SequenceStep, UnmatchedRequest,
+};
+use crate::openapi::{OpenApiValidator, ValidationReport};
+use crate::proxy::Proxy;
+use crate::random::Random;
+use crate::script::ScriptRunner;
+use crate::state::StateStore;
+use crate::tls::TlsConfig;
+use crate::transform::{
+    IncomingRequest, RequestTransform, ResponseTransform, ResponseTransformer, Transformer,
+};
 use crate::{ConditionalResponse, handlers};
 
 /// Main structure of the MockServer
@@ -20,28 +84,571 @@ pub struct MockServer {
 
     request_log: Arc<RwLock<Vec<RequestRecord>>>,
 
+    /// Publishes every logged request once its response is attached
+    request_events: broadcast::Sender<RequestRecord>,
+
+    /// Requests no expectation answered, bounded like the request log
+    unmatched: Arc<RwLock<Vec<UnmatchedRequest>>>,
+
+    /// Callbacks sent after matches, bounded like the request log
+    callback_deliveries: Arc<RwLock<Vec<CallbackDelivery>>>,
+
+    /// Sends the callbacks of matched expectations
+    callback_client: reqwest::Client,
+
     resource_dir: PathBuf,
 
     max_request_log_size: usize,
+
+    journal: Option<Arc<RequestJournal>>,
+
+    persistence_dir: Option<PathBuf>,
+
+    /// Held while a snapshot of the expectations is taken and saved
+    persist_lock: Arc<Mutex<()>>,
+
+    /// Mapping files registered on start, `<resources>/mappings` by default
+    mappings_dir: PathBuf,
+
+    /// Reload mapping and response files when they change on disk
+    hot_reload: bool,
+
+    /// Documents loaded with `load_openapi`
+    openapi: Arc<RwLock<Vec<OpenApiValidator>>>,
+
+    /// Answer requests violating a loaded OpenAPI document with 400
+    validate_requests: bool,
+
     pub(crate) conditional_responses: Arc<RwLock<HashMap<String, ConditionalResponse>>>,
+
+    /// Closures registered with `respond_with`, keyed by responder id
+    responders: Arc<RwLock<HashMap<String, Responder>>>,
+
+    /// Request windows of rate limited expectations, keyed by expectation id
+    pub(crate) rate_limits: Arc<Mutex<HashMap<String, RateLimitWindow>>>,
+
+    /// Number of responses served per expectation id, for rotations
+    pub(crate) response_counters: Arc<Mutex<HashMap<String, usize>>>,
+
+    /// Number of requests answered per expectation id
+    hit_counts: Arc<Mutex<HashMap<String, usize>>>,
+
+    /// Last response and latency totals per expectation id
+    serve_times: Arc<Mutex<HashMap<String, ServeTimes>>>,
+
+    /// Request, match and latency counters served on `/_metrics`
+    metrics: Arc<Mutex<RequestMetrics>>,
+
+    state: StateStore,
+
+    /// Draws chaos rolls, random delays and generated template values
+    random: Random,
+
+    /// The time templates and responder closures see
+    clock: Arc<dyn Clock>,
+
+    /// Compiles and runs response scripts
+    scripts: Arc<ScriptRunner>,
+
+    /// Current state per scenario name; absent scenarios are "Started"
+    scenarios: Arc<RwLock<HashMap<String, String>>>,
+
+    log_handle: Option<LogHandle>,
+
+    /// Receives one JSON line per request to a mocked path
+    access_log: Option<Arc<AccessLog>>,
+
+    /// Lowercase names of headers whose values are never logged or recorded
+    redacted_headers: Vec<String>,
+
+    admin_enabled: bool,
+
+    method_not_allowed: bool,
+
+    handle_signals: bool,
+
+    /// Serves `POST /_shutdown`
+    shutdown_endpoint: bool,
+
+    /// Wakes the running servers to shut down gracefully
+    shutdown: Arc<Notify>,
+
+    drain_timeout: Duration,
+
+    request_log_dump: Option<PathBuf>,
+
+    cors: Option<Arc<CorsConfig>>,
+
+    /// Replaces the plain-text 404 for unmatched requests
+    not_found_response: Option<Arc<MockResponse>>,
+
+    not_found_diagnostics: bool,
+
+    /// Responses replayed for repeated idempotency keys
+    idempotency: Option<Arc<IdempotencyStore>>,
+
+    oidc: Option<Arc<OidcProvider>>,
+
+    cluster: Option<Arc<Cluster>>,
+
+    /// Backend unmatched requests are forwarded to and recorded from
+    proxy: Option<Arc<Proxy>>,
+
+    /// Directories served below a path prefix when no expectation matches
+    static_dirs: Vec<StaticDir>,
+
+    /// How long each connection waits before the server starts talking to it
+    accept_delay: Option<Duration>,
+
+    /// How long each TLS handshake is held before it starts
+    tls_handshake_delay: Option<Duration>,
+
+    /// Applied in order to every request before it is matched
+    transformers: Vec<Transformer>,
+
+    /// Applied in order to every mocked response before it is sent
+    response_transformers: Vec<ResponseTransformer>,
+
+    /// Custom matchers and response generators
+    extensions: ExtensionRegistry,
+
+    /// Random faults injected into mocked endpoints, toggled at runtime
+    chaos: Arc<RwLock<Option<ChaosConfig>>>,
+
+    /// Scheduled outages of mocked endpoints
+    downtime: Arc<RwLock<Vec<DowntimeWindow>>>,
+
+    /// Outages given to the builder, scheduled when the server starts
+    startup_downtime: Vec<DowntimeWindow>,
+
+    /// Directory multipart and binary request bodies are written to
+    capture_dir: Option<PathBuf>,
+
+    /// Egress cap in bytes per second, shared by all connections
+    bandwidth_limit: Option<u64>,
+
+    /// Set once fixtures are loaded and the listener is bound
+    ready: Arc<AtomicBool>,
+
+    /// Set when shutdown starts, ending the open request streams
+    stopping: Arc<watch::Sender<bool>>,
 }
 
+/// Requests buffered per subscriber of [`MockServer::subscribe_requests`]
+const REQUEST_EVENTS_CAPACITY: usize = 256;
+
 impl MockServer {
     pub fn new<P: Into<PathBuf>>(resource_dir: P) -> Self {
+        let resource_dir = resource_dir.into();
         Self {
             expectations: Arc::new(RwLock::new(HashMap::new())),
             request_log: Arc::new(RwLock::new(Vec::new())),
-            resource_dir: resource_dir.into(),
+            request_events: broadcast::channel(REQUEST_EVENTS_CAPACITY).0,
+            unmatched: Arc::new(RwLock::new(Vec::new())),
+            callback_deliveries: Arc::new(RwLock::new(Vec::new())),
+            callback_client: reqwest::Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap_or_default(),
+            mappings_dir: resource_dir.join(MAPPINGS_DIR),
+            resource_dir,
+            hot_reload: false,
+            openapi: Arc::new(RwLock::new(Vec::new())),
+            validate_requests: false,
             max_request_log_size: 1000,
+            journal: None,
+            persistence_dir: None,
+            persist_lock: Arc::new(Mutex::new(())),
             conditional_responses: Arc::new(RwLock::new(HashMap::new())),
+            responders: Arc::new(RwLock::new(HashMap::new())),
+            rate_limits: Arc::new(Mutex::new(HashMap::new())),
+            response_counters: Arc::new(Mutex::new(HashMap::new())),
+            hit_counts: Arc::new(Mutex::new(HashMap::new())),
+            serve_times: Arc::new(Mutex::new(HashMap::new())),
+            metrics: Arc::new(Mutex::new(RequestMetrics::default())),
+            state: StateStore::new(),
+            random: Random::default(),
+            clock: Arc::new(SystemClock),
+            scripts: Arc::new(ScriptRunner::new()),
+            scenarios: Arc::new(RwLock::new(HashMap::new())),
+            log_handle: None,
+            access_log: None,
+            redacted_headers: Vec::new(),
+            admin_enabled: true,
+            method_not_allowed: true,
+            handle_signals: true,
+            shutdown_endpoint: false,
+            shutdown: Arc::new(Notify::new()),
+            drain_timeout: Duration::from_secs(30),
+            request_log_dump: None,
+            cors: None,
+            not_found_response: None,
+            not_found_diagnostics: false,
+            idempotency: None,
+            oidc: None,
+            cluster: None,
+            proxy: None,
+            static_dirs: Vec::new(),
+            accept_delay: None,
+            tls_handshake_delay: None,
+            bandwidth_limit: None,
+            capture_dir: None,
+            transformers: Vec::new(),
+            response_transformers: Vec::new(),
+            extensions: ExtensionRegistry::default(),
+            chaos: Arc::new(RwLock::new(None)),
+            downtime: Arc::new(RwLock::new(Vec::new())),
+            startup_downtime: Vec::new(),
+            ready: Arc::new(AtomicBool::new(false)),
+            stopping: Arc::new(watch::channel(false).0),
         }
     }
+    /// Makes all randomness of the server reproducible
+    ///
+    /// Chaos mode, random delays and template generators such as `{{uuid}}`
+    /// or `{{faker.name}}` then produce the same values for the same requests
+    /// sent in the same order. `reset` starts the sequence over.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.random = Random::seeded(seed);
+        self
+    }
+
+    /// Replaces the system time seen by templates and responder closures
+    ///
+    /// With a [`TestClock`](crate::clock::TestClock) time-of-day stubs can be
+    /// tested deterministically: keep a clone and advance it between requests.
+    pub fn with_clock(mut self, clock: impl Clock + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
     /// Sets the maximum size of the request log
     pub fn with_max_log_size(mut self, size: usize) -> Self {
         self.max_request_log_size = size;
         self
     }
 
+    /// Persists the request log to the SQLite database at `path`
+    ///
+    /// Records from earlier runs are kept, so `get_request_log`, `count_calls`
+    /// and `/_verify` see the full history regardless of the in-memory log size.
+    /// The hit counts of the expectations are restored from the journaled
+    /// responses.
+    pub fn with_request_journal<P: Into<PathBuf>>(mut self, path: P) -> io::Result<Self> {
+        let journal = RequestJournal::open(path)?;
+        self.hit_counts = Arc::new(Mutex::new(journal.hit_counts()?));
+        self.journal = Some(Arc::new(journal));
+        Ok(self)
+    }
+
+    /// Saves expectations created via `/_setup` in `dir` and restores them on start
+    ///
+    /// Expectations defined in code are not saved since the code registers
+    /// them again anyway.
+    pub fn with_persistence<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.persistence_dir = Some(dir.into());
+        self
+    }
+
+    /// Reads mapping files from `dir` instead of `<resources>/mappings`
+    ///
+    /// A missing directory is not an error, there are just no mappings.
+    pub fn with_mappings_dir<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.mappings_dir = dir.into();
+        self
+    }
+
+    /// Reloads mapping and response files while running when they change
+    ///
+    /// Watches the resources directory and, if it lies elsewhere, the
+    /// mappings directory. See [`Self::reload`].
+    pub fn with_hot_reload(mut self) -> Self {
+        self.hot_reload = true;
+        self
+    }
+
+    /// Answers requests that violate a loaded OpenAPI document with 400
+    ///
+    /// The response lists every violated parameter and body constraint.
+    /// Requests the documents do not describe are matched as usual.
+    pub fn with_request_validation(mut self) -> Self {
+        self.validate_requests = true;
+        self
+    }
+
+    /// Disables the admin API (`/_setup`, `/_reset`, ...)
+    ///
+    /// The server becomes read-only after startup; requests to the admin
+    /// paths are handled like any other request.
+    pub fn without_admin(mut self) -> Self {
+        self.admin_enabled = false;
+        self
+    }
+
+    /// Answers 404 instead of 405 when a path is stubbed for other methods only
+    pub fn without_method_not_allowed(mut self) -> Self {
+        self.method_not_allowed = false;
+        self
+    }
+
+    /// Answers unmatched requests with `response` instead of a plain-text 404
+    pub fn with_not_found_response(mut self, response: MockResponse) -> Self {
+        self.not_found_response = Some(Arc::new(response));
+        self
+    }
+
+    /// Answers unmatched requests with a JSON 404 explaining why nothing matched
+    ///
+    /// The body lists the request as parsed and the closest expectations with
+    /// their mismatches. Takes precedence over `with_not_found_response`.
+    pub fn with_not_found_diagnostics(mut self) -> Self {
+        self.not_found_diagnostics = true;
+        self
+    }
+
+    /// Replays the stored response for requests repeating an idempotency key
+    ///
+    /// The first matched request carrying `header` for a method and path is
+    /// answered as usual and its response stored; later requests with the
+    /// same key get that response again, marked with `Idempotent-Replayed: true`,
+    /// without re-evaluating conditionals, rotations or scenarios.
+    pub fn with_idempotency_key(mut self, header: &str) -> Self {
+        self.idempotency = Some(Arc::new(IdempotencyStore::new(header)));
+        self
+    }
+
+    /// Serves `POST /_shutdown`, which stops the server like SIGTERM does
+    ///
+    /// Only available with the admin API.
+    pub fn with_shutdown_endpoint(mut self) -> Self {
+        self.shutdown_endpoint = true;
+        self
+    }
+
+    /// Stops `start()` and `start_tls()` from reacting to SIGINT/SIGTERM
+    pub fn without_signal_handling(mut self) -> Self {
+        self.handle_signals = false;
+        self
+    }
+
+    /// Sets how long in-flight requests may take to finish after shutdown starts
+    pub fn with_drain_timeout(mut self, timeout: Duration) -> Self {
+        self.drain_timeout = timeout;
+        self
+    }
+
+    /// Holds every new connection for `delay` before reading the request
+    ///
+    /// Simulates an overloaded upstream: the TCP connection is established
+    /// but nothing happens on it, so client connect/read timeouts can be tested.
+    pub fn with_accept_delay(mut self, delay: Duration) -> Self {
+        self.accept_delay = Some(delay);
+        self
+    }
+
+    /// Holds every TLS handshake for `delay` before answering the ClientHello
+    ///
+    /// Only applies to [`start_tls`](Self::start_tls). The client connects
+    /// right away but the handshake stalls, so TLS handshake timeouts can be
+    /// tested separately from connect and read timeouts.
+    pub fn with_tls_handshake_delay(mut self, delay: Duration) -> Self {
+        self.tls_handshake_delay = Some(delay);
+        self
+    }
+
+    /// Rewrites incoming requests with a built-in transformation before matching
+    ///
+    /// Transformations run in the order they were added. The request log
+    /// records the transformed request.
+    pub fn with_request_transform(mut self, transform: RequestTransform) -> Self {
+        self.transformers.push(Transformer::Builtin(transform));
+        self
+    }
+
+    /// Rewrites incoming requests with custom code before matching
+    ///
+    /// The body is `None` while deciding on `Expect: 100-continue`, before it
+    /// has been read.
+    pub fn with_request_transformer<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&mut IncomingRequest) + Send + Sync + 'static,
+    {
+        self.transformers
+            .push(Transformer::Custom(Arc::new(transform)));
+        self
+    }
+
+    /// Post-processes every mocked response with a built-in transformation
+    ///
+    /// Runs after the transformations of the matched expectation. Streamed,
+    /// SSE and gRPC-Web responses only get the header transformations.
+    pub fn with_response_transform(mut self, transform: ResponseTransform) -> Self {
+        self.response_transformers
+            .push(ResponseTransformer::Builtin(transform));
+        self
+    }
+
+    /// Post-processes every mocked response with custom code
+    ///
+    /// A body file is already loaded into `cached_file_content` (and
+    /// `cached_json_content` if it is JSON) when the transformer runs.
+    /// For streamed, SSE and gRPC-Web responses the body is not available
+    /// and only changes to the headers take effect.
+    pub fn with_response_transformer<F>(mut self, transform: F) -> Self
+    where
+        F: Fn(&mut MockResponse) + Send + Sync + 'static,
+    {
+        self.response_transformers
+            .push(ResponseTransformer::Custom(Arc::new(transform)));
+        self
+    }
+
+    /// Starts with chaos mode enabled
+    ///
+    /// Can be changed later with `set_chaos` or `POST /_chaos`.
+    pub fn with_chaos(mut self, config: ChaosConfig) -> Self {
+        self.chaos = Arc::new(RwLock::new(Some(config)));
+        self
+    }
+
+    /// Starts with a scheduled outage
+    ///
+    /// A recurring window without `from` is counted from this call on the
+    /// server's clock, so set a [`TestClock`](crate::clock::TestClock) first.
+    /// The window is scheduled when the server starts; more can be added
+    /// later with `add_downtime` or `POST /_downtime`.
+    pub fn with_downtime(mut self, window: DowntimeWindow) -> Self {
+        let now = self.clock.now();
+        self.startup_downtime.push(window.anchored(now));
+        self
+    }
+
+    /// Saves multipart and binary request bodies to files in `dir`
+    ///
+    /// The request log then holds the file's path, size and SHA-256 in
+    /// `upload` instead of the body, so tests can check the exact bytes a
+    /// client uploaded without keeping them in memory.
+    pub fn with_upload_capture<P: Into<PathBuf>>(mut self, dir: P) -> Self {
+        self.capture_dir = Some(dir.into());
+        self
+    }
+
+    /// Caps the combined egress of all responses at `bytes_per_sec`
+    ///
+    /// Lets tests observe clients on slow networks without OS-level traffic
+    /// shaping. Bursts of about 100ms worth of traffic are allowed.
+    pub fn with_bandwidth_limit(mut self, bytes_per_sec: u64) -> Self {
+        self.bandwidth_limit = Some(bytes_per_sec);
+        self
+    }
+
+    /// Writes the request log as JSON to `path` when the server shuts down
+    pub fn with_request_log_dump<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.request_log_dump = Some(path.into());
+        self
+    }
+
+    /// Enables CORS simulation (preflight answers and `Access-Control-*` headers)
+    pub fn with_cors(mut self, config: CorsConfig) -> Self {
+        self.cors = Some(Arc::new(config));
+        self
+    }
+
+    /// Enables the built-in OAuth2 / OpenID Connect provider
+    ///
+    /// Serves `/authorize`, `/token`, `/.well-known/openid-configuration` and
+    /// `/.well-known/jwks.json`, issuing ES256 signed test tokens.
+    ///
+    /// # Panics
+    /// Panics if the signing key cannot be generated.
+    pub fn with_oidc(mut self, config: OidcConfig) -> Self {
+        let provider = OidcProvider::new(config).expect("Failed to create OIDC signing key");
+        self.oidc = Some(Arc::new(provider));
+        self
+    }
+
+    /// Keeps expectations in sync with other instances
+    ///
+    /// Expectations created via `/_setup` and calls to `/_reset` are forwarded
+    /// to all peers, and on start the expectations of a running peer are copied.
+    /// Requires the admin API on every node.
+    ///
+    /// # Arguments
+    /// * `peers` - Base URLs of the other instances, e.g. `http://mock-2:8080`
+    pub fn with_peers<I, S>(mut self, peers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.cluster = Some(Arc::new(Cluster::new(peers)));
+        self
+    }
+
+    /// Forwards requests no expectation matches to `base_url` and records them
+    ///
+    /// The recordings are available from `export_recordings` and `/_recordings`
+    /// and can be written as mapping files to replay the traffic offline.
+    pub fn proxy_to(mut self, base_url: &str) -> Self {
+        self.proxy = Some(Arc::new(Proxy::new(base_url)));
+        self
+    }
+
+    /// Serves the files in `dir` below the path `prefix`
+    ///
+    /// Expectations take precedence, so an app can be served from disk while
+    /// its API is stubbed on the same port. `GET` and `HEAD` requests get the
+    /// file with a `Content-Type` inferred from its extension, directories
+    /// their `index.html`; missing files are answered like any unmatched request.
+    ///
+    /// # Arguments
+    /// * `prefix` - Path prefix, e.g. `/assets`; `/` serves the whole path space
+    /// * `dir` - Directory on disk, relative to the working directory
+    pub fn serve_static<P: Into<PathBuf>>(mut self, prefix: &str, dir: P) -> Self {
+        self.static_dirs.push(StaticDir::new(prefix, dir.into()));
+        self
+    }
+
+    /// Forwards requests no expectation matches to `base_url` without recording them
+    ///
+    /// For partial mocking: stubbed paths are answered locally, everything else
+    /// reaches the real environment with its headers, the `Host` rewritten to
+    /// the backend's and the original kept in `X-Forwarded-Host`.
+    pub fn fallback_proxy(mut self, base_url: &str) -> Self {
+        self.proxy = Some(Arc::new(Proxy::passthrough(base_url)));
+        self
+    }
+
+    /// Attaches a log handle so the level can be changed via `/_log_level`
+    pub fn with_log_handle(mut self, handle: LogHandle) -> Self {
+        self.log_handle = Some(handle);
+        self
+    }
+
+    /// Writes one JSON line per request to a mocked path to `log`
+    ///
+    /// Each line holds the method, path, status, latency, answering
+    /// expectation and request headers, see [`AccessLogEntry`].
+    pub fn with_access_log(mut self, log: AccessLog) -> Self {
+        self.access_log = Some(Arc::new(log));
+        self
+    }
+
+    /// Replaces the values of `headers` with `[REDACTED]` wherever they are kept
+    ///
+    /// Applies to the access log and to the requests and responses in the
+    /// request log and journal, so conditions on these headers no longer
+    /// match in `/_verify`. Expectations still see the real values. Names are
+    /// compared case-insensitively.
+    pub fn with_redacted_headers<I, S>(mut self, headers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.redacted_headers
+            .extend(headers.into_iter().map(|h| h.as_ref().to_lowercase()));
+        self
+    }
+
     /// Starts defining an expectation for a path
     ///
     /// # Arguments
@@ -67,8 +674,98 @@ impl MockServer {
     pub fn expect(&self) -> ExpectationBuilder {
         ExpectationBuilder::new(self.clone())
     }
+
+    /// Starts defining expectations that share the scenario `name`
+    ///
+    /// See [`ScenarioBuilder`] for an example.
+    pub fn scenario(&self, name: &str) -> ScenarioBuilder {
+        ScenarioBuilder::new(self.clone(), name)
+    }
+
+    /// The current state of a scenario
+    pub async fn scenario_state(&self, name: &str) -> String {
+        self.scenarios
+            .read()
+            .await
+            .get(name)
+            .cloned()
+            .unwrap_or_else(|| SCENARIO_STARTED.to_string())
+    }
+
+    /// Counts a match of `expectation`, `false` once its `times` are used up
+    pub(crate) async fn claim_match(&self, expectation: &MockExpectation) -> bool {
+        let mut hit_counts = self.hit_counts.lock().await;
+        let count = hit_counts.entry(expectation.id.clone()).or_insert(0);
+        if expectation.times.is_some_and(|times| *count >= times) {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+
+    /// How many requests the expectation with `id` answered
+    pub async fn hit_count(&self, id: &str) -> usize {
+        self.hit_counts.lock().await.get(id).copied().unwrap_or(0)
+    }
+
+    /// Requests answered per expectation id; unused expectations are missing
+    pub async fn hit_counts(&self) -> HashMap<String, usize> {
+        self.hit_counts.lock().await.clone()
+    }
+
+    /// Hits, last hit and average latency of every registered expectation
+    ///
+    /// Unused expectations are included with zero hits. The busiest
+    /// expectations come first.
+    pub async fn stats(&self) -> Vec<ExpectationStats> {
+        let expectations = self.get_expectations().await;
+        let hit_counts = self.hit_counts.lock().await.clone();
+        let serve_times = self.serve_times.lock().await;
+
+        let mut stats: Vec<ExpectationStats> = expectations
+            .into_iter()
+            .map(|expectation| {
+                let times = serve_times.get(&expectation.id);
+                ExpectationStats {
+                    hits: hit_counts.get(&expectation.id).copied().unwrap_or(0),
+                    last_hit: times.map(|times| times.last_hit),
+                    avg_latency_ms: times.map(ServeTimes::average_latency_ms),
+                    id: expectation.id,
+                    method: expectation.method,
+                    path: expectation.path,
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.path.cmp(&b.path)));
+        stats
+    }
+
+    /// Request counts, latencies and expectation hits in the Prometheus text format
+    ///
+    /// Request counters and latencies cover requests to mocked paths and keep
+    /// counting across resets; expectation hits follow [`MockServer::stats`].
+    pub async fn metrics(&self) -> String {
+        let stats = self.stats().await;
+        self.metrics.lock().await.render(&stats)
+    }
+
+    /// Moves a scenario to `state`
+    pub async fn set_scenario_state(&self, name: &str, state: &str) {
+        self.scenarios
+            .write()
+            .await
+            .insert(name.to_string(), state.to_string());
+    }
+
+    /// Current states of all scenarios that left the initial state
+    pub async fn scenario_states(&self) -> HashMap<String, String> {
+        self.scenarios.read().await.clone()
+    }
     /// Starts the server on the specified port
     ///
+    /// On SIGINT or SIGTERM the server stops accepting connections, lets
+    /// in-flight requests finish (up to the drain timeout) and returns.
+    ///
     /// # Example
     /// ```no_run
     ///
@@ -92,20 +789,278 @@ impl MockServer {
     /// # }
     /// ```
     pub async fn start(&self, port: u16) -> Result<(), Box<dyn std::error::Error>> {
-        // Preload file content before starting
-        self.preload_file_content().await;
+        self.prepare().await?;
+
+        let app = self.create_router();
+
+        let listener = self.bind(port).await?;
+        info!("MockServer running at http://{}", listener.local_addr()?);
+
+        self.serve(listener, app, std::future::pending(), self.handle_signals)
+            .await
+    }
+
+    /// Starts the server in the background on `port`
+    ///
+    /// Returns once the server accepts connections. The handle tells the
+    /// address, builds URLs and shuts the server down. Unlike `start()` it
+    /// leaves SIGINT/SIGTERM to the embedding process.
+    pub async fn start_background(&self, port: u16) -> Result<ServerHandle, StartupError> {
+        self.prepare().await?;
+
+        let app = self.create_router();
+
+        let listener = self.bind(port).await?;
+        let port = listener
+            .local_addr()
+            .map_err(|source| StartupError::Bind {
+                addr: SocketAddr::from(([0, 0, 0, 0], port)),
+                source,
+            })?
+            .port();
+        info!("MockServer running at http://localhost:{}", port);
+
+        Ok(self.serve_in_background(listener, app, port, "http"))
+    }
+
+    /// Spawns `serve` for `listener` and returns the handle controlling it
+    fn serve_in_background<L>(
+        &self,
+        listener: L,
+        app: Router,
+        port: u16,
+        scheme: &'static str,
+    ) -> ServerHandle
+    where
+        L: Listener<Addr = SocketAddr>,
+        ConnectionFault: for<'a> Connected<IncomingStream<'a, L>>,
+    {
+        let (stop, stopped) = oneshot::channel();
+        let server = self.clone();
+        let task = tokio::spawn(async move {
+            let stopped = async {
+                // A dropped handle leaves the server running
+                if stopped.await.is_err() {
+                    std::future::pending::<()>().await;
+                }
+            };
+            if let Err(e) = server.serve(listener, app, stopped, false).await {
+                error!("MockServer on port {} failed: {}", port, e);
+            }
+        });
+
+        ServerHandle::new(port, scheme, stop, task)
+    }
+
+    /// Starts the server in the background on a free port
+    ///
+    /// Like `start_background(0)`: tests need no fixed ports and no sleeping.
+    pub async fn start_random_port(&self) -> Result<ServerHandle, StartupError> {
+        self.start_background(0).await
+    }
+
+    /// Stops running servers gracefully, as `POST /_shutdown` does
+    ///
+    /// In-flight requests get the drain timeout to complete; `start()`
+    /// returns afterwards.
+    pub fn shutdown(&self) {
+        self.shutdown.notify_waiters();
+    }
+
+    /// Starts the server on the specified port, serving HTTPS with `tls`
+    ///
+    /// Behaves like `start()` otherwise. See [`TlsConfig`] for an example
+    /// with a generated self-signed certificate.
+    pub async fn start_tls(
+        &self,
+        port: u16,
+        tls: TlsConfig,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let config = tls.server_config().map_err(StartupError::Tls)?;
+        self.prepare().await?;
+
+        let app = self.create_router();
+
+        let listener = TlsListener::new(self.bind(port).await?, config, self.tls_handshake_delay)?;
+        info!("MockServer running at https://{}", listener.local_addr()?);
+
+        self.serve(listener, app, std::future::pending(), self.handle_signals)
+            .await
+    }
+
+    /// Starts the server in the background on `port`, serving HTTPS with `tls`
+    ///
+    /// Behaves like `start_background()` otherwise; pass port 0 to get a
+    /// free one. The handle's URLs use `https`.
+    pub async fn start_tls_background(
+        &self,
+        port: u16,
+        tls: TlsConfig,
+    ) -> Result<ServerHandle, StartupError> {
+        let config = tls.server_config()?;
+        self.prepare().await?;
 
         let app = self.create_router();
 
         let addr = SocketAddr::from(([0, 0, 0, 0], port));
-        info!("MockServer running at http://{}", addr);
+        let listener = TlsListener::new(self.bind(port).await?, config, self.tls_handshake_delay)
+            .map_err(|source| StartupError::Bind { addr, source })?;
+        let port = listener
+            .local_addr()
+            .map_err(|source| StartupError::Bind { addr, source })?
+            .port();
+        info!("MockServer running at https://localhost:{}", port);
 
-        let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, app).await?;
+        Ok(self.serve_in_background(listener, app, port, "https"))
+    }
+
+    async fn bind(&self, port: u16) -> Result<FaultyListener, StartupError> {
+        let addr = SocketAddr::from(([0, 0, 0, 0], port));
+        let listener = TcpListener::bind(addr)
+            .await
+            .map_err(|source| StartupError::Bind { addr, source })?;
+        Ok(FaultyListener::new(
+            listener,
+            self.accept_delay,
+            self.bandwidth_limit,
+        ))
+    }
+
+    /// Loads everything the server needs before it accepts requests
+    ///
+    /// Registers the mapping files, restores persisted and peer expectations,
+    /// schedules the builder's downtime windows and reads all response files.
+    /// Called by `start()`; unreadable files fail the start instead of
+    /// producing 500 responses later.
+    pub async fn prepare(&self) -> Result<(), StartupError> {
+        self.load_mappings().await?;
+        self.restore_persisted_expectations().await;
+        if let Some(cluster) = &self.cluster {
+            for expectation in cluster.pull().await {
+                self.add_replicated_expectation(expectation).await;
+            }
+        }
+        if !self.startup_downtime.is_empty() {
+            let mut downtime = self.downtime.write().await;
+            for window in &self.startup_downtime {
+                if !downtime.contains(window) {
+                    downtime.push(window.clone());
+                }
+            }
+        }
+
+        let errors = self.load_fixtures().await;
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(StartupError::Fixtures(errors))
+        }
+    }
+
+    /// Whether the server has finished loading and accepts requests
+    ///
+    /// Served as `/_ready`; turns false again once shutdown starts.
+    pub fn is_ready(&self) -> bool {
+        self.ready.load(Ordering::Acquire)
+    }
+
+    /// Serves requests until a shutdown is requested, then drains open connections
+    ///
+    /// Shutdown is triggered by `stop`, [`Self::shutdown`] or, with `signals`,
+    /// a termination signal.
+    async fn serve<L>(
+        &self,
+        listener: L,
+        app: Router,
+        stop: impl Future<Output = ()> + Send + 'static,
+        signals: bool,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        L: Listener<Addr = SocketAddr>,
+        ConnectionFault: for<'a> Connected<IncomingStream<'a, L>>,
+    {
+        let termination: Pin<Box<dyn Future<Output = ()> + Send>> = if signals {
+            Box::pin(shutdown::termination_signal())
+        } else {
+            Box::pin(std::future::pending())
+        };
+        let requested = self.shutdown.clone();
+        let signal = async move {
+            tokio::select! {
+                _ = termination => {},
+                _ = requested.notified() => {},
+                _ = stop => {},
+            }
+        };
+
+        let watcher = if self.hot_reload {
+            match reload::watch(self.clone()) {
+                Ok(watcher) => Some(watcher),
+                Err(e) => {
+                    error!("Failed to watch for file changes: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let draining = Arc::new(Notify::new());
+        let notify = draining.clone();
+        let ready = self.ready.clone();
+        let stopping = self.stopping.clone();
+        stopping.send_replace(false);
+        ready.store(true, Ordering::Release);
+
+        let graceful = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<ConnectionFault>(),
+        )
+        .with_graceful_shutdown(async move {
+            signal.await;
+            ready.store(false, Ordering::Release);
+            stopping.send_replace(true);
+            info!("Shutting down, waiting for in-flight requests");
+            notify.notify_one();
+        });
+
+        let drain_timeout = self.drain_timeout;
+        tokio::select! {
+            result = graceful => result?,
+            _ = async {
+                draining.notified().await;
+                tokio::time::sleep(drain_timeout).await;
+            } => {
+                warn!("Drain timeout of {:?} elapsed, closing remaining connections", drain_timeout);
+            }
+        }
+
+        if let Some(watcher) = watcher {
+            watcher.abort();
+        }
+        self.dump_request_log().await;
+        info!("MockServer stopped");
 
         Ok(())
     }
 
+    /// Writes the request log to the configured dump file, if any
+    async fn dump_request_log(&self) {
+        let Some(path) = &self.request_log_dump else {
+            return;
+        };
+
+        let request_log = self.request_log.read().await;
+        let result = serde_json::to_vec_pretty(&*request_log)
+            .map_err(std::io::Error::from)
+            .and_then(|content| std::fs::write(path, content));
+
+        match result {
+            Ok(()) => info!("Wrote {} requests to {}", request_log.len(), path.display()),
+            Err(e) => error!("Failed to write request log to {}: {}", path.display(), e),
+        }
+    }
+
     /// Creates a router for the server
     fn create_router(&self) -> Router {
         handlers::create_router(self.clone())
@@ -118,29 +1073,584 @@ impl MockServer {
         // Ensure the regex is compiled if needed
         expectation.compile_regex_if_needed();
 
-        let mut expectations = self.expectations.write().await;
+        let mut expectations = self.expectations.write().await;
+
+        let persist = expectation.source == ExpectationSource::SetupApi;
+        expectations
+            .entry(expectation.method.clone())
+            .or_insert_with(Vec::new)
+            .push(expectation);
+        drop(expectations);
+
+        if persist {
+            self.persist_expectations().await;
+        }
+    }
+
+    /// Adds several expectations at once, so requests see all or none of them
+    ///
+    /// With `replace` the registered expectations are swapped out under the
+    /// same lock, no request sees an empty set in between.
+    async fn add_expectations(&self, added: Vec<MockExpectation>, replace: bool) {
+        let mut persist = added
+            .iter()
+            .any(|expectation| expectation.source == ExpectationSource::SetupApi);
+
+        let mut expectations = self.expectations.write().await;
+        let removed: Vec<MockExpectation> = if replace {
+            std::mem::take(&mut *expectations)
+                .into_values()
+                .flatten()
+                .collect()
+        } else {
+            Vec::new()
+        };
+        for mut expectation in added {
+            expectation.compile_regex_if_needed();
+            expectations
+                .entry(expectation.method.clone())
+                .or_insert_with(Vec::new)
+                .push(expectation);
+        }
+        drop(expectations);
+
+        if !removed.is_empty() {
+            let ids: HashSet<&str> = removed.iter().map(|exp| exp.id.as_str()).collect();
+            let kept = |id: &String| !ids.contains(id.as_str());
+            self.rate_limits.lock().await.retain(|id, _| kept(id));
+            self.response_counters.lock().await.retain(|id, _| kept(id));
+            self.hit_counts.lock().await.retain(|id, _| kept(id));
+            self.serve_times.lock().await.retain(|id, _| kept(id));
+            persist |= removed
+                .iter()
+                .any(|expectation| expectation.source == ExpectationSource::SetupApi);
+        }
+
+        if persist {
+            self.persist_expectations().await;
+        }
+    }
+
+    /// The registered expectations as `/_setup` requests, e.g. to save them in a file
+    ///
+    /// The result is a valid mapping file and can be loaded again with
+    /// [`Self::import_expectations`]. Body files are kept as references.
+    /// Expectations relying on closures (`respond_with`, conditional
+    /// responses, custom matchers) cannot be written down and are left out.
+    pub async fn export_expectations(&self) -> Vec<CreateExpectationRequest> {
+        self.get_expectations()
+            .await
+            .into_iter()
+            .filter(|expectation| {
+                expectation.custom_matchers.is_empty()
+                    && expectation.response.conditional_id.is_none()
+                    && expectation.response.responder_id.is_none()
+            })
+            .map(CreateExpectationRequest::from)
+            .collect()
+    }
+
+    /// Registers the expectations of an exported document
+    ///
+    /// Nothing is registered if one of them is invalid; the error names its
+    /// position. With `replace` all current expectations are removed first.
+    /// The expectations count as created via `/_setup`.
+    pub async fn import_expectations(
+        &self,
+        requests: Vec<CreateExpectationRequest>,
+        replace: bool,
+    ) -> Result<Vec<MockExpectation>, String> {
+        for (index, request) in requests.iter().enumerate() {
+            request
+                .validate()
+                .map_err(|e| format!("expectation {}: {}", index + 1, e))?;
+        }
+        let imported: Vec<MockExpectation> =
+            requests.into_iter().map(MockExpectation::from).collect();
+
+        self.add_expectations(imported.clone(), replace).await;
+
+        Ok(imported)
+    }
+
+    /// Unregisters the expectation with `id`, `false` if there is none
+    pub async fn remove_expectation(&self, id: &str) -> bool {
+        let mut expectations = self.expectations.write().await;
+        let mut removed = None;
+        for exps in expectations.values_mut() {
+            if let Some(index) = exps.iter().position(|exp| exp.id == id) {
+                removed = Some(exps.remove(index));
+                break;
+            }
+        }
+        expectations.retain(|_, exps| !exps.is_empty());
+        drop(expectations);
+
+        let Some(removed) = removed else {
+            return false;
+        };
+        self.rate_limits.lock().await.remove(id);
+        self.response_counters.lock().await.remove(id);
+        self.hit_counts.lock().await.remove(id);
+        self.serve_times.lock().await.remove(id);
+        if removed.source == ExpectationSource::SetupApi {
+            self.persist_expectations().await;
+        }
+        true
+    }
+
+    /// Swaps the expectation with `id` for `expectation`, `false` if there is none
+    ///
+    /// The replacement keeps the id and, when the method is unchanged, its
+    /// position among the other expectations. Hit counts, rate limits and
+    /// sequence progress start over.
+    pub async fn replace_expectation(&self, id: &str, mut expectation: MockExpectation) -> bool {
+        expectation.id = id.to_string();
+        expectation.compile_regex_if_needed();
+
+        let mut expectations = self.expectations.write().await;
+        let mut replaced = None;
+        for exps in expectations.values_mut() {
+            if let Some(index) = exps.iter().position(|exp| exp.id == id) {
+                if exps[index].method == expectation.method {
+                    replaced = Some(std::mem::replace(&mut exps[index], expectation.clone()));
+                } else {
+                    replaced = Some(exps.remove(index));
+                }
+                break;
+            }
+        }
+        let Some(replaced) = replaced else {
+            return false;
+        };
+        if replaced.method != expectation.method {
+            expectations
+                .entry(expectation.method.clone())
+                .or_insert_with(Vec::new)
+                .push(expectation.clone());
+        }
+        expectations.retain(|_, exps| !exps.is_empty());
+        drop(expectations);
+
+        self.rate_limits.lock().await.remove(id);
+        self.response_counters.lock().await.remove(id);
+        self.hit_counts.lock().await.remove(id);
+        self.serve_times.lock().await.remove(id);
+        if replaced.source == ExpectationSource::SetupApi
+            || expectation.source == ExpectationSource::SetupApi
+        {
+            self.persist_expectations().await;
+        }
+        true
+    }
+
+    /// Adds an expectation received from a peer unless it is already known
+    /// Swaps all expectations for the ones a peer imported with `replace`
+    pub(crate) async fn replace_replicated_expectations(&self, expectations: Vec<MockExpectation>) {
+        self.add_expectations(expectations, true).await;
+    }
+
+    pub(crate) async fn add_replicated_expectation(&self, expectation: MockExpectation) {
+        let known = self
+            .expectations
+            .read()
+            .await
+            .get(&expectation.method)
+            .is_some_and(|exps| exps.iter().any(|e| e.id == expectation.id));
+
+        if !known {
+            self.add_expectation(expectation).await;
+        }
+    }
+
+    /// Registers the expectations in the mappings directory
+    ///
+    /// Expectations from an earlier load are replaced, so calling this again
+    /// picks up edited files. Returns how many expectations were loaded.
+    pub async fn load_mappings(&self) -> Result<usize, MappingError> {
+        let loaded = if self.mappings_dir.is_dir() {
+            mappings::read_mappings(&self.mappings_dir)?
+        } else {
+            Vec::new()
+        };
+
+        {
+            let mut expectations = self.expectations.write().await;
+            for exps in expectations.values_mut() {
+                exps.retain(|exp| exp.source != ExpectationSource::Mapping);
+            }
+            expectations.retain(|_, exps| !exps.is_empty());
+        }
+
+        let count = loaded.len();
+        for request in loaded {
+            let mut expectation = MockExpectation::from(request);
+            expectation.source = ExpectationSource::Mapping;
+            self.add_expectation(expectation).await;
+        }
+        if count > 0 {
+            info!(
+                "Loaded {} expectations from {}",
+                count,
+                self.mappings_dir.display()
+            );
+        }
+        Ok(count)
+    }
+
+    /// Reads the mapping files and all response files again
+    ///
+    /// Cached response files are dropped first, so edited fixtures are
+    /// served from now on. Invalid mapping files keep the previous mappings.
+    pub async fn reload(&self) -> Result<(), StartupError> {
+        self.load_mappings().await?;
+
+        {
+            let mut expectations = self.expectations.write().await;
+            for exp in expectations.values_mut().flat_map(|exps| exps.iter_mut()) {
+                if exp.response.body_file.is_some() {
+                    exp.response.cached_file_content = None;
+                    exp.response.cached_json_content = None;
+                    exp.response.cached_file_bytes = None;
+                }
+            }
+        }
+
+        let errors = self.load_fixtures().await;
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(StartupError::Fixtures(errors))
+        }
+    }
+
+    /// Directories hot reload watches, the resources and an outside mappings directory
+    fn watched_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs = vec![self.resource_dir.clone()];
+        if self.mappings_dir.is_dir() && !self.mappings_dir.starts_with(&self.resource_dir) {
+            dirs.push(self.mappings_dir.clone());
+        }
+        dirs
+    }
+
+    /// Registers an expectation for every operation of an OpenAPI 3 document
+    ///
+    /// `path` may be JSON or YAML. Responses use the examples of the first
+    /// successful response or sample data synthesized from its schema, see
+    /// `mimic-rs import openapi`. The document is also used by
+    /// [`Self::with_request_validation`]. Returns how many expectations were added.
+    pub async fn load_openapi<P: AsRef<Path>>(&self, path: P) -> Result<usize, ImportError> {
+        let document = import::read_document(path.as_ref())?;
+        let import = import::openapi::convert(&document)?;
+        let validator = OpenApiValidator::new(document)?;
+        self.openapi.write().await.push(validator);
+
+        let count = import.expectations.len();
+        for request in import.expectations {
+            let mut expectation = MockExpectation::from(request);
+            expectation.source = ExpectationSource::Builder;
+            self.add_expectation(expectation).await;
+        }
+        info!(
+            "Loaded {} expectations from {}",
+            count,
+            path.as_ref().display()
+        );
+        Ok(count)
+    }
+
+    /// Registers the stubs of a WireMock root directory
+    ///
+    /// Reads every mapping file below `root_dir/mappings`, translating the
+    /// matchers it can (see `mimic-rs import wiremock`). Files referenced via
+    /// `bodyFileName` are read from `root_dir/__files` once, when loading.
+    /// Returns how many expectations were added.
+    pub async fn load_wiremock<P: AsRef<Path>>(&self, root_dir: P) -> Result<usize, ImportError> {
+        let root_dir = root_dir.as_ref();
+        if !root_dir.is_dir() {
+            return Err(ImportError::Invalid(format!(
+                "{} is not a directory",
+                root_dir.display()
+            )));
+        }
+        let import = import::import_file(ImportFormat::WireMock, root_dir)?;
+
+        let count = self.add_import(import).await;
+        info!("Loaded {} expectations from {}", count, root_dir.display());
+        Ok(count)
+    }
+
+    /// Registers an expectation for every request recorded in a HAR archive
+    ///
+    /// Requests are matched on method, path and query string and answer with
+    /// the recorded status, headers and body; a request recorded several times
+    /// gets its responses in the recorded order. Returns how many expectations
+    /// were added.
+    pub async fn load_har<P: AsRef<Path>>(&self, path: P) -> Result<usize, ImportError> {
+        self.load_har_file(path.as_ref(), false).await
+    }
+
+    /// Like [`Self::load_har`], but delays each response by its recorded server wait time
+    pub async fn load_har_with_latency<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<usize, ImportError> {
+        self.load_har_file(path.as_ref(), true).await
+    }
+
+    async fn load_har_file(&self, path: &Path, keep_latency: bool) -> Result<usize, ImportError> {
+        let import = import::import_har(path, keep_latency)?;
+
+        let count = self.add_import(import).await;
+        info!("Loaded {} expectations from {}", count, path.display());
+        Ok(count)
+    }
+
+    /// Registers converted expectations, serving their fixtures from memory
+    async fn add_import(&self, import: Import) -> usize {
+        let count = import.expectations.len();
+        for request in &import.expectations {
+            let mut expectation = MockExpectation::from(request.clone());
+            expectation.source = ExpectationSource::Builder;
+            import.inline_fixture(&mut expectation.response);
+            for response in &mut expectation.rotate {
+                import.inline_fixture(response);
+            }
+            self.add_expectation(expectation).await;
+        }
+        count
+    }
+
+    /// The violations of `request` if validation is enabled and a loaded document describes it
+    pub(crate) async fn validate_request(
+        &self,
+        request: &IncomingRequest,
+    ) -> Option<ValidationReport> {
+        if !self.validate_requests {
+            return None;
+        }
+        let validators = self.openapi.read().await;
+        validators
+            .iter()
+            .find_map(|validator| validator.validate(request))
+    }
+
+    /// Writes the runtime expectations to the persistence directory, if any
+    ///
+    /// Saves run one at a time, so a later snapshot is never overwritten by
+    /// an earlier one.
+    async fn persist_expectations(&self) {
+        let Some(dir) = &self.persistence_dir else {
+            return;
+        };
+
+        let _saving = self.persist_lock.lock().await;
+        let expectations = self.get_expectations().await;
+        let target = dir.clone();
+        let saved = tokio::task::spawn_blocking(move || persistence::save(&target, &expectations))
+            .await
+            .unwrap_or_else(|e| Err(io::Error::other(e)));
+        if let Err(e) = saved {
+            error!("Failed to persist expectations to {}: {}", dir.display(), e);
+        }
+    }
 
-        expectations
-            .entry(expectation.method.clone())
-            .or_insert_with(Vec::new)
-            .push(expectation);
+    /// Registers the expectations saved by a previous run
+    async fn restore_persisted_expectations(&self) {
+        let Some(dir) = &self.persistence_dir else {
+            return;
+        };
+
+        match persistence::load(dir) {
+            Ok(restored) => {
+                if !restored.is_empty() {
+                    info!(
+                        "Restored {} expectations from {}",
+                        restored.len(),
+                        dir.display()
+                    );
+                }
+                let mut expectations = self.expectations.write().await;
+                for expectation in restored {
+                    let registered = expectations
+                        .entry(expectation.method.clone())
+                        .or_insert_with(Vec::new);
+                    if !registered.iter().any(|e| e.id == expectation.id) {
+                        registered.push(expectation);
+                    }
+                }
+            }
+            Err(e) => error!(
+                "Failed to restore expectations from {}: {}",
+                dir.display(),
+                e
+            ),
+        }
     }
 
     pub(crate) async fn record_request(
         &self,
+        id: &str,
         method: String,
         path: String,
         query_params: &HashMap<String, String>,
         headers: &HashMap<String, String>,
         body: Option<&str>,
     ) {
-        let record = RequestRecord::new(
+        let mut record = RequestRecord::new(
             method,
             path,
             query_params.clone(),
             headers.clone(),
             body.map(String::from),
         );
+        record.id = id.to_string();
+        record.timestamp = self.clock.now();
+        self.log_request(record).await;
+    }
+
+    /// Records a request whose body was written to the capture directory
+    pub(crate) async fn record_upload(
+        &self,
+        id: &str,
+        method: String,
+        path: String,
+        query_params: &HashMap<String, String>,
+        headers: &HashMap<String, String>,
+        upload: CapturedUpload,
+    ) {
+        let mut record =
+            RequestRecord::new(method, path, query_params.clone(), headers.clone(), None);
+        record.id = id.to_string();
+        record.timestamp = self.clock.now();
+        record.upload = Some(upload);
+        self.log_request(record).await;
+    }
+
+    /// Attaches the response to the logged `method` request with the given id
+    ///
+    /// The response counts towards the metrics and the stats of the
+    /// expectation that sent it. Attaching does nothing if the record was
+    /// trimmed from the log in the meantime.
+    pub(crate) async fn record_response(
+        &self,
+        id: &str,
+        method: &str,
+        mut response: RecordedResponse,
+    ) {
+        self.redact(&mut response.headers);
+        self.metrics.lock().await.observe(method, &response);
+        if let Some(expectation_id) = &response.expectation_id {
+            let now = self.clock.now();
+            self.serve_times
+                .lock()
+                .await
+                .entry(expectation_id.clone())
+                .and_modify(|times| times.record(now, response.latency_ms))
+                .or_insert_with(|| ServeTimes::new(now, response.latency_ms));
+        }
+
+        if let Some(journal) = &self.journal {
+            let (id, response) = (id.to_string(), response.clone());
+            if let Err(e) = journal_io(journal, move |journal| {
+                journal.append_response(&id, &response)
+            })
+            .await
+            {
+                error!("Failed to write to {}: {}", journal.path().display(), e);
+            }
+        }
+
+        let mut request_log = self.request_log.write().await;
+        if let Some(record) = request_log.iter_mut().rev().find(|record| record.id == id) {
+            record.response = Some(response);
+            // Fails only when nobody is subscribed
+            let _ = self.request_events.send(record.clone());
+        }
+    }
+
+    /// Receives every request to a mocked path once its response is ready
+    ///
+    /// Only requests logged after subscribing are delivered. A subscriber
+    /// falling more than 256 requests behind skips the oldest ones and gets
+    /// [`broadcast::error::RecvError::Lagged`]. Served as `/_requests/stream`.
+    pub fn subscribe_requests(&self) -> broadcast::Receiver<RequestRecord> {
+        self.request_events.subscribe()
+    }
+
+    /// Resolves once the running server starts shutting down
+    pub(crate) async fn stopping(&self) {
+        let mut stopping = self.stopping.subscribe();
+        let _ = stopping.wait_for(|stopping| *stopping).await;
+    }
+
+    /// Saves an uploaded body if capturing is enabled and the body qualifies
+    pub(crate) async fn capture_upload(
+        &self,
+        content_type: Option<&str>,
+        bytes: &[u8],
+    ) -> Option<CapturedUpload> {
+        let dir = self.capture_dir.as_ref()?;
+        if !capture::is_upload(content_type, bytes) {
+            return None;
+        }
+        match capture::save(dir, content_type, bytes).await {
+            Ok(upload) => Some(upload),
+            Err(e) => {
+                error!("Failed to capture upload in {}: {}", dir.display(), e);
+                None
+            }
+        }
+    }
+
+    /// Writes the access log line for a served request, if enabled
+    pub(crate) fn log_access(
+        &self,
+        method: &str,
+        path: &str,
+        mut headers: HashMap<String, String>,
+        response: &RecordedResponse,
+    ) {
+        let Some(access_log) = &self.access_log else {
+            return;
+        };
+        self.redact(&mut headers);
+
+        let entry = AccessLogEntry {
+            timestamp: self.clock.now(),
+            method: method.to_string(),
+            path: path.to_string(),
+            status: response.status,
+            latency_ms: response.latency_ms,
+            expectation_id: response.expectation_id.clone(),
+            headers,
+        };
+        if let Err(e) = access_log.write(&entry) {
+            error!("Failed to write the access log: {}", e);
+        }
+    }
+
+    /// Replaces the values of the redacted headers in `headers`
+    fn redact(&self, headers: &mut HashMap<String, String>) {
+        for (name, value) in headers.iter_mut() {
+            if self
+                .redacted_headers
+                .iter()
+                .any(|redacted| redacted.eq_ignore_ascii_case(name))
+            {
+                *value = REDACTED.to_string();
+            }
+        }
+    }
+
+    async fn log_request(&self, mut record: RequestRecord) {
+        self.redact(&mut record.headers);
+        if let Some(journal) = &self.journal {
+            let record = record.clone();
+            if let Err(e) = journal_io(journal, move |journal| journal.append(&record)).await {
+                error!("Failed to write to {}: {}", journal.path().display(), e);
+            }
+        }
 
         let mut request_log = self.request_log.write().await;
         request_log.push(record);
@@ -152,10 +1662,142 @@ impl MockServer {
         }
     }
 
+    /// Remembers a request that no expectation answered
+    pub(crate) async fn record_unmatched(
+        &self,
+        request: &IncomingRequest,
+        closest: Option<NearMiss>,
+    ) {
+        let mut record = RequestRecord::new(
+            request.method.clone(),
+            request.path.clone(),
+            request.query_params.clone(),
+            request.headers.clone(),
+            request.body.clone(),
+        );
+        record.timestamp = self.clock.now();
+        self.redact(&mut record.headers);
+
+        let mut unmatched = self.unmatched.write().await;
+        unmatched.push(UnmatchedRequest {
+            request: record,
+            closest,
+        });
+        if unmatched.len() > self.max_request_log_size {
+            let to_remove = unmatched.len() - self.max_request_log_size;
+            unmatched.drain(0..to_remove);
+        }
+    }
+
+    /// Sends `callback` for the expectation `expectation_id` in the background
+    ///
+    /// The delivery is logged once the receiver answered or failed.
+    pub(crate) fn send_callback(&self, expectation_id: &str, callback: Callback) {
+        let server = self.clone();
+        let expectation_id = expectation_id.to_string();
+        tokio::spawn(async move {
+            tokio::time::sleep(callback.delay()).await;
+            let delivery = server.deliver_callback(expectation_id, callback).await;
+            let mut deliveries = server.callback_deliveries.write().await;
+            deliveries.push(delivery);
+            if deliveries.len() > server.max_request_log_size {
+                let to_remove = deliveries.len() - server.max_request_log_size;
+                deliveries.drain(0..to_remove);
+            }
+        });
+    }
+
+    async fn deliver_callback(
+        &self,
+        expectation_id: String,
+        callback: Callback,
+    ) -> CallbackDelivery {
+        let body = callback.body.as_ref().map(|body| match body {
+            serde_json::Value::String(text) => text.clone(),
+            json => json.to_string(),
+        });
+        let mut delivery = CallbackDelivery {
+            expectation_id,
+            method: callback.method.clone(),
+            url: callback.url.clone(),
+            body: body.clone(),
+            timestamp: self.clock.now(),
+            status: None,
+            error: None,
+        };
+
+        let method = match reqwest::Method::from_bytes(callback.method.as_bytes()) {
+            Ok(method) => method,
+            Err(e) => {
+                warn!("Callback to {} not sent: {}", callback.url, e);
+                delivery.error = Some(e.to_string());
+                return delivery;
+            }
+        };
+        let mut request = self.callback_client.request(method, &callback.url);
+        for (name, value) in &callback.headers {
+            request = request.header(name, value);
+        }
+        if let Some(body) = body {
+            let has_content_type = callback
+                .headers
+                .keys()
+                .any(|name| name.eq_ignore_ascii_case("content-type"));
+            if !has_content_type && !matches!(callback.body, Some(serde_json::Value::String(_))) {
+                request = request.header("Content-Type", "application/json");
+            }
+            request = request.body(body);
+        }
+
+        match request.send().await {
+            Ok(response) => {
+                info!(
+                    "Callback {} {} answered {}",
+                    callback.method,
+                    callback.url,
+                    response.status()
+                );
+                delivery.status = Some(response.status().as_u16());
+            }
+            Err(e) => {
+                warn!(
+                    "Callback {} {} failed: {}",
+                    callback.method, callback.url, e
+                );
+                delivery.error = Some(e.to_string());
+            }
+        }
+        delivery
+    }
+
+    /// Callbacks sent after matches, oldest first
+    pub async fn callback_deliveries(&self) -> Vec<CallbackDelivery> {
+        self.callback_deliveries.read().await.clone()
+    }
+
+    /// Forgets the sent callbacks
+    pub async fn clear_callback_deliveries(&self) {
+        self.callback_deliveries.write().await.clear();
+    }
+
+    /// The request log with the served responses as a HAR 1.2 archive
+    ///
+    /// Open it in the browser devtools or feed it back via [`Self::load_har`].
+    pub async fn export_har(&self) -> serde_json::Value {
+        import::to_har(&self.get_request_log().await)
+    }
+
+    /// Requests no expectation answered, oldest first
+    pub async fn unmatched_requests(&self) -> Vec<UnmatchedRequest> {
+        self.unmatched.read().await.clone()
+    }
+
     /// Clears only the request log without affecting expectations
     pub async fn clear_request_log(&self) {
         let mut request_log = self.request_log.write().await;
         request_log.clear();
+        self.unmatched.write().await.clear();
+        self.clear_journal().await;
     }
 
     pub async fn reset(&self) {
@@ -168,6 +1810,24 @@ impl MockServer {
             let mut request_log = self.request_log.write().await;
             request_log.clear();
         }
+        self.unmatched.write().await.clear();
+        self.callback_deliveries.write().await.clear();
+        self.clear_journal().await;
+        self.persist_expectations().await;
+
+        self.rate_limits.lock().await.clear();
+        self.response_counters.lock().await.clear();
+        self.hit_counts.lock().await.clear();
+        self.serve_times.lock().await.clear();
+        self.state.clear().await;
+        self.random.restart();
+        self.scenarios.write().await.clear();
+        if let Some(proxy) = &self.proxy {
+            proxy.clear_recordings();
+        }
+        if let Some(idempotency) = &self.idempotency {
+            idempotency.clear().await;
+        }
     }
 
     pub async fn get_expectations(&self) -> Vec<MockExpectation> {
@@ -178,6 +1838,24 @@ impl MockServer {
             .collect()
     }
 
+    /// The expectation with `id`, if registered
+    pub async fn get_expectation(&self, id: &str) -> Option<MockExpectation> {
+        let expectations = self.expectations.read().await;
+        expectations
+            .values()
+            .flat_map(|v| v.iter())
+            .find(|exp| exp.id == id)
+            .cloned()
+    }
+
+    async fn clear_journal(&self) {
+        if let Some(journal) = &self.journal
+            && let Err(e) = journal_io(journal, |journal| journal.clear()).await
+        {
+            error!("Failed to clear {}: {}", journal.path().display(), e);
+        }
+    }
+
     /// Get expectations for a specific method (performance optimization)
     pub async fn get_expectations_by_method(&self, method: &str) -> Vec<MockExpectation> {
         let expectations = self.expectations.read().await;
@@ -188,48 +1866,472 @@ impl MockServer {
     }
 
     pub async fn get_request_log(&self) -> Vec<RequestRecord> {
+        self.requests_where(None, |_| true).await
+    }
+
+    /// The recorded requests selected by `query`, including journaled ones
+    pub async fn find_requests(&self, query: &RequestQuery) -> Vec<RequestRecord> {
+        self.query_requests(query).await.0
+    }
+
+    /// The page of requests selected by `query` and the number of matching requests
+    pub(crate) async fn query_requests(&self, query: &RequestQuery) -> (Vec<RequestRecord>, usize) {
+        if let Some(journal) = &self.journal {
+            let selected = query.clone();
+            match journal_io(journal, move |journal| journal.query(&selected)).await {
+                Ok(page) => return page,
+                Err(e) => error!("Failed to read {}: {}", journal.path().display(), e),
+            }
+        }
+
         let request_log = self.request_log.read().await;
-        request_log.clone()
+        query.apply(request_log.clone())
     }
 
-    pub async fn count_calls(&self, method: &str, path: &str) -> usize {
+    /// The recorded requests accepted by `predicate`, oldest first
+    ///
+    /// With a journal only the records for `method`, if given, are looked at
+    /// and only the accepted ones are read into memory.
+    pub(crate) async fn requests_where(
+        &self,
+        method: Option<&str>,
+        predicate: impl Fn(&RequestRecord) -> bool + Send + Sync + 'static,
+    ) -> Vec<RequestRecord> {
+        let predicate = Arc::new(predicate);
+        if let Some(journal) = &self.journal {
+            let predicate = predicate.clone();
+            let method = method.map(String::from);
+            match journal_io(journal, move |journal| {
+                journal.read_where(method.as_deref(), &*predicate)
+            })
+            .await
+            {
+                Ok(records) => return records,
+                Err(e) => error!("Failed to read {}: {}", journal.path().display(), e),
+            }
+        }
+
         let request_log = self.request_log.read().await;
         request_log
             .iter()
-            .filter(|r| r.method == method && r.path == path)
-            .count()
+            .filter(|r| predicate(r))
+            .cloned()
+            .collect()
+    }
+
+    /// Counts the calls to `method` and `path`
+    ///
+    /// `path` is matched like an expectation's, so `/api/users/*` and
+    /// `/api/users/{id}` count the calls to every user. The method `*`
+    /// matches any method.
+    pub async fn count_calls(&self, method: &str, path: &str) -> usize {
+        self.count_calls_matching(method, path, &RequestConditions::default())
+            .await
+    }
+
+    /// Counts the calls to `method` and `path` that also meet `conditions`
+    ///
+    /// E.g. the POSTs to `/orders` whose body contains `sku=X`.
+    pub async fn count_calls_matching(
+        &self,
+        method: &str,
+        path: &str,
+        conditions: &RequestConditions,
+    ) -> usize {
+        let pattern = Arc::new(RequestPattern::new(method, path));
+        let method = pattern.method().map(String::from);
+        let conditions = Arc::new(conditions.clone());
+        let matches = move |r: &RequestRecord| pattern.matches(r) && conditions.matches(r);
+
+        if let Some(journal) = &self.journal {
+            let matches = matches.clone();
+            match journal_io(journal, move |journal| {
+                journal.count_where(method.as_deref(), matches)
+            })
+            .await
+            {
+                Ok(count) => return count,
+                Err(e) => error!("Failed to read {}: {}", journal.path().display(), e),
+            }
+        }
+
+        let request_log = self.request_log.read().await;
+        request_log.iter().filter(|r| matches(r)).count()
+    }
+
+    /// Starts an assertion on the recorded requests matching `method` and `path`
+    ///
+    /// `path` may contain `*` wildcards and `{name}` segments, the method `*`
+    /// matches any method.
+    /// See [`VerificationBuilder`] for an example.
+    pub fn verify(&self, method: &str, path: &str) -> VerificationBuilder {
+        VerificationBuilder::new(self.clone(), method, path)
+    }
+
+    /// Fails unless at least `min` recorded requests match `method` and `path`
+    ///
+    /// Shorthand for `verify(method, path).at_least(min).check()`, e.g. for
+    /// clients that may retry.
+    pub async fn verify_at_least(
+        &self,
+        method: &str,
+        path: &str,
+        min: usize,
+    ) -> Result<(), VerificationError> {
+        self.verify(method, path).at_least(min).check().await
+    }
+
+    /// Fails if more than `max` recorded requests match `method` and `path`
+    pub async fn verify_at_most(
+        &self,
+        method: &str,
+        path: &str,
+        max: usize,
+    ) -> Result<(), VerificationError> {
+        self.verify(method, path).at_most(max).check().await
+    }
+
+    /// Fails unless `min` to `max` (inclusive) recorded requests match `method` and `path`
+    pub async fn verify_between(
+        &self,
+        method: &str,
+        path: &str,
+        min: usize,
+        max: usize,
+    ) -> Result<(), VerificationError> {
+        self.verify(method, path).between(min, max).check().await
+    }
+
+    /// Fails unless the steps occurred in the request log in the given order
+    ///
+    /// Other requests may come before, between and after the steps, e.g.
+    /// `verify_sequence([("POST", "/auth"), ("GET", "/data")])` passes when
+    /// `/auth` was called before `/data`. Paths may contain `*` wildcards and
+    /// `{name}` segments.
+    pub async fn verify_sequence<S: Into<SequenceStep>>(
+        &self,
+        steps: impl IntoIterator<Item = S>,
+    ) -> Result<(), VerificationError> {
+        let steps: Vec<SequenceStep> = steps.into_iter().map(Into::into).collect();
+        let mut requests = self.get_request_log().await.into_iter();
+        let mut matched = Vec::new();
+
+        for (index, step) in steps.into_iter().enumerate() {
+            let pattern = RequestPattern::new(&step.method, &step.path);
+            match requests.find(|r| pattern.matches(r) && step.conditions.matches(r)) {
+                Some(request) => matched.push(request),
+                None => {
+                    return Err(VerificationError::OutOfOrder {
+                        step: index + 1,
+                        method: step.method,
+                        path: step.path,
+                        matched,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Fails if an expectation built with `times` answered fewer requests
+    ///
+    /// Catches stubs a test silently never hit; expectations without an
+    /// expected call count are not checked.
+    pub async fn verify_all(&self) -> Result<(), VerificationErrors> {
+        let expectations = self.expectations.read().await;
+        let hit_counts = self.hit_counts.lock().await;
+        let unmet = unmet_expectations(&expectations, &hit_counts);
+        if unmet.is_empty() {
+            return Ok(());
+        }
+        Err(VerificationErrors(unmet))
+    }
+
+    /// Returns a guard that panics on drop while expectations are unmet
+    ///
+    /// Keep it alive for the duration of the test, like mockito's mocks:
+    /// `let _guard = server.verify_on_drop();`. It does not panic while the
+    /// thread is already unwinding.
+    pub fn verify_on_drop(&self) -> VerifyOnDrop {
+        VerifyOnDrop::new(self.clone())
+    }
+
+    /// `verify_all` for synchronous callers, `None` while the state is locked
+    fn try_unmet_expectations(&self) -> Option<Vec<VerificationError>> {
+        let expectations = self.expectations.try_read().ok()?;
+        let hit_counts = self.hit_counts.try_lock().ok()?;
+        Some(unmet_expectations(&expectations, &hit_counts))
+    }
+
+    /// Fails if any recorded request matches `method` and `path`
+    ///
+    /// `path` may contain `*` wildcards and `{name}` segments, the method `*`
+    /// matches any method.
+    pub async fn verify_no_requests_to(
+        &self,
+        method: &str,
+        path: &str,
+    ) -> Result<(), VerificationError> {
+        let pattern = RequestPattern::new(method, path);
+        let requests = self
+            .requests_where(pattern.method().map(String::from).as_deref(), move |r| {
+                pattern.matches(r)
+            })
+            .await;
+        if requests.is_empty() {
+            return Ok(());
+        }
+        Err(VerificationError::UnwantedRequests {
+            method: method.to_string(),
+            path: path.to_string(),
+            requests,
+        })
+    }
+
+    /// Fails if a recorded request matches none of the allowed `(method, path)` pairs
+    ///
+    /// Paths may contain `*` wildcards and `{name}` segments, the method `*`
+    /// matches any method.
+    pub async fn verify_no_unexpected_requests(
+        &self,
+        allowed: &[(&str, &str)],
+    ) -> Result<(), VerificationError> {
+        let patterns: Vec<_> = allowed
+            .iter()
+            .map(|(method, path)| RequestPattern::new(method, path))
+            .collect();
+        let requests: Vec<_> = self
+            .get_request_log()
+            .await
+            .into_iter()
+            .filter(|r| !patterns.iter().any(|pattern| pattern.matches(r)))
+            .collect();
+        if requests.is_empty() {
+            return Ok(());
+        }
+        Err(VerificationError::UnexpectedRequests(requests))
     }
 
     pub fn resource_dir(&self) -> &PathBuf {
         &self.resource_dir
     }
 
+    pub fn admin_enabled(&self) -> bool {
+        self.admin_enabled
+    }
+
+    pub fn shutdown_endpoint_enabled(&self) -> bool {
+        self.shutdown_endpoint
+    }
+
+    pub fn method_not_allowed_enabled(&self) -> bool {
+        self.method_not_allowed
+    }
+
+    pub fn not_found_response(&self) -> Option<&MockResponse> {
+        self.not_found_response.as_deref()
+    }
+
+    pub fn not_found_diagnostics_enabled(&self) -> bool {
+        self.not_found_diagnostics
+    }
+
+    /// The file a static directory serves for `path`, the first match wins
+    pub(crate) fn static_file(&self, path: &str) -> Option<PathBuf> {
+        self.static_dirs.iter().find_map(|dir| dir.resolve(path))
+    }
+
+    /// The source of the server's randomness, seeded with `with_seed`
+    ///
+    /// Use it in `respond_with` closures to keep them reproducible as well.
+    pub fn random(&self) -> &Random {
+        &self.random
+    }
+
+    pub(crate) fn scripts(&self) -> &ScriptRunner {
+        &self.scripts
+    }
+
+    /// The clock of the server, for closures that need the time without a request
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
+    pub fn proxy(&self) -> Option<&Proxy> {
+        self.proxy.as_deref()
+    }
+
+    /// The exchanges recorded in proxy mode as expectations and body files
+    ///
+    /// Write them with `Import::write_to` to get mapping files the server
+    /// loads on start. Empty without `proxy_to`.
+    pub fn export_recordings(&self) -> Import {
+        self.proxy
+            .as_ref()
+            .map(|proxy| proxy.recordings())
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn idempotency(&self) -> Option<&IdempotencyStore> {
+        self.idempotency.as_deref()
+    }
+
+    pub fn cors_config(&self) -> Option<&CorsConfig> {
+        self.cors.as_deref()
+    }
+
+    /// The CORS policy of the best ranked expectation for `method` and `path` that has one
+    pub(crate) async fn expectation_cors(&self, method: &str, path: &str) -> Option<CorsPolicy> {
+        let expectations = self.expectations.read().await;
+        expectations
+            .get(&method.to_uppercase())?
+            .iter()
+            .filter(|exp| exp.cors.is_some() && exp.matches_path(path))
+            .max_by_key(|exp| exp.rank())
+            .and_then(|exp| exp.cors.clone())
+    }
+
+    /// Runs the registered transformations on `request`
+    pub(crate) fn transform_request(&self, request: &mut IncomingRequest) {
+        for transformer in &self.transformers {
+            transformer.apply(request);
+        }
+    }
+
+    /// Makes `extension` available to expectations under its name
+    ///
+    /// Expectations refer to it with `ExpectationBuilder::matcher` or
+    /// `ResponseBuilder::generate`, or the `matchers`/`generator` fields of
+    /// `/_setup`. Registering the same name again replaces the extension.
+    pub fn register_extension<E: Extension>(&self, extension: E) {
+        info!("Registered extension '{}'", extension.name());
+        self.extensions.register(Arc::new(extension));
+    }
+
+    pub fn extensions(&self) -> &ExtensionRegistry {
+        &self.extensions
+    }
+
+    pub(crate) fn has_response_transformers(&self) -> bool {
+        !self.response_transformers.is_empty()
+    }
+
+    /// Runs the global response transformations on `response`
+    pub(crate) fn transform_response(&self, response: &mut MockResponse) {
+        for transformer in &self.response_transformers {
+            transformer.apply(response);
+        }
+    }
+
+    /// Runs the global response transformations that leave the body alone
+    pub(crate) fn transform_response_headers(&self, response: &mut MockResponse) {
+        for transformer in &self.response_transformers {
+            if !transformer.uses_body() {
+                transformer.apply(response);
+            }
+        }
+    }
+
+    /// The active chaos configuration, `None` when chaos mode is off
+    pub async fn chaos(&self) -> Option<ChaosConfig> {
+        self.chaos.read().await.clone()
+    }
+
+    /// Enables (`Some`) or disables (`None`) chaos mode
+    pub async fn set_chaos(&self, config: Option<ChaosConfig>) {
+        match &config {
+            Some(_) => warn!("Chaos mode enabled"),
+            None => info!("Chaos mode disabled"),
+        }
+        *self.chaos.write().await = config;
+    }
+
+    /// The scheduled outages
+    pub async fn downtime(&self) -> Vec<DowntimeWindow> {
+        self.downtime.read().await.clone()
+    }
+
+    /// Schedules an outage; a recurring window without `from` starts now
+    pub async fn add_downtime(&self, window: DowntimeWindow) {
+        warn!("Downtime window scheduled");
+        self.downtime
+            .write()
+            .await
+            .push(window.anchored(self.clock.now()));
+    }
+
+    /// Removes all scheduled outages
+    pub async fn clear_downtime(&self) {
+        info!("Downtime windows cleared");
+        self.downtime.write().await.clear();
+    }
+
+    /// The first outage affecting `path` right now and how long it still lasts
+    pub(crate) async fn active_downtime(&self, path: &str) -> Option<(DowntimeWindow, Duration)> {
+        let now = self.clock.now();
+        self.downtime.read().await.iter().find_map(|window| {
+            window
+                .remaining(path, now)
+                .map(|remaining| (window.clone(), remaining))
+        })
+    }
+
+    /// The key-value state shared by all expectations
+    pub fn state(&self) -> &StateStore {
+        &self.state
+    }
+
+    pub fn oidc(&self) -> Option<&OidcProvider> {
+        self.oidc.as_deref()
+    }
+
+    pub fn cluster(&self) -> Option<&Cluster> {
+        self.cluster.as_deref()
+    }
+
+    pub fn log_handle(&self) -> Option<&LogHandle> {
+        self.log_handle.as_ref()
+    }
+
     /// Preloads content from response files to avoid repeated disk reads
     pub async fn preload_file_content(&self) {
+        for e in self.load_fixtures().await {
+            error!("Error reading file {}: {}", e.file.display(), e.source);
+        }
+    }
+
+    /// Caches the response files of all expectations, returning the failures
+    async fn load_fixtures(&self) -> Vec<FixtureError> {
         use std::fs;
-        use tracing::error;
 
         let resource_dir = self.resource_dir.clone();
         let mut expectations = self.expectations.write().await;
+        let mut errors = Vec::new();
 
         for exps in expectations.values_mut() {
             for exp in exps.iter_mut() {
-                if let Some(file_name) = &exp.response.body_file {
-                    if exp.response.cached_file_content.is_none() {
-                        let file_path = resource_dir.join(file_name);
-                        match fs::read_to_string(&file_path) {
-                            Ok(content) => {
-                                info!("Preloaded file {} for response", file_path.display());
-                                exp.response.cache_file_content(content);
-                            }
-                            Err(e) => {
-                                error!("Error reading file {}: {}", file_path.display(), e);
-                            }
+                if let Some(file_name) = &exp.response.body_file
+                    && exp.response.cached_file_content.is_none()
+                    && exp.response.cached_file_bytes.is_none()
+                {
+                    let file_path = resource_dir.join(file_name);
+                    match fs::read(&file_path) {
+                        Ok(content) => {
+                            info!("Preloaded file {} for response", file_path.display());
+                            exp.response.cache_file(content);
                         }
+                        Err(source) => errors.push(FixtureError {
+                            method: exp.method.clone(),
+                            path: exp.path.clone(),
+                            file: file_path,
+                            source,
+                        }),
                     }
                 }
             }
         }
+
+        errors
     }
 
     /// Get a conditional response by ID
@@ -243,4 +2345,54 @@ impl MockServer {
         let mut responses = self.conditional_responses.write().await;
         responses.insert(id, response);
     }
+
+    /// Get a responder registered with `respond_with` by ID
+    pub async fn get_responder(&self, id: &str) -> Option<Responder> {
+        self.responders.read().await.get(id).cloned()
+    }
+
+    /// Add a responder to the server
+    pub async fn add_responder(&self, id: String, responder: Responder) {
+        self.responders.write().await.insert(id, responder);
+    }
+}
+
+/// Runs the blocking `f` on `journal` in the blocking pool
+async fn journal_io<T, F>(journal: &Arc<RequestJournal>, f: F) -> io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&RequestJournal) -> io::Result<T> + Send + 'static,
+{
+    let journal = journal.clone();
+    tokio::task::spawn_blocking(move || f(&journal))
+        .await
+        .unwrap_or_else(|e| Err(io::Error::other(e)))
+}
+
+/// Expectations with `times` that answered fewer requests, sorted by method and path
+fn unmet_expectations(
+    expectations: &HashMap<String, Vec<MockExpectation>>,
+    hit_counts: &HashMap<String, usize>,
+) -> Vec<VerificationError> {
+    let mut unmet: Vec<_> = expectations
+        .values()
+        .flatten()
+        .filter_map(|expectation| {
+            let expected = expectation.times?;
+            let actual = hit_counts.get(&expectation.id).copied().unwrap_or(0);
+            (actual < expected).then_some((expectation, expected, actual))
+        })
+        .collect();
+    unmet.sort_by(|(a, ..), (b, ..)| (&a.method, &a.path).cmp(&(&b.method, &b.path)));
+
+    unmet
+        .into_iter()
+        .map(|(expectation, expected, actual)| VerificationError::Hits {
+            id: expectation.id.clone(),
+            method: expectation.method.clone(),
+            path: expectation.path.clone(),
+            expected,
+            actual,
+        })
+        .collect()
 }
@@ -0,0 +1,346 @@
+use axum::extract::connect_info::Connected;
+use axum::serve::{IncomingStream, Listener};
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tokio::time::Sleep;
+use tokio_rustls::TlsAcceptor;
+use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::server::TlsStream;
+use tracing::debug;
+
+use crate::models::Fault;
+
+/// What `Fault::MalformedChunk` sends: a valid head, then a bogus chunk size
+const MALFORMED_CHUNK: &[u8] =
+    b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\nnot-a-chunk-size\r\n";
+
+/// Listener that injects network-level faults into accepted connections
+pub(crate) struct FaultyListener {
+    inner: TcpListener,
+
+    accept_delay: Option<Duration>,
+
+    bandwidth: Option<Arc<Bandwidth>>,
+}
+
+impl FaultyListener {
+    pub(crate) fn new(
+        inner: TcpListener,
+        accept_delay: Option<Duration>,
+        bandwidth_limit: Option<u64>,
+    ) -> Self {
+        Self {
+            inner,
+            accept_delay,
+            bandwidth: bandwidth_limit.map(|rate| Arc::new(Bandwidth::new(rate))),
+        }
+    }
+}
+
+impl Listener for FaultyListener {
+    type Io = FaultyStream;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        let (stream, addr) = Listener::accept(&mut self.inner).await;
+        let stream = FaultyStream {
+            inner: stream,
+            // Each connection waits on its own, so a slow accept does not
+            // hold up the connections behind it
+            accept_delay: self
+                .accept_delay
+                .map(|delay| Box::pin(tokio::time::sleep(delay))),
+            bandwidth: self.bandwidth.clone(),
+            throttle: None,
+            fault: ConnectionFault::default(),
+            garbage_sent: 0,
+        };
+        (stream, addr)
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        self.inner.local_addr()
+    }
+}
+
+/// How long a client gets to complete the TLS handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Listener that terminates TLS on top of a [`FaultyListener`]
+///
+/// Handshakes run in their own tasks, so a slow or broken client does not
+/// hold up the connections accepted after it. A handshake delay is waited
+/// out before the handshake starts and does not count towards its timeout.
+pub(crate) struct TlsListener {
+    connections: mpsc::Receiver<(TlsStream<FaultyStream>, SocketAddr)>,
+
+    local_addr: SocketAddr,
+
+    accept_task: JoinHandle<()>,
+}
+
+impl TlsListener {
+    pub(crate) fn new(
+        mut inner: FaultyListener,
+        config: Arc<ServerConfig>,
+        handshake_delay: Option<Duration>,
+    ) -> io::Result<Self> {
+        let local_addr = inner.local_addr()?;
+        let acceptor = TlsAcceptor::from(config);
+        let (sender, connections) = mpsc::channel(64);
+        let accept_task = tokio::spawn(async move {
+            loop {
+                let (stream, addr) = inner.accept().await;
+                let acceptor = acceptor.clone();
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    if let Some(delay) = handshake_delay {
+                        tokio::time::sleep(delay).await;
+                    }
+                    match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                        Ok(Ok(stream)) => {
+                            let _ = sender.send((stream, addr)).await;
+                        }
+                        Ok(Err(e)) => debug!("TLS handshake with {} failed: {}", addr, e),
+                        Err(_) => debug!("TLS handshake with {} timed out", addr),
+                    }
+                });
+            }
+        });
+        Ok(Self {
+            connections,
+            local_addr,
+            accept_task,
+        })
+    }
+}
+
+impl Drop for TlsListener {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+impl Listener for TlsListener {
+    type Io = TlsStream<FaultyStream>;
+    type Addr = SocketAddr;
+
+    async fn accept(&mut self) -> (Self::Io, Self::Addr) {
+        match self.connections.recv().await {
+            Some(connection) => connection,
+            // The accept task only ends when the listener is dropped
+            None => std::future::pending().await,
+        }
+    }
+
+    fn local_addr(&self) -> io::Result<Self::Addr> {
+        Ok(self.local_addr)
+    }
+}
+
+/// Fault a handler wants applied to the connection of its request
+///
+/// Handlers get it through `ConnectInfo`; the stream breaks on the next write.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ConnectionFault(Arc<Mutex<Option<Fault>>>);
+
+impl ConnectionFault {
+    pub(crate) fn inject(&self, fault: Fault) {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner()) = Some(fault);
+    }
+
+    fn get(&self) -> Option<Fault> {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Connected<IncomingStream<'_, FaultyListener>> for ConnectionFault {
+    fn connect_info(stream: IncomingStream<'_, FaultyListener>) -> Self {
+        stream.io().fault.clone()
+    }
+}
+
+impl Connected<IncomingStream<'_, TlsListener>> for ConnectionFault {
+    fn connect_info(stream: IncomingStream<'_, TlsListener>) -> Self {
+        stream.io().get_ref().0.fault.clone()
+    }
+}
+
+/// Token bucket shared by all connections to cap the egress rate
+pub(crate) struct Bandwidth {
+    bytes_per_sec: u64,
+
+    /// Largest burst, about 100ms worth of traffic
+    capacity: f64,
+
+    bucket: Mutex<(f64, Instant)>,
+}
+
+impl Bandwidth {
+    fn new(bytes_per_sec: u64) -> Self {
+        let bytes_per_sec = bytes_per_sec.max(1);
+        let capacity = (bytes_per_sec as f64 / 10.0).max(1.0);
+        Self {
+            bytes_per_sec,
+            capacity,
+            bucket: Mutex::new((capacity, Instant::now())),
+        }
+    }
+
+    fn give_back(&self, unused: usize) {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        bucket.0 = (bucket.0 + unused as f64).min(self.capacity);
+    }
+
+    /// Takes up to `wanted` bytes from the bucket, or returns how long to wait
+    fn take(&self, wanted: usize) -> Result<usize, Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let now = Instant::now();
+        let refill = now.duration_since(bucket.1).as_secs_f64() * self.bytes_per_sec as f64;
+        bucket.0 = (bucket.0 + refill).min(self.capacity);
+        bucket.1 = now;
+
+        if bucket.0 >= 1.0 {
+            let granted = (bucket.0 as usize).min(wanted);
+            bucket.0 -= granted as f64;
+            return Ok(granted);
+        }
+
+        // Wait for a reasonable chunk instead of waking up for every byte
+        let chunk = (wanted as f64).min(self.capacity / 4.0).max(1.0);
+        Err(Duration::from_secs_f64(
+            (chunk - bucket.0) / self.bytes_per_sec as f64,
+        ))
+    }
+}
+
+/// Connection subject to the listener's faults
+///
+/// Nothing is read or written until the accept delay elapsed, writes
+/// are throttled by the shared bandwidth limit, and an injected fault
+/// replaces whatever is written next.
+pub(crate) struct FaultyStream {
+    inner: TcpStream,
+
+    accept_delay: Option<Pin<Box<Sleep>>>,
+
+    bandwidth: Option<Arc<Bandwidth>>,
+
+    throttle: Option<Pin<Box<Sleep>>>,
+
+    fault: ConnectionFault,
+
+    /// Bytes of `MALFORMED_CHUNK` already written
+    garbage_sent: usize,
+}
+
+impl FaultyStream {
+    fn poll_accept_delay(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if let Some(delay) = &mut self.accept_delay {
+            if delay.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.accept_delay = None;
+        }
+        Poll::Ready(())
+    }
+
+    /// Breaks the connection instead of writing the response
+    ///
+    /// Fails the write so the server drops the connection: with a reset,
+    /// silently, or after the malformed chunk was sent.
+    fn poll_write_fault(&mut self, fault: Fault, cx: &mut Context<'_>) -> Poll<io::Result<usize>> {
+        match fault {
+            Fault::ConnectionReset => {
+                if let Err(e) = self.inner.set_zero_linger() {
+                    return Poll::Ready(Err(e));
+                }
+                Poll::Ready(Err(io::ErrorKind::ConnectionReset.into()))
+            }
+            Fault::EmptyResponse => Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+            Fault::MalformedChunk => {
+                while self.garbage_sent < MALFORMED_CHUNK.len() {
+                    let garbage = &MALFORMED_CHUNK[self.garbage_sent..];
+                    match Pin::new(&mut self.inner).poll_write(cx, garbage) {
+                        Poll::Ready(Ok(written)) => self.garbage_sent += written,
+                        other => return other,
+                    }
+                }
+                Poll::Ready(Err(io::ErrorKind::BrokenPipe.into()))
+            }
+        }
+    }
+}
+
+impl AsyncRead for FaultyStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if this.poll_accept_delay(cx).is_pending() {
+            return Poll::Pending;
+        }
+        Pin::new(&mut this.inner).poll_read(cx, buf)
+    }
+}
+
+impl AsyncWrite for FaultyStream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+        if this.poll_accept_delay(cx).is_pending() {
+            return Poll::Pending;
+        }
+        if let Some(fault) = this.fault.get() {
+            return this.poll_write_fault(fault, cx);
+        }
+
+        let Some(bandwidth) = &this.bandwidth else {
+            return Pin::new(&mut this.inner).poll_write(cx, buf);
+        };
+        loop {
+            if let Some(throttle) = &mut this.throttle {
+                if throttle.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                this.throttle = None;
+            }
+            match bandwidth.take(buf.len()) {
+                Ok(granted) => {
+                    let result = Pin::new(&mut this.inner).poll_write(cx, &buf[..granted]);
+                    // Hand back what was not sent
+                    let written = match &result {
+                        Poll::Ready(Ok(written)) => *written,
+                        _ => 0,
+                    };
+                    if written < granted {
+                        bandwidth.give_back(granted - written);
+                    }
+                    return result;
+                }
+                Err(wait) => this.throttle = Some(Box::pin(tokio::time::sleep(wait))),
+            }
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.get_mut().inner).poll_shutdown(cx)
+    }
+}
@@ -0,0 +1,48 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::models::{ExpectationSource, MockExpectation};
+
+/// File inside the persistence directory holding the runtime expectations
+const EXPECTATIONS_FILE: &str = "expectations.json";
+
+/// Reads the persisted expectations, or none if nothing was saved yet
+pub(crate) fn load(dir: &Path) -> io::Result<Vec<MockExpectation>> {
+    let path = dir.join(EXPECTATIONS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let mut expectations: Vec<MockExpectation> = serde_json::from_str(&content)?;
+    for expectation in &mut expectations {
+        expectation.compile_regex_if_needed();
+    }
+
+    Ok(expectations)
+}
+
+/// Saves the expectations created through the HTTP API
+///
+/// Expectations defined in code are skipped; they are registered again by the
+/// code on the next start. The file is replaced atomically.
+pub(crate) fn save(dir: &Path, expectations: &[MockExpectation]) -> io::Result<()> {
+    let runtime: Vec<&MockExpectation> = expectations
+        .iter()
+        .filter(|e| e.source == ExpectationSource::SetupApi)
+        .collect();
+
+    fs::create_dir_all(dir)?;
+    let path = dir.join(EXPECTATIONS_FILE);
+    let tmp_path = dir.join(format!(
+        ".{}.{}.tmp",
+        EXPECTATIONS_FILE,
+        uuid::Uuid::new_v4()
+    ));
+    if let Err(e) = fs::write(&tmp_path, serde_json::to_vec_pretty(&runtime)?) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+    fs::rename(tmp_path, path)
+}
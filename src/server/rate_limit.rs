@@ -0,0 +1,106 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde_json::json;
+
+use crate::models::{MockResponse, RateLimit};
+
+/// Timestamps of the requests counted against a rate limited expectation
+#[derive(Debug, Default)]
+pub(crate) struct RateLimitWindow {
+    hits: VecDeque<DateTime<Utc>>,
+}
+
+/// Outcome of counting a request against a [`RateLimit`]
+#[derive(Debug)]
+pub(crate) struct RateLimitStatus {
+    limit: u32,
+    remaining: u32,
+    reset: Duration,
+    exceeded: bool,
+}
+
+impl RateLimitWindow {
+    /// Counts a request made at `now`, unless the budget is already used up
+    pub(crate) fn acquire(
+        &mut self,
+        rate_limit: &RateLimit,
+        now: DateTime<Utc>,
+    ) -> RateLimitStatus {
+        let window = rate_limit.window();
+        while let Some(oldest) = self.hits.front() {
+            if elapsed(*oldest, now) >= window {
+                self.hits.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        let exceeded = self.hits.len() >= rate_limit.limit as usize;
+        if !exceeded {
+            self.hits.push_back(now);
+        }
+
+        let reset = self
+            .hits
+            .front()
+            .map(|oldest| window.saturating_sub(elapsed(*oldest, now)))
+            .unwrap_or_default();
+
+        RateLimitStatus {
+            limit: rate_limit.limit,
+            remaining: rate_limit.limit.saturating_sub(self.hits.len() as u32),
+            reset,
+            exceeded,
+        }
+    }
+}
+
+/// Time from `since` to `now`, zero if the clock was set back
+fn elapsed(since: DateTime<Utc>, now: DateTime<Utc>) -> Duration {
+    (now - since).to_std().unwrap_or_default()
+}
+
+impl RateLimitStatus {
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.exceeded
+    }
+
+    /// Adds the `X-RateLimit-*` headers to `response`
+    pub(crate) fn apply_headers(&self, response: &mut MockResponse) {
+        let reset_secs = self.reset_secs().to_string();
+        response
+            .headers
+            .insert("X-RateLimit-Limit".to_string(), self.limit.to_string());
+        response.headers.insert(
+            "X-RateLimit-Remaining".to_string(),
+            self.remaining.to_string(),
+        );
+        response
+            .headers
+            .insert("X-RateLimit-Reset".to_string(), reset_secs);
+    }
+
+    /// The `429 Too Many Requests` answer for an exhausted budget
+    pub(crate) fn too_many_requests(&self) -> MockResponse {
+        let mut response = MockResponse::new(429)
+            .with_header("Retry-After", &self.reset_secs().to_string())
+            .with_json_body(json!({
+                "error": "rate_limit_exceeded",
+                "retry_after": self.reset_secs(),
+            }));
+        self.apply_headers(&mut response);
+        response
+    }
+
+    /// Seconds until the next request is accepted, rounded up
+    fn reset_secs(&self) -> u64 {
+        let secs = self.reset.as_secs();
+        if self.reset.subsec_nanos() > 0 {
+            secs + 1
+        } else {
+            secs
+        }
+    }
+}
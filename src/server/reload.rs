@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use super::MockServer;
+
+/// Quiet period after a change before reloading, editors save in several steps
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Reloads mapping and response files into `server` whenever they change
+///
+/// The returned task owns the watcher; aborting it stops watching.
+pub(super) fn watch(server: MockServer) -> notify::Result<JoinHandle<()>> {
+    let (changed, mut changes) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        if let Ok(event) = event
+            && is_change(&event.kind)
+        {
+            let _ = changed.send(());
+        }
+    })?;
+
+    for dir in server.watched_dirs() {
+        watcher.watch(&dir, RecursiveMode::Recursive)?;
+        info!("Watching {} for changes", dir.display());
+    }
+
+    Ok(tokio::spawn(async move {
+        let _watcher = watcher;
+        while changes.recv().await.is_some() {
+            tokio::time::sleep(DEBOUNCE).await;
+            while changes.try_recv().is_ok() {}
+
+            match server.reload().await {
+                Ok(()) => info!("Reloaded mappings and response files"),
+                Err(e) => error!("Reload failed: {}", e),
+            }
+        }
+    }))
+}
+
+/// Whether the event changed file contents, reads and metadata updates do not count
+fn is_change(kind: &EventKind) -> bool {
+    match kind {
+        EventKind::Create(_) | EventKind::Remove(_) => true,
+        EventKind::Modify(modify) => !matches!(modify, ModifyKind::Metadata(_)),
+        _ => false,
+    }
+}
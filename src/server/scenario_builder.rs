@@ -0,0 +1,88 @@
+use super::MockServer;
+use super::expectation_builder::ExpectationBuilder;
+
+/// Builder for expectations that share a scenario state machine
+///
+/// # Example
+/// ```
+/// # use mimic_rs::MockServer;
+/// # use serde_json::json;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let server = MockServer::new("./resources");
+/// let checkout = server.scenario("checkout");
+/// checkout.set_state("empty").await;
+///
+/// checkout.expect_get("/cart")
+///     .in_state("empty")
+///     .respond()
+///     .json(json!({"items": []}))
+///     .build().await;
+///
+/// checkout.expect_post("/cart/items")
+///     .in_state("empty")
+///     .respond()
+///     .status(201)
+///     .will_transition_to("filled")
+///     .build().await;
+/// # }
+/// ```
+pub struct ScenarioBuilder {
+    server: MockServer,
+
+    name: String,
+}
+
+impl ScenarioBuilder {
+    pub(crate) fn new(server: MockServer, name: &str) -> Self {
+        Self {
+            server,
+            name: name.to_string(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Starts defining an expectation that belongs to this scenario
+    pub fn expect(&self) -> ExpectationBuilder {
+        self.server.expect().in_scenario(&self.name)
+    }
+
+    /// Starts defining a GET expectation for `path`
+    pub fn expect_get(&self, path: &str) -> ExpectationBuilder {
+        self.expect().method("GET").path(path)
+    }
+
+    /// Starts defining a POST expectation for `path`
+    pub fn expect_post(&self, path: &str) -> ExpectationBuilder {
+        self.expect().method("POST").path(path)
+    }
+
+    /// Starts defining a PUT expectation for `path`
+    pub fn expect_put(&self, path: &str) -> ExpectationBuilder {
+        self.expect().method("PUT").path(path)
+    }
+
+    /// Starts defining a PATCH expectation for `path`
+    pub fn expect_patch(&self, path: &str) -> ExpectationBuilder {
+        self.expect().method("PATCH").path(path)
+    }
+
+    /// Starts defining a DELETE expectation for `path`
+    pub fn expect_delete(&self, path: &str) -> ExpectationBuilder {
+        self.expect().method("DELETE").path(path)
+    }
+
+    /// The current state (`"Started"` until the first transition)
+    pub async fn state(&self) -> String {
+        self.server.scenario_state(&self.name).await
+    }
+
+    /// Moves the scenario to `state`, e.g. to choose the initial state
+    pub async fn set_state(&self, state: &str) {
+        self.server.set_scenario_state(&self.name, state).await;
+    }
+}
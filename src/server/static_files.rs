@@ -0,0 +1,48 @@
+use std::path::{Component, Path, PathBuf};
+
+/// A directory on disk served below a path prefix
+#[derive(Clone, Debug)]
+pub(crate) struct StaticDir {
+    /// Without trailing slash, empty for the root
+    prefix: String,
+
+    dir: PathBuf,
+}
+
+impl StaticDir {
+    pub(crate) fn new(prefix: &str, dir: PathBuf) -> Self {
+        let prefix = prefix.trim_end_matches('/');
+        let prefix = if prefix.is_empty() || prefix.starts_with('/') {
+            prefix.to_string()
+        } else {
+            format!("/{}", prefix)
+        };
+        Self { prefix, dir }
+    }
+
+    /// The file serving `path`, `None` if the path is outside the prefix or
+    /// no such file exists
+    ///
+    /// Directories are served by their `index.html`. Segments leaving the
+    /// directory, such as `..`, never resolve.
+    pub(crate) fn resolve(&self, path: &str) -> Option<PathBuf> {
+        let rest = path.strip_prefix(&self.prefix)?;
+        if !rest.is_empty() && !rest.starts_with('/') {
+            return None;
+        }
+
+        let relative = Path::new(rest.trim_start_matches('/'));
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return None;
+        }
+
+        let mut file = self.dir.join(relative);
+        if file.is_dir() {
+            file.push("index.html");
+        }
+        file.is_file().then_some(file)
+    }
+}
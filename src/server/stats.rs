@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+
+/// Responses sent by an expectation, for [`crate::models::ExpectationStats`]
+#[derive(Debug)]
+pub(crate) struct ServeTimes {
+    pub(crate) last_hit: DateTime<Utc>,
+
+    responses: u32,
+
+    total_latency_ms: f64,
+}
+
+impl ServeTimes {
+    pub(crate) fn new(at: DateTime<Utc>, latency_ms: f64) -> Self {
+        Self {
+            last_hit: at,
+            responses: 1,
+            total_latency_ms: latency_ms,
+        }
+    }
+
+    /// Counts a response sent at `at` that took `latency_ms`
+    pub(crate) fn record(&mut self, at: DateTime<Utc>, latency_ms: f64) {
+        self.last_hit = at;
+        self.responses += 1;
+        self.total_latency_ms += latency_ms;
+    }
+
+    pub(crate) fn average_latency_ms(&self) -> f64 {
+        self.total_latency_ms / f64::from(self.responses)
+    }
+}
@@ -0,0 +1,33 @@
+use regex::Regex;
+
+use crate::models::{RequestRecord, compile_path_template, compile_wildcard};
+
+/// Method and path recorded requests are checked against
+///
+/// The path is matched like an expectation's, so it may contain `*`
+/// wildcards and `{name}` segments. The method `*` accepts any method.
+pub(crate) struct RequestPattern {
+    method: String,
+
+    path: Regex,
+}
+
+impl RequestPattern {
+    pub(crate) fn new(method: &str, path: &str) -> Self {
+        Self {
+            method: method.to_uppercase(),
+            path: compile_path_template(path)
+                .or_else(|_| compile_wildcard(path))
+                .expect("escaped wildcard paths are valid regexes"),
+        }
+    }
+
+    /// The method requests must have, `None` for any method
+    pub(crate) fn method(&self) -> Option<&str> {
+        (self.method != "*").then_some(self.method.as_str())
+    }
+
+    pub(crate) fn matches(&self, record: &RequestRecord) -> bool {
+        (self.method == "*" || self.method == record.method) && self.path.is_match(&record.path)
+    }
+}
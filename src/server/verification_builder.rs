@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use super::MockServer;
+use super::error::VerificationError;
+use super::verification::RequestPattern;
+use crate::models::{CallTimes, RequestConditions, ValueMatcher};
+
+/// Builder for assertions on how many matching requests were recorded and when
+///
+/// # Example
+/// ```
+/// # use mimic_rs::MockServer;
+/// # use std::time::Duration;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let server = MockServer::new("./resources");
+///
+/// // ...exercise a client that retries POST /retry...
+///
+/// let result = server.verify("POST", "/retry")
+///     .times(3)
+///     .within(Duration::from_secs(5))
+///     .at_least_apart(Duration::from_secs(1))
+///     .check().await;
+/// # assert!(result.is_err());
+/// # }
+/// ```
+pub struct VerificationBuilder {
+    server: MockServer,
+
+    method: String,
+
+    path: String,
+
+    times: Option<CallTimes>,
+
+    within: Option<Duration>,
+
+    min_gap: Option<Duration>,
+
+    conditions: RequestConditions,
+}
+
+impl VerificationBuilder {
+    pub(crate) fn new(server: MockServer, method: &str, path: &str) -> Self {
+        Self {
+            server,
+            method: method.to_string(),
+            path: path.to_string(),
+            times: None,
+            within: None,
+            min_gap: None,
+            conditions: RequestConditions::default(),
+        }
+    }
+
+    /// Only counts requests whose query parameter `name` satisfies `matcher`
+    pub fn query_param(mut self, name: &str, matcher: ValueMatcher) -> Self {
+        self.conditions = self.conditions.query_param(name, matcher);
+        self
+    }
+
+    /// Only counts requests whose header `name` satisfies `matcher`
+    pub fn header(mut self, name: &str, matcher: ValueMatcher) -> Self {
+        self.conditions = self.conditions.header(name, matcher);
+        self
+    }
+
+    /// Only counts requests whose body satisfies `matcher`
+    pub fn body(mut self, matcher: ValueMatcher) -> Self {
+        self.conditions = self.conditions.body(matcher);
+        self
+    }
+
+    /// Requires exactly `count` matching requests
+    pub fn times(mut self, count: usize) -> Self {
+        self.times = Some(CallTimes::Exactly(count));
+        self
+    }
+
+    /// Requires `min` or more matching requests
+    pub fn at_least(mut self, min: usize) -> Self {
+        self.times = Some(CallTimes::at_least(min));
+        self
+    }
+
+    /// Requires no more than `max` matching requests
+    pub fn at_most(mut self, max: usize) -> Self {
+        self.times = Some(CallTimes::at_most(max));
+        self
+    }
+
+    /// Requires between `min` and `max` matching requests, both inclusive
+    pub fn between(mut self, min: usize, max: usize) -> Self {
+        self.times = Some(CallTimes::between(min, max));
+        self
+    }
+
+    /// Requires the first and last matching request to be at most `window` apart
+    pub fn within(mut self, window: Duration) -> Self {
+        self.within = Some(window);
+        self
+    }
+
+    /// Requires consecutive matching requests to be at least `gap` apart
+    pub fn at_least_apart(mut self, gap: Duration) -> Self {
+        self.min_gap = Some(gap);
+        self
+    }
+
+    /// Checks the recorded requests against every constraint set so far
+    pub async fn check(self) -> Result<(), VerificationError> {
+        let pattern = RequestPattern::new(&self.method, &self.path);
+        let conditions = self.conditions.clone();
+        let method = pattern.method().map(String::from);
+        let mut requests = self
+            .server
+            .requests_where(method.as_deref(), move |r| {
+                pattern.matches(r) && conditions.matches(r)
+            })
+            .await;
+        requests.sort_by_key(|r| r.timestamp);
+
+        match self.times {
+            Some(times) if times.accepts(requests.len()) => {}
+            Some(CallTimes::Exactly(expected)) => {
+                return Err(VerificationError::CallCount {
+                    method: self.method,
+                    path: self.path,
+                    expected,
+                    requests,
+                });
+            }
+            Some(expected) => {
+                return Err(VerificationError::CallRange {
+                    method: self.method,
+                    path: self.path,
+                    expected,
+                    requests,
+                });
+            }
+            None => {}
+        }
+
+        if let Some(window) = self.within
+            && let (Some(first), Some(last)) = (requests.first(), requests.last())
+        {
+            let elapsed = elapsed(first.timestamp, last.timestamp);
+            if elapsed > window {
+                return Err(VerificationError::OutsideWindow {
+                    method: self.method,
+                    path: self.path,
+                    window,
+                    elapsed,
+                    requests,
+                });
+            }
+        }
+
+        if let Some(min_gap) = self.min_gap
+            && let Some(gap) = requests
+                .windows(2)
+                .map(|pair| elapsed(pair[0].timestamp, pair[1].timestamp))
+                .find(|gap| *gap < min_gap)
+        {
+            return Err(VerificationError::TooClose {
+                method: self.method,
+                path: self.path,
+                min_gap,
+                gap,
+                requests,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn elapsed(from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> Duration {
+    (to - from).to_std().unwrap_or_default()
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use tokio::sync::RwLock;
+
+/// Key-value store shared by all expectations of a server
+///
+/// Templates read it via `{{state.key}}` (dotted paths reach into nested
+/// objects), expectations change it with [`StateUpdate`]s and tests can seed
+/// or inspect it directly or through the `/_state` admin endpoints.
+#[derive(Clone, Debug, Default)]
+pub struct StateStore {
+    values: Arc<RwLock<Map<String, Value>>>,
+}
+
+impl StateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn get(&self, key: &str) -> Option<Value> {
+        self.values.read().await.get(key).cloned()
+    }
+
+    pub async fn set(&self, key: &str, value: Value) {
+        self.values.write().await.insert(key.to_string(), value);
+    }
+
+    pub async fn remove(&self, key: &str) -> Option<Value> {
+        self.values.write().await.remove(key)
+    }
+
+    /// Adds `by` to a numeric value (missing or non-numeric values count as 0)
+    ///
+    /// Returns the new value.
+    pub async fn increment(&self, key: &str, by: i64) -> i64 {
+        let mut values = self.values.write().await;
+        let current = values.get(key).and_then(Value::as_i64).unwrap_or(0);
+        let updated = current.saturating_add(by);
+        values.insert(key.to_string(), Value::from(updated));
+        updated
+    }
+
+    /// Returns a copy of all values
+    pub async fn snapshot(&self) -> Map<String, Value> {
+        self.values.read().await.clone()
+    }
+
+    /// Sets the `Some` and removes the `None` values in one go
+    pub(crate) async fn apply(&self, changes: Vec<(String, Option<Value>)>) {
+        let mut values = self.values.write().await;
+        for (key, value) in changes {
+            match value {
+                Some(value) => values.insert(key, value),
+                None => values.remove(&key),
+            };
+        }
+    }
+
+    /// Replaces all values with `values`
+    pub async fn replace(&self, values: Map<String, Value>) {
+        *self.values.write().await = values;
+    }
+
+    pub async fn clear(&self) {
+        self.values.write().await.clear();
+    }
+}
+
+/// A change an expectation applies to the state store whenever it matches
+///
+/// `set` values may contain templates, e.g. `"{{request.body}}"`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum StateUpdate {
+    Set {
+        key: String,
+        value: Value,
+    },
+    Increment {
+        key: String,
+        #[serde(default = "default_increment")]
+        by: i64,
+    },
+    Remove {
+        key: String,
+    },
+}
+
+fn default_increment() -> i64 {
+    1
+}
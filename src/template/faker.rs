@@ -0,0 +1,152 @@
+use serde_json::Value;
+
+use crate::random::Random;
+
+const FIRST_NAMES: &[&str] = &[
+    "Ada",
+    "Alan",
+    "Barbara",
+    "Dennis",
+    "Edsger",
+    "Frances",
+    "Grace",
+    "Guido",
+    "Hedy",
+    "John",
+    "Katherine",
+    "Ken",
+    "Linus",
+    "Margaret",
+    "Niklaus",
+    "Radia",
+    "Richard",
+    "Shafi",
+    "Tim",
+    "Yukihiro",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Allen",
+    "Berners-Lee",
+    "Dijkstra",
+    "Goldwasser",
+    "Hamilton",
+    "Hopper",
+    "Johnson",
+    "Kernighan",
+    "Lamarr",
+    "Liskov",
+    "Lovelace",
+    "Matsumoto",
+    "McCarthy",
+    "Perlman",
+    "Ritchie",
+    "Rossum",
+    "Stallman",
+    "Thompson",
+    "Torvalds",
+    "Turing",
+    "Wirth",
+];
+
+const CITIES: &[&str] = &[
+    "Amsterdam",
+    "Berlin",
+    "Brno",
+    "Buenos Aires",
+    "Cape Town",
+    "Lisbon",
+    "London",
+    "Melbourne",
+    "Montreal",
+    "Nairobi",
+    "Oslo",
+    "Prague",
+    "Seoul",
+    "Tokyo",
+    "Vienna",
+];
+
+const COUNTRIES: &[&str] = &[
+    "Argentina",
+    "Australia",
+    "Austria",
+    "Canada",
+    "Czechia",
+    "Germany",
+    "Japan",
+    "Kenya",
+    "Netherlands",
+    "Norway",
+    "Portugal",
+    "South Africa",
+    "South Korea",
+    "United Kingdom",
+];
+
+const STREETS: &[&str] = &[
+    "Main Street",
+    "Oak Avenue",
+    "Elm Street",
+    "Station Road",
+    "Park Lane",
+    "Church Street",
+    "High Street",
+    "Maple Drive",
+    "Mill Road",
+    "River Walk",
+];
+
+const COMPANY_WORDS: &[&str] = &[
+    "Acme", "Apex", "Blue", "Bright", "Cloud", "Delta", "Global", "Nimbus", "Orbit", "Pioneer",
+    "Quantum", "Summit", "Vertex",
+];
+
+const COMPANY_SUFFIXES: &[&str] = &["Inc.", "Ltd.", "GmbH", "Labs", "Systems", "Group"];
+
+const WORDS: &[&str] = &[
+    "alpha", "bridge", "candle", "delta", "ember", "forest", "garden", "harbor", "island",
+    "jungle", "kernel", "lantern", "meadow", "nebula", "orchard", "pebble", "quartz", "river",
+    "summit", "timber", "valley", "willow",
+];
+
+/// Generates fake data for `{{faker.<field>}}`, `None` for unknown fields
+pub(super) fn generate(field: &str, random: &Random) -> Option<Value> {
+    let pick = |values: &[&'static str]| random.pick(values).copied().unwrap_or_default();
+    let value = match field {
+        "first_name" => pick(FIRST_NAMES).to_string(),
+        "last_name" => pick(LAST_NAMES).to_string(),
+        "name" => format!("{} {}", pick(FIRST_NAMES), pick(LAST_NAMES)),
+        "username" => format!(
+            "{}{}",
+            pick(FIRST_NAMES).to_lowercase(),
+            random.range(1..1000)
+        ),
+        "email" => format!(
+            "{}.{}@example.com",
+            pick(FIRST_NAMES).to_lowercase(),
+            pick(LAST_NAMES).to_lowercase().replace('-', "")
+        ),
+        "phone" => format!(
+            "+1-555-{:03}-{:04}",
+            random.range(100..1000),
+            random.range(0..10000)
+        ),
+        "company" => format!("{} {}", pick(COMPANY_WORDS), pick(COMPANY_SUFFIXES)),
+        "street_address" => format!("{} {}", random.range(1..300), pick(STREETS)),
+        "city" => pick(CITIES).to_string(),
+        "country" => pick(COUNTRIES).to_string(),
+        "zip_code" => format!("{:05}", random.range(0..100000)),
+        "word" => pick(WORDS).to_string(),
+        "sentence" => {
+            let count = random.range(4..9);
+            let words: Vec<&str> = (0..count).map(|_| pick(WORDS)).collect();
+            let sentence = words.join(" ");
+            let mut chars = sentence.chars();
+            let first = chars.next()?.to_uppercase();
+            format!("{}{}.", first, chars.as_str())
+        }
+        _ => return None,
+    };
+    Some(Value::String(value))
+}
@@ -0,0 +1,234 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use chrono::{DateTime, Months, TimeDelta, Utc};
+use ring::digest;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use super::faker;
+use crate::random::Random;
+
+/// Lowercase hex encoding of `bytes`
+pub(crate) fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Lowercase hex SHA-256 digest of `bytes`
+pub(crate) fn sha256_hex(bytes: &[u8]) -> String {
+    hex(digest::digest(&digest::SHA256, bytes).as_ref())
+}
+
+/// A helper call such as `randomInt 1 100` or `now format='%Y'`
+pub(super) struct HelperCall<'a> {
+    pub name: &'a str,
+
+    pub args: Vec<Value>,
+
+    pub named: HashMap<&'a str, Value>,
+}
+
+/// Whether `name` is a built-in helper
+pub(super) fn is_helper(name: &str) -> bool {
+    matches!(
+        name,
+        "uuid"
+            | "now"
+            | "base64"
+            | "sha256"
+            | "randomInt"
+            | "random_int"
+            | "xmlEscape"
+            | "xmlElements"
+    ) || name.starts_with("faker.")
+}
+
+/// Formats `time` with the strftime `format`, `None` for invalid specifiers
+fn format_time(time: DateTime<Utc>, format: &str) -> Option<String> {
+    let mut formatted = String::new();
+    write!(formatted, "{}", time.format(format)).ok()?;
+    Some(formatted)
+}
+
+/// Evaluates a helper, `None` for missing or invalid arguments
+pub(super) fn call(helper: &HelperCall, now: DateTime<Utc>, random: &Random) -> Option<Value> {
+    match helper.name {
+        "uuid" => Some(Value::String(random.uuid().to_string())),
+        "now" => {
+            let now = match helper.named.get("offset") {
+                Some(Value::String(offset)) => apply_offset(now, offset)?,
+                Some(_) => return None,
+                None => now,
+            };
+            Some(Value::String(match helper.named.get("format") {
+                Some(Value::String(format)) => format_time(now, format)?,
+                Some(_) => return None,
+                None => now.to_rfc3339(),
+            }))
+        }
+        "base64" => {
+            let input = text(helper.args.first()?);
+            Some(Value::String(STANDARD.encode(input)))
+        }
+        "sha256" => {
+            let input = text(helper.args.first()?);
+            Some(Value::String(sha256_hex(input.as_bytes())))
+        }
+        "randomInt" | "random_int" => {
+            let min = helper.args.first()?.as_i64()?;
+            let max = helper.args.get(1)?.as_i64()?;
+            (min <= max).then(|| Value::from(random.range(min..=max)))
+        }
+        "xmlEscape" => Some(Value::String(xml_escape(&text(helper.args.first()?)))),
+        "xmlElements" => {
+            let tag = helper.args.get(1)?.as_str()?;
+            let mut xml = String::new();
+            match helper.args.first()? {
+                Value::Array(items) => {
+                    for item in items {
+                        write_element(&mut xml, tag, item);
+                    }
+                }
+                value => write_element(&mut xml, tag, value),
+            }
+            Some(Value::String(xml))
+        }
+        name => faker::generate(name.strip_prefix("faker.")?, random),
+    }
+}
+
+/// Escapes the characters that are special in XML text and attributes
+fn xml_escape(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes `<tag>value</tag>`; objects become child elements, arrays repeat the child
+fn write_element(xml: &mut String, tag: &str, value: &Value) {
+    match value {
+        Value::Array(items) => {
+            for item in items {
+                write_element(xml, tag, item);
+            }
+        }
+        Value::Object(fields) => {
+            xml.push_str(&format!("<{}>", tag));
+            for (name, field) in fields {
+                write_element(xml, name, field);
+            }
+            xml.push_str(&format!("</{}>", tag));
+        }
+        Value::Null => xml.push_str(&format!("<{}/>", tag)),
+        scalar => xml.push_str(&format!("<{}>{}</{}>", tag, xml_escape(&text(scalar)), tag)),
+    }
+}
+
+/// Shifts `time` by an offset such as `+3 days` or `-1 month +2 hours`
+fn apply_offset(time: DateTime<Utc>, offset: &str) -> Option<DateTime<Utc>> {
+    let mut time = time;
+    let mut parts = offset.split_whitespace();
+    while let Some(amount) = parts.next() {
+        // Accept both "+3 days" and "+3days"
+        let split = amount
+            .char_indices()
+            .find(|(i, c)| *i > 0 && c.is_ascii_alphabetic())
+            .map_or(amount.len(), |(i, _)| i);
+        let (number, unit) = amount.split_at(split);
+        let unit = if unit.is_empty() { parts.next()? } else { unit };
+        let number: i64 = number.parse().ok()?;
+        let unit = unit.to_lowercase();
+
+        time = match unit.trim_end_matches('s') {
+            "second" | "sec" => time.checked_add_signed(TimeDelta::try_seconds(number)?)?,
+            "minute" | "min" => time.checked_add_signed(TimeDelta::try_minutes(number)?)?,
+            "hour" | "h" => time.checked_add_signed(TimeDelta::try_hours(number)?)?,
+            "day" | "d" => time.checked_add_signed(TimeDelta::try_days(number)?)?,
+            "week" | "w" => time.checked_add_signed(TimeDelta::try_weeks(number)?)?,
+            "month" => shift_months(time, number)?,
+            "year" | "y" => shift_months(time, number.checked_mul(12)?)?,
+            _ => return None,
+        };
+    }
+    Some(time)
+}
+
+/// Calendar month arithmetic, clamping to the end of shorter months
+fn shift_months(time: DateTime<Utc>, months: i64) -> Option<DateTime<Utc>> {
+    let magnitude = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+    if months >= 0 {
+        time.checked_add_months(magnitude)
+    } else {
+        time.checked_sub_months(magnitude)
+    }
+}
+
+/// Strings as they are, everything else as JSON text
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Splits an expression into tokens, keeping quoted strings together
+///
+/// Quoted tokens keep their quotes so literals can be told apart from paths.
+pub(super) fn tokenize(expression: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut quote = None;
+
+    for (i, c) in expression.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c == '\'' || c == '"' => {
+                quote = Some(c);
+                start.get_or_insert(i);
+            }
+            None if c.is_whitespace() => {
+                if let Some(s) = start.take() {
+                    tokens.push(&expression[s..i]);
+                }
+            }
+            None => {
+                start.get_or_insert(i);
+            }
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(&expression[s..]);
+    }
+    tokens
+}
+
+/// A quoted string, number or boolean literal
+pub(super) fn literal(token: &str) -> Option<Value> {
+    let unquoted = token
+        .strip_prefix('\'')
+        .and_then(|t| t.strip_suffix('\''))
+        .or_else(|| token.strip_prefix('"').and_then(|t| t.strip_suffix('"')));
+    if let Some(s) = unquoted {
+        return Some(Value::String(s.to_string()));
+    }
+    match token {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+    token
+        .parse::<i64>()
+        .map(Value::from)
+        .ok()
+        .or_else(|| token.parse::<f64>().ok().map(Value::from))
+}
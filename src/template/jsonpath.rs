@@ -0,0 +1,95 @@
+use serde_json::Value;
+
+enum Segment {
+    Name(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// Evaluates a JSONPath such as `$.items[0].name` or `$['first name']`
+///
+/// Supports child names, array indexes (negative ones count from the end)
+/// and `*` wildcards. With a wildcard all matches are returned as an array,
+/// otherwise the single match.
+pub(crate) fn select(value: &Value, path: &str) -> Option<Value> {
+    let segments = parse(path)?;
+
+    let mut nodes = vec![value];
+    for segment in &segments {
+        nodes = nodes
+            .into_iter()
+            .flat_map(|node| children(node, segment))
+            .collect();
+    }
+
+    if segments
+        .iter()
+        .any(|segment| matches!(segment, Segment::Wildcard))
+    {
+        Some(Value::Array(nodes.into_iter().cloned().collect()))
+    } else {
+        nodes.first().map(|&node| node.clone())
+    }
+}
+
+/// Whether `path` is a JSONPath [`select`] understands
+pub(crate) fn is_valid(path: &str) -> bool {
+    parse(path).is_some()
+}
+
+fn parse(path: &str) -> Option<Vec<Segment>> {
+    let mut rest = path.trim().strip_prefix('$')?;
+    let mut segments = Vec::new();
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('.') {
+            let end = after.find(['.', '[']).unwrap_or(after.len());
+            segments.push(match &after[..end] {
+                "" => return None,
+                "*" => Segment::Wildcard,
+                name => Segment::Name(name.to_string()),
+            });
+            rest = &after[end..];
+        } else if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            segments.push(bracket(after[..end].trim())?);
+            rest = &after[end + 1..];
+        } else {
+            return None;
+        }
+    }
+
+    Some(segments)
+}
+
+/// The content of `[...]`: `*`, an index or a quoted name
+fn bracket(inner: &str) -> Option<Segment> {
+    if inner == "*" {
+        return Some(Segment::Wildcard);
+    }
+    let quoted = inner
+        .strip_prefix('\'')
+        .and_then(|s| s.strip_suffix('\''))
+        .or_else(|| inner.strip_prefix('"').and_then(|s| s.strip_suffix('"')));
+    match quoted {
+        Some(name) => Some(Segment::Name(name.to_string())),
+        None => inner.parse().ok().map(Segment::Index),
+    }
+}
+
+fn children<'v>(node: &'v Value, segment: &Segment) -> Vec<&'v Value> {
+    match (segment, node) {
+        (Segment::Name(name), Value::Object(map)) => map.get(name).into_iter().collect(),
+        (Segment::Index(index), Value::Array(items)) => {
+            let index = if *index < 0 {
+                items.len().checked_sub(index.unsigned_abs() as usize)
+            } else {
+                Some(*index as usize)
+            };
+            index.and_then(|i| items.get(i)).into_iter().collect()
+        }
+        (Segment::Wildcard, Value::Object(map)) => map.values().collect(),
+        (Segment::Wildcard, Value::Array(items)) => items.iter().collect(),
+        _ => Vec::new(),
+    }
+}
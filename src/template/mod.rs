@@ -0,0 +1,198 @@
+mod faker;
+mod helpers;
+pub(crate) mod jsonpath;
+
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+use crate::random::Random;
+use crate::transform::IncomingRequest;
+use helpers::HelperCall;
+pub(crate) use helpers::{hex, sha256_hex};
+
+/// Data available to `{{...}}` placeholders in responses
+pub(crate) struct TemplateContext<'a> {
+    pub state: &'a Map<String, Value>,
+
+    pub request: &'a IncomingRequest,
+
+    /// Values captured by `{name}` segments of the expectation's path
+    pub path_params: &'a HashMap<String, String>,
+
+    /// Source of the generated values
+    pub random: &'a Random,
+}
+
+impl TemplateContext<'_> {
+    /// Resolves a path or a helper call such as `sha256 request.body`
+    fn resolve(&self, expression: &str) -> Option<Value> {
+        let tokens = helpers::tokenize(expression);
+        let (&name, arguments) = tokens.split_first()?;
+        if matches!(
+            name,
+            "request.header" | "request.query" | "request.body.jsonpath"
+        ) {
+            let argument = self.argument(arguments.first()?)?;
+            return self.request_accessor(name, argument.as_str()?);
+        }
+        if !helpers::is_helper(name) {
+            return self.resolve_path(expression);
+        }
+
+        let mut call = HelperCall {
+            name,
+            args: Vec::new(),
+            named: HashMap::new(),
+        };
+        for argument in arguments {
+            match argument.split_once('=') {
+                Some((key, value)) if !key.starts_with(['\'', '"']) => {
+                    call.named.insert(key, self.argument(value)?);
+                }
+                _ => call.args.push(self.argument(argument)?),
+            }
+        }
+        helpers::call(&call, self.request.received_at, self.random)
+    }
+
+    /// Evaluates `request.header 'X-Foo'`, `request.query 'page'` or
+    /// `request.body.jsonpath '$.name'`
+    fn request_accessor(&self, name: &str, argument: &str) -> Option<Value> {
+        let request = self.request;
+        let value = match name {
+            "request.header" => request.headers.get(&argument.to_lowercase())?,
+            "request.query" => request.query_params.get(argument)?,
+            _ => {
+                let json = serde_json::from_str::<Value>(request.body.as_deref()?).ok()?;
+                return jsonpath::select(&json, argument);
+            }
+        };
+        Some(Value::String(value.clone()))
+    }
+
+    /// A literal or the value of a path
+    fn argument(&self, token: &str) -> Option<Value> {
+        helpers::literal(token).or_else(|| self.resolve_path(token))
+    }
+
+    /// Resolves a path such as `state.order.id`, `path.id` or `request.body.name`
+    fn resolve_path(&self, expression: &str) -> Option<Value> {
+        let mut segments = expression.split('.');
+        match segments.next()? {
+            "path" => {
+                let name = segments.next()?;
+                if segments.next().is_some() {
+                    return None;
+                }
+                self.path_params.get(name).cloned().map(Value::String)
+            }
+            "state" => {
+                let key = segments.next()?;
+                lookup(self.state.get(key)?, segments).cloned()
+            }
+            "request" => match segments.next()? {
+                "path" if segments.next().is_none() => {
+                    Some(Value::String(self.request.path.clone()))
+                }
+                "method" if segments.next().is_none() => {
+                    Some(Value::String(self.request.method.clone()))
+                }
+                "body" => {
+                    let body = self.request.body.as_deref()?;
+                    match serde_json::from_str::<Value>(body) {
+                        Ok(json) => lookup(&json, segments).cloned(),
+                        Err(_) => segments
+                            .next()
+                            .is_none()
+                            .then(|| Value::String(body.to_string())),
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+fn lookup<'v, 's>(value: &'v Value, segments: impl Iterator<Item = &'s str>) -> Option<&'v Value> {
+    let mut current = value;
+    for segment in segments {
+        current = match current {
+            Value::Object(map) => map.get(segment)?,
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?)?,
+            _ => return None,
+        };
+    }
+    Some(current)
+}
+
+/// Renders placeholders in all strings of a JSON value
+///
+/// A string consisting of a single placeholder is replaced by the resolved
+/// value itself, keeping its JSON type; placeholders inside longer strings
+/// are interpolated. Unknown placeholders are left untouched.
+pub(crate) fn render_value(value: &Value, context: &TemplateContext) -> Value {
+    match value {
+        Value::String(s) => {
+            if let Some(expression) = single_placeholder(s)
+                && let Some(resolved) = context.resolve(expression)
+            {
+                return resolved;
+            }
+            Value::String(render_str(s, context))
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| render_value(item, context))
+                .collect(),
+        ),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), render_value(value, context)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+/// Interpolates placeholders in a string
+pub(crate) fn render_str(template: &str, context: &TemplateContext) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            output.push_str(&rest[start..]);
+            return output;
+        };
+
+        let placeholder = &rest[start..start + 2 + end + 2];
+        match context.resolve(after[..end].trim()) {
+            Some(Value::String(s)) => output.push_str(&s),
+            Some(value) => output.push_str(&value.to_string()),
+            None => output.push_str(placeholder),
+        }
+        rest = &after[end + 2..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Whether any string in `value` contains a placeholder
+pub(crate) fn has_placeholders(value: &Value) -> bool {
+    match value {
+        Value::String(s) => s.contains("{{"),
+        Value::Array(items) => items.iter().any(has_placeholders),
+        Value::Object(map) => map.values().any(has_placeholders),
+        _ => false,
+    }
+}
+
+fn single_placeholder(s: &str) -> Option<&str> {
+    let inner = s.trim().strip_prefix("{{")?.strip_suffix("}}")?;
+    (!inner.contains("{{") && !inner.contains("}}")).then(|| inner.trim())
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use thiserror::Error;
+use tokio_rustls::rustls::pki_types::pem::{self, PemObject};
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{self, ServerConfig};
+
+/// Problems with the certificate or key of a TLS listener
+#[derive(Debug, Error)]
+pub enum TlsError {
+    #[error("invalid PEM: {0}")]
+    Pem(#[from] pem::Error),
+
+    #[error("no certificate found in PEM")]
+    NoCertificate,
+
+    #[error("invalid certificate or key: {0}")]
+    Rustls(#[from] rustls::Error),
+}
+
+/// Certificate chain and private key served by `MockServer::start_tls`
+///
+/// # Example
+/// ```no_run
+/// # use mimic_rs::MockServer;
+/// # use mimic_rs::tls::TlsConfig;
+/// #
+/// # #[tokio::main]
+/// # async fn main() {
+/// let tls = TlsConfig::self_signed();
+/// // Trust `tls.cert_pem()` in the client under test
+/// MockServer::new("./resources").start_tls(8443, tls).await.unwrap();
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct TlsConfig {
+    cert_pem: String,
+
+    key_pem: String,
+}
+
+impl TlsConfig {
+    /// Uses a PEM certificate chain and a PEM private key
+    pub fn from_pem(cert_pem: impl Into<String>, key_pem: impl Into<String>) -> Self {
+        Self {
+            cert_pem: cert_pem.into(),
+            key_pem: key_pem.into(),
+        }
+    }
+
+    /// Reads the PEM certificate chain and private key from files
+    pub fn from_files(cert: impl AsRef<Path>, key: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::from_pem(
+            fs::read_to_string(cert)?,
+            fs::read_to_string(key)?,
+        ))
+    }
+
+    /// Generates a self-signed certificate for `localhost`, `127.0.0.1` and `::1`
+    ///
+    /// # Panics
+    /// Panics if the key pair cannot be generated.
+    pub fn self_signed() -> Self {
+        Self::self_signed_for(["localhost", "127.0.0.1", "::1"])
+    }
+
+    /// Generates a self-signed certificate for the given host names and IPs
+    ///
+    /// # Panics
+    /// Panics if the key pair cannot be generated.
+    pub fn self_signed_for<I, S>(hosts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let hosts: Vec<String> = hosts.into_iter().map(Into::into).collect();
+        let certified = rcgen::generate_simple_self_signed(hosts)
+            .expect("Failed to generate self-signed certificate");
+        Self::from_pem(certified.cert.pem(), certified.signing_key.serialize_pem())
+    }
+
+    /// The certificate chain, e.g. to add as a trusted root in a client
+    pub fn cert_pem(&self) -> &str {
+        &self.cert_pem
+    }
+
+    pub(crate) fn server_config(&self) -> Result<Arc<ServerConfig>, TlsError> {
+        let certs = CertificateDer::pem_slice_iter(self.cert_pem.as_bytes())
+            .collect::<Result<Vec<_>, _>>()?;
+        if certs.is_empty() {
+            return Err(TlsError::NoCertificate);
+        }
+        let key = PrivateKeyDer::from_pem_slice(self.key_pem.as_bytes())?;
+
+        let mut config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()?
+                .with_no_client_auth()
+                .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+        Ok(Arc::new(config))
+    }
+}
@@ -0,0 +1,12 @@
+//! Rewriting of requests before they are matched and of responses before
+//! they are sent
+//!
+//! Lets mocks sit behind infrastructure that changes requests on the way,
+//! e.g. a gateway adding a path prefix, and apply cross-cutting response
+//! changes (standard headers, signatures, envelopes) without touching every stub.
+
+mod request;
+mod response;
+
+pub use request::*;
+pub use response::*;
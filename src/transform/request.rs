@@ -0,0 +1,129 @@
+use axum::body::Bytes;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// The parts of an incoming request that matching looks at
+///
+/// Header names are lowercase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IncomingRequest {
+    pub method: String,
+
+    pub path: String,
+
+    pub query_params: HashMap<String, String>,
+
+    pub headers: HashMap<String, String>,
+
+    /// `None` while the body has not been read yet, or when it is not UTF-8
+    pub body: Option<String>,
+
+    /// The body as received, also when it is binary
+    pub raw_body: Option<Bytes>,
+
+    /// When the request came in, by the server's clock
+    pub received_at: DateTime<Utc>,
+}
+
+/// Built-in transformations, also usable from JSON
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RequestTransform {
+    /// Removes a leading path prefix such as `/gateway/v1`
+    StripPathPrefix {
+        prefix: String,
+    },
+
+    /// Renames a header, e.g. a gateway specific one to what the stubs expect
+    RenameHeader {
+        from: String,
+        to: String,
+    },
+
+    SetHeader {
+        name: String,
+        value: String,
+    },
+
+    RemoveHeader {
+        name: String,
+    },
+
+    /// Replaces a JSON body with the value at `pointer` (RFC 6901),
+    /// unwrapping envelopes like `{"payload": {...}}`
+    UnwrapBody {
+        pointer: String,
+    },
+}
+
+impl RequestTransform {
+    pub fn apply(&self, request: &mut IncomingRequest) {
+        match self {
+            Self::StripPathPrefix { prefix } => {
+                let prefix = prefix.trim_end_matches('/');
+                if let Some(rest) = request.path.strip_prefix(prefix)
+                    && (rest.is_empty() || rest.starts_with('/'))
+                {
+                    request.path = if rest.is_empty() {
+                        "/".to_string()
+                    } else {
+                        rest.to_string()
+                    };
+                }
+            }
+            Self::RenameHeader { from, to } => {
+                if let Some(value) = request.headers.remove(&from.to_lowercase()) {
+                    request.headers.insert(to.to_lowercase(), value);
+                }
+            }
+            Self::SetHeader { name, value } => {
+                request.headers.insert(name.to_lowercase(), value.clone());
+            }
+            Self::RemoveHeader { name } => {
+                request.headers.remove(&name.to_lowercase());
+            }
+            Self::UnwrapBody { pointer } => {
+                let unwrapped = request
+                    .body
+                    .as_deref()
+                    .and_then(|body| serde_json::from_str::<serde_json::Value>(body).ok())
+                    .and_then(|json| json.pointer(pointer).cloned());
+                if let Some(inner) = unwrapped {
+                    request.body = Some(match inner {
+                        serde_json::Value::String(text) => text,
+                        other => other.to_string(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// A registered request transformation: built-in or custom code
+#[derive(Clone)]
+pub enum Transformer {
+    Builtin(RequestTransform),
+
+    Custom(Arc<dyn Fn(&mut IncomingRequest) + Send + Sync>),
+}
+
+impl Transformer {
+    pub fn apply(&self, request: &mut IncomingRequest) {
+        match self {
+            Self::Builtin(transform) => transform.apply(request),
+            Self::Custom(transform) => transform(request),
+        }
+    }
+}
+
+impl fmt::Debug for Transformer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin(transform) => f.debug_tuple("Builtin").field(transform).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
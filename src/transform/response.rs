@@ -0,0 +1,126 @@
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::models::MockResponse;
+use crate::template::hex;
+
+/// Built-in response transformations, also usable from JSON
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponseTransform {
+    SetHeader {
+        name: String,
+        value: String,
+    },
+
+    RemoveHeader {
+        name: String,
+    },
+
+    /// Wraps the body as `{"<key>": body, ...extra}`
+    WrapBody {
+        key: String,
+
+        #[serde(skip_serializing_if = "Map::is_empty", default)]
+        extra: Map<String, Value>,
+    },
+
+    /// Adds the hex encoded HMAC-SHA256 of the body as `header`
+    SignBody {
+        header: String,
+        secret: String,
+    },
+}
+
+impl ResponseTransform {
+    /// Whether the transformation reads or replaces the body
+    ///
+    /// Streamed and gRPC-Web bodies are produced while sending, so only the
+    /// other transformations run on those responses.
+    pub(crate) fn uses_body(&self) -> bool {
+        matches!(self, Self::WrapBody { .. } | Self::SignBody { .. })
+    }
+
+    /// Applies the transformation to a response whose body file is already loaded
+    pub fn apply(&self, response: &mut MockResponse) {
+        match self {
+            Self::SetHeader { name, value } => {
+                remove_header(response, name);
+                response.headers.insert(name.clone(), value.clone());
+            }
+            Self::RemoveHeader { name } => remove_header(response, name),
+            Self::WrapBody { key, extra } => {
+                let body = match (&response.body, &response.cached_json_content) {
+                    (Some(body), _) => body.clone(),
+                    (None, Some(json)) => json.clone(),
+                    (None, None) => response
+                        .cached_file_content
+                        .clone()
+                        .map_or(Value::Null, Value::String),
+                };
+                let mut envelope = extra.clone();
+                envelope.insert(key.clone(), body);
+                response.body = Some(Value::Object(envelope));
+                response.cached_json_content = None;
+                response.cached_file_content = None;
+                response.cached_file_bytes = None;
+                remove_header(response, "Content-Type");
+                response
+                    .headers
+                    .insert("Content-Type".to_string(), "application/json".to_string());
+            }
+            Self::SignBody { header, secret } => {
+                let body = response
+                    .get_json_string()
+                    .or_else(|| response.cached_file_content.clone())
+                    .unwrap_or_default();
+                let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+                let signature = hex(hmac::sign(&key, body.as_bytes()).as_ref());
+                response.headers.insert(header.clone(), signature);
+            }
+        }
+    }
+}
+
+fn remove_header(response: &mut MockResponse, name: &str) {
+    response
+        .headers
+        .retain(|key, _| !key.eq_ignore_ascii_case(name));
+}
+
+/// A registered response transformation: built-in or custom code
+#[derive(Clone)]
+pub enum ResponseTransformer {
+    Builtin(ResponseTransform),
+
+    Custom(Arc<dyn Fn(&mut MockResponse) + Send + Sync>),
+}
+
+impl ResponseTransformer {
+    /// Whether the transformation needs the body, custom code never does
+    pub(crate) fn uses_body(&self) -> bool {
+        match self {
+            Self::Builtin(transform) => transform.uses_body(),
+            Self::Custom(_) => false,
+        }
+    }
+
+    pub fn apply(&self, response: &mut MockResponse) {
+        match self {
+            Self::Builtin(transform) => transform.apply(response),
+            Self::Custom(transform) => transform(response),
+        }
+    }
+}
+
+impl fmt::Debug for ResponseTransformer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Builtin(transform) => f.debug_tuple("Builtin").field(transform).finish(),
+            Self::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
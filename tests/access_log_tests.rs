@@ -0,0 +1,76 @@
+use mimic_rs::MockServer;
+use mimic_rs::logging::{AccessLog, AccessLogEntry, REDACTED};
+use reqwest::Client;
+use serde_json::json;
+use std::fs;
+
+fn temp_file(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("mimic-{}-{}.jsonl", name, uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_access_log_lines_with_redacted_headers() {
+    let path = temp_file("access");
+    let server = MockServer::new("./tests/resources")
+        .with_access_log(AccessLog::file(&path).unwrap())
+        .with_redacted_headers(["Authorization", "set-cookie"]);
+
+    let users = server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .header("Authorization", "Bearer secret")
+        .respond()
+        .status(200)
+        .header("Set-Cookie", "session=abc")
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/api/users/1"))
+        .header("Authorization", "Bearer secret")
+        .header("X-Trace", "t-1")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["set-cookie"], "session=abc");
+
+    let response = client
+        .post(handle.url("/api/missing"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let entries: Vec<AccessLogEntry> = fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].method, "GET");
+    assert_eq!(entries[0].path, "/api/users/1");
+    assert_eq!(entries[0].status, 200);
+    assert_eq!(entries[0].expectation_id.as_deref(), Some(users.id()));
+    assert_eq!(entries[0].headers["authorization"], REDACTED);
+    assert_eq!(entries[0].headers["x-trace"], "t-1");
+    assert!(entries[0].latency_ms >= 0.0);
+
+    assert_eq!(entries[1].method, "POST");
+    assert_eq!(entries[1].status, 404);
+    assert_eq!(entries[1].expectation_id, None);
+
+    let log = server.get_request_log().await;
+    assert_eq!(log[0].headers["authorization"], REDACTED);
+    assert_eq!(log[0].headers["x-trace"], "t-1");
+    let response = log[0].response.as_ref().unwrap();
+    assert_eq!(response.headers["set-cookie"], REDACTED);
+
+    let _ = fs::remove_file(path);
+}
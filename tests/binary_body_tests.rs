@@ -0,0 +1,174 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+
+const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+
+#[tokio::test]
+async fn test_binary_request_body_matching() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/upload")
+        .method("POST")
+        .body_bytes([0x00, 0x9f, 0x92, 0x96])
+        .respond()
+        .status(201)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/api/upload"))
+        .body(vec![0x00, 0x9f, 0x92, 0x96])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client
+        .post(handle.url("/api/upload"))
+        .body(vec![0x00, 0x9f, 0x92, 0x97])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_binary_response_body() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/images/logo.png")
+        .method("GET")
+        .respond()
+        .status(200)
+        .header("Content-Type", "image/png")
+        .body_bytes(PNG_HEADER)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/download")
+        .method("GET")
+        .respond()
+        .status(200)
+        .body_bytes([0xff, 0xfe, 0x00])
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/images/logo.png"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    assert_eq!(response.bytes().await.unwrap().as_ref(), PNG_HEADER);
+
+    let response = client.get(handle.url("/download")).send().await.unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/octet-stream"
+    );
+    assert_eq!(response.bytes().await.unwrap().as_ref(), [0xff, 0xfe, 0x00]);
+}
+
+#[tokio::test]
+async fn test_binary_bodies_via_setup() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "PUT",
+            "path": "/api/blob",
+            "body_base64": "AJ+Slg==",
+            "response": {"status_code": 200, "body_base64": "iVBORw0KGgo="}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client
+        .put(handle.url("/api/blob"))
+        .body(vec![0x00, 0x9f, 0x92, 0x96])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.bytes().await.unwrap().as_ref(), PNG_HEADER);
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "PUT",
+            "path": "/api/broken",
+            "body_base64": "not base64!",
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn test_file_responses() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/pixel")
+        .method("GET")
+        .respond()
+        .file("pixel.png")
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/report")
+        .method("GET")
+        .respond()
+        .file("report.csv")
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/version")
+        .method("GET")
+        .respond()
+        .text_file("version.log")
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client.get(handle.url("/pixel")).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-type"], "image/png");
+    let body = response.bytes().await.unwrap();
+    assert_eq!(
+        body.as_ref(),
+        std::fs::read("tests/resources/pixel.png").unwrap()
+    );
+
+    let response = client.get(handle.url("/report")).send().await.unwrap();
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+    assert_eq!(response.text().await.unwrap(), "id,name\n1,Alice\n");
+
+    // Text that happens to be valid JSON is not re-served as JSON
+    let response = client.get(handle.url("/version")).send().await.unwrap();
+    let content_types: Vec<_> = response.headers().get_all("content-type").iter().collect();
+    assert_eq!(content_types, ["text/plain; charset=utf-8"]);
+    assert_eq!(response.text().await.unwrap(), "42\n");
+}
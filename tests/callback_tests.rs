@@ -0,0 +1,111 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Polls `/_callbacks` until `count` deliveries are logged
+async fn wait_for_deliveries(client: &Client, url: &str, count: usize) -> Vec<Value> {
+    for _ in 0..50 {
+        let deliveries: Vec<Value> = client.get(url).send().await.unwrap().json().await.unwrap();
+        if deliveries.len() >= count {
+            return deliveries;
+        }
+        sleep(Duration::from_millis(50)).await;
+    }
+    panic!("expected {} callback deliveries", count);
+}
+
+#[tokio::test]
+async fn test_callback_fires_after_match() {
+    let receiver = MockServer::new("./tests/resources");
+    receiver
+        .expect()
+        .path("/hooks/payments")
+        .method("POST")
+        .respond()
+        .status(204)
+        .build()
+        .await;
+    let receiver_handle = receiver.start_random_port().await.unwrap();
+
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/payments")
+        .method("POST")
+        .then_callback(
+            &receiver_handle.url("/hooks/payments"),
+            "POST",
+            Some(json!({"order": "{{request.body.order_id}}", "status": "paid"})),
+            Duration::from_millis(200),
+        )
+        .respond()
+        .status(202)
+        .json(json!({"status": "pending"}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/api/payments"))
+        .json(&json!({"order_id": 42}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 202);
+    // The response does not wait for the delayed callback
+    assert!(receiver.get_request_log().await.is_empty());
+
+    let deliveries = wait_for_deliveries(&client, &handle.url("/_callbacks"), 1).await;
+    assert_eq!(deliveries[0]["url"], receiver_handle.url("/hooks/payments"));
+    assert_eq!(deliveries[0]["status"], 204);
+
+    let received = receiver.get_request_log().await;
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0].headers["content-type"], "application/json");
+    let body: Value = serde_json::from_str(received[0].body.as_deref().unwrap()).unwrap();
+    assert_eq!(body, json!({"order": 42, "status": "paid"}));
+
+    let response = client
+        .delete(handle.url("/_callbacks"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 204);
+    assert!(server.callback_deliveries().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_failed_callback_via_setup_is_logged() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "PUT",
+            "path": "/api/jobs/{id}",
+            "callbacks": [{
+                "url": "http://localhost:9/jobs/{{path.id}}/done",
+                "body": "finished"
+            }],
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client.put(handle.url("/api/jobs/7")).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let deliveries = wait_for_deliveries(&client, &handle.url("/_callbacks"), 1).await;
+    assert_eq!(deliveries[0]["method"], "POST");
+    assert_eq!(deliveries[0]["url"], "http://localhost:9/jobs/7/done");
+    assert_eq!(deliveries[0]["body"], "finished");
+    assert!(deliveries[0]["status"].is_null());
+    assert!(deliveries[0]["error"].is_string());
+}
@@ -0,0 +1,170 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::ChaosConfig;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_chaos_errors_and_delays_from_builder() {
+    let port = 9240;
+    let server = MockServer::new("./tests/resources").with_chaos(
+        ChaosConfig::new().errors(1.0).error_statuses([503]).delay(
+            1.0,
+            Duration::from_millis(200),
+            Duration::from_millis(200),
+        ),
+    );
+
+    server
+        .expect()
+        .path("/api/payments")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/payments", port);
+
+    let started = Instant::now();
+    let resp = client.get(&url).send().await.unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(200));
+    assert_eq!(resp.status().as_u16(), 503);
+    assert_eq!(resp.headers()["x-mimic-chaos"], "error");
+
+    // Admin endpoints are not affected
+    let resp = client
+        .delete(format!("http://localhost:{}/_chaos", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_chaos_via_http_api() {
+    let port = 9241;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/inventory")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"items": []}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let resp = client
+        .post(format!("{}/_chaos", base))
+        .json(&json!({"error_probability": 1.5}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let resp = client
+        .post(format!("{}/_chaos", base))
+        .json(&json!({"fault_probability": 1.0}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let config: Value = client
+        .get(format!("{}/_chaos", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(config["fault_probability"], 1.0);
+
+    // The connection is dropped before a complete response arrives
+    let result = async {
+        client
+            .get(format!("{}/api/inventory", base))
+            .send()
+            .await?
+            .bytes()
+            .await
+    }
+    .await;
+    assert!(result.is_err());
+
+    server.set_chaos(None).await;
+    let config: Value = client
+        .get(format!("{}/_chaos", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(config, Value::Null);
+
+    let resp = client
+        .get(format!("{}/api/inventory", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_chaos_delay_given_in_reverse() {
+    let config =
+        ChaosConfig::new().delay(1.0, Duration::from_millis(150), Duration::from_millis(50));
+    assert_eq!((config.min_delay_ms, config.max_delay_ms), (50, 150));
+    assert!(config.validate().is_ok());
+
+    // Reversed fields set directly must not panic either
+    let config = ChaosConfig {
+        min_delay_ms: 150,
+        max_delay_ms: 50,
+        ..config
+    };
+    let server = MockServer::new("./tests/resources").with_chaos(config);
+    server
+        .expect()
+        .path("/api/slow")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let started = Instant::now();
+    let resp = Client::new()
+        .get(handle.url("/api/slow"))
+        .send()
+        .await
+        .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(50));
+    assert_eq!(resp.status().as_u16(), 200);
+}
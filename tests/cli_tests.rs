@@ -0,0 +1,279 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::{CreateExpectationRequest, Fault};
+use serde_json::json;
+use std::fs;
+use std::process::Command;
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn temp_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("mimic-{}-{}", name, uuid::Uuid::new_v4()))
+}
+
+#[tokio::test]
+async fn test_export_command() {
+    let port = 9060;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/exported")
+        .method("POST")
+        .header("X-Tenant", "acme")
+        .respond()
+        .status(201)
+        .json(json!({"exported": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let out_dir = temp_dir("export");
+    let status = tokio::task::spawn_blocking({
+        let out_dir = out_dir.clone();
+        move || {
+            Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+                .args(["--quiet", "export", "--from"])
+                .arg(format!("http://localhost:{}", port))
+                .arg("--out")
+                .arg(&out_dir)
+                .status()
+                .unwrap()
+        }
+    })
+    .await
+    .unwrap();
+    assert!(status.success());
+
+    let files: Vec<_> = fs::read_dir(out_dir.join("mappings"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    assert_eq!(files.len(), 1);
+
+    let mapping: CreateExpectationRequest =
+        serde_json::from_str(&fs::read_to_string(&files[0]).unwrap()).unwrap();
+    assert_eq!(mapping.method, "POST");
+    assert_eq!(mapping.path, "/api/exported");
+    assert_eq!(mapping.headers.get("x-tenant").unwrap(), "acme");
+    assert_eq!(mapping.response.status_code, 201);
+
+    fs::remove_dir_all(out_dir).unwrap();
+}
+
+#[test]
+fn test_import_command() {
+    let out_dir = temp_dir("import");
+
+    let status = Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+        .args([
+            "--quiet",
+            "import",
+            "openapi",
+            "./tests/resources/import/openapi.json",
+        ])
+        .arg(format!("--out={}", out_dir.display()))
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    assert_eq!(fs::read_dir(out_dir.join("mappings")).unwrap().count(), 3);
+
+    fs::remove_dir_all(out_dir).unwrap();
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let output = Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+        .args(["export", "--out", "./somewhere"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--from"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+        .args(["--tls-handshake-delay", "500"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--tls-cert"));
+}
+
+#[tokio::test]
+async fn test_replay_command() {
+    let port = 9061;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/stable")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"version": 1}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/changed")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"version": 2}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/broken")
+        .method("GET")
+        .respond()
+        .fault(Fault::ConnectionReset)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let recording = |path: &str, version: u32| -> CreateExpectationRequest {
+        serde_json::from_value(json!({
+            "method": "GET",
+            "path": path,
+            "response": {"status_code": 200, "body": {"version": version}}
+        }))
+        .unwrap()
+    };
+
+    let recordings_dir = temp_dir("replay");
+    mimic_rs::mappings::write_mappings(
+        &recordings_dir.join("mappings"),
+        &[
+            recording("/api/broken", 1),
+            recording("/api/users/{id}", 1),
+            recording("/api/stable", 1),
+            recording("/api/changed", 1),
+        ],
+    )
+    .unwrap();
+
+    let output = tokio::task::spawn_blocking({
+        let recordings_dir = recordings_dir.clone();
+        move || {
+            Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+                .args(["--quiet", "replay"])
+                .arg(&recordings_dir)
+                .arg("--target")
+                .arg(format!("http://localhost:{}", port))
+                .output()
+                .unwrap()
+        }
+    })
+    .await
+    .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout.contains("OK       GET /api/stable"));
+    assert!(stdout.contains("MISMATCH GET /api/changed"));
+    assert!(stdout.contains("body /version: expected 1, got 2"));
+    assert!(stdout.contains("ERROR    GET /api/broken"));
+    assert!(!stdout.contains("/api/users/{id}"));
+    assert!(stdout.contains("1 passed, 2 failed, 1 skipped"));
+
+    fs::remove_dir_all(recordings_dir).unwrap();
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_graceful_shutdown_on_sigterm() {
+    let port = 9062;
+    let dump_file = temp_dir("dump").with_extension("json");
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+        .arg("--quiet")
+        .arg("--dump-requests")
+        .arg(&dump_file)
+        .arg(port.to_string())
+        .arg("./tests/resources")
+        .spawn()
+        .unwrap();
+
+    sleep(Duration::from_millis(300)).await;
+
+    let resp = reqwest::get(format!("http://localhost:{}/api/anything", port))
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let killed = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(killed.success());
+
+    let status = tokio::task::spawn_blocking(move || child.wait().unwrap())
+        .await
+        .unwrap();
+    assert!(status.success());
+
+    let dumped: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&dump_file).unwrap()).unwrap();
+    assert_eq!(dumped[0]["path"], "/api/anything");
+
+    fs::remove_file(dump_file).unwrap();
+}
+
+#[tokio::test]
+async fn test_export_command_wiremock_format() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/users/{id}")
+        .method("GET")
+        .respond()
+        .json(json!({"name": "Ann"}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let out_dir = temp_dir("export-wiremock");
+    let status = tokio::task::spawn_blocking({
+        let out_dir = out_dir.clone();
+        let from = handle.base_url();
+        move || {
+            Command::new(env!("CARGO_BIN_EXE_mimic-rs"))
+                .args(["--quiet", "export", "--format", "wiremock", "--from"])
+                .arg(from)
+                .arg("--out")
+                .arg(&out_dir)
+                .status()
+                .unwrap()
+        }
+    })
+    .await
+    .unwrap();
+    assert!(status.success());
+
+    let document: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(out_dir.join("mappings/mimic-rs.json")).unwrap())
+            .unwrap();
+    let mapping = &document["mappings"][0];
+    assert_eq!(mapping["request"]["method"], "GET");
+    assert_eq!(mapping["request"]["urlPathTemplate"], "/api/users/{id}");
+    assert_eq!(mapping["response"]["jsonBody"]["name"], "Ann");
+
+    handle.shutdown().await;
+    fs::remove_dir_all(out_dir).unwrap();
+}
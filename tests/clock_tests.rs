@@ -0,0 +1,186 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use chrono::{DateTime, Timelike, Utc};
+use mimic_rs::auth::JwtRequirement;
+use mimic_rs::clock::TestClock;
+use mimic_rs::models::DowntimeWindow;
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use ring::hmac;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+fn at(time: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(time).unwrap().to_utc()
+}
+
+fn hs256_token(secret: &str, claims: Value) -> String {
+    let header = URL_SAFE_NO_PAD.encode(json!({"alg": "HS256", "typ": "JWT"}).to_string());
+    let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+    let signing_input = format!("{}.{}", header, payload);
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(hmac::sign(&key, signing_input.as_bytes()));
+    format!("{}.{}", signing_input, signature)
+}
+
+#[tokio::test]
+async fn test_time_of_day_stub_with_test_clock() {
+    let clock = TestClock::new(at("2026-03-02T08:30:00Z"));
+    let server = MockServer::new("./tests/resources").with_clock(clock.clone());
+    server
+        .expect()
+        .path("/api/greeting")
+        .method("GET")
+        .respond()
+        .respond_with(|request| {
+            let greeting = match request.received_at.hour() {
+                0..12 => "Good morning!",
+                12..18 => "Good afternoon!",
+                _ => "Good evening!",
+            };
+            MockResponse::new(200).with_json_body(json!({"greeting": greeting}))
+        })
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let mut greetings = Vec::new();
+    for _ in 0..3 {
+        let body: Value = client
+            .get(handle.url("/api/greeting"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        greetings.push(body["greeting"].clone());
+        clock.advance(Duration::from_secs(5 * 3600));
+    }
+    assert_eq!(
+        greetings,
+        ["Good morning!", "Good afternoon!", "Good evening!"]
+    );
+}
+
+#[tokio::test]
+async fn test_templates_use_the_server_clock() {
+    let clock = TestClock::new(at("2026-01-31T23:00:00Z"));
+    let server = MockServer::new("./tests/resources").with_clock(clock.clone());
+    server
+        .expect()
+        .path("/api/token")
+        .method("GET")
+        .respond()
+        .json(json!({
+            "issued_at": "{{now}}",
+            "expires": "{{now offset='+1 month' format='%Y-%m-%d'}}"
+        }))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let body: Value = client
+        .get(handle.url("/api/token"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["issued_at"], "2026-01-31T23:00:00+00:00");
+    assert_eq!(body["expires"], "2026-02-28");
+
+    clock.set(at("2030-06-15T12:00:00Z"));
+    assert_eq!(server.clock().now(), at("2030-06-15T12:00:00Z"));
+    let body: Value = client
+        .get(handle.url("/api/token"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["issued_at"], "2030-06-15T12:00:00+00:00");
+}
+
+#[tokio::test]
+async fn test_downtime_and_rate_limits_follow_the_server_clock() {
+    let clock = TestClock::new(at("2026-05-01T12:00:00Z"));
+    let server = MockServer::new("./tests/resources")
+        .with_clock(clock.clone())
+        .with_downtime(
+            DowntimeWindow::every(Duration::from_secs(3600), Duration::from_secs(60))
+                .paths(["/api/reports"]),
+        );
+    server
+        .expect()
+        .path("/api/reports")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/search")
+        .method("GET")
+        .rate_limit(1, Duration::from_secs(60))
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+    let status = |path: &'static str| {
+        let request = client.get(handle.url(path)).send();
+        async move { request.await.unwrap().status().as_u16() }
+    };
+
+    // The outage starts with the server's clock, not the system time
+    assert_eq!(status("/api/reports").await, 503);
+    clock.advance(Duration::from_secs(61));
+    assert_eq!(status("/api/reports").await, 200);
+
+    assert_eq!(status("/api/search").await, 200);
+    assert_eq!(status("/api/search").await, 429);
+    clock.advance(Duration::from_secs(60));
+    assert_eq!(status("/api/search").await, 200);
+
+    let requests = server.get_request_log().await;
+    assert_eq!(requests[0].timestamp, at("2026-05-01T12:00:00Z"));
+    assert_eq!(requests[1].timestamp, at("2026-05-01T12:01:01Z"));
+}
+
+#[tokio::test]
+async fn test_token_expiry_uses_the_server_clock() {
+    let clock = TestClock::new(at("2026-05-01T12:00:00Z"));
+    let server = MockServer::new("./tests/resources").with_clock(clock.clone());
+    server
+        .expect()
+        .path("/api/me")
+        .method("GET")
+        .jwt(JwtRequirement::hmac("secret"))
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let now = at("2026-05-01T12:00:00Z").timestamp();
+    let token = hs256_token("secret", json!({"nbf": now - 60, "exp": now + 60}));
+    let call = || {
+        Client::new()
+            .get(handle.url("/api/me"))
+            .bearer_auth(&token)
+            .send()
+    };
+
+    assert_eq!(call().await.unwrap().status().as_u16(), 200);
+    clock.advance(Duration::from_secs(120));
+    assert_eq!(call().await.unwrap().status().as_u16(), 401);
+    clock.set(at("2026-05-01T11:00:00Z"));
+    assert_eq!(call().await.unwrap().status().as_u16(), 401);
+}
@@ -0,0 +1,196 @@
+use mimic_rs::{MockServer, ServerHandle};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn start(server: &MockServer, port: u16) {
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+}
+
+/// Starts two nodes peering with each other; returns node B to inspect it
+async fn start_pair() -> (ServerHandle, MockServer, ServerHandle) {
+    // Node B needs the address of node A before A can be told about B
+    let port_a = std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let node_b =
+        MockServer::new("./tests/resources").with_peers([format!("http://localhost:{}", port_a)]);
+    let handle_b = node_b.start_random_port().await.unwrap();
+    let node_a = MockServer::new("./tests/resources").with_peers([handle_b.base_url()]);
+    let handle_a = node_a.start_background(port_a).await.unwrap();
+    (handle_a, node_b, handle_b)
+}
+
+#[tokio::test]
+async fn test_cluster_synchronizes_expectations() {
+    let (port_a, port_b, port_c) = (9170, 9171, 9172);
+    let node_a =
+        MockServer::new("./tests/resources").with_peers([format!("http://localhost:{}", port_b)]);
+    let node_b =
+        MockServer::new("./tests/resources").with_peers([format!("http://localhost:{}", port_a)]);
+
+    start(&node_a, port_a).await;
+    start(&node_b, port_b).await;
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+
+    // Stub added on node A is served by node B
+    let created: Value = client
+        .post(format!("http://localhost:{}/_setup", port_a))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/shared",
+            "response": {"status_code": 200, "body": {"node": "any"}}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/shared", port_b))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(node_b.get_expectations().await[0].id, created["id"]);
+
+    // A node joining later copies the current stubs
+    let node_c =
+        MockServer::new("./tests/resources").with_peers([format!("http://localhost:{}", port_a)]);
+    start(&node_c, port_c).await;
+    sleep(Duration::from_millis(200)).await;
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/shared", port_c))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    // Reset on node B clears node A as well
+    client
+        .post(format!("http://localhost:{}/_reset", port_b))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/shared", port_a))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+    assert_eq!(node_a.get_expectations().await.len(), 0);
+}
+
+#[tokio::test]
+async fn test_cluster_replicates_replace_and_delete() {
+    let (handle_a, node_b, handle_b) = start_pair().await;
+
+    let client = Client::new();
+    let created: Value = client
+        .post(handle_a.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/shared",
+            "response": {"status_code": 200, "body": {"version": 1}}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = created["id"].as_str().unwrap();
+
+    // Replacing on node A changes the stub on node B
+    let resp = client
+        .put(handle_a.url(&format!("/_expectations/{}", id)))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/shared",
+            "response": {"status_code": 200, "body": {"version": 2}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let body: Value = client
+        .get(handle_b.url("/api/shared"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["version"], 2);
+    assert_eq!(node_b.get_expectations().await[0].id, id);
+
+    // Deleting on node A removes it from node B
+    let resp = client
+        .delete(handle_a.url(&format!("/_expectations/{}", id)))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client
+        .get(handle_b.url("/api/shared"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+    assert!(node_b.get_expectations().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_cluster_replicates_replacing_import() {
+    let (handle_a, node_b, handle_b) = start_pair().await;
+    let client = Client::new();
+
+    client
+        .post(handle_b.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/old",
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client
+        .post(handle_a.url("/_expectations/import?replace=true"))
+        .json(&json!([{
+            "method": "GET",
+            "path": "/api/new",
+            "response": {"status_code": 200}
+        }]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    // Node B ends up with the same single expectation as node A
+    let paths: Vec<String> = node_b
+        .get_expectations()
+        .await
+        .into_iter()
+        .map(|expectation| expectation.path)
+        .collect();
+    assert_eq!(paths, ["/api/new"]);
+
+    let resp = client.get(handle_b.url("/api/old")).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
@@ -91,3 +91,150 @@ async fn test_status_code_based_conditionals() {
     let resp3 = client.get(&url).send().await.unwrap();
     assert_eq!(resp3.status().as_u16(), 200);
 }
+
+#[tokio::test]
+async fn test_respond_with_request_data() {
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/users/{id}/orders")
+        .method("POST")
+        .respond()
+        .respond_with(|request| {
+            let order = request.json().unwrap_or_default();
+            MockResponse::new(201)
+                .with_header(
+                    "X-Request-Id",
+                    request.header("X-Request-Id").unwrap_or("-"),
+                )
+                .with_json_body(json!({
+                    "method": request.method,
+                    "path": request.path,
+                    "user": request.path_param("id"),
+                    "dry_run": request.query_param("dry_run") == Some("true"),
+                    "sku": order["sku"],
+                }))
+        })
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for user in ["7", "42"] {
+        let response = client
+            .post(handle.url(&format!("/api/users/{}/orders?dry_run=true", user)))
+            .header("X-Request-Id", format!("req-{}", user))
+            .json(&json!({"sku": "X-1"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 201);
+        assert_eq!(
+            response.headers()["x-request-id"],
+            format!("req-{}", user).as_str()
+        );
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["method"], "POST");
+        assert_eq!(body["path"], format!("/api/users/{}/orders", user));
+        assert_eq!(body["user"], user);
+        assert_eq!(body["dry_run"], true);
+        assert_eq!(body["sku"], "X-1");
+    }
+}
+
+#[tokio::test]
+async fn test_respond_with_async() {
+    let server = MockServer::new("./tests/resources");
+    let inventory =
+        std::sync::Arc::new(tokio::sync::Mutex::new(std::collections::HashMap::from([
+            ("X-1".to_string(), 3),
+        ])));
+
+    let stock = inventory.clone();
+    server
+        .expect()
+        .path("/api/stock/{sku}")
+        .method("GET")
+        .respond()
+        .respond_with_async(move |request| {
+            let stock = stock.clone();
+            async move {
+                sleep(Duration::from_millis(10)).await;
+                let sku = request.path_param("sku").unwrap_or_default();
+                match stock.lock().await.get(sku) {
+                    Some(count) => {
+                        MockResponse::new(200).with_json_body(json!({"sku": sku, "count": count}))
+                    }
+                    None => MockResponse::new(404),
+                }
+            }
+        })
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/api/stock/X-1"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 3);
+
+    inventory.lock().await.insert("X-1".to_string(), 0);
+    let body: Value = client
+        .get(handle.url("/api/stock/X-1"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["count"], 0);
+
+    let response = client
+        .get(handle.url("/api/stock/Y-2"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_conditional_registered_by_build() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+
+    let mock = server
+        .expect()
+        .path("/api/immediate")
+        .respond()
+        .status(500)
+        .conditional(|count| MockResponse::new(200).with_json_body(json!({"count": count})))
+        .build()
+        .await;
+
+    let expectation = server.get_expectation(mock.id()).await.unwrap();
+    let conditional_id = expectation.response.conditional_id.unwrap();
+    assert!(
+        server
+            .get_conditional_response(&conditional_id)
+            .await
+            .is_some()
+    );
+
+    // No chance for a background task to run between build() and the request
+    let response = Client::new()
+        .get(handle.url("/api/immediate"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["count"], 1);
+}
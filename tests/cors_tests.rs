@@ -0,0 +1,295 @@
+use mimic_rs::MockServer;
+use mimic_rs::cors::{CorsConfig, CorsPolicy};
+use reqwest::{Client, Method};
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_preflight_and_response_headers() {
+    let port = 9070;
+    let server = MockServer::new("./tests/resources").with_cors(CorsConfig::new(
+        CorsPolicy::permissive()
+            .allow_origins(&["https://app.example.com"])
+            .expose_headers(&["X-Request-Id"])
+            .max_age(Duration::from_secs(600)),
+    ));
+
+    server
+        .expect()
+        .path("/api/items")
+        .method("POST")
+        .respond()
+        .status(201)
+        .json(json!({"created": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/items", port);
+
+    let preflight = client
+        .request(Method::OPTIONS, &url)
+        .header("Origin", "https://app.example.com")
+        .header("Access-Control-Request-Method", "POST")
+        .header("Access-Control-Request-Headers", "content-type, x-trace")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(preflight.status().as_u16(), 204);
+    let headers = preflight.headers();
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert!(
+        headers["access-control-allow-methods"]
+            .to_str()
+            .unwrap()
+            .contains("POST")
+    );
+    assert_eq!(
+        headers["access-control-allow-headers"],
+        "content-type, x-trace"
+    );
+    assert_eq!(headers["access-control-max-age"], "600");
+
+    let resp = client
+        .post(&url)
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status().as_u16(), 201);
+    assert_eq!(
+        resp.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(
+        resp.headers()["access-control-expose-headers"],
+        "X-Request-Id"
+    );
+
+    // Requests from other origins are served without CORS headers
+    let other = client
+        .post(&url)
+        .header("Origin", "https://evil.example.com")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(other.status().as_u16(), 201);
+    assert!(!other.headers().contains_key("access-control-allow-origin"));
+}
+
+#[tokio::test]
+async fn test_strict_mode_with_path_policies() {
+    let port = 9071;
+    let server = MockServer::new("./tests/resources").with_cors(
+        CorsConfig::new(CorsPolicy::permissive())
+            .path(
+                "/api/admin/*",
+                CorsPolicy::default()
+                    .allow_origins(&["https://admin.example.com"])
+                    .allow_methods(&["GET"])
+                    .allow_credentials(),
+            )
+            .strict(),
+    );
+
+    server
+        .expect()
+        .path("/api/admin/users")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"users": []}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/admin/users", port);
+
+    let rejected = client
+        .get(&url)
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(rejected.status().as_u16(), 403);
+
+    let wrong_method = client
+        .request(Method::OPTIONS, &url)
+        .header("Origin", "https://admin.example.com")
+        .header("Access-Control-Request-Method", "DELETE")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(wrong_method.status().as_u16(), 403);
+
+    let allowed = client
+        .get(&url)
+        .header("Origin", "https://admin.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(allowed.status().as_u16(), 200);
+    assert_eq!(
+        allowed.headers()["access-control-allow-credentials"],
+        "true"
+    );
+
+    // Other paths fall back to the permissive default policy
+    let preflight = client
+        .request(
+            Method::OPTIONS,
+            format!("http://localhost:{}/api/public", port),
+        )
+        .header("Origin", "https://app.example.com")
+        .header("Access-Control-Request-Method", "GET")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(preflight.status().as_u16(), 204);
+    assert_eq!(preflight.headers()["access-control-allow-origin"], "*");
+}
+
+#[tokio::test]
+async fn test_expectation_cors_policy() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/public/{id}")
+        .method("PUT")
+        .cors(
+            CorsPolicy::permissive()
+                .allow_origins(&["https://app.example.com"])
+                .allow_credentials(),
+        )
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/private")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let preflight = client
+        .request(Method::OPTIONS, handle.url("/api/public/7"))
+        .header("Origin", "https://app.example.com")
+        .header("Access-Control-Request-Method", "PUT")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(preflight.status().as_u16(), 204);
+    assert_eq!(
+        preflight.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+    assert_eq!(
+        preflight.headers()["access-control-allow-credentials"],
+        "true"
+    );
+
+    let response = client
+        .put(handle.url("/api/public/7"))
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "https://app.example.com"
+    );
+
+    let response = client
+        .put(handle.url("/api/public/7"))
+        .header("Origin", "https://evil.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        !response
+            .headers()
+            .contains_key("access-control-allow-origin")
+    );
+
+    // Expectations without a policy get no CORS headers without with_cors
+    let response = client
+        .get(handle.url("/api/private"))
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        !response
+            .headers()
+            .contains_key("access-control-allow-origin")
+    );
+}
+
+#[tokio::test]
+async fn test_expectation_cors_overrides_strict_server_policy() {
+    let server = MockServer::new("./tests/resources").with_cors(
+        CorsConfig::new(CorsPolicy::default().allow_origins(&["https://app.example.com"])).strict(),
+    );
+    server
+        .expect()
+        .path("/api/widget")
+        .method("GET")
+        .cors(CorsPolicy::permissive())
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/account")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/api/widget"))
+        .header("Origin", "https://partner.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+
+    let response = client
+        .get(handle.url("/api/account"))
+        .header("Origin", "https://partner.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 403);
+}
@@ -0,0 +1,120 @@
+use mimic_rs::{Matcher, MockServer, ReceivedRequest};
+use reqwest::Client;
+use serde_json::{Value, json};
+
+/// Accepts requests whose `X-Signature` is the HMAC-SHA256 of the body
+struct HmacSignature {
+    secret: &'static str,
+}
+
+impl Matcher for HmacSignature {
+    fn matches(&self, request: &ReceivedRequest) -> bool {
+        let body = request.body.as_deref().unwrap_or_default();
+        request.header("X-Signature") == Some(sign(self.secret, body).as_str())
+    }
+}
+
+fn sign(secret: &str, body: &str) -> String {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.as_bytes());
+    ring::hmac::sign(&key, body.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_matcher_trait() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/webhooks")
+        .method("POST")
+        .matches(HmacSignature { secret: "s3cret" })
+        .respond()
+        .status(204)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/webhooks")
+        .method("POST")
+        .respond()
+        .status(401)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let body = r#"{"event":"paid"}"#;
+    let response = client
+        .post(handle.url("/webhooks"))
+        .header("X-Signature", sign("s3cret", body))
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 204);
+
+    let response = client
+        .post(handle.url("/webhooks"))
+        .header("X-Signature", sign("wrong", body))
+        .body(body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn test_closure_matchers() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/orders/{id}")
+        .method("PUT")
+        .matches(|request: &ReceivedRequest| {
+            request
+                .path_param("id")
+                .is_some_and(|id| id.parse::<u32>().is_ok())
+        })
+        .matches(|request: &ReceivedRequest| {
+            request
+                .json()
+                .is_some_and(|order| order["quantity"].as_u64() > Some(0))
+        })
+        .respond()
+        .status(200)
+        .json(json!({"updated": true}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let put = |path: &str, body: Value| client.put(handle.url(path)).json(&body).send();
+
+    assert_eq!(
+        put("/api/orders/12", json!({"quantity": 2}))
+            .await
+            .unwrap()
+            .status()
+            .as_u16(),
+        200
+    );
+    assert_eq!(
+        put("/api/orders/abc", json!({"quantity": 2}))
+            .await
+            .unwrap()
+            .status()
+            .as_u16(),
+        404
+    );
+    assert_eq!(
+        put("/api/orders/12", json!({"quantity": 0}))
+            .await
+            .unwrap()
+            .status()
+            .as_u16(),
+        404
+    );
+}
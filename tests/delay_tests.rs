@@ -0,0 +1,102 @@
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_response_delay_from_builder() {
+    let port = 9324;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/slow")
+        .method("GET")
+        .respond()
+        .status(200)
+        .delay(Duration::from_millis(300))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/jitter")
+        .method("GET")
+        .respond()
+        .rotate([MockResponse::new(200)
+            .with_random_delay(Duration::from_millis(100), Duration::from_millis(200))])
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+
+    let started = Instant::now();
+    let resp = client
+        .get(format!("http://localhost:{}/api/slow", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert!(started.elapsed() >= Duration::from_millis(300));
+
+    let started = Instant::now();
+    client
+        .get(format!("http://localhost:{}/api/jitter", port))
+        .send()
+        .await
+        .unwrap();
+    assert!(started.elapsed() >= Duration::from_millis(100));
+
+    // Clients with a shorter timeout give up
+    let result = client
+        .get(format!("http://localhost:{}/api/slow", port))
+        .timeout(Duration::from_millis(100))
+        .send()
+        .await;
+    assert!(result.unwrap_err().is_timeout());
+}
+
+#[tokio::test]
+async fn test_response_delay_via_http_api() {
+    let port = 9325;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/slow",
+            "response": {
+                "status_code": 200,
+                "delay": {"min_ms": 200, "max_ms": 200}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let started = Instant::now();
+    let resp = client
+        .get(format!("http://localhost:{}/api/slow", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert!(started.elapsed() >= Duration::from_millis(200));
+}
@@ -0,0 +1,163 @@
+use chrono::Utc;
+use mimic_rs::MockServer;
+use mimic_rs::models::DowntimeWindow;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_recurring_downtime_from_builder() {
+    let port = 9322;
+    let server = MockServer::new("./tests/resources").with_downtime(
+        DowntimeWindow::every(Duration::from_secs(1), Duration::from_millis(500))
+            .paths(["/api/payments/*"]),
+    );
+
+    for path in ["/api/payments/1", "/api/users"] {
+        server
+            .expect()
+            .path(path)
+            .method("GET")
+            .respond()
+            .status(200)
+            .build()
+            .await;
+    }
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let payments = format!("http://localhost:{}/api/payments/1", port);
+
+    // Down for the first 500ms of every second
+    let resp = client.get(&payments).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 503);
+    assert_eq!(resp.headers()["retry-after"], "1");
+    assert_eq!(resp.headers()["x-mimic-downtime"], "scheduled");
+
+    // Other paths are unaffected
+    let resp = client
+        .get(format!("http://localhost:{}/api/users", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    sleep(Duration::from_millis(500)).await;
+    let resp = client.get(&payments).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    sleep(Duration::from_millis(500)).await;
+    let resp = client.get(&payments).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 503);
+}
+
+#[tokio::test]
+async fn test_downtime_via_http_api() {
+    let port = 9323;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/data")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let downtime = format!("http://localhost:{}/_downtime", port);
+    let data = format!("http://localhost:{}/api/data", port);
+
+    let resp = client
+        .post(&downtime)
+        .json(&json!({"every_ms": 1000, "duration_ms": 2000}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+
+    let now = Utc::now();
+    let resp = client
+        .post(&downtime)
+        .json(&json!({
+            "start": now - chrono::Duration::seconds(1),
+            "end": now + chrono::Duration::seconds(60),
+            "status": 502,
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let windows: Value = client
+        .get(&downtime)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(windows.as_array().unwrap().len(), 1);
+    assert_eq!(windows[0]["status"], 502);
+
+    let resp = client.get(&data).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 502);
+    let retry_after: u64 = resp.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((59..=60).contains(&retry_after));
+
+    // The admin API stays available during the outage
+    client.delete(&downtime).send().await.unwrap();
+    let resp = client.get(&data).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let now = Utc::now();
+    client
+        .post(&downtime)
+        .json(&json!({
+            "start": now - chrono::Duration::seconds(1),
+            "end": now + chrono::Duration::seconds(60),
+            "drop_connections": true,
+        }))
+        .send()
+        .await
+        .unwrap();
+    let result = client.get(&data).send().await;
+    assert!(result.is_err() || result.unwrap().bytes().await.is_err());
+}
+
+#[tokio::test]
+async fn test_builder_downtime_is_scheduled_once_on_start() {
+    let server = MockServer::new("./tests/resources").with_downtime(DowntimeWindow::every(
+        Duration::from_secs(60),
+        Duration::from_secs(1),
+    ));
+    let _first = server.start_random_port().await.unwrap();
+    assert_eq!(server.downtime().await.len(), 1);
+
+    // A clone shares the schedule; its own windows join it without repeating the first
+    let second = server.clone().with_downtime(DowntimeWindow::every(
+        Duration::from_secs(30),
+        Duration::from_secs(1),
+    ));
+    let _second = second.start_random_port().await.unwrap();
+    assert_eq!(server.downtime().await.len(), 2);
+}
@@ -0,0 +1,125 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::ExpectContinue;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::sleep;
+
+/// Sends the request head with `Expect: 100-continue` and returns the first bytes answered
+async fn send_head(stream: &mut TcpStream, path: &str, body_len: usize) -> String {
+    let head = format!(
+        "POST {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nExpect: 100-continue\r\n\r\n",
+        path, body_len
+    );
+    stream.write_all(head.as_bytes()).await.unwrap();
+    read_some(stream).await
+}
+
+async fn read_some(stream: &mut TcpStream) -> String {
+    let mut buf = vec![0u8; 1024];
+    let n = stream.read(&mut buf).await.unwrap();
+    String::from_utf8_lossy(&buf[..n]).to_string()
+}
+
+#[tokio::test]
+async fn test_expect_continue_modes() {
+    let port = 9220;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/upload/accept")
+        .method("POST")
+        .expect_continue(ExpectContinue::Accept)
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/upload/slow")
+        .method("POST")
+        .expect_continue(ExpectContinue::delay(Duration::from_millis(300)))
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/upload/reject")
+        .method("POST")
+        .expect_continue(ExpectContinue::Reject)
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let addr = format!("localhost:{}", port);
+
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let interim = send_head(&mut stream, "/upload/accept", 5).await;
+    assert!(interim.starts_with("HTTP/1.1 100 Continue"));
+    stream.write_all(b"hello").await.unwrap();
+    let response = read_some(&mut stream).await;
+    assert!(response.contains("201 Created"), "{}", response);
+
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let started = Instant::now();
+    let interim = send_head(&mut stream, "/upload/slow", 5).await;
+    assert!(interim.starts_with("HTTP/1.1 100 Continue"));
+    assert!(started.elapsed() >= Duration::from_millis(300));
+
+    let mut stream = TcpStream::connect(&addr).await.unwrap();
+    let response = send_head(&mut stream, "/upload/reject", 5).await;
+    assert!(
+        response.starts_with("HTTP/1.1 417 Expectation Failed"),
+        "{}",
+        response
+    );
+
+    assert_eq!(server.count_calls("POST", "/upload/reject").await, 1);
+}
+
+#[tokio::test]
+async fn test_expect_continue_via_http_api() {
+    let port = 9221;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = reqwest::Client::new()
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "PUT",
+            "path": "/files/*",
+            "expect_continue": {"mode": "reject"},
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let mut stream = TcpStream::connect(format!("localhost:{}", port))
+        .await
+        .unwrap();
+    let head = "PUT /files/big.iso HTTP/1.1\r\nHost: localhost\r\nContent-Length: 1000000\r\nExpect: 100-continue\r\n\r\n";
+    stream.write_all(head.as_bytes()).await.unwrap();
+    let response = read_some(&mut stream).await;
+    assert!(response.starts_with("HTTP/1.1 417"), "{}", response);
+}
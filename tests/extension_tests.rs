@@ -0,0 +1,158 @@
+use mimic_rs::extension::{Extension, RequestMatcher, ResponseGenerator};
+use mimic_rs::transform::IncomingRequest;
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Accepts requests whose `X-Signature` is the HMAC-SHA256 of the body
+struct HmacSignature;
+
+impl RequestMatcher for HmacSignature {
+    fn matches(&self, request: &IncomingRequest, config: &Value) -> bool {
+        let secret = config["secret"].as_str().unwrap_or_default();
+        let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.as_bytes());
+        let body = request.body.as_deref().unwrap_or_default();
+        let expected: String = ring::hmac::sign(&key, body.as_bytes())
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        request.headers.get("x-signature") == Some(&expected)
+    }
+}
+
+impl Extension for HmacSignature {
+    fn name(&self) -> &str {
+        "hmac"
+    }
+
+    fn as_matcher(&self) -> Option<&dyn RequestMatcher> {
+        Some(self)
+    }
+}
+
+/// Echoes the request path with a configurable status
+struct Echo;
+
+impl ResponseGenerator for Echo {
+    fn generate(&self, request: &IncomingRequest, config: &Value) -> MockResponse {
+        let status = config["status"].as_u64().unwrap_or(200) as u16;
+        MockResponse::new(status).with_json_body(json!({"path": request.path}))
+    }
+}
+
+impl Extension for Echo {
+    fn name(&self) -> &str {
+        "echo"
+    }
+
+    fn as_generator(&self) -> Option<&dyn ResponseGenerator> {
+        Some(self)
+    }
+}
+
+fn sign(secret: &str, body: &str) -> String {
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, secret.as_bytes());
+    ring::hmac::sign(&key, body.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_extension_matcher_and_generator() {
+    let port = 9270;
+    let server = MockServer::new("./tests/resources");
+    server.register_extension(HmacSignature);
+    server.register_extension(Echo);
+
+    server
+        .expect()
+        .path("/webhooks/*")
+        .method("POST")
+        .matcher("hmac", json!({"secret": "whsec"}))
+        .respond()
+        .generate("echo", json!({"status": 202}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/webhooks/github", port);
+    let payload = r#"{"action":"opened"}"#;
+
+    let resp = client
+        .post(&url)
+        .header("X-Signature", sign("whsec", payload))
+        .body(payload)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 202);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["path"], "/webhooks/github");
+
+    let resp = client
+        .post(&url)
+        .header("X-Signature", sign("wrong", payload))
+        .body(payload)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_extensions_referenced_via_http_api() {
+    let port = 9271;
+    let server = MockServer::new("./tests/resources");
+    server.register_extension(Echo);
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    for (path, generator) in [("/api/echo", "echo"), ("/api/missing", "nope")] {
+        let resp = client
+            .post(format!("http://localhost:{}/_setup", port))
+            .json(&json!({
+                "method": "GET",
+                "path": path,
+                "response": {"status_code": 200, "generator": {"name": generator}}
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status().as_u16(), 201);
+    }
+
+    let body: Value = client
+        .get(format!("http://localhost:{}/api/echo", port))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["path"], "/api/echo");
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/missing", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 500);
+}
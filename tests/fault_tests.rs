@@ -0,0 +1,120 @@
+use mimic_rs::models::Fault;
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::time::sleep;
+
+/// Sends a bare GET and returns what the server wrote before closing
+async fn raw_get(port: u16, path: &str) -> std::io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect(("127.0.0.1", port)).await?;
+    stream
+        .write_all(format!("GET {} HTTP/1.1\r\nHost: localhost\r\n\r\n", path).as_bytes())
+        .await?;
+    let mut received = Vec::new();
+    stream.read_to_end(&mut received).await?;
+    Ok(received)
+}
+
+#[tokio::test]
+async fn test_faults_from_builder() {
+    let port = 9326;
+    let server = MockServer::new("./tests/resources");
+
+    for (path, fault) in [
+        ("/reset", Fault::ConnectionReset),
+        ("/empty", Fault::EmptyResponse),
+        ("/malformed", Fault::MalformedChunk),
+    ] {
+        server
+            .expect()
+            .path(path)
+            .method("GET")
+            .respond()
+            .status(200)
+            .json(json!({"never": "sent"}))
+            .fault(fault)
+            .build()
+            .await;
+    }
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let error = raw_get(port, "/reset").await.unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::ConnectionReset);
+
+    assert!(raw_get(port, "/empty").await.unwrap().is_empty());
+
+    let received = raw_get(port, "/malformed").await.unwrap();
+    let received = String::from_utf8(received).unwrap();
+    assert!(received.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(received.contains("not-a-chunk-size"));
+    assert!(!received.contains("never"));
+
+    let client = Client::new();
+    for path in ["/reset", "/empty", "/malformed"] {
+        let result = client
+            .get(format!("http://localhost:{}{}", port, path))
+            .send()
+            .await;
+        let failed = match result {
+            Ok(resp) => resp.bytes().await.is_err(),
+            Err(_) => true,
+        };
+        assert!(failed, "{} answered normally", path);
+    }
+
+    // The server keeps serving other connections
+    assert_eq!(server.count_calls("GET", "/empty").await, 2);
+}
+
+#[tokio::test]
+async fn test_fault_via_http_api_and_rotation() {
+    let port = 9327;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/flaky",
+            "response": {"status_code": 200, "fault": "empty_response"}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert!(raw_get(port, "/api/flaky").await.unwrap().is_empty());
+
+    // A fault on the first attempt, a proper answer on the retry
+    server
+        .expect()
+        .path("/api/retry")
+        .method("GET")
+        .respond()
+        .rotate([
+            MockResponse::new(200).with_fault(Fault::ConnectionReset),
+            MockResponse::new(200).with_json_body(json!({"ok": true})),
+        ])
+        .build()
+        .await;
+
+    let url = format!("http://localhost:{}/api/retry", port);
+    assert!(client.get(&url).send().await.is_err());
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
@@ -0,0 +1,154 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+async fn graphql_server() -> MockServer {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/graphql")
+        .method("POST")
+        .graphql_operation("GetUser")
+        .graphql_variables(json!({"id": "1"}))
+        .respond()
+        .json(json!({"data": {"user": {"id": "1", "name": "Alice"}}}))
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/graphql")
+        .method("POST")
+        .graphql_operation("GetUser")
+        .respond()
+        .json(json!({"data": {"user": null}}))
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/graphql")
+        .method("POST")
+        .graphql_query_contains("createOrder(input: $input) { id }")
+        .respond()
+        .json(json!({"data": {"createOrder": {"id": "o-1"}}}))
+        .build()
+        .await;
+    server
+}
+
+async fn post(client: &Client, url: String, body: Value) -> (u16, Value) {
+    let response = client.post(url).json(&body).send().await.unwrap();
+    let status = response.status().as_u16();
+    (status, response.json().await.unwrap_or(Value::Null))
+}
+
+#[tokio::test]
+async fn test_graphql_operation_and_variables() {
+    let server = graphql_server().await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+    let url = || handle.url("/graphql");
+
+    // Operation name from the query text, variables matched partially
+    let (status, body) = post(
+        &client,
+        url(),
+        json!({
+            "query": "# fetch one user\nquery GetUser($id: ID!) { user(id: $id) { id name } }",
+            "variables": {"id": "1", "locale": "en"}
+        }),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["user"]["name"], "Alice");
+
+    // operationName picks the operation of a multi-operation document
+    let (_, body) = post(
+        &client,
+        url(),
+        json!({
+            "query": "query ListUsers { users { id } } query GetUser { user(id: 2) { id } }",
+            "operationName": "GetUser",
+            "variables": {"id": "2"}
+        }),
+    )
+    .await;
+    assert_eq!(body["data"]["user"], Value::Null);
+
+    let (status, _) = post(
+        &client,
+        url(),
+        json!({"query": "query ListUsers { users { id } }"}),
+    )
+    .await;
+    assert_eq!(status, 404);
+}
+
+#[tokio::test]
+async fn test_graphql_query_contains_ignores_formatting() {
+    let server = graphql_server().await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let (status, body) = post(
+        &client,
+        handle.url("/graphql"),
+        json!({
+            "query": "mutation CreateOrder($input: OrderInput!) {\n  createOrder(input: $input) {\n    id\n  }\n}",
+            "variables": {"input": {"sku": "A-1"}}
+        }),
+    )
+    .await;
+    assert_eq!(status, 200);
+    assert_eq!(body["data"]["createOrder"]["id"], "o-1");
+
+    // Batched operations match when one of them does
+    let (status, _) = post(
+        &client,
+        handle.url("/graphql"),
+        json!([
+            {"query": "{ viewer { id } }"},
+            {"query": "mutation { createOrder(input: $input) { id } }"}
+        ]),
+    )
+    .await;
+    assert_eq!(status, 200);
+}
+
+#[tokio::test]
+async fn test_graphql_get_and_setup() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/graphql",
+            "graphql": {"operation_name": "Ping", "variables": {"verbose": true}},
+            "response": {"status_code": 200, "body": {"data": {"ping": "pong"}}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client
+        .get(handle.url("/graphql"))
+        .query(&[
+            ("query", "query Ping { ping }"),
+            ("variables", r#"{"verbose": true}"#),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = client
+        .get(handle.url("/graphql"))
+        .query(&[("query", "query Ping { ping }")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+}
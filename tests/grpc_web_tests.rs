@@ -0,0 +1,143 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// Serialized `HelloReply { message: "Hello" }`
+const HELLO_REPLY: &[u8] = &[0x0a, 0x05, b'H', b'e', b'l', b'l', b'o'];
+
+#[tokio::test]
+async fn test_grpc_web_binary_and_text_framing() {
+    let port = 9190;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .grpc("helloworld.Greeter", "SayHello")
+        .respond()
+        .grpc_web(HELLO_REPLY)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/helloworld.Greeter/SayHello", port);
+
+    let resp = client
+        .post(&url)
+        .header("Content-Type", "application/grpc-web+proto")
+        .body(vec![0u8, 0, 0, 0, 0])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["content-type"], "application/grpc-web+proto");
+    let body = resp.bytes().await.unwrap();
+
+    let mut expected = vec![0x00, 0, 0, 0, HELLO_REPLY.len() as u8];
+    expected.extend_from_slice(HELLO_REPLY);
+    let trailers = b"grpc-status:0\r\n";
+    expected.extend_from_slice(&[0x80, 0, 0, 0, trailers.len() as u8]);
+    expected.extend_from_slice(trailers);
+    assert_eq!(body.to_vec(), expected);
+
+    let resp = client
+        .post(&url)
+        .header("Content-Type", "application/grpc-web-text")
+        .body(STANDARD.encode([0u8, 0, 0, 0, 0]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.headers()["content-type"],
+        "application/grpc-web-text+proto"
+    );
+    let body = resp.text().await.unwrap();
+    assert_eq!(STANDARD.decode(body).unwrap(), expected);
+}
+
+#[tokio::test]
+async fn test_grpc_web_error_status_via_http_api() {
+    let port = 9191;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "POST",
+            "path": "/users.UserService/GetUser",
+            "response": {
+                "status_code": 200,
+                "grpc_web": {"status": 5, "status_message": "user not found"}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let body = client
+        .post(format!(
+            "http://localhost:{}/users.UserService/GetUser",
+            port
+        ))
+        .header("Content-Type", "application/grpc-web+proto")
+        .send()
+        .await
+        .unwrap()
+        .bytes()
+        .await
+        .unwrap();
+
+    assert_eq!(body[0], 0x80);
+    let trailers = String::from_utf8(body[5..].to_vec()).unwrap();
+    assert_eq!(trailers, "grpc-status:5\r\ngrpc-message:user not found\r\n");
+}
+
+#[tokio::test]
+async fn test_grpc_web_message_encoding_and_http_status() {
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .grpc("users.UserService", "GetUser")
+        .respond()
+        .status(503)
+        .grpc_web_error(14, "disk 100% full\nretry später")
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+
+    let resp = Client::new()
+        .post(handle.url("/users.UserService/GetUser"))
+        .header("Content-Type", "application/grpc-web+proto")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 503);
+
+    let body = resp.bytes().await.unwrap();
+    let trailers = String::from_utf8(body[5..].to_vec()).unwrap();
+    assert_eq!(
+        trailers,
+        "grpc-status:14\r\ngrpc-message:disk 100%25 full%0Aretry sp%C3%A4ter\r\n"
+    );
+}
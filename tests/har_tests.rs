@@ -0,0 +1,218 @@
+use mimic_rs::MockServer;
+use mimic_rs::import;
+use mimic_rs::models::{ResponseDelay, SequenceEnd};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+const POLLING_HAR: &str = "./tests/resources/import/polling.har";
+
+#[test]
+fn test_repeated_entries_become_a_sequence() {
+    let imported = import::import_har(Path::new(POLLING_HAR), true).unwrap();
+
+    assert_eq!(imported.expectations.len(), 2);
+    let create = &imported.expectations[0];
+    assert_eq!(
+        create.response.delay,
+        Some(ResponseDelay::fixed(Duration::from_millis(151)))
+    );
+    assert!(create.rotate.is_empty());
+
+    let status = &imported.expectations[1];
+    assert_eq!(status.rotate.len(), 2);
+    assert_eq!(status.rotate_end, SequenceEnd::RepeatLast);
+    // wait is unknown (-1), the total time is used instead
+    assert_eq!(
+        status.rotate[0].delay,
+        Some(ResponseDelay::fixed(Duration::from_millis(40)))
+    );
+    assert_eq!(
+        status.rotate[1].delay,
+        Some(ResponseDelay::fixed(Duration::from_millis(30)))
+    );
+
+    let without_latency = import::import_har(Path::new(POLLING_HAR), false).unwrap();
+    assert_eq!(without_latency.expectations[0].response.delay, None);
+}
+
+#[tokio::test]
+async fn test_load_har_replays_the_session() {
+    let server = MockServer::new("./tests/resources");
+    assert_eq!(server.load_har(POLLING_HAR).await.unwrap(), 2);
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client.post(handle.url("/api/jobs")).send().await.unwrap();
+    assert_eq!(response.status(), 202);
+    let job: Value = response.json().await.unwrap();
+    assert_eq!(job["id"], "j1");
+
+    let running: Value = client
+        .get(handle.url("/api/jobs/j1"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(running["state"], "running");
+
+    // The text body is served from memory, and stays the answer afterwards
+    for _ in 0..2 {
+        let done = client
+            .get(handle.url("/api/jobs/j1"))
+            .send()
+            .await
+            .unwrap()
+            .text()
+            .await
+            .unwrap();
+        assert_eq!(done, "done");
+    }
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_load_har_with_latency() {
+    let server = MockServer::new("./tests/resources");
+    server.load_har_with_latency(POLLING_HAR).await.unwrap();
+    let handle = server.start_random_port().await.unwrap();
+
+    let started = Instant::now();
+    let response = Client::new()
+        .post(handle.url("/api/jobs"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    assert!(started.elapsed() >= Duration::from_millis(150));
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_request_log_keeps_the_response() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/items")
+        .method("POST")
+        .respond()
+        .status(201)
+        .header("X-Item", "7")
+        .json(json!({"id": 7}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    client
+        .post(handle.url("/api/items"))
+        .body("{\"name\":\"lamp\"}")
+        .send()
+        .await
+        .unwrap();
+    client.get(handle.url("/missing")).send().await.unwrap();
+
+    let log = server.get_request_log().await;
+    assert_eq!(log.len(), 2);
+    let created = log[0].response.as_ref().unwrap();
+    assert_eq!(created.status, 201);
+    assert_eq!(created.headers.get("x-item").unwrap(), "7");
+    assert_eq!(created.body.as_deref(), Some("{\"id\":7}"));
+    assert_eq!(log[1].response.as_ref().unwrap().status, 404);
+    assert_ne!(log[0].id, log[1].id);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_export_request_log_as_har() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/items")
+        .method("GET")
+        .query_param("page", "2")
+        .respond()
+        .json(json!({"items": ["lamp"]}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    client
+        .get(handle.url("/api/items?page=2"))
+        .send()
+        .await
+        .unwrap();
+
+    let response = client
+        .get(handle.url("/_requests/export?format=har"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(
+        response
+            .headers()
+            .get("content-disposition")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .contains("requests.har")
+    );
+    let har: Value = response.json().await.unwrap();
+    let entries = har["log"]["entries"].as_array().unwrap();
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry["request"]["method"], "GET");
+    assert!(
+        entry["request"]["url"]
+            .as_str()
+            .unwrap()
+            .ends_with("/api/items?page=2")
+    );
+    assert_eq!(
+        entry["request"]["queryString"],
+        json!([{"name": "page", "value": "2"}])
+    );
+    assert_eq!(entry["response"]["status"], 200);
+    assert_eq!(entry["response"]["statusText"], "OK");
+    assert_eq!(entry["response"]["content"]["mimeType"], "application/json");
+    assert_eq!(
+        entry["response"]["content"]["text"],
+        "{\"items\":[\"lamp\"]}"
+    );
+
+    let response = client
+        .get(handle.url("/_requests/export?format=csv"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    // The export replays on another instance
+    let file = std::env::temp_dir().join(format!("mimic-{}.har", uuid::Uuid::new_v4()));
+    fs::write(&file, server.export_har().await.to_string()).unwrap();
+    let replay = MockServer::new("./tests/resources");
+    replay.load_har(&file).await.unwrap();
+    let replay_handle = replay.start_random_port().await.unwrap();
+    let items: Value = client
+        .get(replay_handle.url("/api/items?page=2"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(items, json!({"items": ["lamp"]}));
+
+    replay_handle.shutdown().await;
+    handle.shutdown().await;
+    fs::remove_file(file).unwrap();
+}
@@ -0,0 +1,114 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::{SseEvent, SseStream};
+use reqwest::Client;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_head_uses_get_stub() {
+    let port = 9314;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/users/1")
+        .method("GET")
+        .respond()
+        .status(200)
+        .header("ETag", "\"v1\"")
+        .json(serde_json::json!({"id": 1, "name": "Ada"}))
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/reports")
+        .method("HEAD")
+        .respond()
+        .status(204)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/reports")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+
+    let get = client
+        .get(format!("http://localhost:{}/api/users/1", port))
+        .send()
+        .await
+        .unwrap();
+    let get_length = get.content_length().unwrap();
+
+    let resp = client
+        .head(format!("http://localhost:{}/api/users/1", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["etag"].to_str().unwrap(), "\"v1\"");
+    assert_eq!(
+        resp.headers()["content-length"].to_str().unwrap(),
+        get_length.to_string()
+    );
+    assert_eq!(
+        resp.headers()["content-type"].to_str().unwrap(),
+        "application/json"
+    );
+    assert!(resp.bytes().await.unwrap().is_empty());
+
+    // A HEAD stub of its own wins over the GET stub
+    let resp = client
+        .head(format!("http://localhost:{}/api/reports", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client
+        .head(format!("http://localhost:{}/api/missing", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_head_on_endless_stream() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/events")
+        .method("GET")
+        .respond()
+        .sse(
+            SseStream::new()
+                .event(SseEvent::new("tick").delay(Duration::from_millis(50)))
+                .repeat(),
+        )
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let response = tokio::time::timeout(
+        Duration::from_secs(2),
+        Client::new().head(handle.url("/events")).send(),
+    )
+    .await
+    .expect("HEAD must not wait for the stream to end")
+    .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+}
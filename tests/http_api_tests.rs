@@ -155,3 +155,464 @@ async fn test_reset_api() {
 
     assert_eq!(resp_after.status().as_u16(), 404);
 }
+
+#[tokio::test]
+async fn test_admin_api_disabled() {
+    let port = 9043;
+    let server = MockServer::new("./tests/resources").without_admin();
+
+    server
+        .expect()
+        .path("/api/read-only")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"read_only": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+
+    let reset_resp = client
+        .post(format!("http://localhost:{}/_reset", port))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(reset_resp.status().as_u16(), 404);
+
+    let setup_resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/injected",
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(setup_resp.status().as_u16(), 404);
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/read-only", port))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_inspect_expectations() {
+    let server = MockServer::new("./tests/resources");
+
+    let mock = server
+        .expect()
+        .path("/api/builder")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    let base = handle.base_url();
+
+    let created: Value = client
+        .post(format!("{}/_setup", base))
+        .json(&json!({
+            "method": "POST",
+            "path": "/api/setup",
+            "response": {"status_code": 201}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    for _ in 0..2 {
+        client
+            .get(format!("{}/api/builder", base))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    let expectations: Vec<Value> = client
+        .get(format!("{}/_expectations", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(expectations.len(), 2);
+    let builder = expectations
+        .iter()
+        .find(|exp| exp["id"] == mock.id())
+        .unwrap();
+    assert_eq!(builder["hits"], 2);
+    assert_eq!(builder["source"], "builder");
+
+    let response = client
+        .get(format!(
+            "{}/_expectations/{}",
+            base,
+            created["id"].as_str().unwrap()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let setup: Value = response.json().await.unwrap();
+    assert_eq!(setup["path"], "/api/setup");
+    assert_eq!(setup["source"], "setup_api");
+    assert_eq!(setup["hits"], 0);
+
+    let response = client
+        .get(format!("{}/_expectations/unknown", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_replace_and_delete_expectation() {
+    let server = MockServer::new("./tests/resources");
+
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    let base = handle.base_url();
+
+    let created: Value = client
+        .post(format!("{}/_setup", base))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/flag",
+            "response": {"status_code": 200, "body": {"enabled": false}}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id = created["id"].as_str().unwrap();
+
+    let response = client
+        .put(format!("{}/_expectations/{}", base, id))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/flag",
+            "response": {"status_code": 200, "body": {"enabled": true}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let replaced: Value = response.json().await.unwrap();
+    assert_eq!(replaced["id"], id);
+
+    let body: Value = client
+        .get(format!("{}/api/flag", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["enabled"], true);
+
+    let response = client
+        .delete(format!("{}/_expectations/{}", base, id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = client
+        .get(format!("{}/api/flag", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = client
+        .delete(format!("{}/_expectations/{}", base, id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let response = client
+        .put(format!("{}/_expectations/{}", base, id))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/flag",
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+}
+
+#[tokio::test]
+async fn test_verify_with_range() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/retry")
+        .method("POST")
+        .respond()
+        .status(503)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for _ in 0..2 {
+        client.post(handle.url("/api/retry")).send().await.unwrap();
+    }
+
+    let verify = |times: serde_json::Value| {
+        client
+            .post(handle.url("/_verify"))
+            .json(&json!({"method": "POST", "path": "/api/retry", "times": times}))
+            .send()
+    };
+
+    let response = verify(json!({"at_least": 1, "at_most": 3})).await.unwrap();
+    assert_eq!(response.status(), 200);
+    let result: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(result["expected"], json!({"at_least": 1, "at_most": 3}));
+    assert_eq!(result["actual"], 2);
+
+    assert_eq!(verify(json!({"at_least": 3})).await.unwrap().status(), 400);
+    assert_eq!(verify(json!({"at_most": 2})).await.unwrap().status(), 200);
+    assert_eq!(verify(json!(2)).await.unwrap().status(), 200);
+
+    // Invalid ranges are rejected with a message instead of a result
+    let response = verify(json!({"at_least": 3, "at_most": 1})).await.unwrap();
+    assert_eq!(response.status(), 400);
+    assert!(
+        response
+            .text()
+            .await
+            .unwrap()
+            .contains("greater than at_most")
+    );
+    let response = verify(json!({})).await.unwrap();
+    assert_eq!(response.status(), 400);
+    assert!(
+        response
+            .text()
+            .await
+            .unwrap()
+            .contains("at_least or at_most")
+    );
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_dashboard() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client.get(handle.url("/_ui")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/html")
+    );
+    let page = response.text().await.unwrap();
+    assert!(page.contains("/_expectations"));
+    assert!(page.contains("/_requests"));
+    assert!(page.contains("/_setup"));
+
+    handle.shutdown().await;
+
+    let server = MockServer::new("./tests/resources").without_admin();
+    let handle = server.start_random_port().await.unwrap();
+    let response = client.get(handle.url("/_ui")).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_export_and_import_expectations() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/users/{id}")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"id": 1}))
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/fixture")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json_file("order_item.json")
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/_expectations/export"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+    let json_document = response.text().await.unwrap();
+    let exported: Value = serde_json::from_str(&json_document).unwrap();
+    assert_eq!(exported.as_array().unwrap().len(), 2);
+    assert!(
+        exported
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|e| e["response"]["body_file"] == "order_item.json")
+    );
+
+    let yaml_document = client
+        .get(handle.url("/_expectations/export?format=yaml"))
+        .send()
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(yaml_document.contains("path: /api/users/{id}"));
+
+    // Reload the YAML snapshot into an empty server
+    server.reset().await;
+    let response = client
+        .post(handle.url("/_expectations/import"))
+        .header(header::CONTENT_TYPE, "application/yaml")
+        .body(yaml_document)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let imported: Value = response.json().await.unwrap();
+    assert_eq!(imported.as_array().unwrap().len(), 2);
+
+    let response = client.get(handle.url("/api/users/7")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let response = client.get(handle.url("/api/fixture")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Importing again adds to the current set unless replace is given
+    client
+        .post(handle.url("/_expectations/import"))
+        .body(json_document.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(server.get_expectations().await.len(), 4);
+    client
+        .post(handle.url("/_expectations/import?replace=true"))
+        .body(json_document)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(server.get_expectations().await.len(), 2);
+
+    // One invalid expectation rejects the whole document
+    let response = client
+        .post(handle.url("/_expectations/import"))
+        .json(&json!([
+            {"method": "GET", "path": "/ok", "response": {"status_code": 200}},
+            {"method": "GET", "path": "/bad", "header_matchers": {"x": {"matches": "("}},
+             "response": {"status_code": 200}}
+        ]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    assert!(response.text().await.unwrap().starts_with("expectation 2:"));
+    assert_eq!(server.get_expectations().await.len(), 2);
+
+    let response = client
+        .post(handle.url("/_expectations/import"))
+        .body("not a document")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_setup_batch_api() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup/batch"))
+        .json(&json!([
+            {"method": "GET", "path": "/api/one", "response": {"status_code": 200}},
+            {"method": "POST", "path": "/api/two", "response": {"status_code": 201}}
+        ]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let ids: Vec<String> = response.json().await.unwrap();
+    assert_eq!(ids.len(), 2);
+
+    let expectations = server.get_expectations().await;
+    let one = expectations.iter().find(|e| e.id == ids[0]).unwrap();
+    assert_eq!(one.path, "/api/one");
+    let two = expectations.iter().find(|e| e.id == ids[1]).unwrap();
+    assert_eq!(two.path, "/api/two");
+
+    let response = client.post(handle.url("/api/two")).send().await.unwrap();
+    assert_eq!(response.status(), 201);
+
+    // Nothing is registered when one expectation is invalid
+    let response = client
+        .post(handle.url("/_setup/batch"))
+        .json(&json!([
+            {"method": "GET", "path": "/api/three", "response": {"status_code": 200}},
+            {"method": "GET", "path": "/api/four", "body_base64": "%%%",
+             "response": {"status_code": 200}}
+        ]))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    assert!(response.text().await.unwrap().starts_with("expectation 2:"));
+    assert_eq!(server.get_expectations().await.len(), 2);
+
+    handle.shutdown().await;
+}
@@ -0,0 +1,137 @@
+use mimic_rs::models::{Fault, SseEvent, SseStream};
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_idempotency_key_replays_stored_response() {
+    let port = 9321;
+    let server = MockServer::new("./tests/resources").with_idempotency_key("Idempotency-Key");
+
+    server
+        .expect()
+        .path("/api/payments")
+        .method("POST")
+        .respond()
+        .rotate([
+            MockResponse::new(201).with_json_body(json!({"id": "pay_1"})),
+            MockResponse::new(201).with_json_body(json!({"id": "pay_2"})),
+        ])
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/payments", port);
+    let pay = |key: &'static str| client.post(&url).header("Idempotency-Key", key).send();
+
+    let first = pay("abc").await.unwrap();
+    assert_eq!(first.status().as_u16(), 201);
+    assert!(first.headers().get("idempotent-replayed").is_none());
+    assert_eq!(
+        first.json::<serde_json::Value>().await.unwrap()["id"],
+        "pay_1"
+    );
+
+    let retry = pay("abc").await.unwrap();
+    assert_eq!(retry.status().as_u16(), 201);
+    assert_eq!(retry.headers()["idempotent-replayed"], "true");
+    assert_eq!(
+        retry.json::<serde_json::Value>().await.unwrap()["id"],
+        "pay_1"
+    );
+
+    let other = pay("def").await.unwrap();
+    assert_eq!(
+        other.json::<serde_json::Value>().await.unwrap()["id"],
+        "pay_2"
+    );
+
+    // Requests without a key are evaluated every time
+    let plain = client.post(&url).send().await.unwrap();
+    assert_eq!(
+        plain.json::<serde_json::Value>().await.unwrap()["id"],
+        "pay_1"
+    );
+
+    // Every request is still recorded
+    assert_eq!(server.count_calls("POST", "/api/payments").await, 4);
+
+    server.reset().await;
+    server
+        .expect()
+        .path("/api/payments")
+        .method("POST")
+        .respond()
+        .status(202)
+        .build()
+        .await;
+    let after_reset = pay("abc").await.unwrap();
+    assert_eq!(after_reset.status().as_u16(), 202);
+    assert!(after_reset.headers().get("idempotent-replayed").is_none());
+}
+
+#[tokio::test]
+async fn test_idempotency_skips_faults_and_streams() {
+    let server = MockServer::new("./tests/resources").with_idempotency_key("Idempotency-Key");
+
+    server
+        .expect()
+        .path("/api/payments")
+        .method("POST")
+        .respond()
+        .rotate([
+            MockResponse::new(200).with_fault(Fault::ConnectionReset),
+            MockResponse::new(201).with_json_body(json!({"id": "pay_1"})),
+        ])
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/events")
+        .method("POST")
+        .respond()
+        .sse(
+            SseStream::new()
+                .event(SseEvent::new("tick").delay(Duration::from_millis(50)))
+                .repeat(),
+        )
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    let pay = || {
+        client
+            .post(handle.url("/api/payments"))
+            .header("Idempotency-Key", "abc")
+            .send()
+    };
+
+    // The broken connection is not replayed as a success
+    assert!(pay().await.is_err());
+    let retry = pay().await.unwrap();
+    assert_eq!(retry.status().as_u16(), 201);
+    assert!(retry.headers().get("idempotent-replayed").is_none());
+
+    // An endless stream is passed through instead of buffered
+    let events = tokio::time::timeout(
+        Duration::from_secs(2),
+        client
+            .post(handle.url("/api/events"))
+            .header("Idempotency-Key", "abc")
+            .send(),
+    )
+    .await
+    .expect("stream must not be buffered")
+    .unwrap();
+    assert_eq!(events.headers()["content-type"], "text/event-stream");
+}
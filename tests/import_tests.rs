@@ -0,0 +1,121 @@
+use mimic_rs::import::{self, ImportFormat};
+use mimic_rs::models::{CreateExpectationRequest, JsonBodyMatch};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn test_import_openapi() {
+    let imported = import::import_file(
+        ImportFormat::OpenApi,
+        Path::new("./tests/resources/import/openapi.json"),
+    )
+    .unwrap();
+
+    assert_eq!(imported.expectations.len(), 3);
+
+    let get_user = imported
+        .expectations
+        .iter()
+        .find(|e| e.method == "GET" && e.path == "/v1/users/*")
+        .unwrap();
+    assert_eq!(get_user.response.status_code, 200);
+    let body = get_user.response.body.as_ref().unwrap();
+    assert_eq!(body["name"], "Alice");
+    assert_eq!(body["roles"][0], "admin");
+
+    let delete_user = imported
+        .expectations
+        .iter()
+        .find(|e| e.method == "DELETE")
+        .unwrap();
+    assert_eq!(delete_user.response.status_code, 204);
+    assert!(delete_user.response.body.is_none());
+
+    let health = imported
+        .expectations
+        .iter()
+        .find(|e| e.path == "/v1/health")
+        .unwrap();
+    assert_eq!(health.response.body.as_ref().unwrap()["status"], "up");
+}
+
+#[test]
+fn test_import_har() {
+    let imported = import::import_file(
+        ImportFormat::Har,
+        Path::new("./tests/resources/import/session.har"),
+    )
+    .unwrap();
+
+    assert_eq!(imported.expectations.len(), 2);
+
+    let products = &imported.expectations[0];
+    assert_eq!(products.path, "/api/products");
+    assert_eq!(products.query_params.get("page").unwrap(), "2");
+    assert_eq!(products.response.body.as_ref().unwrap()["page"], 2);
+    assert!(!products.response.headers.contains_key("Content-Length"));
+
+    let page = &imported.expectations[1];
+    let fixture_name = page.response.body_file.as_ref().unwrap();
+    assert!(fixture_name.ends_with(".html"));
+    assert_eq!(imported.fixtures[0].content, b"<h1>Hello</h1>");
+}
+
+#[test]
+fn test_import_postman() {
+    let imported = import::import_file(
+        ImportFormat::Postman,
+        Path::new("./tests/resources/import/collection.postman.json"),
+    )
+    .unwrap();
+
+    assert_eq!(imported.expectations.len(), 2);
+    assert_eq!(imported.expectations[0].path, "/orders/*");
+    assert_eq!(
+        imported.expectations[0].response.body.as_ref().unwrap()["state"],
+        "paid"
+    );
+    assert_eq!(imported.expectations[1].method, "POST");
+    assert_eq!(imported.expectations[1].path, "/orders");
+}
+
+#[test]
+fn test_import_wiremock_and_write_mappings() {
+    let imported = import::import_file(
+        ImportFormat::WireMock,
+        Path::new("./tests/resources/import/wiremock/mappings/stubs.json"),
+    )
+    .unwrap();
+
+    assert_eq!(imported.expectations[0].path, "/api/users/*");
+    assert_eq!(
+        imported.expectations[0].headers.get("accept").unwrap(),
+        "application/json"
+    );
+    assert_eq!(imported.expectations[1].path, "/api/report");
+    assert_eq!(
+        imported.expectations[1].query_params.get("format").unwrap(),
+        "csv"
+    );
+    assert_eq!(
+        imported.expectations[2].json_body,
+        Some(JsonBodyMatch::Contains(json!({"name": "Ann"})))
+    );
+
+    let out_dir = std::env::temp_dir().join(format!("mimic-import-{}", uuid::Uuid::new_v4()));
+    let written = imported.write_to(&out_dir).unwrap();
+
+    assert_eq!(written.len(), 3);
+    assert_eq!(
+        fs::read_to_string(out_dir.join("report.csv")).unwrap(),
+        "id,name\n1,Bob\n"
+    );
+
+    let mapping: CreateExpectationRequest =
+        serde_json::from_str(&fs::read_to_string(&written[0]).unwrap()).unwrap();
+    assert_eq!(mapping.method, "GET");
+    assert_eq!(mapping.response.body.unwrap()["name"], "Bob");
+
+    fs::remove_dir_all(out_dir).unwrap();
+}
@@ -0,0 +1,119 @@
+use mimic_rs::MockServer;
+use mimic_rs::journal::RequestJournal;
+use reqwest::Client;
+use serde_json::json;
+use std::fs;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_request_journal_survives_restart() {
+    let journal_path =
+        std::env::temp_dir().join(format!("mimic-journal-{}.db", uuid::Uuid::new_v4()));
+    let client = Client::new();
+
+    // First run with a tiny in-memory log
+    let port = 9130;
+    let server = MockServer::new("./tests/resources")
+        .with_max_log_size(1)
+        .with_request_journal(&journal_path)
+        .unwrap();
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    for _ in 0..3 {
+        client
+            .get(format!("http://localhost:{}/api/ping", port))
+            .send()
+            .await
+            .unwrap();
+    }
+
+    assert_eq!(server.count_calls("GET", "/api/ping").await, 3);
+    assert_eq!(server.get_request_log().await.len(), 3);
+
+    // Second run picks up the previous history
+    let port = 9131;
+    let server = MockServer::new("./tests/resources")
+        .with_request_journal(&journal_path)
+        .unwrap();
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    client
+        .get(format!("http://localhost:{}/api/ping", port))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client
+        .post(format!("http://localhost:{}/_verify", port))
+        .json(&json!({"method": "GET", "path": "/api/ping", "times": 4}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let journal = RequestJournal::open(&journal_path).unwrap();
+    assert_eq!(journal.read_all().unwrap().len(), 4);
+
+    server.reset().await;
+    assert_eq!(journal.count("GET", "/api/ping").unwrap(), 0);
+
+    fs::remove_file(journal_path).unwrap();
+}
+
+#[tokio::test]
+async fn test_journal_keeps_responses_and_hit_counts() {
+    let journal_path =
+        std::env::temp_dir().join(format!("mimic-journal-{}.db", uuid::Uuid::new_v4()));
+    let client = Client::new();
+
+    let server = MockServer::new("./tests/resources")
+        .with_request_journal(&journal_path)
+        .unwrap();
+    let mock = server
+        .expect()
+        .path("/orders")
+        .method("POST")
+        .respond()
+        .status(201)
+        .json(json!({"id": 7}))
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    for _ in 0..2 {
+        client.post(handle.url("/orders")).send().await.unwrap();
+    }
+
+    let requests = server.get_request_log().await;
+    assert_eq!(requests.len(), 2);
+    let response = requests[0].response.as_ref().unwrap();
+    assert_eq!(response.status, 201);
+    assert_eq!(response.expectation_id.as_deref(), Some(mock.id()));
+    assert_eq!(response.body.as_deref(), Some(r#"{"id":7}"#));
+
+    // A restarted server knows the responses and how often they were sent
+    let server = MockServer::new("./tests/resources")
+        .with_request_journal(&journal_path)
+        .unwrap();
+    assert_eq!(server.hit_count(mock.id()).await, 2);
+
+    let journal = RequestJournal::open(&journal_path).unwrap();
+    let records = journal.read_where(Some("post"), |_| true).unwrap();
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.response.is_some()));
+
+    fs::remove_file(journal_path).unwrap();
+}
@@ -0,0 +1,123 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_body_json_ignores_key_order_and_whitespace() {
+    let port = 9337;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/users")
+        .method("POST")
+        .body_json(json!({"name": "Alice", "roles": ["admin", "dev"]}))
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/users", port);
+
+    let response = client
+        .post(&url)
+        .body("{ \"roles\": [\"admin\", \"dev\"],\n  \"name\": \"Alice\" }")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    // Extra fields, reordered arrays and non-JSON bodies do not match
+    for body in [
+        r#"{"name": "Alice", "roles": ["admin", "dev"], "age": 30}"#,
+        r#"{"name": "Alice", "roles": ["dev", "admin"]}"#,
+        "name=Alice",
+    ] {
+        let response = client.post(&url).body(body).send().await.unwrap();
+        assert_eq!(response.status(), 404, "body {} should not match", body);
+    }
+}
+
+#[tokio::test]
+async fn test_body_json_partial_matches_subset() {
+    let port = 9338;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .body_json_partial(json!({
+            "customer": {"tier": "gold"},
+            "items": [{"sku": "B2"}]
+        }))
+        .respond()
+        .status(202)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/orders", port);
+
+    let response = client
+        .post(&url)
+        .json(&json!({
+            "id": 7,
+            "customer": {"name": "Ada", "tier": "gold"},
+            "items": [{"sku": "A1", "qty": 1}, {"sku": "B2", "qty": 3}]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    let response = client
+        .post(&url)
+        .json(&json!({
+            "customer": {"name": "Ada", "tier": "silver"},
+            "items": [{"sku": "B2"}]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    // The same match over the HTTP API
+    let response = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "PUT",
+            "path": "/api/settings",
+            "json_body": {"contains": {"theme": "dark"}},
+            "response": {"status_code": 204}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let response = client
+        .put(format!("http://localhost:{}/api/settings", port))
+        .json(&json!({"theme": "dark", "lang": "en"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+}
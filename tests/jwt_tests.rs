@@ -0,0 +1,207 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use mimic_rs::MockServer;
+use mimic_rs::auth::{JwtRequirement, OidcConfig};
+use reqwest::Client;
+use ring::hmac;
+use serde_json::{Map, Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn hs256_token(secret: &str, claims: Value) -> String {
+    let header = URL_SAFE_NO_PAD.encode(json!({"alg": "HS256", "typ": "JWT"}).to_string());
+    let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+    let signing_input = format!("{}.{}", header, payload);
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let signature = URL_SAFE_NO_PAD.encode(hmac::sign(&key, signing_input.as_bytes()));
+    format!("{}.{}", signing_input, signature)
+}
+
+#[tokio::test]
+async fn test_hmac_token_validation() {
+    let port = 9090;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .jwt(
+            JwtRequirement::hmac("secret")
+                .audience("orders-api")
+                .scope("orders:write"),
+        )
+        .respond()
+        .status(201)
+        .json(json!({"created": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/orders", port);
+    let exp = chrono::Utc::now().timestamp() + 60;
+
+    // Missing token
+    let resp = client.post(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+    assert!(
+        resp.headers()["www-authenticate"]
+            .to_str()
+            .unwrap()
+            .contains("invalid_token")
+    );
+
+    // Wrong secret
+    let token = hs256_token("other", json!({"aud": "orders-api", "exp": exp}));
+    let resp = client.post(&url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    // Expired
+    let token = hs256_token(
+        "secret",
+        json!({"aud": "orders-api", "scope": "orders:write", "exp": exp - 120}),
+    );
+    let resp = client.post(&url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+
+    // Valid signature but missing scope
+    let token = hs256_token(
+        "secret",
+        json!({"aud": "orders-api", "scope": "orders:read", "exp": exp}),
+    );
+    let resp = client.post(&url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 403);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["error"], "insufficient_scope");
+
+    // Valid
+    let token = hs256_token(
+        "secret",
+        json!({"aud": "orders-api", "scope": "orders:read orders:write", "exp": exp}),
+    );
+    let resp = client.post(&url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+}
+
+#[tokio::test]
+async fn test_claim_based_matching_with_provider_tokens() {
+    let port = 9091;
+    let server = MockServer::new("./tests/resources").with_oidc(OidcConfig::default());
+
+    server
+        .expect()
+        .path("/api/profile")
+        .method("GET")
+        .jwt(JwtRequirement::provider().claim("roles", json!("admin")))
+        .respond()
+        .status(200)
+        .json(json!({"view": "admin"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/profile")
+        .method("GET")
+        .jwt(JwtRequirement::provider())
+        .respond()
+        .status(200)
+        .json(json!({"view": "user"}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let provider = server.oidc().unwrap();
+    let issuer = provider.issuer(Some(&format!("localhost:{}", port)));
+    let mut admin_claims = Map::new();
+    admin_claims.insert("roles".to_string(), json!(["admin", "user"]));
+    let admin_token = provider
+        .issue_token(&issuer, "alice", "mimic-rs", &admin_claims)
+        .unwrap();
+    let user_token = provider
+        .issue_token(&issuer, "bob", "mimic-rs", &Map::new())
+        .unwrap();
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/profile", port);
+
+    let resp = client
+        .get(&url)
+        .bearer_auth(admin_token)
+        .send()
+        .await
+        .unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["view"], "admin");
+
+    let resp = client
+        .get(&url)
+        .bearer_auth(user_token)
+        .send()
+        .await
+        .unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["view"], "user");
+
+    let resp = client
+        .get(&url)
+        .bearer_auth("not.a.jwt")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 401);
+}
+
+#[tokio::test]
+async fn test_jwt_requirement_via_http_api() {
+    let port = 9092;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/secure",
+            "jwt": {
+                "key": {"hmac": "secret"},
+                "claims": {"tenant": "acme"},
+                "forbidden_response": {"status_code": 404}
+            },
+            "response": {"status_code": 200, "body": {"ok": true}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let url = format!("http://localhost:{}/api/secure", port);
+
+    let token = hs256_token("secret", json!({"tenant": "other"}));
+    let resp = client.get(&url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let token = hs256_token("secret", json!({"tenant": "acme"}));
+    let resp = client.get(&url).bearer_auth(token).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
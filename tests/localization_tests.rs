@@ -0,0 +1,108 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_localized_bodies_follow_accept_language() {
+    let port = 9210;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/greeting")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"greeting": "Hello"}))
+        .localized("fr", json!({"greeting": "Bonjour"}))
+        .localized_file("de", "greeting_de.json")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/greeting", port);
+
+    let resp = client
+        .get(&url)
+        .header("Accept-Language", "fr-CA, en;q=0.8")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-language"], "fr");
+    assert_eq!(resp.headers()["vary"], "Accept-Language");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["greeting"], "Bonjour");
+
+    let resp = client
+        .get(&url)
+        .header("Accept-Language", "fr;q=0.5, de")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-language"], "de");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["greeting"], "Hallo");
+
+    let resp = client
+        .get(&url)
+        .header("Accept-Language", "ja")
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.headers().get("content-language").is_none());
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["greeting"], "Hello");
+}
+
+#[tokio::test]
+async fn test_localized_bodies_via_http_api() {
+    let port = 9211;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/labels",
+            "response": {
+                "status_code": 200,
+                "body": {"save": "Save"},
+                "languages": [
+                    {"language": "pt-BR", "body": {"save": "Salvar"}},
+                    {"language": "de", "body_file": "greeting_de.json"}
+                ]
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let body: Value = client
+        .get(format!("http://localhost:{}/api/labels", port))
+        .header("Accept-Language", "pt-BR")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["save"], "Salvar");
+}
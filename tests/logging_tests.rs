@@ -0,0 +1,69 @@
+use mimic_rs::MockServer;
+use mimic_rs::logging::{self, LogConfig};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_change_log_level_at_runtime() {
+    let port = 9050;
+    let handle = logging::init(&LogConfig::default()).unwrap();
+    let server = MockServer::new("./tests/resources").with_log_handle(handle);
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/_log_level", port);
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["level"], "info");
+
+    let resp = client
+        .put(&url)
+        .json(&json!({"level": "debug"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let body: Value = client.get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["level"], "debug");
+
+    let resp = client
+        .put(&url)
+        .json(&json!({"level": "verbose"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn test_log_level_without_handle() {
+    let port = 9051;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .get(format!("http://localhost:{}/_log_level", port))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(resp.status().as_u16(), 501);
+}
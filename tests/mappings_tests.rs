@@ -0,0 +1,147 @@
+use mimic_rs::MockServer;
+use mimic_rs::server::StartupError;
+use reqwest::Client;
+use serde_json::Value;
+use std::fs;
+
+#[tokio::test]
+async fn test_mapping_files_are_registered_on_start() {
+    let server = MockServer::new("./tests/resources/stubs");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client.get(handle.url("/api/users/7")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    let user: Value = response.json().await.unwrap();
+    assert_eq!(user["id"], "7");
+    assert_eq!(user["name"], "Ada");
+
+    let response = client
+        .delete(handle.url("/api/users/7"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let response = client.post(handle.url("/api/orders")).send().await.unwrap();
+    assert_eq!(response.status(), 201);
+
+    let expectations: Vec<Value> = client
+        .get(handle.url("/_expectations"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(expectations.len(), 3);
+    assert!(expectations.iter().all(|exp| exp["source"] == "mapping"));
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_reloading_mappings_replaces_earlier_ones() {
+    let dir = std::env::temp_dir().join(format!("mimic-mappings-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("ping.yml"),
+        "method: GET\npath: /ping\nresponse:\n  status_code: 200\n",
+    )
+    .unwrap();
+
+    let server = MockServer::new("./tests/resources").with_mappings_dir(&dir);
+    server
+        .expect()
+        .path("/from-code")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    assert_eq!(server.load_mappings().await.unwrap(), 1);
+
+    fs::write(
+        dir.join("ping.yml"),
+        "method: GET\npath: /pong\nresponse:\n  status_code: 200\n",
+    )
+    .unwrap();
+    assert_eq!(server.load_mappings().await.unwrap(), 1);
+
+    let mut paths: Vec<String> = server
+        .get_expectations()
+        .await
+        .into_iter()
+        .map(|exp| exp.path)
+        .collect();
+    paths.sort();
+    assert_eq!(paths, ["/from-code", "/pong"]);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_invalid_mapping_file_aborts_start() {
+    let dir = std::env::temp_dir().join(format!("mimic-mappings-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("broken.yaml"), "method: [GET\n").unwrap();
+
+    let server = MockServer::new("./tests/resources").with_mappings_dir(&dir);
+    let error = server.prepare().await.unwrap_err();
+    assert!(matches!(error, StartupError::Mappings(_)));
+    assert!(error.to_string().contains("broken.yaml"));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_hot_reload_picks_up_changed_files() {
+    let dir = std::env::temp_dir().join(format!("mimic-watch-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(dir.join("mappings")).unwrap();
+    fs::write(
+        dir.join("mappings/ping.yaml"),
+        "method: GET\npath: /ping\nresponse:\n  status_code: 200\n  body_file: ping.json\n",
+    )
+    .unwrap();
+    fs::write(dir.join("ping.json"), r#"{"version": 1}"#).unwrap();
+
+    let server = MockServer::new(&dir).with_hot_reload();
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let body: Value = client
+        .get(handle.url("/ping"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["version"], 1);
+
+    fs::write(
+        dir.join("mappings/ping.yaml"),
+        "method: GET\npath: /pong\nresponse:\n  status_code: 200\n  body_file: ping.json\n",
+    )
+    .unwrap();
+    fs::write(dir.join("ping.json"), r#"{"version": 2}"#).unwrap();
+
+    let mut reloaded = false;
+    for _ in 0..50 {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        let response = client.get(handle.url("/pong")).send().await.unwrap();
+        if response.status() == 200 {
+            let body: Value = response.json().await.unwrap();
+            assert_eq!(body["version"], 2);
+            reloaded = true;
+            break;
+        }
+    }
+    assert!(reloaded, "the edited mapping was not reloaded");
+
+    let response = client.get(handle.url("/ping")).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.shutdown().await;
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,84 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn start(port: u16, server: MockServer) {
+    for method in ["GET", "PUT"] {
+        server
+            .expect()
+            .path("/api/users/1")
+            .method(method)
+            .respond()
+            .status(200)
+            .build()
+            .await;
+    }
+    server
+        .expect()
+        .path("/api/search")
+        .method("POST")
+        .body("{\"q\":\"ada\"}")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    tokio::spawn(async move {
+        server.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+}
+
+#[tokio::test]
+async fn test_wrong_method_returns_405_with_allow() {
+    let port = 9315;
+    start(port, MockServer::new("./tests/resources")).await;
+
+    let client = Client::new();
+    let resp = client
+        .delete(format!("http://localhost:{}/api/users/1", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 405);
+    assert_eq!(
+        resp.headers()["allow"].to_str().unwrap(),
+        "GET, HEAD, OPTIONS, PUT"
+    );
+
+    // The method is stubbed, only the body differs
+    let resp = client
+        .post(format!("http://localhost:{}/api/search", port))
+        .body("{\"q\":\"bob\"}")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let resp = client
+        .delete(format!("http://localhost:{}/api/orders/1", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_method_not_allowed_can_be_disabled() {
+    let port = 9316;
+    start(
+        port,
+        MockServer::new("./tests/resources").without_method_not_allowed(),
+    )
+    .await;
+
+    let resp = Client::new()
+        .delete(format!("http://localhost:{}/api/users/1", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+    assert!(resp.headers().get("allow").is_none());
+}
@@ -0,0 +1,62 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+
+#[tokio::test]
+async fn test_metrics_count_requests_matches_and_hits() {
+    let server = MockServer::new("./tests/resources");
+
+    let users = server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for id in 1..=2 {
+        client
+            .get(handle.url(&format!("/api/users/{}", id)))
+            .send()
+            .await
+            .unwrap();
+    }
+    client
+        .post(handle.url("/api/missing"))
+        .send()
+        .await
+        .unwrap();
+
+    let response = client.get(handle.url("/_metrics")).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(
+        response.headers()["content-type"]
+            .to_str()
+            .unwrap()
+            .starts_with("text/plain; version=0.0.4")
+    );
+    let body = response.text().await.unwrap();
+    let lines: Vec<&str> = body.lines().collect();
+
+    assert!(lines.contains(&"# TYPE mimic_requests_total counter"));
+    assert!(lines.contains(&"mimic_requests_total{method=\"GET\",status=\"200\"} 2"));
+    assert!(lines.contains(&"mimic_requests_total{method=\"POST\",status=\"404\"} 1"));
+    assert!(lines.contains(&"mimic_requests_matched_total 2"));
+    assert!(lines.contains(&"mimic_requests_unmatched_total 1"));
+    assert!(lines.contains(&"# TYPE mimic_response_latency_seconds histogram"));
+    assert!(lines.contains(&"mimic_response_latency_seconds_bucket{le=\"+Inf\"} 3"));
+    assert!(lines.contains(&"mimic_response_latency_seconds_count 3"));
+    let hits = format!(
+        "mimic_expectation_hits_total{{id=\"{}\",method=\"GET\",path=\"/api/users/*\"}} 2",
+        users.id()
+    );
+    assert!(lines.contains(&hits.as_str()), "{}", body);
+
+    // Admin requests are not counted
+    assert_eq!(server.metrics().await, body);
+}
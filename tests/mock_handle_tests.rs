@@ -0,0 +1,78 @@
+use mimic_rs::MockServer;
+use mimic_rs::server::VerificationError;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_mock_handle_counts_verifies_and_removes() {
+    let port = 9344;
+    let server = MockServer::new("./tests/resources");
+
+    let specific = server
+        .expect()
+        .path("/api/users/1")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"id": 1}))
+        .build()
+        .await;
+
+    let fallback = server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .respond()
+        .status(404)
+        .build()
+        .await;
+
+    assert_ne!(specific.id(), fallback.id());
+    assert!(
+        server
+            .get_expectations()
+            .await
+            .iter()
+            .any(|exp| exp.id == specific.id())
+    );
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/users/1", port);
+
+    for _ in 0..2 {
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.status(), 200);
+    }
+    client
+        .get(format!("http://localhost:{}/api/users/2", port))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(specific.calls().await, 2);
+    assert_eq!(fallback.calls().await, 1);
+    specific.verify(2).await.unwrap();
+    match specific.verify(3).await {
+        Err(VerificationError::Hits {
+            expected, actual, ..
+        }) => assert_eq!((expected, actual), (3, 2)),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    // Removing the specific stub lets the fallback answer
+    assert!(specific.remove().await);
+    assert!(!specific.remove().await);
+    let response = client.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+    assert_eq!(fallback.calls().await, 2);
+    assert_eq!(specific.calls().await, 0);
+}
@@ -0,0 +1,120 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::{Multipart, MultipartPart};
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_multipart_related_response() {
+    let port = 9300;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/documents/1")
+        .method("GET")
+        .respond()
+        .status(200)
+        .multipart(
+            Multipart::related()
+                .boundary("doc-boundary")
+                .part(
+                    MultipartPart::json(json!({"id": 1, "title": "Report"}))
+                        .header("Content-ID", "<meta>"),
+                )
+                .part(
+                    MultipartPart::file("application/xml", "order.xml")
+                        .header("Content-ID", "<doc>"),
+                ),
+        )
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .get(format!("http://localhost:{}/api/documents/1", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(
+        resp.headers()["content-type"],
+        "multipart/related; boundary=\"doc-boundary\"; type=\"application/json\""
+    );
+
+    let body = resp.text().await.unwrap();
+    let parts: Vec<&str> = body.split("--doc-boundary").collect();
+    // Preamble, two parts and the closing "--\r\n"
+    assert_eq!(parts.len(), 4);
+    assert_eq!(
+        parts[1],
+        "\r\nContent-ID: <meta>\r\nContent-Type: application/json\r\n\r\n{\"id\":1,\"title\":\"Report\"}\r\n"
+    );
+    assert!(
+        parts[2].starts_with("\r\nContent-ID: <doc>\r\nContent-Type: application/xml\r\n\r\n<?xml")
+    );
+    assert_eq!(parts[3], "--\r\n");
+}
+
+#[tokio::test]
+async fn test_multipart_mixed_via_http_api() {
+    let port = 9301;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "POST",
+            "path": "/batch",
+            "response": {
+                "status_code": 200,
+                "multipart": {
+                    "parts": [
+                        {"headers": {"Content-Type": "text/plain"}, "body": "first"},
+                        {"headers": {"Content-Type": "text/plain"}, "body": "second"}
+                    ]
+                }
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let resp = client
+        .post(format!("http://localhost:{}/batch", port))
+        .send()
+        .await
+        .unwrap();
+    let content_type = resp.headers()["content-type"].to_str().unwrap().to_string();
+    assert!(content_type.starts_with("multipart/mixed; boundary=\"mimic-"));
+    let boundary = content_type
+        .split("boundary=\"")
+        .nth(1)
+        .unwrap()
+        .trim_end_matches('"')
+        .to_string();
+
+    let body = resp.text().await.unwrap();
+    assert_eq!(
+        body,
+        format!(
+            "--{b}\r\nContent-Type: text/plain\r\n\r\nfirst\r\n--{b}\r\nContent-Type: text/plain\r\n\r\nsecond\r\n--{b}--\r\n",
+            b = boundary
+        )
+    );
+}
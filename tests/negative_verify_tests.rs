@@ -0,0 +1,79 @@
+use mimic_rs::MockServer;
+use mimic_rs::server::VerificationError;
+use reqwest::Client;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_negative_verification() {
+    let port = 9319;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    client
+        .get(format!("http://localhost:{}/api/users/1", port))
+        .send()
+        .await
+        .unwrap();
+
+    server
+        .verify_no_requests_to("DELETE", "/api/users/*")
+        .await
+        .unwrap();
+    server
+        .verify_no_unexpected_requests(&[("GET", "/api/users/*")])
+        .await
+        .unwrap();
+
+    client
+        .delete(format!("http://localhost:{}/api/users/1", port))
+        .send()
+        .await
+        .unwrap();
+
+    let error = server
+        .verify_no_requests_to("delete", "/api/users/*")
+        .await
+        .unwrap_err();
+    match &error {
+        VerificationError::UnwantedRequests { requests, .. } => {
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].path, "/api/users/1");
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+    assert!(error.to_string().contains("DELETE /api/users/1"));
+
+    let error = server
+        .verify_no_unexpected_requests(&[("GET", "/api/users/*")])
+        .await
+        .unwrap_err();
+    match error {
+        VerificationError::UnexpectedRequests(requests) => {
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].method, "DELETE");
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+
+    server
+        .verify_no_unexpected_requests(&[("*", "/api/users/*")])
+        .await
+        .unwrap();
+}
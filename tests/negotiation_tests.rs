@@ -0,0 +1,115 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_variant_selected_by_accept_header() {
+    let port = 9200;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/report")
+        .method("GET")
+        .respond()
+        .status(200)
+        .variant("json", json!({"total": 3}))
+        .variant("xml", "<report><total>3</total></report>")
+        .variant("csv", "total\n3\n")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/report", port);
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, json!({"total": 3}));
+
+    let resp = client
+        .get(&url)
+        .header("Accept", "application/xml")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["content-type"], "application/xml");
+    assert_eq!(resp.headers()["vary"], "Accept");
+    assert_eq!(
+        resp.text().await.unwrap(),
+        "<report><total>3</total></report>"
+    );
+
+    let resp = client
+        .get(&url)
+        .header("Accept", "application/json;q=0.5, text/*")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-type"], "text/csv");
+    assert_eq!(resp.text().await.unwrap(), "total\n3\n");
+
+    let resp = client
+        .get(&url)
+        .header("Accept", "image/png")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 406);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(
+        body["available"],
+        json!(["application/json", "application/xml", "text/csv"])
+    );
+}
+
+#[tokio::test]
+async fn test_variants_via_http_api() {
+    let port = 9201;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/greeting",
+            "response": {
+                "status_code": 200,
+                "variants": [
+                    {"media_type": "text/plain", "body": "hello"},
+                    {"media_type": "text/html", "body": "<p>hello</p>"}
+                ]
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/greeting", port))
+        .header("Accept", "text/plain;q=0, */*")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["content-type"], "text/html");
+    assert_eq!(resp.text().await.unwrap(), "<p>hello</p>");
+}
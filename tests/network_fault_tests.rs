@@ -0,0 +1,90 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_accept_delay_holds_each_connection() {
+    let port = 9230;
+    let server = MockServer::new("./tests/resources").with_accept_delay(Duration::from_millis(400));
+
+    server
+        .expect()
+        .path("/api/slow-upstream")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let url = format!("http://localhost:{}/api/slow-upstream", port);
+
+    // Times out before the server gets to the request
+    let impatient = Client::builder()
+        .timeout(Duration::from_millis(150))
+        .build()
+        .unwrap();
+    assert!(impatient.get(&url).send().await.unwrap_err().is_timeout());
+
+    // Concurrent connections are delayed side by side, not one after another
+    let client = Client::builder().pool_max_idle_per_host(0).build().unwrap();
+    let started = Instant::now();
+    let (first, second) = tokio::join!(client.get(&url).send(), client.get(&url).send());
+    assert_eq!(first.unwrap().status().as_u16(), 200);
+    assert_eq!(second.unwrap().status().as_u16(), 200);
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(400));
+    assert!(elapsed < Duration::from_millis(800), "{:?}", elapsed);
+}
+
+#[tokio::test]
+async fn test_bandwidth_limit_is_shared_by_all_responses() {
+    let port = 9231;
+    let server = MockServer::new("./tests/resources").with_bandwidth_limit(100 * 1024);
+
+    let payload = "x".repeat(50 * 1024);
+    server
+        .expect()
+        .path("/api/blob")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"data": payload}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/blob", port);
+
+    // 4 x 50KB at 100KB/s, minus the initial 10KB burst
+    let started = Instant::now();
+    let responses = fetch_four_times(&client, &url).await;
+    for body in responses {
+        assert!(body.len() > 50 * 1024);
+    }
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(1800), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(4), "{:?}", elapsed);
+}
+
+async fn fetch_four_times(client: &Client, url: &str) -> Vec<String> {
+    let fetch = || async { client.get(url).send().await.unwrap().text().await.unwrap() };
+    let (a, b, c, d) = tokio::join!(fetch(), fetch(), fetch(), fetch());
+    vec![a, b, c, d]
+}
@@ -0,0 +1,158 @@
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_not_found_diagnostics_list_near_misses() {
+    let port = 9317;
+    let server = MockServer::new("./tests/resources").with_not_found_diagnostics();
+
+    server
+        .expect()
+        .path("/api/users")
+        .method("GET")
+        .query_param("page", "1")
+        .header("x-tenant", "acme")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/orders")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .get(format!("http://localhost:{}/api/users?page=2", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["request"]["path"], "/api/users");
+    assert_eq!(body["request"]["query_params"], json!({"page": "2"}));
+
+    let near_misses = body["near_misses"].as_array().unwrap();
+    assert_eq!(near_misses.len(), 2);
+    assert_eq!(near_misses[0]["path"], "/api/users");
+    assert_eq!(
+        near_misses[0]["mismatches"],
+        json!([
+            "query parameter page: expected 1, got 2",
+            "header x-tenant: expected acme, missing"
+        ])
+    );
+    assert_eq!(
+        near_misses[1]["mismatches"],
+        json!(["path: expected /api/orders"])
+    );
+}
+
+#[tokio::test]
+async fn test_custom_not_found_response() {
+    let port = 9318;
+    let server = MockServer::new("./tests/resources").with_not_found_response(
+        MockResponse::new(404).with_json_body(json!({"error": "not_found"})),
+    );
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .get(format!("http://localhost:{}/api/anything", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body, json!({"error": "not_found"}));
+}
+
+#[tokio::test]
+async fn test_unmatched_requests_report_closest_expectation() {
+    let server = MockServer::new("./tests/resources");
+
+    let users = server
+        .expect()
+        .path("/api/users")
+        .method("GET")
+        .header("x-tenant", "acme")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    client
+        .get(handle.url("/api/users"))
+        .header("x-tenant", "acme")
+        .send()
+        .await
+        .unwrap();
+    client
+        .get(handle.url("/api/users"))
+        .header("x-tenant", "other")
+        .send()
+        .await
+        .unwrap();
+    client.get(handle.url("/nothing")).send().await.unwrap();
+
+    let unmatched = server.unmatched_requests().await;
+    assert_eq!(unmatched.len(), 2);
+    let closest = unmatched[0].closest.as_ref().unwrap();
+    assert_eq!(closest.id, users.id());
+    assert_eq!(
+        closest.mismatches,
+        ["header x-tenant: expected acme, got other"]
+    );
+
+    let report: Vec<Value> = client
+        .get(handle.url("/_requests/unmatched"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report.len(), 2);
+    assert_eq!(report[1]["request"]["path"], "/nothing");
+    let mismatches = report[1]["closest"]["mismatches"].as_array().unwrap();
+    assert!(
+        mismatches
+            .iter()
+            .any(|m| m.as_str().unwrap().starts_with("path"))
+    );
+
+    server.clear_request_log().await;
+    assert!(server.unmatched_requests().await.is_empty());
+
+    handle.shutdown().await;
+}
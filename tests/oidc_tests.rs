@@ -0,0 +1,186 @@
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use mimic_rs::MockServer;
+use mimic_rs::auth::{DecodedJwt, OidcConfig};
+use reqwest::{Client, redirect};
+use ring::signature::{ECDSA_P256_SHA256_FIXED, UnparsedPublicKey};
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+fn verify_with_jwk(token: &str, jwk: &Value) -> DecodedJwt {
+    let decoded = DecodedJwt::decode(token).unwrap();
+    let mut point = vec![0x04];
+    point.extend(URL_SAFE_NO_PAD.decode(jwk["x"].as_str().unwrap()).unwrap());
+    point.extend(URL_SAFE_NO_PAD.decode(jwk["y"].as_str().unwrap()).unwrap());
+
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_FIXED, point)
+        .verify(decoded.signing_input.as_bytes(), &decoded.signature)
+        .unwrap();
+    decoded
+}
+
+#[tokio::test]
+async fn test_client_credentials_token() {
+    let port = 9080;
+    let server = MockServer::new("./tests/resources").with_oidc(
+        OidcConfig::default()
+            .audience("orders-api")
+            .expires_in(Duration::from_secs(120))
+            .claim("roles", json!(["admin"])),
+    );
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let discovery: Value = client
+        .get(format!("{}/.well-known/openid-configuration", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(discovery["issuer"], base);
+    assert_eq!(discovery["token_endpoint"], format!("{}/token", base));
+
+    let jwks: Value = client
+        .get(discovery["jwks_uri"].as_str().unwrap())
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let resp = client
+        .post(format!("{}/token", base))
+        .basic_auth("billing-service", Some("secret"))
+        .form(&[
+            ("grant_type", "client_credentials"),
+            ("scope", "orders:read"),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["token_type"], "Bearer");
+    assert_eq!(body["expires_in"], 120);
+
+    let token = verify_with_jwk(body["access_token"].as_str().unwrap(), &jwks["keys"][0]);
+    assert_eq!(token.header["kid"], jwks["keys"][0]["kid"]);
+    assert_eq!(token.claims["iss"], base);
+    assert_eq!(token.claims["sub"], "billing-service");
+    assert_eq!(token.claims["aud"], "orders-api");
+    assert_eq!(token.claims["scope"], "orders:read");
+    assert_eq!(token.claims["roles"][0], "admin");
+    assert_eq!(
+        token.claims["exp"].as_i64().unwrap() - token.claims["iat"].as_i64().unwrap(),
+        120
+    );
+
+    let invalid = client
+        .post(format!("{}/token", base))
+        .form(&[("grant_type", "device_code")])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(invalid.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn test_authorization_code_flow() {
+    let port = 9081;
+    let server = MockServer::new("./tests/resources")
+        .with_oidc(OidcConfig::default().issuer("https://idp.example.com"));
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::builder()
+        .redirect(redirect::Policy::none())
+        .build()
+        .unwrap();
+    let base = format!("http://localhost:{}", port);
+
+    let authorize = client
+        .get(format!("{}/authorize", base))
+        .query(&[
+            ("response_type", "code"),
+            ("client_id", "web-app"),
+            ("redirect_uri", "https://app.example.com/callback"),
+            ("scope", "openid profile"),
+            ("state", "xyz"),
+            ("nonce", "n-123"),
+            ("login_hint", "alice"),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert!(authorize.status().is_redirection());
+
+    let location = authorize.headers()["location"]
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert!(location.starts_with("https://app.example.com/callback?code="));
+    assert!(location.ends_with("&state=xyz"));
+    let code = location
+        .split("code=")
+        .nth(1)
+        .unwrap()
+        .split('&')
+        .next()
+        .unwrap();
+
+    let token_resp = |code: String| {
+        client
+            .post(format!("{}/token", base))
+            .form(&[
+                ("grant_type", "authorization_code".to_string()),
+                ("code", code),
+                ("client_id", "web-app".to_string()),
+            ])
+            .send()
+    };
+
+    let body: Value = token_resp(code.to_string())
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let id_token = DecodedJwt::decode(body["id_token"].as_str().unwrap()).unwrap();
+    assert_eq!(id_token.claims["iss"], "https://idp.example.com");
+    assert_eq!(id_token.claims["sub"], "alice");
+    assert_eq!(id_token.claims["aud"], "web-app");
+    assert_eq!(id_token.claims["nonce"], "n-123");
+
+    // Codes can only be exchanged once
+    let reused = token_resp(code.to_string()).await.unwrap();
+    assert_eq!(reused.status().as_u16(), 400);
+
+    let refreshed = client
+        .post(format!("{}/token", base))
+        .form(&[
+            ("grant_type", "refresh_token"),
+            ("refresh_token", body["refresh_token"].as_str().unwrap()),
+        ])
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(refreshed.status().as_u16(), 200);
+}
@@ -0,0 +1,205 @@
+use mimic_rs::MockServer;
+use mimic_rs::import::ImportError;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_load_openapi_yaml() {
+    let server = MockServer::new("./tests/resources");
+    let loaded = server
+        .load_openapi("./tests/resources/import/petstore.yaml")
+        .await
+        .unwrap();
+    assert_eq!(loaded, 3);
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let pets: Value = client
+        .get(handle.url("/pets"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pets, json!([{"id": 0, "name": "Rex"}]));
+
+    let response = client.post(handle.url("/pets")).send().await.unwrap();
+    assert_eq!(response.status(), 201);
+    let created: Value = response.json().await.unwrap();
+    assert_eq!(created, json!({"id": 1, "name": "Rex"}));
+
+    let pet: Value = client
+        .get(handle.url("/pets/42"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(pet["name"], "Rex");
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_load_openapi_json_with_server_prefix() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .load_openapi("./tests/resources/import/openapi.json")
+        .await
+        .unwrap();
+
+    let handle = server.start_random_port().await.unwrap();
+    let response = Client::new()
+        .get(handle.url("/v1/health"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_load_openapi_rejects_other_documents() {
+    let server = MockServer::new("./tests/resources");
+    let error = server
+        .load_openapi("./tests/resources/import/session.har")
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ImportError::Invalid(_)));
+    assert!(server.get_expectations().await.is_empty());
+}
+
+async fn validating_server() -> (MockServer, mimic_rs::ServerHandle) {
+    let server = MockServer::new("./tests/resources").with_request_validation();
+    server
+        .load_openapi("./tests/resources/import/petstore.yaml")
+        .await
+        .unwrap();
+    let handle = server.start_random_port().await.unwrap();
+    (server, handle)
+}
+
+#[tokio::test]
+async fn test_valid_requests_pass_validation() {
+    let (_server, handle) = validating_server().await;
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/pets?limit=10"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .post(handle.url("/pets"))
+        .json(&json!({"name": "Rex", "tag": null}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+
+    let response = client
+        .get(handle.url("/pets/7"))
+        .header("X-Api-Version", "2")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Not described by the document, left to the expectations
+    let response = client.get(handle.url("/owners")).send().await.unwrap();
+    assert_eq!(response.status(), 404);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_invalid_requests_get_a_validation_report() {
+    let (_server, handle) = validating_server().await;
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/pets?limit=500"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["operation"], "GET /pets");
+    assert_eq!(
+        report["errors"],
+        json!(["query parameter limit: must be at most 100"])
+    );
+
+    let report: Value = client
+        .get(handle.url("/pets/rex"))
+        .header("X-Api-Version", "3")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(report["operation"], "GET /pets/{petId}");
+    assert_eq!(
+        report["errors"],
+        json!([
+            "path parameter petId: expected integer, got string",
+            "header parameter X-Api-Version: \"3\" is not one of [\"1\",\"2\"]"
+        ])
+    );
+
+    let report: Value = client
+        .post(handle.url("/pets"))
+        .json(&json!({"name": "", "color": "brown"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        report["errors"],
+        json!(["body.color: not allowed", "body.name: violates minLength 1"])
+    );
+
+    let response = client.post(handle.url("/pets")).send().await.unwrap();
+    assert_eq!(response.status(), 400);
+    let report: Value = response.json().await.unwrap();
+    assert_eq!(report["errors"], json!(["body: required"]));
+
+    let response = client
+        .post(handle.url("/pets"))
+        .header("Content-Type", "text/plain")
+        .body("Rex")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_validation_is_off_by_default() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .load_openapi("./tests/resources/import/petstore.yaml")
+        .await
+        .unwrap();
+    let handle = server.start_random_port().await.unwrap();
+
+    let response = Client::new()
+        .get(handle.url("/pets?limit=500"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    handle.shutdown().await;
+}
@@ -0,0 +1,125 @@
+use mimic_rs::MockServer;
+use mimic_rs::cors::{CorsConfig, CorsPolicy};
+use reqwest::{Client, Method};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_options_lists_stubbed_methods() {
+    let port = 9312;
+    let server =
+        MockServer::new("./tests/resources").with_cors(CorsConfig::new(CorsPolicy::permissive()));
+
+    for method in ["GET", "DELETE"] {
+        server
+            .expect()
+            .path("/api/users/1")
+            .method(method)
+            .respond()
+            .status(200)
+            .build()
+            .await;
+    }
+    server
+        .expect()
+        .path("/api/users")
+        .method("POST")
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+
+    let resp = client
+        .request(
+            Method::OPTIONS,
+            format!("http://localhost:{}/api/users/1", port),
+        )
+        .header("Origin", "https://app.example.com")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+    assert_eq!(
+        resp.headers()["allow"].to_str().unwrap(),
+        "DELETE, GET, HEAD, OPTIONS"
+    );
+    assert_eq!(
+        resp.headers()["access-control-allow-origin"]
+            .to_str()
+            .unwrap(),
+        "*"
+    );
+
+    let resp = client
+        .request(
+            Method::OPTIONS,
+            format!("http://localhost:{}/api/users", port),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+    assert_eq!(resp.headers()["allow"].to_str().unwrap(), "OPTIONS, POST");
+
+    // Paths without stubs are still unknown
+    let resp = client
+        .request(
+            Method::OPTIONS,
+            format!("http://localhost:{}/api/orders", port),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
+
+#[tokio::test]
+async fn test_explicit_options_stub_wins() {
+    let port = 9313;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/items")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/items")
+        .method("OPTIONS")
+        .respond()
+        .status(200)
+        .header("Allow", "GET")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .request(
+            Method::OPTIONS,
+            format!("http://localhost:{}/api/items", port),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["allow"].to_str().unwrap(), "GET");
+}
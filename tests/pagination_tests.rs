@@ -0,0 +1,112 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_paginate_with_page_and_limit() {
+    let port = 9110;
+    let server = MockServer::new("./tests/resources");
+
+    let users: Vec<Value> = (1..=5).map(|id| json!({"id": id})).collect();
+    server
+        .expect()
+        .path("/api/users")
+        .method("GET")
+        .respond()
+        .status(200)
+        .paginate(json!(users), 2)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/users", port);
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-total-count"], "5");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"], json!([{"id": 1}, {"id": 2}]));
+    assert_eq!(body["page"], 1);
+    assert_eq!(body["total"], 5);
+    assert_eq!(body["total_pages"], 3);
+    assert_eq!(body["next_page"], 2);
+
+    let resp = client
+        .get(&url)
+        .query(&[("page", "3")])
+        .send()
+        .await
+        .unwrap();
+    let link = resp.headers()["link"].to_str().unwrap().to_string();
+    assert!(link.contains("</api/users?page=2&limit=2>; rel=\"prev\""));
+    assert!(!link.contains("rel=\"next\""));
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"], json!([{"id": 5}]));
+    assert_eq!(body["next_page"], Value::Null);
+
+    let resp = client
+        .get(&url)
+        .query(&[("page", "2"), ("limit", "4")])
+        .send()
+        .await
+        .unwrap();
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["data"], json!([{"id": 5}]));
+    assert_eq!(body["limit"], 4);
+}
+
+#[tokio::test]
+async fn test_paginate_with_cursor() {
+    let port = 9111;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/events",
+            "response": {
+                "status_code": 200,
+                "paginate": {"items": ["a", "b", "c"], "page_size": 2}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let url = format!("http://localhost:{}/api/events", port);
+
+    let body: Value = client.get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(body["data"], json!(["a", "b"]));
+    let cursor = body["next_cursor"].as_str().unwrap().to_string();
+
+    let body: Value = client
+        .get(&url)
+        .query(&[("cursor", cursor.as_str())])
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["data"], json!(["c"]));
+    assert_eq!(body["next_cursor"], Value::Null);
+}
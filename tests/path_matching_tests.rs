@@ -98,3 +98,146 @@ async fn test_multiple_wildcards() {
         assert_eq!(body["wildcards"], true);
     }
 }
+
+#[tokio::test]
+async fn test_most_specific_and_priority_win() {
+    let port = 9342;
+    let server = MockServer::new("./tests/resources");
+
+    // Registered before the specific stubs on purpose
+    server
+        .expect()
+        .path("/api/*")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"handler": "fallback"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/users")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"handler": "users"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/users")
+        .method("GET")
+        .query_param("active", "true")
+        .respond()
+        .status(200)
+        .json(json!({"handler": "active users"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/orders/{id}")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"handler": "order"}))
+        .build()
+        .await;
+
+    // Maintenance stub that overrides everything under /api/admin
+    server
+        .expect()
+        .path("/api/admin/*")
+        .method("GET")
+        .priority(10)
+        .respond()
+        .status(503)
+        .json(json!({"handler": "maintenance"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/admin/stats")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"handler": "stats"}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    for (path, expected) in [
+        ("/api/users", "users"),
+        ("/api/users?active=true", "active users"),
+        ("/api/orders/7", "order"),
+        ("/api/products", "fallback"),
+        ("/api/admin/stats", "maintenance"),
+    ] {
+        let body: Value = client
+            .get(format!("{}{}", base, path))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["handler"], expected, "GET {}", path);
+    }
+}
+
+#[tokio::test]
+async fn test_count_and_verify_with_path_patterns() {
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/users/{id}")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for path in ["/api/users/1", "/api/users/2", "/api/users/2/posts"] {
+        client.get(handle.url(path)).send().await.unwrap();
+    }
+
+    assert_eq!(server.count_calls("GET", "/api/users/1").await, 1);
+    assert_eq!(server.count_calls("GET", "/api/users/{id}").await, 2);
+    assert_eq!(server.count_calls("GET", "/api/users/*").await, 3);
+    assert_eq!(server.count_calls("*", "/api/users/{id}/posts").await, 1);
+    assert_eq!(server.count_calls("POST", "/api/users/*").await, 0);
+
+    let response = client
+        .post(handle.url("/_verify"))
+        .json(&json!({"method": "GET", "path": "/api/users/{id}", "times": 2}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .post(handle.url("/_verify"))
+        .json(&json!({"method": "GET", "path": "/api/users/*", "times": 2}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["actual"], 3);
+}
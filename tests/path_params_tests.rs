@@ -0,0 +1,164 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_path_parameters_in_json_body() {
+    let port = 9333;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/users/{id}")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({
+            "id": "{{path.id}}",
+            "self": "/api/users/{{path.id}}",
+            "missing": "{{path.name}}"
+        }))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/orders/{orderId}/items/{itemId}")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json_file("order_item.json")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let body: Value = client
+        .get(format!("{}/api/users/42", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["id"], "42");
+    assert_eq!(body["self"], "/api/users/42");
+    assert_eq!(body["missing"], "{{path.name}}");
+
+    // A parameter covers exactly one segment
+    let response = client
+        .get(format!("{}/api/users/42/posts", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+
+    let body: Value = client
+        .get(format!("{}/orders/A-7/items/3", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        body,
+        json!({"order": "A-7", "item": "3", "href": "/orders/A-7/items/3"})
+    );
+}
+
+#[tokio::test]
+async fn test_path_parameters_with_wildcards_and_state() {
+    let port = 9334;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/files/{bucket}/*")
+        .method("PUT")
+        .respond()
+        .status(201)
+        .json(json!({"bucket": "{{path.bucket}}"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/carts/{cart}/checkout")
+        .method("POST")
+        .set_state("last_cart", json!("{{path.cart}}"))
+        .respond()
+        .status(204)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let response = client
+        .put(format!("{}/files/photos/2024/cat.png", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["bucket"], "photos");
+
+    let response = client
+        .post(format!("{}/api/carts/c-19/checkout", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+    assert_eq!(server.state().get("last_cart").await, Some(json!("c-19")));
+}
+
+#[tokio::test]
+async fn test_invalid_path_parameter_names_rejected() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for path in ["/api/users/{user-id}", "/api/{id}/items/{id}", "/api/{}"] {
+        let response = client
+            .post(handle.url("/_setup"))
+            .json(&json!({
+                "method": "GET",
+                "path": path,
+                "response": {"status_code": 200, "headers": {}}
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400, "{}", path);
+    }
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/users/{user_id2}",
+            "response": {"status_code": 200, "headers": {}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 201);
+}
@@ -0,0 +1,118 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::fs;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_setup_expectations_survive_restart() {
+    let state_dir = std::env::temp_dir().join(format!("mimic-persist-{}", uuid::Uuid::new_v4()));
+    let client = Client::new();
+
+    // First run: one expectation from code, one via the HTTP API
+    let port = 9140;
+    let server = MockServer::new("./tests/resources").with_persistence(&state_dir);
+    server
+        .expect()
+        .path("/api/from-code")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let created: Value = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/durable/*",
+            "response": {"status_code": 200, "body": {"durable": true}}
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(created["source"], "setup_api");
+
+    // Second run restores only the runtime expectation
+    let port = 9141;
+    let server = MockServer::new("./tests/resources").with_persistence(&state_dir);
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/durable/1", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["durable"], true);
+
+    let resp = client
+        .get(format!("http://localhost:{}/api/from-code", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let expectations = server.get_expectations().await;
+    assert_eq!(expectations.len(), 1);
+    assert_eq!(expectations[0].id, created["id"].as_str().unwrap());
+
+    // Reset clears the saved expectations as well
+    server.reset().await;
+    let saved: Value =
+        serde_json::from_str(&fs::read_to_string(state_dir.join("expectations.json")).unwrap())
+            .unwrap();
+    assert_eq!(saved, json!([]));
+
+    fs::remove_dir_all(state_dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_concurrent_setups_are_all_persisted() {
+    let state_dir = std::env::temp_dir().join(format!("mimic-persist-{}", uuid::Uuid::new_v4()));
+    let server = MockServer::new("./tests/resources").with_persistence(&state_dir);
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let setups = (0..20).map(|i| {
+        client
+            .post(handle.url("/_setup"))
+            .json(&json!({
+                "method": "GET",
+                "path": format!("/api/concurrent/{}", i),
+                "response": {"status_code": 200}
+            }))
+            .send()
+    });
+    for resp in futures_util::future::join_all(setups).await {
+        assert_eq!(resp.unwrap().status().as_u16(), 201);
+    }
+
+    let saved: Vec<Value> =
+        serde_json::from_str(&fs::read_to_string(state_dir.join("expectations.json")).unwrap())
+            .unwrap();
+    assert_eq!(saved.len(), 20);
+
+    // No temporary files are left behind
+    assert_eq!(fs::read_dir(&state_dir).unwrap().count(), 1);
+
+    fs::remove_dir_all(state_dir).unwrap();
+}
@@ -0,0 +1,244 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_header_predicates() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/profile")
+        .method("GET")
+        .header_matches("Authorization", r"^Bearer [A-Za-z0-9.\-_]+$")
+        .header_exists("X-Request-Id")
+        .respond()
+        .status(200)
+        .json(json!({"name": "Alice"}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let status = |authorization: &'static str, request_id: Option<&'static str>| {
+        let mut request = client
+            .get(handle.url("/api/profile"))
+            .header("Authorization", authorization);
+        if let Some(request_id) = request_id {
+            request = request.header("X-Request-Id", request_id);
+        }
+        async move { request.send().await.unwrap().status().as_u16() }
+    };
+
+    assert_eq!(status("Bearer abc.def-1", Some("r-1")).await, 200);
+    assert_eq!(status("Bearer any-token", Some("")).await, 200);
+    assert_eq!(status("Basic dXNlcjpwdw==", Some("r-1")).await, 404);
+    assert_eq!(status("Bearer abc", None).await, 404);
+}
+
+#[tokio::test]
+async fn test_query_param_predicates() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/items")
+        .query_param_matches("page", "^[0-9]+$")
+        .query_param_missing("debug")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for (query, expected) in [
+        ("?page=2", 200),
+        ("?page=2&sort=name", 200),
+        ("?page=two", 404),
+        ("", 404),
+        ("?page=2&debug=true", 404),
+    ] {
+        let response = client
+            .get(handle.url(&format!("/api/items{}", query)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), expected, "query {}", query);
+    }
+}
+
+#[tokio::test]
+async fn test_predicates_over_http_api() {
+    let server = MockServer::new("./tests/resources").with_not_found_diagnostics();
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/reports",
+            "header_matchers": {"authorization": {"matches": "^Bearer "}},
+            "query_matchers": {"format": {"matches": "^(csv|json)$"}, "debug": {"present": false}},
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client
+        .get(handle.url("/api/reports?format=csv"))
+        .header("Authorization", "Bearer t")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = client
+        .get(handle.url("/api/reports?format=xml"))
+        .header("Authorization", "Bearer t")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+    let body: Value = response.json().await.unwrap();
+    let mismatches = body["near_misses"][0]["mismatches"].to_string();
+    assert!(
+        mismatches.contains("query parameter format: expected matching ^(csv|json)$, got xml"),
+        "{}",
+        mismatches
+    );
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/broken",
+            "header_matchers": {"authorization": {"matches": "^Bearer ("}},
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 400);
+    assert!(response.text().await.unwrap().contains("invalid regex"));
+}
+
+#[tokio::test]
+async fn test_negative_matchers() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/account")
+        .method("GET")
+        .without_header("Authorization")
+        .respond()
+        .status(401)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/account")
+        .method("GET")
+        .without_query_param("debug")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/logs")
+        .method("POST")
+        .body_not_contains("password")
+        .respond()
+        .status(202)
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/logs")
+        .method("POST")
+        .respond()
+        .status(422)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client.get(handle.url("/api/account")).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 401);
+
+    let response = client
+        .get(handle.url("/api/account"))
+        .header("Authorization", "Bearer t")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+
+    let response = client
+        .get(handle.url("/api/account?debug=1"))
+        .header("Authorization", "Bearer t")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 404);
+
+    for (body, expected) in [
+        (r#"{"user":"alice"}"#, 202),
+        ("", 202),
+        (r#"{"user":"alice","password":"hunter2"}"#, 422),
+    ] {
+        let response = client
+            .post(handle.url("/api/logs"))
+            .body(body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), expected, "body {}", body);
+    }
+}
+
+#[tokio::test]
+async fn test_negated_matchers_over_http_api() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "POST",
+            "path": "/api/events",
+            "header_matchers": {"x-debug": {"present": false}},
+            "body_matchers": [{"not": {"matches": "\"card\":\\s*\"[0-9]{16}\""}}],
+            "response": {"status_code": 204}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+    let created: Value = response.json().await.unwrap();
+    assert_eq!(
+        created["body_matchers"][0]["not"]["matches"],
+        "\"card\":\\s*\"[0-9]{16}\""
+    );
+
+    let send = |body: &'static str| client.post(handle.url("/api/events")).body(body).send();
+    assert_eq!(
+        send(r#"{"card": "**** 1234"}"#)
+            .await
+            .unwrap()
+            .status()
+            .as_u16(),
+        204
+    );
+    assert_eq!(
+        send(r#"{"card": "4111111111111111"}"#)
+            .await
+            .unwrap()
+            .status()
+            .as_u16(),
+        404
+    );
+}
@@ -0,0 +1,241 @@
+use mimic_rs::MockServer;
+use mimic_rs::mappings;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_proxy_forwards_and_records_unmatched_requests() {
+    let upstream_port = 9330;
+    let port = 9331;
+
+    let upstream = MockServer::new("./tests/resources");
+    upstream
+        .expect()
+        .path("/api/users/1")
+        .method("GET")
+        .respond()
+        .status(200)
+        .header("X-Upstream", "yes")
+        .json(json!({"id": 1, "name": "Ada"}))
+        .build()
+        .await;
+    upstream
+        .expect()
+        .path("/api/users")
+        .method("POST")
+        .body(r#"{"name":"Grace"}"#)
+        .respond()
+        .status(201)
+        .json(json!({"id": 2}))
+        .build()
+        .await;
+
+    let server = MockServer::new("./tests/resources")
+        .proxy_to(&format!("http://localhost:{}", upstream_port));
+    server
+        .expect()
+        .path("/api/local")
+        .method("GET")
+        .respond()
+        .status(204)
+        .build()
+        .await;
+
+    let upstream_clone = upstream.clone();
+    tokio::spawn(async move {
+        upstream_clone.start(upstream_port).await.unwrap();
+    });
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    // Matched requests are answered locally and not recorded
+    let resp = client
+        .get(format!("{}/api/local", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client
+        .get(format!("{}/api/users/1?expand=true", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-upstream"], "yes");
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["name"], "Ada");
+
+    let resp = client
+        .post(format!("{}/api/users", base))
+        .body(r#"{"name":"Grace"}"#)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    // Upstream answers for unknown paths are passed through and recorded too
+    let resp = client
+        .get(format!("{}/api/missing", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    assert_eq!(upstream.count_calls("GET", "/api/users/1").await, 1);
+    assert_eq!(upstream.count_calls("GET", "/api/local").await, 0);
+
+    let recordings = server.export_recordings();
+    assert_eq!(recordings.expectations.len(), 3);
+    let user = &recordings.expectations[0];
+    assert_eq!(user.method, "GET");
+    assert_eq!(user.path, "/api/users/1");
+    assert_eq!(user.query_params["expand"], "true");
+    assert_eq!(user.response.status_code, 200);
+    assert_eq!(user.response.body, Some(json!({"id": 1, "name": "Ada"})));
+    assert_eq!(
+        recordings.expectations[1].body.as_deref(),
+        Some(r#"{"name":"Grace"}"#)
+    );
+    // The plain-text 404 body becomes a body file
+    assert_eq!(recordings.fixtures.len(), 1);
+
+    let listed: Value = client
+        .get(format!("{}/_recordings", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(listed.as_array().unwrap().len(), 3);
+
+    let out_dir = std::env::temp_dir().join(format!("mimic-proxy-{}", uuid::Uuid::new_v4()));
+    let resp = client
+        .post(format!("{}/_recordings/export", base))
+        .json(&json!({"out": out_dir}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let written = mappings::read_mappings(&out_dir.join(mappings::MAPPINGS_DIR)).unwrap();
+    assert_eq!(written.len(), 3);
+    let body_file = written[2].response.body_file.as_ref().unwrap();
+    assert!(out_dir.join(body_file).is_file());
+    std::fs::remove_dir_all(&out_dir).unwrap();
+
+    client
+        .delete(format!("{}/_recordings", base))
+        .send()
+        .await
+        .unwrap();
+    assert!(server.export_recordings().expectations.is_empty());
+}
+
+#[tokio::test]
+async fn test_proxy_answers_bad_gateway_when_upstream_is_down() {
+    let port = 9332;
+    let server = MockServer::new("./tests/resources").proxy_to("http://localhost:9");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .get(format!("http://localhost:{}/api/anything", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 502);
+    assert!(server.export_recordings().expectations.is_empty());
+}
+
+#[tokio::test]
+async fn test_fallback_proxy_forwards_without_recording() {
+    let upstream = MockServer::new("./tests/resources");
+    upstream
+        .expect()
+        .path("/api/orders")
+        .method("GET")
+        .respond()
+        .status(200)
+        .header("X-Upstream", "yes")
+        .json(json!([{"id": 7}]))
+        .build()
+        .await;
+    upstream
+        .expect()
+        .path("/old")
+        .method("GET")
+        .respond()
+        .status(302)
+        .header("Location", "/new")
+        .build()
+        .await;
+    let upstream_handle = upstream.start_random_port().await.unwrap();
+
+    let server = MockServer::new("./tests/resources").fallback_proxy(&upstream_handle.url(""));
+    server
+        .expect()
+        .path("/api/flags")
+        .method("GET")
+        .respond()
+        .json(json!({"new_checkout": true}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap();
+
+    let flags: Value = client
+        .get(handle.url("/api/flags"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(flags, json!({"new_checkout": true}));
+
+    let resp = client
+        .get(handle.url("/api/orders"))
+        .header("Authorization", "Bearer token")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-upstream"], "yes");
+    assert_eq!(resp.json::<Value>().await.unwrap(), json!([{"id": 7}]));
+
+    // Redirects are passed to the client instead of being followed
+    let resp = client.get(handle.url("/old")).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 302);
+    assert_eq!(resp.headers()["location"], "/new");
+
+    let forwarded = upstream.get_request_log().await;
+    assert_eq!(forwarded.len(), 2);
+    assert_eq!(forwarded[0].headers["authorization"], "Bearer token");
+    let original_host = handle
+        .url("")
+        .trim_start_matches("http://")
+        .trim_end_matches('/')
+        .to_string();
+    assert_eq!(forwarded[0].headers["x-forwarded-host"], original_host);
+    assert_ne!(forwarded[0].headers["host"], original_host);
+
+    assert!(server.export_recordings().expectations.is_empty());
+}
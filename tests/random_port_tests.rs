@@ -0,0 +1,47 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_start_random_port_serves_immediately() {
+    let first = MockServer::new("./tests/resources");
+    let second = MockServer::new("./tests/resources");
+
+    for (server, name) in [(&first, "first"), (&second, "second")] {
+        server
+            .expect()
+            .path("/api/name")
+            .method("GET")
+            .respond()
+            .status(200)
+            .json(json!({"name": name}))
+            .build()
+            .await;
+    }
+
+    let first_running = first.start_random_port().await.unwrap();
+    let second_running = second.start_random_port().await.unwrap();
+
+    assert_ne!(first_running.port(), 0);
+    assert_ne!(first_running.port(), second_running.port());
+    assert_eq!(first_running.address().port(), first_running.port());
+    assert!(first_running.address().ip().is_loopback());
+    assert_eq!(
+        first_running.url("api/name"),
+        first_running.url("/api/name")
+    );
+
+    let client = Client::new();
+    for (running, name) in [(&first_running, "first"), (&second_running, "second")] {
+        let body: Value = client
+            .get(running.url("/api/name"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(body["name"], name);
+    }
+    assert!(!first_running.is_finished());
+}
@@ -0,0 +1,168 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_rate_limit_exhausts_and_recovers() {
+    let port = 9100;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/limited")
+        .method("GET")
+        .rate_limit(2, Duration::from_secs(1))
+        .respond()
+        .status(200)
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/limited", port);
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-ratelimit-limit"], "2");
+    assert_eq!(resp.headers()["x-ratelimit-remaining"], "1");
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-ratelimit-remaining"], "0");
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 429);
+    assert_eq!(resp.headers()["retry-after"], "1");
+    assert_eq!(resp.headers()["x-ratelimit-remaining"], "0");
+
+    sleep(Duration::from_millis(1100)).await;
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-ratelimit-remaining"], "1");
+}
+
+#[tokio::test]
+async fn test_rate_limit_via_http_api() {
+    let port = 9101;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "POST",
+            "path": "/api/login",
+            "rate_limit": {"limit": 1, "window_secs": 60},
+            "response": {"status_code": 204}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let url = format!("http://localhost:{}/api/login", port);
+
+    let resp = client.post(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client.post(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 429);
+    let retry_after: u64 = resp.headers()["retry-after"]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after > 0 && retry_after <= 60);
+}
+
+#[tokio::test]
+async fn test_sub_second_rate_limit_on_streamed_response() {
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/feed")
+        .method("GET")
+        .rate_limit(1, Duration::from_millis(300))
+        .respond()
+        .status(200)
+        .stream_body(["a", "b"], Duration::ZERO)
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+    let url = handle.url("/api/feed");
+
+    // Streamed responses carry the headers too
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["x-ratelimit-limit"], "1");
+    assert_eq!(resp.headers()["x-ratelimit-remaining"], "0");
+
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 429);
+
+    // The window is 300ms, not rounded up to a second
+    sleep(Duration::from_millis(400)).await;
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_rate_limit_window_in_milliseconds_via_http_api() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let resp = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/burst",
+            "rate_limit": {"limit": 1, "window_ms": 250},
+            "response": {"status_code": 204}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+    assert_eq!(
+        server.get_expectations().await[0]
+            .rate_limit
+            .as_ref()
+            .unwrap()
+            .window(),
+        Duration::from_millis(250)
+    );
+
+    let resp = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/never",
+            "rate_limit": {"limit": 1, "window_ms": 0},
+            "response": {"status_code": 204}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert!(resp.status().is_client_error());
+}
@@ -0,0 +1,64 @@
+use mimic_rs::MockServer;
+use mimic_rs::server::StartupError;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_missing_fixture_fails_start() {
+    let port = 9180;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/report")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json_file("does-not-exist.json")
+        .build()
+        .await;
+
+    let error = server.start(port).await.unwrap_err();
+    match error.downcast_ref::<StartupError>() {
+        Some(StartupError::Fixtures(errors)) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].path, "/api/report");
+            assert!(errors[0].file.ends_with("does-not-exist.json"));
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    assert!(!server.is_ready());
+    assert!(
+        reqwest::get(format!("http://localhost:{}/_health", port))
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_health_and_readiness_probes() {
+    let port = 9181;
+    let server = MockServer::new("./tests/resources").without_admin();
+    assert!(!server.is_ready());
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    assert!(server.is_ready());
+
+    let resp = reqwest::get(format!("http://localhost:{}/_health", port))
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = reqwest::get(format!("http://localhost:{}/_ready", port))
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.text().await.unwrap(), "ready");
+}
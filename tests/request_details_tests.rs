@@ -171,3 +171,48 @@ async fn test_request_body() {
 
     assert_eq!(resp_wrong.status().as_u16(), 404);
 }
+
+#[tokio::test]
+async fn test_request_log_records_the_served_response() {
+    let server = MockServer::new("./tests/resources");
+    let mock = server
+        .expect()
+        .path("/api/slow")
+        .method("GET")
+        .respond()
+        .status(200)
+        .delay(Duration::from_millis(50))
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    client.get(handle.url("/api/slow")).send().await.unwrap();
+    client
+        .get(handle.url("/api/fallback"))
+        .send()
+        .await
+        .unwrap();
+
+    let log = server.get_request_log().await;
+    let matched = log[0].response.as_ref().unwrap();
+    assert_eq!(matched.expectation_id.as_deref(), Some(mock.id()));
+    assert_eq!(matched.status, 200);
+    assert_eq!(
+        matched.headers.get("content-type").unwrap(),
+        "application/json"
+    );
+    assert!(matched.latency_ms >= 50.0);
+
+    let fallback = log[1].response.as_ref().unwrap();
+    assert_eq!(fallback.expectation_id, None);
+    assert_eq!(fallback.status, 404);
+
+    let har = server.export_har().await;
+    let entry = &har["log"]["entries"][0];
+    assert_eq!(entry["_expectationId"], mock.id());
+    assert!(entry["time"].as_f64().unwrap() >= 50.0);
+
+    handle.shutdown().await;
+}
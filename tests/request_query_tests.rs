@@ -0,0 +1,117 @@
+use chrono::Utc;
+use mimic_rs::MockServer;
+use mimic_rs::models::RequestQuery;
+use reqwest::Client;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn send_requests(client: &Client, base: &str) {
+    for path in ["/api/orders", "/api/users", "/health"] {
+        client
+            .get(format!("{}{}", base, path))
+            .send()
+            .await
+            .unwrap();
+    }
+    for i in 0..3 {
+        client
+            .post(format!("{}/api/orders/{}", base, i))
+            .send()
+            .await
+            .unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_find_requests() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    send_requests(&client, &handle.base_url()).await;
+    let before_last = Utc::now();
+    sleep(Duration::from_millis(10)).await;
+    client
+        .delete(handle.url("/api/orders/9"))
+        .send()
+        .await
+        .unwrap();
+
+    let posts = server
+        .find_requests(&RequestQuery::new().method("post").path_prefix("/api"))
+        .await;
+    assert_eq!(posts.len(), 3);
+    assert_eq!(posts[0].path, "/api/orders/0");
+
+    let newest = server
+        .find_requests(&RequestQuery::new().newest_first().limit(2))
+        .await;
+    let paths: Vec<&str> = newest.iter().map(|r| r.path.as_str()).collect();
+    assert_eq!(paths, ["/api/orders/9", "/api/orders/2"]);
+
+    let recent = server
+        .find_requests(&RequestQuery::new().since(before_last))
+        .await;
+    assert_eq!(recent.len(), 1);
+    assert_eq!(recent[0].method, "DELETE");
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_requests_endpoint() {
+    check_requests_endpoint(MockServer::new("./tests/resources")).await;
+}
+
+#[tokio::test]
+async fn test_requests_endpoint_with_journal() {
+    let journal_path =
+        std::env::temp_dir().join(format!("mimic-journal-{}.db", uuid::Uuid::new_v4()));
+    let server = MockServer::new("./tests/resources")
+        .with_request_journal(&journal_path)
+        .unwrap();
+    check_requests_endpoint(server).await;
+    std::fs::remove_file(journal_path).unwrap();
+}
+
+async fn check_requests_endpoint(server: MockServer) {
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    send_requests(&client, &handle.base_url()).await;
+
+    let response = client
+        .get(handle.url("/_requests?path_prefix=/api&offset=1&limit=2"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.headers()["x-total-count"], "5");
+    let requests: Vec<Value> = response.json().await.unwrap();
+    let paths: Vec<&str> = requests
+        .iter()
+        .map(|r| r["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["/api/users", "/api/orders/0"]);
+
+    let requests: Vec<Value> = client
+        .get(handle.url("/_requests?method=POST&order=desc&limit=1"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0]["path"], "/api/orders/2");
+
+    let response = client
+        .get(handle.url("/_requests?since=yesterday"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+
+    handle.shutdown().await;
+}
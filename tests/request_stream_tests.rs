@@ -0,0 +1,85 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::RequestRecord;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+
+/// Reads the stream until `count` request events arrived
+async fn read_records(response: &mut reqwest::Response, count: usize) -> Vec<RequestRecord> {
+    let mut text = String::new();
+    while text.matches("event: request\n").count() < count || !text.ends_with("\n\n") {
+        let chunk = response.chunk().await.unwrap().expect("stream ended early");
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    text.lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect()
+}
+
+#[tokio::test]
+async fn test_stream_requests_as_they_happen() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    // Requests before subscribing are not replayed
+    client.get(handle.url("/api/users/0")).send().await.unwrap();
+
+    let mut stream = client
+        .get(handle.url("/_requests/stream"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(stream.status(), 200);
+    assert_eq!(stream.headers()["content-type"], "text/event-stream");
+
+    client.get(handle.url("/api/users/1")).send().await.unwrap();
+    client
+        .post(handle.url("/api/missing"))
+        .send()
+        .await
+        .unwrap();
+
+    let records = read_records(&mut stream, 2).await;
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].path, "/api/users/1");
+    assert_eq!(records[0].response.as_ref().unwrap().status, 200);
+    assert_eq!(records[1].method, "POST");
+    assert_eq!(records[1].response.as_ref().unwrap().status, 404);
+
+    // Open streams do not hold up the shutdown
+    tokio::time::timeout(Duration::from_secs(5), handle.shutdown())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_subscribe_requests() {
+    let server = MockServer::new("./tests/resources");
+    let mut requests = server.subscribe_requests();
+
+    let handle = server.start_random_port().await.unwrap();
+    Client::new()
+        .delete(handle.url("/api/users/1"))
+        .send()
+        .await
+        .unwrap();
+
+    let record = requests.recv().await.unwrap();
+    assert_eq!(record.method, "DELETE");
+    assert_eq!(record.path, "/api/users/1");
+    assert_eq!(record.response.unwrap().status, 404);
+
+    handle.shutdown().await;
+}
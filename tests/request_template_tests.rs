@@ -0,0 +1,104 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_request_data_in_json_body() {
+    let port = 9335;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .respond()
+        .status(201)
+        .json(json!({
+            "path": "{{request.path}}",
+            "client": "{{request.header 'X-Client'}}",
+            "page": "{{request.query 'page'}}",
+            "customer": "{{request.body.jsonpath '$.customer.name'}}",
+            "first_sku": "{{request.body.jsonpath '$.items[0].sku'}}",
+            "last_sku": "{{request.body.jsonpath '$.items[-1].sku'}}",
+            "quantities": "{{request.body.jsonpath '$.items[*].qty'}}",
+            "note": "{{request.body.jsonpath \"$['delivery note']\"}}",
+            "summary": "{{request.body.jsonpath '$.customer.name'}} via {{request.header 'x-client'}}",
+            "missing_header": "{{request.header 'X-Missing'}}",
+            "missing_field": "{{request.body.jsonpath '$.nope'}}"
+        }))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let body: Value = Client::new()
+        .post(format!("http://localhost:{}/api/orders?page=2", port))
+        .header("X-Client", "web")
+        .json(&json!({
+            "customer": {"name": "Ada"},
+            "items": [{"sku": "A1", "qty": 2}, {"sku": "B2", "qty": 5}],
+            "delivery note": "back door"
+        }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert_eq!(body["path"], "/api/orders");
+    assert_eq!(body["client"], "web");
+    assert_eq!(body["page"], "2");
+    assert_eq!(body["customer"], "Ada");
+    assert_eq!(body["first_sku"], "A1");
+    assert_eq!(body["last_sku"], "B2");
+    assert_eq!(body["quantities"], json!([2, 5]));
+    assert_eq!(body["note"], "back door");
+    assert_eq!(body["summary"], "Ada via web");
+    assert_eq!(body["missing_header"], "{{request.header 'X-Missing'}}");
+    assert_eq!(body["missing_field"], "{{request.body.jsonpath '$.nope'}}");
+}
+
+#[tokio::test]
+async fn test_request_data_in_body_file() {
+    let port = 9336;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/echo")
+        .method("GET")
+        .respond()
+        .status(200)
+        .header("Content-Type", "text/plain")
+        .json_file("echo.txt")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let response = Client::new()
+        .get(format!("http://localhost:{}/api/echo?page=7", port))
+        .header("X-Trace-Id", "abc-123")
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(
+        response.text().await.unwrap().trim_end(),
+        "GET /api/echo page=7 trace=abc-123"
+    );
+}
@@ -0,0 +1,32 @@
+;; Answers 201 with the input it read from stdin as the body and sets
+;; the state key "echoed"
+(module
+  (import "wasi_snapshot_preview1" "fd_read"
+    (func $fd_read (param i32 i32 i32 i32) (result i32)))
+  (import "wasi_snapshot_preview1" "fd_write"
+    (func $fd_write (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 2)
+  (data (i32.const 0) "{\"status\":201,\"headers\":{\"X-Wasm\":\"echo\"},\"body\":")
+  (data (i32.const 100) ",\"state\":{\"echoed\":true}}")
+
+  (func (export "_start")
+    (local $len i32)
+    (local $n i32)
+    ;; Read stdin to offset 1024, one iovec at 200, count at 216
+    (block $done
+      (loop $read
+        (i32.store (i32.const 200) (i32.add (i32.const 1024) (local.get $len)))
+        (i32.store (i32.const 204) (i32.const 4096))
+        (drop (call $fd_read (i32.const 0) (i32.const 200) (i32.const 1) (i32.const 216)))
+        (local.set $n (i32.load (i32.const 216)))
+        (br_if $done (i32.eqz (local.get $n)))
+        (local.set $len (i32.add (local.get $len) (local.get $n)))
+        (br $read)))
+    ;; Write prefix, input and suffix with three iovecs at 300
+    (i32.store (i32.const 300) (i32.const 0))
+    (i32.store (i32.const 304) (i32.const 49))
+    (i32.store (i32.const 308) (i32.const 1024))
+    (i32.store (i32.const 312) (local.get $len))
+    (i32.store (i32.const 316) (i32.const 100))
+    (i32.store (i32.const 320) (i32.const 25))
+    (drop (call $fd_write (i32.const 1) (i32.const 300) (i32.const 3) (i32.const 216)))))
@@ -0,0 +1,6 @@
+;; Never finishes, stopped by the fuel limit
+(module
+  (memory (export "memory") 1)
+  (func (export "_start")
+    (loop $forever
+      (br $forever))))
@@ -0,0 +1,151 @@
+use mimic_rs::MockServer;
+use mimic_rs::transform::ResponseTransform;
+use reqwest::Client;
+use serde_json::{Map, Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_expectation_and_global_response_transforms() {
+    let port = 9260;
+    let server = MockServer::new("./tests/resources")
+        .with_response_transform(ResponseTransform::SetHeader {
+            name: "X-Request-Source".to_string(),
+            value: "mock".to_string(),
+        })
+        .with_response_transformer(|response| {
+            response
+                .headers
+                .insert("X-Status".to_string(), response.status_code.to_string());
+        });
+
+    let mut extra = Map::new();
+    extra.insert("version".to_string(), json!(2));
+    server
+        .expect()
+        .path("/api/profile")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"name": "Ada"}))
+        .transform(ResponseTransform::WrapBody {
+            key: "data".to_string(),
+            extra,
+        })
+        .transform(ResponseTransform::SignBody {
+            header: "X-Signature".to_string(),
+            secret: "s3cr3t".to_string(),
+        })
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .get(format!("http://localhost:{}/api/profile", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["x-request-source"], "mock");
+    assert_eq!(resp.headers()["x-status"], "200");
+
+    let signature = resp.headers()["x-signature"].to_str().unwrap().to_string();
+    let raw = resp.text().await.unwrap();
+    let key = ring::hmac::Key::new(ring::hmac::HMAC_SHA256, b"s3cr3t");
+    let expected: String = ring::hmac::sign(&key, raw.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    assert_eq!(signature, expected);
+
+    let body: Value = serde_json::from_str(&raw).unwrap();
+    assert_eq!(body, json!({"data": {"name": "Ada"}, "version": 2}));
+}
+
+#[tokio::test]
+async fn test_response_transforms_via_http_api() {
+    let port = 9261;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/greeting",
+            "response_transforms": [{"type": "wrap_body", "key": "result"}],
+            "response": {"status_code": 200, "body_file": "greeting_de.json"}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let body: Value = client
+        .get(format!("http://localhost:{}/api/greeting", port))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body, json!({"result": {"greeting": "Hallo"}}));
+}
+
+#[tokio::test]
+async fn test_streamed_responses_get_header_transforms() {
+    let server = MockServer::new("./tests/resources")
+        .with_response_transform(ResponseTransform::SetHeader {
+            name: "X-Request-Source".to_string(),
+            value: "mock".to_string(),
+        })
+        .with_response_transform(ResponseTransform::SignBody {
+            header: "X-Global-Signature".to_string(),
+            secret: "s3cr3t".to_string(),
+        });
+
+    server
+        .expect()
+        .path("/api/feed")
+        .method("GET")
+        .respond()
+        .status(200)
+        .stream_body(["a", "b"], Duration::ZERO)
+        .transform(ResponseTransform::SetHeader {
+            name: "X-Feed".to_string(),
+            value: "live".to_string(),
+        })
+        .transform(ResponseTransform::SignBody {
+            header: "X-Signature".to_string(),
+            secret: "s3cr3t".to_string(),
+        })
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let resp = Client::new()
+        .get(handle.url("/api/feed"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["x-request-source"], "mock");
+    assert_eq!(resp.headers()["x-feed"], "live");
+
+    // The body is not known up front, so it is not signed
+    assert!(!resp.headers().contains_key("x-signature"));
+    assert!(!resp.headers().contains_key("x-global-signature"));
+    assert_eq!(resp.text().await.unwrap(), "ab");
+}
@@ -0,0 +1,176 @@
+use mimic_rs::models::SequenceEnd;
+use mimic_rs::{MockResponse, MockServer};
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_rotate_cycles_through_responses() {
+    let port = 9160;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/flaky")
+        .method("GET")
+        .respond()
+        .rotate([
+            MockResponse::new(200).with_json_body(json!({"attempt": "a"})),
+            MockResponse::new(503),
+            MockResponse::new(200).with_json_body(json!({"attempt": "c"})),
+        ])
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/flaky", port);
+
+    let mut statuses = Vec::new();
+    for _ in 0..4 {
+        statuses.push(client.get(&url).send().await.unwrap().status().as_u16());
+    }
+    assert_eq!(statuses, vec![200, 503, 200, 200]);
+
+    // Fifth call is the second response again
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 503);
+}
+
+#[tokio::test]
+async fn test_rotate_via_http_api() {
+    let port = 9161;
+    let server = MockServer::new("./tests/resources");
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/color",
+            "response": {"status_code": 200},
+            "rotate": [
+                {"status_code": 200, "body": "red"},
+                {"status_code": 200, "body": "green"}
+            ]
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let url = format!("http://localhost:{}/api/color", port);
+    let mut colors = Vec::new();
+    for _ in 0..3 {
+        colors.push(client.get(&url).send().await.unwrap().text().await.unwrap());
+    }
+    assert_eq!(colors, vec!["\"red\"", "\"green\"", "\"red\""]);
+}
+
+#[tokio::test]
+async fn test_response_sequence_repeats_last() {
+    let port = 9340;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/accounts")
+        .method("POST")
+        .respond_sequence()
+        .then(MockResponse::new(201))
+        .then(MockResponse::new(409))
+        .then(MockResponse::new(200))
+        .when_exhausted(SequenceEnd::RepeatLast)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/accounts", port);
+
+    let mut statuses = Vec::new();
+    for _ in 0..5 {
+        statuses.push(client.post(&url).send().await.unwrap().status().as_u16());
+    }
+    assert_eq!(statuses, vec![201, 409, 200, 200, 200]);
+}
+
+#[tokio::test]
+async fn test_exhausted_sequence_falls_through() {
+    let port = 9341;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/tokens/abc")
+        .method("GET")
+        .respond_sequence()
+        .then(MockResponse::new(200))
+        .when_exhausted(SequenceEnd::NotFound)
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/tokens/*")
+        .method("GET")
+        .respond()
+        .status(410)
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/once")
+        .method("GET")
+        .respond_sequence()
+        .then(MockResponse::new(200))
+        .then(MockResponse::new(202))
+        .when_exhausted(SequenceEnd::NotFound)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let mut statuses = Vec::new();
+    for _ in 0..3 {
+        let url = format!("{}/api/once", base);
+        statuses.push(client.get(&url).send().await.unwrap().status().as_u16());
+    }
+    assert_eq!(statuses, vec![200, 202, 404]);
+
+    // Once used up the next matching expectation answers
+    let url = format!("{}/api/tokens/abc", base);
+    let mut statuses = Vec::new();
+    for _ in 0..2 {
+        statuses.push(client.get(&url).send().await.unwrap().status().as_u16());
+    }
+    assert_eq!(statuses, vec![200, 410]);
+}
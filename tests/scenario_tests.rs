@@ -0,0 +1,252 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_scenario_builder_transitions() {
+    let port = 9150;
+    let server = MockServer::new("./tests/resources");
+
+    let checkout = server.scenario("checkout");
+    checkout.set_state("empty").await;
+
+    checkout
+        .expect_get("/cart")
+        .in_state("empty")
+        .respond()
+        .status(200)
+        .json(json!({"items": []}))
+        .build()
+        .await;
+
+    checkout
+        .expect_post("/cart/items")
+        .in_state("empty")
+        .respond()
+        .status(201)
+        .will_transition_to("filled")
+        .build()
+        .await;
+
+    checkout
+        .expect_get("/cart")
+        .in_state("filled")
+        .respond()
+        .status(200)
+        .json(json!({"items": ["book"]}))
+        .build()
+        .await;
+
+    checkout
+        .expect_delete("/cart")
+        .respond()
+        .status(204)
+        .will_transition_to("empty")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let cart_url = format!("http://localhost:{}/cart", port);
+
+    let body: Value = client
+        .get(&cart_url)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["items"], json!([]));
+
+    let resp = client
+        .post(format!("http://localhost:{}/cart/items", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+    assert_eq!(checkout.state().await, "filled");
+
+    // Adding again is not possible in state "filled"
+    let resp = client
+        .post(format!("http://localhost:{}/cart/items", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let body: Value = client
+        .get(&cart_url)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["items"], json!(["book"]));
+
+    let resp = client.delete(&cart_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+    assert_eq!(checkout.state().await, "empty");
+}
+
+#[tokio::test]
+async fn test_scenario_metadata_is_serialized() {
+    let port = 9151;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .scenario("login")
+        .expect_post("/login")
+        .in_state("Started")
+        .respond()
+        .status(200)
+        .will_transition_to("logged_in")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let expectations: Value = client
+        .get(format!("http://localhost:{}/_expectations", port))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(
+        expectations[0]["scenario"],
+        json!({"name": "login", "required_state": "Started", "new_state": "logged_in"})
+    );
+
+    // Scenario expectations can be created via the HTTP API as well
+    client
+        .post(format!("http://localhost:{}/_setup", port))
+        .json(&json!({
+            "method": "GET",
+            "path": "/profile",
+            "scenario": {"name": "login", "required_state": "logged_in"},
+            "response": {"status_code": 200}
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    let profile_url = format!("http://localhost:{}/profile", port);
+    let resp = client.get(&profile_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    client
+        .post(format!("http://localhost:{}/login", port))
+        .send()
+        .await
+        .unwrap();
+
+    let resp = client.get(&profile_url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+}
+
+#[tokio::test]
+async fn test_in_scenario_login_fetch_logout() {
+    let port = 9339;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .method("POST")
+        .path("/login")
+        .in_scenario("session")
+        .when_state("Started")
+        .will_set_state("logged_in")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    server
+        .expect()
+        .method("GET")
+        .path("/profile")
+        .in_scenario("session")
+        .when_state("logged_in")
+        .respond()
+        .status(200)
+        .json(json!({"name": "Ada"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .method("POST")
+        .path("/logout")
+        .in_scenario("session")
+        .when_state("logged_in")
+        .will_set_state("Started")
+        .respond()
+        .status(204)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let resp = client
+        .get(format!("{}/profile", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let resp = client.post(format!("{}/login", base)).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(server.scenario_state("session").await, "logged_in");
+
+    let resp = client
+        .get(format!("{}/profile", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+
+    let resp = client
+        .post(format!("{}/logout", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client
+        .get(format!("{}/profile", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+    let resp = client
+        .post(format!("{}/logout", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+}
@@ -0,0 +1,188 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_script_response() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/greeting")
+        .method("GET")
+        .respond()
+        .header("X-Source", "script")
+        .script(
+            r#"
+            let name = request.query["name"] ?? "stranger";
+            `Hello, ${name}!`
+            "#,
+        )
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/orders/{id}")
+        .method("POST")
+        .respond()
+        .script_file("scripts/order.rhai")
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/api/greeting?name=Ada"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["x-source"], "script");
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/plain; charset=utf-8"
+    );
+    assert_eq!(response.text().await.unwrap(), "Hello, Ada!");
+
+    let response = client
+        .post(handle.url("/api/orders/17"))
+        .header("X-User", "grace")
+        .json(&json!({"amount": 250}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+    assert_eq!(response.headers()["x-order"], "17");
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body, json!({"id": "17", "amount": 250, "by": "grace"}));
+
+    let response = client
+        .post(handle.url("/api/orders/18"))
+        .json(&json!({"amount": 5000}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 402);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body, json!({"error": "limit exceeded"}));
+}
+
+#[tokio::test]
+async fn test_script_via_setup() {
+    let server = MockServer::new("./tests/resources");
+    server.state().set("visits", json!(41)).await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/visits",
+            "response": {
+                "status_code": 200,
+                "script": {"source": "#{ body: #{ visits: state.visits + 1 } }"}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let body: Value = client
+        .get(handle.url("/api/visits"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body, json!({"visits": 42}));
+
+    // Syntax errors are reported when the expectation is created
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/broken",
+            "response": {"status_code": 200, "script": {"source": "#{ status: "}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 400);
+}
+
+#[tokio::test]
+async fn test_failing_script_answers_500() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/loop")
+        .method("GET")
+        .respond()
+        .script("loop {}")
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/typo")
+        .method("GET")
+        .respond()
+        .script("#{ stauts: 200 }")
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for path in ["/loop", "/typo"] {
+        let response = client.get(handle.url(path)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 500, "{}", path);
+    }
+    let response = client.get(handle.url("/typo")).send().await.unwrap();
+    assert!(response.text().await.unwrap().contains("stauts"));
+}
+
+#[tokio::test]
+async fn test_mapping_file_script_updates_state() {
+    let dir = std::env::temp_dir().join(format!("mimic-scripts-{}", uuid::Uuid::new_v4()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("cart.yaml"),
+        r#"method: POST
+path: /api/carts/{id}/items
+response:
+  status_code: 200
+  script: |
+    let items = state.cart ?? [];
+    items.push(request.json.sku);
+    state.cart = items;
+    state.remove("checked_out");
+    #{ status: 201, body: #{ cart: path.id, items: items.len() } }
+"#,
+    )
+    .unwrap();
+
+    let server = MockServer::new("./tests/resources").with_mappings_dir(&dir);
+    server.state().set("checked_out", json!(true)).await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for (sku, count) in [("apple", 1), ("pear", 2)] {
+        let response = client
+            .post(handle.url("/api/carts/c1/items"))
+            .json(&json!({"sku": sku}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), 201);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body, json!({"cart": "c1", "items": count}));
+    }
+
+    assert_eq!(
+        server.state().get("cart").await,
+        Some(json!(["apple", "pear"]))
+    );
+    assert_eq!(server.state().get("checked_out").await, None);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
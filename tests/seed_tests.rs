@@ -0,0 +1,120 @@
+use mimic_rs::models::ChaosConfig;
+use mimic_rs::{MockResponse, MockServer};
+use rand::Rng;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+/// Statuses and bodies of ten requests to a seeded server
+async fn run(seed: u64) -> Vec<(u16, Value)> {
+    let server = MockServer::new("./tests/resources")
+        .with_seed(seed)
+        .with_chaos(
+            ChaosConfig::new()
+                .errors(0.5)
+                .error_statuses([500, 502, 503]),
+        );
+    server
+        .expect()
+        .path("/api/users/random")
+        .method("GET")
+        .respond()
+        .json(json!({
+            "id": "{{uuid}}",
+            "name": "{{faker.name}}",
+            "score": "{{random_int 1 1000}}"
+        }))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let mut responses = Vec::new();
+    for _ in 0..10 {
+        let response = client
+            .get(handle.url("/api/users/random"))
+            .send()
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        responses.push((status, response.json().await.unwrap()));
+    }
+    responses
+}
+
+#[tokio::test]
+async fn test_seed_makes_randomness_reproducible() {
+    let first = run(42).await;
+    assert_eq!(first, run(42).await);
+    assert_ne!(first, run(7).await);
+
+    // Chaos rolled some errors and let some requests through
+    assert!(first.iter().any(|(status, _)| *status == 200));
+    assert!(first.iter().any(|(status, _)| *status != 200));
+}
+
+#[tokio::test]
+async fn test_reset_restarts_the_sequence() {
+    let server = MockServer::new("./tests/resources").with_seed(1);
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        server
+            .expect()
+            .path("/api/id")
+            .method("GET")
+            .respond()
+            .json(json!({"id": "{{uuid}}"}))
+            .build()
+            .await;
+        let body: Value = client
+            .get(handle.url("/api/id"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        ids.push(body["id"].clone());
+        server.reset().await;
+    }
+    assert_eq!(ids[0], ids[1]);
+}
+
+#[tokio::test]
+async fn test_seeded_random_in_closures() {
+    let server = MockServer::new("./tests/resources").with_seed(5);
+    let random = server.random().clone();
+    server
+        .expect()
+        .path("/api/dice")
+        .method("GET")
+        .respond()
+        .respond_with(move |_request| {
+            let roll = random.with_rng(|rng| rng.random_range(1..=6i64));
+            MockResponse::new(200).with_json_body(json!({"roll": roll}))
+        })
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let mut rolls = Vec::new();
+    for _ in 0..5 {
+        let body: Value = client
+            .get(handle.url("/api/dice"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        rolls.push(body["roll"].as_i64().unwrap());
+    }
+
+    server.random().restart();
+    let expected: Vec<i64> = (0..5).map(|_| server.random().range(1..=6)).collect();
+    assert_eq!(rolls, expected);
+    assert_eq!(server.random().seed(), Some(5));
+}
@@ -0,0 +1,69 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use std::time::Duration;
+use tokio::time::{sleep, timeout};
+
+#[tokio::test]
+async fn test_server_handle_shutdown() {
+    let server = MockServer::new("./tests/resources").without_signal_handling();
+    server
+        .expect()
+        .path("/api/ping")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let running = server.start_random_port().await.unwrap();
+    let url = running.url("/api/ping");
+
+    let client = Client::new();
+    let response = client.get(&url).send().await.unwrap();
+    assert_eq!(response.status(), 200);
+
+    timeout(Duration::from_secs(5), running.shutdown())
+        .await
+        .expect("shutdown should not hang");
+
+    assert!(Client::new().get(&url).send().await.is_err());
+}
+
+#[tokio::test]
+async fn test_shutdown_endpoint() {
+    let server = MockServer::new("./tests/resources")
+        .without_signal_handling()
+        .with_shutdown_endpoint();
+    let running = server.start_random_port().await.unwrap();
+
+    let response = Client::new()
+        .post(running.url("/_shutdown"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+
+    timeout(Duration::from_secs(5), async {
+        while !running.is_finished() {
+            sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("the server should stop after /_shutdown");
+}
+
+#[tokio::test]
+async fn test_shutdown_endpoint_is_opt_in() {
+    let server = MockServer::new("./tests/resources").without_signal_handling();
+    let running = server.start_random_port().await.unwrap();
+
+    let response = Client::new()
+        .post(running.url("/_shutdown"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 404);
+    assert!(!running.is_finished());
+
+    running.shutdown().await;
+}
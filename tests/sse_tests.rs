@@ -0,0 +1,102 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::{SseEvent, SseStream};
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+/// Reads the stream until `count` events arrived
+async fn read_events(response: &mut reqwest::Response, count: usize) -> String {
+    let mut text = String::new();
+    while text.matches("\n\n").count() < count {
+        let chunk = response.chunk().await.unwrap().expect("stream ended early");
+        text.push_str(std::str::from_utf8(&chunk).unwrap());
+    }
+    text
+}
+
+#[tokio::test]
+async fn test_sse_script() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/events")
+        .method("GET")
+        .respond()
+        .sse(
+            SseStream::new()
+                .event(SseEvent::new("connected").retry(Duration::from_secs(3)))
+                .event(
+                    SseEvent::new(json!({"price": 42}).to_string())
+                        .event("update")
+                        .id("1")
+                        .delay(Duration::from_millis(150)),
+                )
+                .event(SseEvent::new("line one\nline two")),
+        )
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let started = Instant::now();
+    let mut response = Client::new()
+        .get(handle.url("/events"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    assert_eq!(response.headers()["cache-control"], "no-cache");
+
+    let text = read_events(&mut response, 3).await;
+    assert!(started.elapsed() >= Duration::from_millis(150));
+    assert_eq!(
+        text,
+        "retry: 3000\ndata: connected\n\n\
+         id: 1\nevent: update\ndata: {\"price\":42}\n\n\
+         data: line one\ndata: line two\n\n"
+    );
+
+    // The connection stays open after the last event
+    let next = tokio::time::timeout(Duration::from_millis(200), response.chunk()).await;
+    assert!(next.is_err());
+}
+
+#[tokio::test]
+async fn test_sse_repeat_and_close() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/ticks")
+        .method("GET")
+        .respond()
+        .sse(
+            SseStream::new()
+                .event(SseEvent::new("tick").delay(Duration::from_millis(10)))
+                .repeat(),
+        )
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let mut response = client.get(handle.url("/ticks")).send().await.unwrap();
+    let text = read_events(&mut response, 5).await;
+    assert!(text.starts_with("data: tick\n\ndata: tick\n\n"));
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/once",
+            "response": {
+                "status_code": 200,
+                "sse": {"events": [{"event": "done", "data": "bye"}], "close": true}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client.get(handle.url("/once")).send().await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "event: done\ndata: bye\n\n");
+}
@@ -0,0 +1,157 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_state_shared_between_expectations() {
+    let port = 9120;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .increment_state("orderCount")
+        .set_state("lastOrder", json!("{{request.body}}"))
+        .respond()
+        .status(201)
+        .json(json!({"number": "{{state.orderCount}}", "message": "Order {{state.orderCount}} created"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/orders/latest")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"order": "{{state.lastOrder}}", "item": "{{state.lastOrder.item}}"}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    client
+        .post(format!("{}/api/orders", base))
+        .json(&json!({"item": "book"}))
+        .send()
+        .await
+        .unwrap();
+    let resp = client
+        .post(format!("{}/api/orders", base))
+        .json(&json!({"item": "pen"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+    let body: Value = resp.json().await.unwrap();
+    assert_eq!(body["number"], 2);
+    assert_eq!(body["message"], "Order 2 created");
+
+    let body: Value = client
+        .get(format!("{}/api/orders/latest", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["order"], json!({"item": "pen"}));
+    assert_eq!(body["item"], "pen");
+
+    assert_eq!(server.state().get("orderCount").await, Some(json!(2)));
+}
+
+#[tokio::test]
+async fn test_state_admin_endpoints() {
+    let port = 9121;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/feature")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"enabled": "{{state.flags.beta}}"}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let resp = client
+        .put(format!("{}/_state", base))
+        .json(&json!({"flags": {"beta": true}, "tenant": "acme"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let body: Value = client
+        .get(format!("{}/api/feature", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["enabled"], true);
+
+    client
+        .put(format!("{}/_state/tenant", base))
+        .json(&json!("globex"))
+        .send()
+        .await
+        .unwrap();
+    let value: Value = client
+        .get(format!("{}/_state/tenant", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(value, "globex");
+
+    let resp = client
+        .delete(format!("{}/_state/tenant", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 204);
+
+    let resp = client
+        .get(format!("{}/_state/tenant", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    let state: Value = client
+        .get(format!("{}/_state", base))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(state, json!({"flags": {"beta": true}}));
+}
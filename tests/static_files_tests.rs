@@ -0,0 +1,89 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_serve_static_directory() {
+    let server =
+        MockServer::new("./tests/resources").serve_static("/assets", "./tests/resources/public");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/assets/app.css"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/css; charset=utf-8"
+    );
+    assert_eq!(response.text().await.unwrap(), "body { margin: 0; }\n");
+
+    for path in ["/assets", "/assets/", "/assets/docs/"] {
+        let response = client.get(handle.url(path)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 200, "{}", path);
+        assert_eq!(
+            response.headers()["content-type"],
+            "text/html; charset=utf-8"
+        );
+    }
+
+    let response = client
+        .head(handle.url("/assets/app.css"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["content-length"], "20");
+
+    for path in [
+        "/assets/missing.js",
+        "/assets/../binary_body_tests.rs",
+        "/assetsx/app.css",
+    ] {
+        let response = client.get(handle.url(path)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 404, "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn test_expectations_win_over_static_files() {
+    let server = MockServer::new("./tests/resources").serve_static("/", "./tests/resources/public");
+    server
+        .expect()
+        .path("/api/config")
+        .method("GET")
+        .respond()
+        .json(json!({"feature": true}))
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/app.css")
+        .method("GET")
+        .respond()
+        .status(503)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let index = client.get(handle.url("/")).send().await.unwrap();
+    assert_eq!(index.status().as_u16(), 200);
+    assert!(index.text().await.unwrap().contains("<title>App</title>"));
+
+    let config: Value = client
+        .get(handle.url("/api/config"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(config, json!({"feature": true}));
+
+    let stylesheet = client.get(handle.url("/app.css")).send().await.unwrap();
+    assert_eq!(stylesheet.status().as_u16(), 503);
+}
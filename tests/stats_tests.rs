@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use mimic_rs::MockServer;
+use mimic_rs::clock::TestClock;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_stats_attribute_wildcard_hits_to_expectations() {
+    let now: DateTime<Utc> = "2026-05-04T10:00:00Z".parse().unwrap();
+    let clock = TestClock::new(now);
+    let server = MockServer::new("./tests/resources").with_clock(clock.clone());
+
+    let users = server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .respond()
+        .status(200)
+        .delay(Duration::from_millis(100))
+        .json(json!({"ok": true}))
+        .build()
+        .await;
+    let unused = server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for id in 1..=3 {
+        let response = client
+            .get(handle.url(&format!("/api/users/{}", id)))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        clock.advance(Duration::from_secs(60));
+    }
+
+    let stats = server.stats().await;
+    assert_eq!(stats.len(), 2);
+    assert_eq!(stats[0].id, users.id());
+    assert_eq!(stats[0].path, "/api/users/*");
+    assert_eq!(stats[0].hits, 3);
+    assert_eq!(
+        stats[0].last_hit,
+        Some(now + chrono::Duration::seconds(120))
+    );
+    assert!(stats[0].avg_latency_ms.unwrap() >= 100.0);
+
+    assert_eq!(stats[1].id, unused.id());
+    assert_eq!(stats[1].hits, 0);
+    assert_eq!(stats[1].last_hit, None);
+    assert_eq!(stats[1].avg_latency_ms, None);
+
+    let body: Value = client
+        .get(handle.url("/_stats"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body[0]["id"], users.id());
+    assert_eq!(body[0]["method"], "GET");
+    assert_eq!(body[0]["hits"], 3);
+    assert_eq!(body[0]["last_hit"], "2026-05-04T10:02:00Z");
+    assert!(body[0]["avg_latency_ms"].as_f64().unwrap() >= 100.0);
+    assert_eq!(body[1]["hits"], 0);
+    assert!(body[1].get("last_hit").is_none());
+
+    server.reset().await;
+    server
+        .expect()
+        .path("/api/users/*")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+    let stats = server.stats().await;
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats[0].hits, 0);
+    assert_eq!(stats[0].last_hit, None);
+}
@@ -0,0 +1,97 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::{MockResponse, StreamBody};
+use reqwest::Client;
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn test_stream_body_chunks() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/events.ndjson")
+        .method("GET")
+        .respond()
+        .status(200)
+        .header("Content-Type", "application/x-ndjson")
+        .stream_body(
+            ["{\"n\":1}\n", "{\"n\":2}\n", "{\"n\":3}\n"],
+            Duration::from_millis(150),
+        )
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let started = Instant::now();
+    let mut response = Client::new()
+        .get(handle.url("/api/events.ndjson"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 200);
+    assert_eq!(response.headers()["transfer-encoding"], "chunked");
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+
+    let mut chunks = Vec::new();
+    let mut arrivals = Vec::new();
+    while let Some(chunk) = response.chunk().await.unwrap() {
+        chunks.push(String::from_utf8(chunk.to_vec()).unwrap());
+        arrivals.push(started.elapsed());
+    }
+    assert_eq!(chunks.concat(), "{\"n\":1}\n{\"n\":2}\n{\"n\":3}\n");
+    assert!(arrivals[0] < Duration::from_millis(150));
+    assert!(*arrivals.last().unwrap() >= Duration::from_millis(300));
+}
+
+#[tokio::test]
+async fn test_stream_body_via_setup() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/download",
+            "response": {
+                "status_code": 200,
+                "stream": {"chunks": ["abc", "def"], "chunk_delay_ms": 10}
+            }
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client.get(handle.url("/download")).send().await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "abcdef");
+}
+
+#[tokio::test]
+async fn test_stream_body_on_mock_response() {
+    let server = MockServer::new("./tests/resources");
+    let chunk = "x".repeat(64 * 1024);
+    server
+        .expect()
+        .path("/large")
+        .method("GET")
+        .respond()
+        .rotate([MockResponse::new(200).with_stream(StreamBody::new(
+            std::iter::repeat_n(chunk, 16),
+            Duration::ZERO,
+        ))])
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let body = Client::new()
+        .get(handle.url("/large"))
+        .send()
+        .await
+        .unwrap()
+        .bytes()
+        .await
+        .unwrap();
+    assert_eq!(body.len(), 16 * 64 * 1024);
+}
@@ -0,0 +1,191 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_builtin_template_helpers() {
+    let port = 9280;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/tokens")
+        .method("POST")
+        .respond()
+        .status(201)
+        .json(json!({
+            "id": "{{uuid}}",
+            "issued": "{{now format='%Y-%m-%d'}}",
+            "created_at": "{{now}}",
+            "encoded": "{{base64 request.body.user}}",
+            "literal": "{{base64 'hello world'}}",
+            "digest": "{{sha256 request.body.user}}",
+            "attempts": "{{randomInt 1 3}}",
+            "label": "token for {{request.body.user}} #{{randomInt 7 7}}",
+            "unknown": "{{randomInt 5}}"
+        }))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let body: Value = Client::new()
+        .post(format!("http://localhost:{}/api/tokens", port))
+        .json(&json!({"user": "ada"}))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    assert!(uuid::Uuid::parse_str(body["id"].as_str().unwrap()).is_ok());
+    assert_eq!(
+        body["issued"],
+        chrono::Utc::now().format("%Y-%m-%d").to_string()
+    );
+    assert!(chrono::DateTime::parse_from_rfc3339(body["created_at"].as_str().unwrap()).is_ok());
+    assert_eq!(body["encoded"], STANDARD.encode("ada"));
+    assert_eq!(body["literal"], STANDARD.encode("hello world"));
+    assert_eq!(
+        body["digest"],
+        "fdee430d40bd57deeac186cd9790033d0f06f909a8806e7ce6e717ab7c7d5029"
+    );
+    let attempts = body["attempts"].as_i64().unwrap();
+    assert!((1..=3).contains(&attempts));
+    assert_eq!(body["label"], "token for ada #7");
+    assert_eq!(body["unknown"], "{{randomInt 5}}");
+}
+
+#[tokio::test]
+async fn test_now_with_relative_offsets() {
+    let port = 9281;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/subscription")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({
+            "expires": "{{now offset='+3 days' format='%Y-%m-%d'}}",
+            "renewed": "{{now offset='-1 month' format='%Y-%m'}}",
+            "reminder": "{{now offset='+1 day -2 hours' format='%Y-%m-%dT%H'}}",
+            "invalid": "{{now offset='soon'}}",
+            "bad_format": "{{now format='%Q'}}"
+        }))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let body: Value = Client::new()
+        .get(format!("http://localhost:{}/api/subscription", port))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+
+    let now = chrono::Utc::now();
+    assert_eq!(
+        body["expires"],
+        (now + chrono::TimeDelta::days(3))
+            .format("%Y-%m-%d")
+            .to_string()
+    );
+    assert_eq!(
+        body["renewed"],
+        (now - chrono::Months::new(1)).format("%Y-%m").to_string()
+    );
+    assert_eq!(
+        body["reminder"],
+        (now + chrono::TimeDelta::hours(22))
+            .format("%Y-%m-%dT%H")
+            .to_string()
+    );
+    assert_eq!(body["invalid"], "{{now offset='soon'}}");
+    assert_eq!(body["bad_format"], "{{now format='%Q'}}");
+}
+
+#[tokio::test]
+async fn test_data_generators() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/users/random")
+        .method("GET")
+        .respond()
+        .json_file("generated_user.json")
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/profile")
+        .method("GET")
+        .respond()
+        .json(json!({
+            "company": "{{faker.company}}",
+            "bio": "{{faker.first_name}} lives at {{faker.street_address}}",
+            "phone": "{{faker.phone}}",
+            "unknown": "{{faker.favorite_color}}"
+        }))
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let mut ids = Vec::new();
+    for _ in 0..2 {
+        let user: Value = client
+            .get(handle.url("/api/users/random"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let name = user["name"].as_str().unwrap();
+        assert_eq!(name.split(' ').count(), 2, "{}", name);
+        assert!(user["email"].as_str().unwrap().ends_with("@example.com"));
+        assert!(!user["city"].as_str().unwrap().contains("{{"));
+        let score = user["score"].as_i64().unwrap();
+        assert!((1..=100).contains(&score));
+        assert_eq!(
+            user["created_at"],
+            chrono::Utc::now().format("%Y-%m-%d").to_string()
+        );
+        ids.push(user["id"].as_str().unwrap().to_string());
+    }
+    // Every response gets fresh values
+    assert_ne!(ids[0], ids[1]);
+
+    let profile: Value = client
+        .get(handle.url("/api/profile"))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert!(!profile["company"].as_str().unwrap().is_empty());
+    assert!(profile["bio"].as_str().unwrap().contains(" lives at "));
+    assert!(profile["phone"].as_str().unwrap().starts_with("+1-555-"));
+    assert_eq!(profile["unknown"], "{{faker.favorite_color}}");
+}
@@ -0,0 +1,129 @@
+use mimic_rs::MockServer;
+use mimic_rs::server::VerificationError;
+use reqwest::Client;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_time_windowed_verification() {
+    let port = 9320;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/retry")
+        .method("POST")
+        .respond()
+        .status(503)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    for _ in 0..3 {
+        client
+            .post(format!("http://localhost:{}/retry", port))
+            .send()
+            .await
+            .unwrap();
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    server
+        .verify("POST", "/retry")
+        .times(3)
+        .within(Duration::from_secs(5))
+        .at_least_apart(Duration::from_millis(150))
+        .check()
+        .await
+        .unwrap();
+
+    let error = server
+        .verify("POST", "/retry")
+        .times(2)
+        .check()
+        .await
+        .unwrap_err();
+    match &error {
+        VerificationError::CallCount {
+            expected, requests, ..
+        } => {
+            assert_eq!(*expected, 2);
+            assert_eq!(requests.len(), 3);
+        }
+        other => panic!("unexpected error: {}", other),
+    }
+
+    let error = server
+        .verify("POST", "/retry")
+        .within(Duration::from_millis(100))
+        .check()
+        .await
+        .unwrap_err();
+    assert!(matches!(error, VerificationError::OutsideWindow { .. }));
+
+    let error = server
+        .verify("*", "/retry")
+        .at_least_apart(Duration::from_secs(1))
+        .check()
+        .await
+        .unwrap_err();
+    match error {
+        VerificationError::TooClose { gap, .. } => assert!(gap < Duration::from_secs(1)),
+        other => panic!("unexpected error: {}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_range_verification() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/flaky")
+        .method("GET")
+        .respond()
+        .status(503)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    for _ in 0..2 {
+        client.get(handle.url("/flaky")).send().await.unwrap();
+    }
+
+    server.verify_at_least("GET", "/flaky", 1).await.unwrap();
+    server.verify_at_most("GET", "/flaky", 3).await.unwrap();
+    server.verify_between("GET", "/flaky", 2, 2).await.unwrap();
+    server.verify_at_most("DELETE", "/flaky", 0).await.unwrap();
+
+    let error = server
+        .verify_between("GET", "/flaky", 3, 5)
+        .await
+        .unwrap_err();
+    match &error {
+        VerificationError::CallRange { requests, .. } => assert_eq!(requests.len(), 2),
+        other => panic!("unexpected error: {}", other),
+    }
+    assert!(
+        error
+            .to_string()
+            .starts_with("expected between 3 and 5 GET /flaky request(s), got 2")
+    );
+
+    let error = server
+        .verify("GET", "/flaky")
+        .at_most(1)
+        .check()
+        .await
+        .unwrap_err();
+    assert!(error.to_string().starts_with("expected at most 1 GET"));
+
+    handle.shutdown().await;
+}
@@ -0,0 +1,143 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_expectations_limited_by_times() {
+    let port = 9343;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/tokens/redeem")
+        .method("POST")
+        .once()
+        .respond()
+        .status(200)
+        .json(json!({"redeemed": true}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/tokens/redeem")
+        .method("POST")
+        .respond()
+        .status(410)
+        .json(json!({"error": "token already used"}))
+        .build()
+        .await;
+
+    server
+        .expect()
+        .path("/api/jobs")
+        .method("GET")
+        .times(2)
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let base = format!("http://localhost:{}", port);
+
+    let response = client
+        .post(format!("{}/api/tokens/redeem", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .post(format!("{}/api/tokens/redeem", base))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 410);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "token already used");
+
+    let mut statuses = Vec::new();
+    for _ in 0..3 {
+        let response = client
+            .get(format!("{}/api/jobs", base))
+            .send()
+            .await
+            .unwrap();
+        statuses.push(response.status().as_u16());
+    }
+    assert_eq!(statuses, vec![200, 200, 404]);
+}
+
+#[tokio::test]
+async fn test_verify_all_reports_unmet_expectations() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/login")
+        .method("POST")
+        .once()
+        .respond()
+        .build()
+        .await;
+    server
+        .expect()
+        .path("/api/poll")
+        .times(3)
+        .respond()
+        .build()
+        .await;
+    // No expected call count, never checked
+    server.expect().path("/api/unused").respond().build().await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    client.post(handle.url("/api/login")).send().await.unwrap();
+    client.get(handle.url("/api/poll")).send().await.unwrap();
+
+    let errors = server.verify_all().await.unwrap_err();
+    assert_eq!(errors.0.len(), 1);
+    let message = errors.to_string();
+    assert!(message.contains("1 unmet expectation(s)"), "{}", message);
+    assert!(message.contains("GET /api/poll"), "{}", message);
+    assert!(message.contains("answered 1"), "{}", message);
+
+    client.get(handle.url("/api/poll")).send().await.unwrap();
+    client.get(handle.url("/api/poll")).send().await.unwrap();
+    assert!(server.verify_all().await.is_ok());
+}
+
+#[tokio::test]
+async fn test_verify_on_drop_panics_when_unmet() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/ping")
+        .once()
+        .respond()
+        .build()
+        .await;
+
+    let guard = server.verify_on_drop();
+    let dropped = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(guard)));
+    let message = dropped.unwrap_err();
+    let message = message.downcast_ref::<String>().unwrap();
+    assert!(message.contains("GET /api/ping"), "{}", message);
+
+    let handle = server.start_random_port().await.unwrap();
+    Client::new()
+        .get(handle.url("/api/ping"))
+        .send()
+        .await
+        .unwrap();
+    drop(server.verify_on_drop());
+}
@@ -0,0 +1,125 @@
+use mimic_rs::MockServer;
+use mimic_rs::server::StartupError;
+use mimic_rs::tls::TlsConfig;
+use reqwest::{Certificate, Client};
+use serde_json::json;
+use std::time::{Duration, Instant};
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_start_tls_with_self_signed_certificate() {
+    let port = 9328;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/api/secure")
+        .method("GET")
+        .respond()
+        .status(200)
+        .json(json!({"secure": true}))
+        .build()
+        .await;
+
+    let tls = TlsConfig::self_signed();
+    let cert = Certificate::from_pem(tls.cert_pem().as_bytes()).unwrap();
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start_tls(port, tls).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::builder()
+        .add_root_certificate(cert)
+        .build()
+        .unwrap();
+    let resp = client
+        .get(format!("https://localhost:{}/api/secure", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    let body: serde_json::Value = resp.json().await.unwrap();
+    assert_eq!(body["secure"], true);
+
+    // Clients that do not trust the certificate are refused
+    let untrusting = Client::new();
+    assert!(
+        untrusting
+            .get(format!("https://localhost:{}/api/secure", port))
+            .send()
+            .await
+            .is_err()
+    );
+
+    // Plain HTTP is not served on the TLS port
+    assert!(
+        client
+            .get(format!("http://localhost:{}/api/secure", port))
+            .timeout(Duration::from_secs(2))
+            .send()
+            .await
+            .is_err()
+    );
+
+    assert_eq!(server.count_calls("GET", "/api/secure").await, 1);
+}
+
+#[tokio::test]
+async fn test_tls_handshake_delay() {
+    let server =
+        MockServer::new("./tests/resources").with_tls_handshake_delay(Duration::from_millis(400));
+
+    server
+        .expect()
+        .path("/api/secure")
+        .method("GET")
+        .respond()
+        .status(200)
+        .build()
+        .await;
+
+    let tls = TlsConfig::self_signed();
+    let cert = Certificate::from_pem(tls.cert_pem().as_bytes()).unwrap();
+
+    let handle = server.start_tls_background(0, tls).await.unwrap();
+    let url = handle.url("/api/secure");
+    assert!(url.starts_with("https://"));
+
+    // The TCP connection is accepted but the handshake does not finish in time
+    let impatient = Client::builder()
+        .add_root_certificate(cert.clone())
+        .timeout(Duration::from_millis(150))
+        .build()
+        .unwrap();
+    assert!(impatient.get(&url).send().await.unwrap_err().is_timeout());
+
+    let client = Client::builder()
+        .add_root_certificate(cert)
+        .build()
+        .unwrap();
+    let started = Instant::now();
+    let resp = client.get(&url).send().await.unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert!(started.elapsed() >= Duration::from_millis(400));
+
+    // Requests on the established connection are not delayed again
+    let started = Instant::now();
+    client.get(&url).send().await.unwrap();
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
+
+#[tokio::test]
+async fn test_start_tls_rejects_invalid_pem() {
+    let server = MockServer::new("./tests/resources");
+    let error = server
+        .start_tls(9329, TlsConfig::from_pem("not a certificate", "not a key"))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        error.downcast_ref::<StartupError>(),
+        Some(StartupError::Tls(_))
+    ));
+}
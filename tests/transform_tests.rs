@@ -0,0 +1,107 @@
+use mimic_rs::MockServer;
+use mimic_rs::transform::RequestTransform;
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_builtin_transforms_run_before_matching() {
+    let port = 9250;
+    let server = MockServer::new("./tests/resources")
+        .with_request_transform(RequestTransform::StripPathPrefix {
+            prefix: "/gateway/v1".to_string(),
+        })
+        .with_request_transform(RequestTransform::RenameHeader {
+            from: "X-Gateway-User".to_string(),
+            to: "X-User".to_string(),
+        })
+        .with_request_transform(RequestTransform::UnwrapBody {
+            pointer: "/payload".to_string(),
+        });
+
+    server
+        .expect()
+        .path("/api/orders")
+        .method("POST")
+        .header("x-user", "alice")
+        .body(r#"{"item":"book"}"#)
+        .respond()
+        .status(201)
+        .json(json!({"created": true}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let resp = client
+        .post(format!("http://localhost:{}/gateway/v1/api/orders", port))
+        .header("X-Gateway-User", "alice")
+        .json(&json!({"payload": {"item": "book"}, "trace": "abc"}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    // A path that merely shares the prefix characters is left alone
+    let resp = client
+        .post(format!("http://localhost:{}/gateway/v10/api/orders", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 404);
+
+    assert_eq!(server.count_calls("POST", "/api/orders").await, 1);
+}
+
+#[tokio::test]
+async fn test_custom_transformer() {
+    let port = 9251;
+    let server = MockServer::new("./tests/resources").with_request_transformer(|request| {
+        if let Some(method) = request.headers.remove("x-http-method-override") {
+            request.method = method.to_uppercase();
+        }
+        request.query_params.remove("cache_buster");
+    });
+
+    server
+        .expect()
+        .path("/api/items/1")
+        .method("DELETE")
+        .respond()
+        .status(200)
+        .json(json!({"deleted": 1}))
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let body: Value = Client::new()
+        .post(format!(
+            "http://localhost:{}/api/items/1?cache_buster=123",
+            port
+        ))
+        .header("X-HTTP-Method-Override", "delete")
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(body["deleted"], 1);
+
+    let log = server.get_request_log().await;
+    assert_eq!(log[0].method, "DELETE");
+    assert!(log[0].query_params.is_empty());
+}
@@ -0,0 +1,79 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use std::fs;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_binary_and_multipart_uploads_are_captured() {
+    let port = 9310;
+    let capture_dir = std::env::temp_dir().join(format!("mimic-uploads-{}", uuid::Uuid::new_v4()));
+    let server = MockServer::new("./tests/resources").with_upload_capture(&capture_dir);
+
+    server
+        .expect()
+        .path("/api/upload")
+        .method("POST")
+        .respond()
+        .status(201)
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let client = Client::new();
+    let url = format!("http://localhost:{}/api/upload", port);
+    let image: Vec<u8> = vec![0x89, b'P', b'N', b'G', 0x00, 0xff, 0xfe];
+
+    let resp = client
+        .post(&url)
+        .header("Content-Type", "image/png")
+        .body(image.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 201);
+
+    let multipart = "--x\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.txt\"\r\n\r\nhello\r\n--x--\r\n";
+    client
+        .post(&url)
+        .header("Content-Type", "multipart/form-data; boundary=x")
+        .body(multipart)
+        .send()
+        .await
+        .unwrap();
+
+    // Plain JSON stays in the log
+    client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .body("{}")
+        .send()
+        .await
+        .unwrap();
+
+    let log = server.get_request_log().await;
+    assert_eq!(log.len(), 3);
+
+    let upload = log[0].upload.as_ref().unwrap();
+    assert!(log[0].body.is_none());
+    assert_eq!(fs::read(&upload.path).unwrap(), image);
+    assert_eq!(upload.size, image.len() as u64);
+    assert_eq!(upload.content_type.as_deref(), Some("image/png"));
+    let hash = ring::digest::digest(&ring::digest::SHA256, &image);
+    let hex: String = hash.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+    assert_eq!(upload.sha256, hex);
+
+    let upload = log[1].upload.as_ref().unwrap();
+    assert_eq!(fs::read_to_string(&upload.path).unwrap(), multipart);
+
+    assert!(log[2].upload.is_none());
+    assert_eq!(log[2].body.as_deref(), Some("{}"));
+
+    fs::remove_dir_all(capture_dir).unwrap();
+}
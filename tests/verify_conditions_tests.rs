@@ -0,0 +1,250 @@
+use mimic_rs::MockServer;
+use mimic_rs::models::{RequestConditions, ValueMatcher};
+use mimic_rs::server::VerificationError;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+async fn place_orders(server: &MockServer) -> mimic_rs::ServerHandle {
+    server
+        .expect()
+        .path("/orders")
+        .method("POST")
+        .respond()
+        .status(201)
+        .build()
+        .await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    for (sku, channel) in [("X", "web"), ("Y", "web"), ("X", "app")] {
+        client
+            .post(handle.url(&format!("/orders?channel={}", channel)))
+            .header("Authorization", "Bearer token-123")
+            .json(&json!({"sku": sku, "quantity": 1}))
+            .send()
+            .await
+            .unwrap();
+    }
+    handle
+}
+
+#[tokio::test]
+async fn test_count_calls_matching() {
+    let server = MockServer::new("./tests/resources");
+    let handle = place_orders(&server).await;
+
+    let count = |conditions: RequestConditions| {
+        let server = server.clone();
+        async move {
+            server
+                .count_calls_matching("POST", "/orders", &conditions)
+                .await
+        }
+    };
+
+    assert_eq!(count(RequestConditions::new()).await, 3);
+    assert_eq!(
+        count(RequestConditions::new().body(ValueMatcher::contains("\"sku\":\"X\""))).await,
+        2
+    );
+    assert_eq!(
+        count(RequestConditions::new().body(ValueMatcher::json_path_equals("$.sku", "Y"))).await,
+        1
+    );
+    assert_eq!(
+        count(
+            RequestConditions::new()
+                .query_param("channel", ValueMatcher::equals("web"))
+                .body(ValueMatcher::json_path_equals("$.sku", "X"))
+        )
+        .await,
+        1
+    );
+    assert_eq!(
+        count(RequestConditions::new().header(
+            "authorization",
+            ValueMatcher::matches_regex(r"^Bearer \S+$")
+        ))
+        .await,
+        3
+    );
+    assert_eq!(
+        count(RequestConditions::new().header("x-missing", ValueMatcher::contains(""))).await,
+        0
+    );
+    assert_eq!(
+        count(RequestConditions::new().body(ValueMatcher::json_path("$.coupon"))).await,
+        0
+    );
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_verify_builder_with_conditions() {
+    let server = MockServer::new("./tests/resources");
+    let handle = place_orders(&server).await;
+
+    server
+        .verify("POST", "/orders")
+        .body(ValueMatcher::json_path_equals("$.sku", "X"))
+        .query_param("channel", ValueMatcher::equals("app"))
+        .times(1)
+        .check()
+        .await
+        .unwrap();
+
+    let result = server
+        .verify("POST", "/orders")
+        .header("Authorization", ValueMatcher::equals("Bearer other"))
+        .times(1)
+        .check()
+        .await;
+    assert!(matches!(
+        result,
+        Err(VerificationError::CallCount { expected: 1, ref requests, .. }) if requests.is_empty()
+    ));
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_verify_endpoint_with_conditions() {
+    let server = MockServer::new("./tests/resources");
+    let handle = place_orders(&server).await;
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_verify"))
+        .json(&json!({
+            "method": "POST",
+            "path": "/orders",
+            "times": 2,
+            "query_params": {"channel": {"equals": "web"}},
+            "headers": {"Authorization": {"contains": "token-123"}},
+            "body": {"matches": "\"quantity\":\\s*1"}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .post(handle.url("/_verify"))
+        .json(&json!({
+            "method": "POST",
+            "path": "/orders",
+            "times": 1,
+            "body": {"json_path": {"path": "$.sku", "value": "X"}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let result: Value = response.json().await.unwrap();
+    assert_eq!(result["actual"], 2);
+
+    let response = client
+        .post(handle.url("/_verify"))
+        .json(&json!({
+            "method": "POST",
+            "path": "/orders",
+            "times": 1,
+            "body": {"matches": "("}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    assert!(response.text().await.unwrap().contains("invalid regex"));
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_verify_sequence() {
+    let server = MockServer::new("./tests/resources");
+    for path in ["/auth", "/data", "/logout"] {
+        server.expect().path(path).respond().build().await;
+    }
+    let handle = server.start_random_port().await.unwrap();
+
+    let client = Client::new();
+    for path in ["/auth", "/data/1", "/auth", "/logout"] {
+        client.get(handle.url(path)).send().await.unwrap();
+    }
+
+    assert!(
+        server
+            .verify_sequence([("GET", "/auth"), ("GET", "/data/*"), ("GET", "/logout")])
+            .await
+            .is_ok()
+    );
+    assert!(
+        server
+            .verify_sequence([("GET", "/auth"), ("GET", "/logout")])
+            .await
+            .is_ok()
+    );
+
+    let error = server
+        .verify_sequence([("GET", "/logout"), ("GET", "/auth")])
+        .await
+        .unwrap_err();
+    match error {
+        VerificationError::OutOfOrder {
+            step,
+            path,
+            matched,
+            ..
+        } => {
+            assert_eq!(step, 2);
+            assert_eq!(path, "/auth");
+            assert_eq!(matched.len(), 1);
+        }
+        other => panic!("unexpected error: {:?}", other),
+    }
+
+    // Only the steps after a match count, /data comes after the first /auth only
+    assert!(
+        server
+            .verify_sequence([("GET", "/logout"), ("GET", "/data/*")])
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_verify_sequence_endpoint() {
+    let server = MockServer::new("./tests/resources");
+    let handle = place_orders(&server).await;
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_verify/sequence"))
+        .json(&json!({"steps": [
+            {"method": "POST", "path": "/orders", "query_params": {"channel": {"equals": "web"}}},
+            {"method": "POST", "path": "/orders", "query_params": {"channel": {"equals": "app"}}}
+        ]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["matched"], 2);
+
+    let response = client
+        .post(handle.url("/_verify/sequence"))
+        .json(&json!({"steps": [
+            {"method": "POST", "path": "/orders", "query_params": {"channel": {"equals": "app"}}},
+            {"method": "POST", "path": "/orders", "query_params": {"channel": {"equals": "web"}}}
+        ]}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 400);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["success"], false);
+    assert_eq!(body["matched"], 1);
+    assert!(body["error"].as_str().unwrap().contains("step 2"));
+}
@@ -0,0 +1,94 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::{Value, json};
+
+#[tokio::test]
+async fn test_wasm_module_response() {
+    let server = MockServer::new("./tests/resources");
+    server
+        .expect()
+        .path("/api/orders/{id}")
+        .method("POST")
+        .respond()
+        .wasm_module("modules/echo.wat")
+        .build()
+        .await;
+    server.state().set("orders", json!(2)).await;
+    let handle = server.start_random_port().await.unwrap();
+
+    let response = Client::new()
+        .post(handle.url("/api/orders/17?dry_run=true"))
+        .json(&json!({"amount": 250}))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+    assert_eq!(response.headers()["x-wasm"], "echo");
+    let input: Value = response.json().await.unwrap();
+    assert_eq!(input["request"]["method"], "POST");
+    assert_eq!(input["request"]["path"], "/api/orders/17");
+    assert_eq!(input["request"]["query"]["dry_run"], "true");
+    assert_eq!(input["request"]["json"], json!({"amount": 250}));
+    assert_eq!(input["path"], json!({"id": "17"}));
+    assert_eq!(input["state"], json!({"orders": 2}));
+
+    // The state the module returned is written back
+    assert_eq!(server.state().get("echoed").await, Some(json!(true)));
+    assert_eq!(server.state().get("orders").await, None);
+}
+
+#[tokio::test]
+async fn test_failing_wasm_modules_answer_500() {
+    let server = MockServer::new("./tests/resources");
+    for (path, module) in [
+        ("/spin", "modules/spin.wat"),
+        ("/exit", "modules/exit.wat"),
+        ("/missing", "modules/missing.wasm"),
+    ] {
+        server
+            .expect()
+            .path(path)
+            .method("GET")
+            .respond()
+            .wasm_module(module)
+            .build()
+            .await;
+    }
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    for (path, error) in [
+        ("/spin", "ran out of fuel"),
+        ("/exit", "exited with status 3"),
+        ("/missing", "cannot be read"),
+    ] {
+        let response = client.get(handle.url(path)).send().await.unwrap();
+        assert_eq!(response.status().as_u16(), 500, "{}", path);
+        let body = response.text().await.unwrap();
+        assert!(body.contains(error), "{}: {}", path, body);
+    }
+}
+
+#[tokio::test]
+async fn test_wasm_module_via_setup() {
+    let server = MockServer::new("./tests/resources");
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .post(handle.url("/_setup"))
+        .json(&json!({
+            "method": "GET",
+            "path": "/api/echo",
+            "response": {"status_code": 200, "script": {"wasm": "modules/echo.wat"}}
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+
+    let response = client.get(handle.url("/api/echo")).send().await.unwrap();
+    assert_eq!(response.status().as_u16(), 201);
+    let input: Value = response.json().await.unwrap();
+    assert_eq!(input["request"]["path"], "/api/echo");
+}
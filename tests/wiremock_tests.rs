@@ -0,0 +1,140 @@
+use mimic_rs::MockServer;
+use mimic_rs::import::{self, ImportFormat};
+use mimic_rs::models::{JsonBodyMatch, ResponseDelay};
+use reqwest::Client;
+use serde_json::{Value, json};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const WIREMOCK_ROOT: &str = "./tests/resources/import/wiremock";
+
+#[test]
+fn test_import_wiremock_directory() {
+    let imported = import::import_file(ImportFormat::WireMock, Path::new(WIREMOCK_ROOT)).unwrap();
+
+    // orders.json comes before stubs.json
+    assert_eq!(imported.expectations.len(), 5);
+    let pay = &imported.expectations[0];
+    assert_eq!(pay.path, "/api/orders/{id}/pay");
+    assert_eq!(pay.response.status_code, 202);
+    assert_eq!(
+        pay.response.delay,
+        Some(ResponseDelay::fixed(Duration::from_millis(50)))
+    );
+    let scenario = pay.scenario.as_ref().unwrap();
+    assert_eq!(scenario.name, "checkout");
+    assert_eq!(scenario.required_state.as_deref(), Some("Started"));
+    assert_eq!(scenario.new_state.as_deref(), Some("Paid"));
+    assert_eq!(imported.expectations[2].path, "/api/users/*");
+}
+
+#[test]
+fn test_wiremock_export_round_trip() {
+    let imported = import::import_file(ImportFormat::WireMock, Path::new(WIREMOCK_ROOT)).unwrap();
+    let document = import::to_wiremock(&imported.expectations);
+
+    let mappings = document["mappings"].as_array().unwrap();
+    assert_eq!(mappings.len(), 5);
+    assert_eq!(
+        mappings[0]["request"]["urlPathTemplate"],
+        "/api/orders/{id}/pay"
+    );
+    assert_eq!(mappings[0]["scenarioName"], "checkout");
+    assert_eq!(mappings[0]["response"]["fixedDelayMilliseconds"], 50);
+    assert_eq!(mappings[2]["request"]["urlPathPattern"], "/api/users/.*");
+    assert_eq!(
+        mappings[2]["request"]["headers"]["accept"],
+        json!({"equalTo": "application/json"})
+    );
+    assert_eq!(mappings[3]["request"]["urlPath"], "/api/report");
+    assert_eq!(
+        mappings[3]["request"]["queryParameters"]["format"],
+        json!({"equalTo": "csv"})
+    );
+    assert_eq!(mappings[3]["response"]["bodyFileName"], "report.csv");
+
+    let root = std::env::temp_dir().join(format!("mimic-wiremock-{}", uuid::Uuid::new_v4()));
+    fs::create_dir_all(root.join("mappings")).unwrap();
+    fs::write(
+        root.join("mappings/exported.json"),
+        serde_json::to_string(&document).unwrap(),
+    )
+    .unwrap();
+
+    let reimported = import::import_file(ImportFormat::WireMock, &root).unwrap();
+    assert_eq!(reimported.expectations.len(), 5);
+    assert_eq!(
+        reimported.expectations[0].scenario,
+        imported.expectations[0].scenario
+    );
+    assert_eq!(reimported.expectations[2].path, "/api/users/*");
+    assert_eq!(
+        reimported.expectations[4].json_body,
+        Some(JsonBodyMatch::Contains(json!({"name": "Ann"})))
+    );
+
+    fs::remove_dir_all(root).unwrap();
+}
+
+#[tokio::test]
+async fn test_serve_wiremock_directory() {
+    let server = MockServer::new("./tests/resources");
+    assert_eq!(server.load_wiremock(WIREMOCK_ROOT).await.unwrap(), 5);
+    let handle = server.start_random_port().await.unwrap();
+    let client = Client::new();
+
+    let response = client
+        .get(handle.url("/api/users/42"))
+        .header("Accept", "application/json")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    let user: Value = response.json().await.unwrap();
+    assert_eq!(user["name"], "Bob");
+
+    // Served from __files
+    let response = client
+        .get(handle.url("/api/report?format=csv"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "id,name\n1,Bob\n");
+
+    let response = client
+        .post(handle.url("/api/orders/7/pay"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 202);
+    assert_eq!(response.text().await.unwrap(), "payment accepted");
+
+    let response = client
+        .post(handle.url("/api/orders/7/pay"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 409);
+
+    handle.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_load_wiremock_requires_a_mappings_directory() {
+    let server = MockServer::new("./tests/resources");
+
+    assert!(
+        server
+            .load_wiremock("./tests/resources/stubs")
+            .await
+            .is_err()
+    );
+    assert!(
+        server
+            .load_wiremock("./tests/resources/import/openapi.json")
+            .await
+            .is_err()
+    );
+}
@@ -0,0 +1,49 @@
+use mimic_rs::MockServer;
+use reqwest::Client;
+use serde_json::json;
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_xml_file_is_templated() {
+    let port = 9290;
+    let server = MockServer::new("./tests/resources");
+
+    server
+        .expect()
+        .path("/soap/orders")
+        .method("POST")
+        .increment_state("orders")
+        .respond()
+        .status(200)
+        .xml_file("order.xml")
+        .build()
+        .await;
+
+    let server_clone = server.clone();
+    tokio::spawn(async move {
+        server_clone.start(port).await.unwrap();
+    });
+
+    sleep(Duration::from_millis(100)).await;
+
+    let resp = Client::new()
+        .post(format!("http://localhost:{}/soap/orders", port))
+        .json(&json!({
+            "customer": "Smith & <Sons>",
+            "items": [{"sku": "A-1", "qty": 2}, {"sku": "B-2", "qty": 1}]
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status().as_u16(), 200);
+    assert_eq!(resp.headers()["content-type"], "application/xml");
+
+    let xml = resp.text().await.unwrap();
+    assert!(xml.contains("<Customer>Smith &amp; &lt;Sons&gt;</Customer>"));
+    assert!(xml.contains(
+        "<Items><Item><qty>2</qty><sku>A-1</sku></Item><Item><qty>1</qty><sku>B-2</sku></Item></Items>"
+    ));
+    assert!(xml.contains("<Count>1</Count>"));
+    assert!(xml.starts_with("<?xml version=\"1.0\""));
+}